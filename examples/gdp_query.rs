@@ -0,0 +1,127 @@
+//! Command-line CSV wrangling example: loads the GDP data set, joins it with its region metadata,
+//! filters it with a query expression specified at runtime, and displays or exports the result.
+//!
+//! Usage:
+//!
+//! ```text
+//! cargo run --example gdp_query -- [QUERY] [--sort] [--stats] [--json]
+//! ```
+//!
+//! For example:
+//!
+//! ```text
+//! cargo run --example gdp_query -- "Region == 'South Asia' && Year1983 > 1e10" --sort
+//! ```
+//!
+//! Available fields are `CountryName`, `CountryCode`, `Region`, and `Year1983`.
+#[macro_use]
+extern crate agnes;
+extern crate serde_json;
+
+use std::env;
+use std::fmt::Debug;
+use std::path::Path;
+use std::process;
+
+use agnes::join::{Equal, Join};
+use agnes::source::csv::{CsvReader, CsvSource, IntoCsvSrcSchema};
+use agnes::value::Value;
+
+fn load_csv_file<Schema>(filename: &str, schema: Schema) -> CsvReader<Schema::CsvSrcSchema>
+where
+    Schema: IntoCsvSrcSchema,
+    <Schema as IntoCsvSrcSchema>::CsvSrcSchema: Debug,
+{
+    let data_filepath = Path::new(file!()) // start as this file
+        .parent()
+        .unwrap() // navigate up to examples directory
+        .join("data") // navigate into data directory
+        .join(filename); // navigate to target file
+
+    let source = CsvSource::new(data_filepath).unwrap();
+    CsvReader::new(&source, schema).unwrap()
+}
+
+tablespace![
+    pub table gdp {
+        CountryName: String,
+        CountryCode: String,
+        Year1983: f64,
+    }
+    pub table gdp_metadata {
+        CountryCode: String,
+        Region: String,
+    }
+];
+
+fn main() {
+    let mut query = None;
+    let (mut sort, mut stats, mut json) = (false, false, false);
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--sort" => sort = true,
+            "--stats" => stats = true,
+            "--json" => json = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("unknown option: {}", arg);
+                process::exit(1);
+            }
+            _ => query = Some(arg),
+        }
+    }
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldname gdp::CountryCode = "Country Code";
+        fieldname gdp::Year1983 = "1983";
+    ];
+    let mut csv_rdr = load_csv_file("gdp/API_NY.GDP.MKTP.CD_DS2_en_csv_v2.csv", gdp_schema);
+    let dv_gdp = csv_rdr.read().unwrap().into_view();
+
+    let gdp_metadata_schema = schema![
+        fieldindex gdp_metadata::CountryCode = 0usize;
+        fieldname gdp_metadata::Region = "Region";
+    ];
+    let mut csv_rdr = load_csv_file(
+        "gdp/Metadata_Country_API_NY.GDP.MKTP.CD_DS2_en_csv_v2.csv",
+        gdp_metadata_schema,
+    );
+    let dv_gdp_metadata = csv_rdr.read().unwrap().into_view();
+
+    let dv = dv_gdp
+        .join::<Join<gdp::CountryCode, gdp_metadata::CountryCode, Equal>, _, _>(&dv_gdp_metadata)
+        .v::<Labels![
+            gdp::CountryName,
+            gdp::CountryCode,
+            gdp_metadata::Region,
+            gdp::Year1983
+        ]>();
+
+    let dv = match query {
+        Some(ref query) => match dv.query(query) {
+            Ok(dv) => dv,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        None => dv,
+    };
+
+    // sort by descending GDP, with missing values last
+    let dv = if sort {
+        dv.sort_by_label_comparator::<gdp::Year1983, _>(|left: Value<&f64>, right: Value<&f64>| {
+            right.partial_cmp(&left).unwrap()
+        })
+    } else {
+        dv
+    };
+
+    if json {
+        println!("{}", serde_json::to_string(&dv).unwrap());
+    } else if stats {
+        println!("{}", dv.view_stats());
+    } else {
+        println!("{}", dv);
+    }
+}
@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate agnes;
+
+use agnes::source::csv::load_csv_from_path;
+
+tablespace![
+    table gdp {
+        CountryName: String,
+        Year: u32,
+        Gdp: f64,
+    }
+];
+
+fn main() {
+    // stack the values of the year columns into a single `Gdp` field, with the column each value
+    // came from in the `Year` field
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldnames gdp::Gdp = ["2013", "2014", "2015"], stack gdp::Year;
+    ];
+
+    // load the CSV file from a path
+    let gdp_view = load_csv_from_path(
+        "./examples/data/gdp/API_NY.GDP.MKTP.CD_DS2_en_csv_v2.csv",
+        gdp_schema,
+    )
+    .expect("CSV loading failed.");
+
+    // three records (one for each year) for each country
+    println!("{}", gdp_view);
+}
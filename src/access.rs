@@ -4,12 +4,18 @@ Traits for accessing data within agnes data structures.
 The [DataIndex](trait.DataIndex.html) trait provides index-based access to a field's data as well
 as method which generates a [DataIterator](struct.DataIterator.html).
 */
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::rc::Rc;
+use std::slice;
+
+use bit_vec::BitVec;
 
 use error::*;
+use field::FieldData;
 use frame::Framed;
+use stats::FieldSummary;
 use value::Value;
 
 /// Trait that provides access to values in a data field.
@@ -28,6 +34,44 @@ pub trait DataIndex: Debug {
         self.len() == 0
     }
 
+    /// Returns the number of missing (NA) values in this field. Implementors with direct access to
+    /// their missing value mask should override this to avoid checking every value.
+    fn count_na(&self) -> usize {
+        (0..self.len())
+            .filter(|&idx| self.get_datum(idx).map_or(false, |value| value.is_na()))
+            .count()
+    }
+
+    /// Returns the number of existing (non-NA) values in this field.
+    fn count_exists(&self) -> usize {
+        self.len() - self.count_na()
+    }
+
+    /// Returns the cached [summary statistics](../stats/struct.FieldSummary.html) of this field,
+    /// if available. Returns `None` by default; implementors which cache their summary (such as
+    /// fields stored in a `DataStore`) should override this.
+    fn cached_summary(&self) -> Option<FieldSummary<Self::DType>>
+    where
+        Self::DType: Clone + PartialOrd,
+    {
+        None
+    }
+
+    /// Returns the cached 64-bit hash of the value at index `idx`, if available. Returns `None` by
+    /// default; implementors which can cache the hashes of their values (such as fields stored in
+    /// a `DataStore`, after calling [cache_hashes](../store/trait.CacheHashes.html)) should
+    /// override this. Equal values always have equal cached hashes.
+    fn cached_hash(&self, _idx: usize) -> Option<u64> {
+        None
+    }
+
+    /// Returns the underlying `FieldData` of this field if its values are stored contiguously and
+    /// in order, allowing scans to iterate over the stored values directly rather than looking up
+    /// each index. Returns `None` by default.
+    fn as_field_data(&self) -> Option<&FieldData<Self::DType>> {
+        None
+    }
+
     /// Returns an iterator over the values in this field.
     fn iter(&self) -> DataIterator<Self::DType>
     where
@@ -36,6 +80,19 @@ pub trait DataIndex: Debug {
         DataIterator::new(self)
     }
 
+    /// Returns an iterator over consecutive, non-overlapping blocks of `chunk_size` values in this
+    /// field. Each block is itself an iterator over its values; the final block will be shorter
+    /// than `chunk_size` if the length of this field is not evenly divisible by `chunk_size`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    fn chunks(&self, chunk_size: usize) -> Chunks<Self::DType>
+    where
+        Self: Sized,
+    {
+        Chunks::new(self, chunk_size)
+    }
+
     /// Returns a new `DataIndex`-implementing object which provides access to the values in this
     /// field as permuted by `permutation`. `permutation` is a slice of indices into this
     /// `DataIndex`.
@@ -56,10 +113,7 @@ pub trait DataIndex: Debug {
         Self::DType: Clone,
     {
         self.iter()
-            .filter_map(|value| match value {
-                Value::Exists(value) => Some(value.clone()),
-                Value::Na => None,
-            })
+            .filter_map(|value| value.cloned().into_option())
             .collect()
     }
 
@@ -96,21 +150,56 @@ pub struct DataIterator<'a, T>
 where
     T: 'a,
 {
-    data: &'a dyn DataIndex<DType = T>,
-    cur_idx: usize,
-    phantom: PhantomData<T>,
+    source: IterSource<'a, T>,
 }
+
+// fields which expose their underlying `FieldData` are scanned directly, with separate loops for
+// fields with and without missing values; all other fields are accessed index-by-index
+enum IterSource<'a, T>
+where
+    T: 'a,
+{
+    Dense(slice::Iter<'a, T>),
+    Masked {
+        values: slice::Iter<'a, T>,
+        mask: &'a BitVec,
+        indices: Range<usize>,
+    },
+    Indexed {
+        data: &'a dyn DataIndex<DType = T>,
+        indices: Range<usize>,
+    },
+}
+
 impl<'a, T> DataIterator<'a, T>
 where
     T: 'a,
 {
     /// Create a new `DataIterator` from a type that implements `DataIndex`.
     pub fn new(data: &'a dyn DataIndex<DType = T>) -> DataIterator<'a, T> {
-        DataIterator {
-            data,
-            cur_idx: 0,
-            phantom: PhantomData,
-        }
+        DataIterator::over_range(data, 0..data.len())
+    }
+
+    // creates a new `DataIterator` over the values of `data` at the indices in `indices`
+    fn over_range(
+        data: &'a dyn DataIndex<DType = T>,
+        indices: Range<usize>,
+    ) -> DataIterator<'a, T> {
+        let source = match data.as_field_data() {
+            Some(field) => {
+                let values = field.values()[indices.clone()].iter();
+                match field.na_mask().bits() {
+                    Some(mask) => IterSource::Masked {
+                        values,
+                        mask,
+                        indices,
+                    },
+                    None => IterSource::Dense(values),
+                }
+            }
+            None => IterSource::Indexed { data, indices },
+        };
+        DataIterator { source }
     }
 
     /// Returns an iterator applying function `F` to the stored values (where they exist) to this
@@ -128,23 +217,152 @@ where
     }
 }
 
+// returns the value stored at `idx`, or `Value::Na` if the mask marks it as missing
+#[inline]
+fn masked_value<'a, T>(value: &'a T, mask: &BitVec, idx: usize) -> Value<&'a T> {
+    if mask[idx] {
+        Value::Exists(value)
+    } else {
+        Value::Na
+    }
+}
+
 impl<'a, T> Iterator for DataIterator<'a, T>
 where
     T: 'a,
 {
     type Item = Value<&'a T>;
 
+    #[inline]
     fn next(&mut self) -> Option<Value<&'a T>> {
-        if self.cur_idx < self.data.len() {
-            let out = Some(self.data.get_datum(self.cur_idx).unwrap());
-            self.cur_idx += 1;
-            out
-        } else {
-            None
+        match self.source {
+            IterSource::Dense(ref mut values) => values.next().map(Value::Exists),
+            IterSource::Masked {
+                ref mut values,
+                mask,
+                ref mut indices,
+            } => values
+                .next()
+                .and_then(|value| indices.next().map(|idx| masked_value(value, mask, idx))),
+            IterSource::Indexed {
+                data,
+                ref mut indices,
+            } => indices.next().map(|idx| data.get_datum(idx).unwrap()),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.source {
+            IterSource::Dense(ref values) | IterSource::Masked { ref values, .. } => {
+                values.size_hint()
+            }
+            IterSource::Indexed { ref indices, .. } => indices.size_hint(),
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for DataIterator<'a, T>
+where
+    T: 'a,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Value<&'a T>> {
+        match self.source {
+            IterSource::Dense(ref mut values) => values.next_back().map(Value::Exists),
+            IterSource::Masked {
+                ref mut values,
+                mask,
+                ref mut indices,
+            } => values.next_back().and_then(|value| {
+                indices
+                    .next_back()
+                    .map(|idx| masked_value(value, mask, idx))
+            }),
+            IterSource::Indexed {
+                data,
+                ref mut indices,
+            } => indices.next_back().map(|idx| data.get_datum(idx).unwrap()),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DataIterator<'a, T> where T: 'a {}
+
+/// Iterator over consecutive blocks of values in a data structure that implements DataIndex, as
+/// returned by [chunks](trait.DataIndex.html#method.chunks). Each block is a
+/// [DataIterator](struct.DataIterator.html).
+pub struct Chunks<'a, T>
+where
+    T: 'a,
+{
+    data: &'a dyn DataIndex<DType = T>,
+    chunk_size: usize,
+    indices: Range<usize>,
+}
+
+impl<'a, T> Chunks<'a, T>
+where
+    T: 'a,
+{
+    /// Create a new `Chunks` iterator over blocks of `chunk_size` values of `data`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    pub fn new(data: &'a dyn DataIndex<DType = T>, chunk_size: usize) -> Chunks<'a, T> {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        Chunks {
+            data,
+            chunk_size,
+            indices: 0..data.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T>
+where
+    T: 'a,
+{
+    type Item = DataIterator<'a, T>;
+
+    fn next(&mut self) -> Option<DataIterator<'a, T>> {
+        if self.indices.start >= self.indices.end {
+            return None;
+        }
+        let start = self.indices.start;
+        let end = self.indices.end.min(start.saturating_add(self.chunk_size));
+        self.indices.start = end;
+        Some(DataIterator::over_range(self.data, start..end))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.indices.end - self.indices.start;
+        let remaining = len / self.chunk_size + if len % self.chunk_size == 0 { 0 } else { 1 };
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Chunks<'a, T>
+where
+    T: 'a,
+{
+    fn next_back(&mut self) -> Option<DataIterator<'a, T>> {
+        if self.indices.start >= self.indices.end {
+            return None;
         }
+        // the last chunk holds the remainder when the length isn't a multiple of the chunk size
+        let len = self.indices.end - self.indices.start;
+        let last_len = match len % self.chunk_size {
+            0 => self.chunk_size,
+            rem => rem,
+        };
+        let end = self.indices.end;
+        self.indices.end = end - last_len;
+        Some(DataIterator::over_range(self.data, end - last_len..end))
     }
 }
 
+impl<'a, T> ExactSizeIterator for Chunks<'a, T> where T: 'a {}
+
 /// Mapping iterator applying function `F` to the data in a data structure that implement DataIndex.
 /// `T` is the data type held within this data structure, and `I` is the base iterator that is being
 /// mapped over.
@@ -209,6 +427,103 @@ where
     }
 }
 
+/// Iterator over borrowed string slices of a field containing `String` data, as returned by
+/// [iter_str](trait.StrDataIndex.html#method.iter_str).
+pub type StrIterator<'a> =
+    ValueMap<'a, String, DataIterator<'a, String>, fn(&'a String) -> &'a str>;
+
+/// Trait that provides `&str`-based access to fields containing `String` data. Values are borrowed
+/// from the underlying field storage, so scans over string fields do not need to clone each value.
+pub trait StrDataIndex: DataIndex<DType = String> {
+    /// Returns the string slice (possibly NA) at the specified index, if it exists.
+    fn get_str(&self, idx: usize) -> Result<Value<&str>> {
+        self.get_datum(idx).map(|value| value.map(|s| s.as_str()))
+    }
+
+    /// Returns an iterator over the (possibly NA) string slices in this field.
+    fn iter_str(&self) -> StrIterator
+    where
+        Self: Sized,
+    {
+        self.iter()
+            .map_existing(String::as_str as fn(&String) -> &str)
+    }
+
+    /// Collects borrowed slices of the existing strings in this field into a new `Vec`, without
+    /// cloning the underlying data.
+    ///
+    /// If this field has missing values, this method will return a vector of length less than that
+    /// returned by the `len` method.
+    fn to_str_vec(&self) -> Vec<&str>
+    where
+        Self: Sized,
+    {
+        self.iter_str().filter_map(Value::into_option).collect()
+    }
+}
+impl<DI> StrDataIndex for DI where DI: DataIndex<DType = String> {}
+
+/// Trait that provides type-erased access to the values in a data field, allowing fields of
+/// different data types to be handled together at runtime as trait objects (e.g.
+/// `Box<dyn ErasedDataIndex>`). Values are accessed as formatted strings. A `DataIndex` can be
+/// converted into an `ErasedDataIndex` using the [Erased](struct.Erased.html) wrapper.
+pub trait ErasedDataIndex: Debug {
+    /// Returns the length of this data field.
+    fn len(&self) -> usize;
+
+    /// Returns whether or not this field is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether or not the value at the specified index is missing (NA), if it exists.
+    fn is_na(&self, idx: usize) -> Result<bool>;
+
+    /// Returns the (possibly NA) value at the specified index formatted as a `String`, if it
+    /// exists.
+    fn get_string(&self, idx: usize) -> Result<Value<String>>;
+
+    /// Returns the number of missing (NA) values in this field.
+    fn count_na(&self) -> usize;
+}
+
+/// Wrapper providing [ErasedDataIndex](trait.ErasedDataIndex.html) access to a field implementing
+/// [DataIndex](trait.DataIndex.html) whose data type implements `Display`.
+#[derive(Debug, Clone)]
+pub struct Erased<DI> {
+    data: DI,
+}
+impl<DI> Erased<DI> {
+    /// Wraps the field `data`.
+    pub fn new(data: DI) -> Erased<DI> {
+        Erased { data }
+    }
+    /// Returns the wrapped field.
+    pub fn into_inner(self) -> DI {
+        self.data
+    }
+}
+impl<DI> ErasedDataIndex for Erased<DI>
+where
+    DI: DataIndex,
+    DI::DType: Display,
+{
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+    fn is_na(&self, idx: usize) -> Result<bool> {
+        self.data.get_datum(idx).map(|value| value.is_na())
+    }
+    fn get_string(&self, idx: usize) -> Result<Value<String>> {
+        self.data
+            .get_datum(idx)
+            .map(|value| value.map(|value| value.to_string()))
+    }
+    fn count_na(&self) -> usize {
+        self.data.count_na()
+    }
+}
+
 /// Trait to provide the number of rows of this data structure.
 pub trait NRows {
     /// Return the number of rows in this data structure.
@@ -227,7 +542,7 @@ where
 mod tests {
     use super::*;
 
-    use field::FieldData;
+    use store::DataRef;
 
     #[test]
     fn convert() {
@@ -253,4 +568,111 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn str_access() {
+        let field_data = FieldData::from_field_vec(vec![
+            Value::Exists("foo".to_string()),
+            Value::Na,
+            Value::Exists("bar".to_string()),
+        ]);
+        assert_eq!(field_data.get_str(0).unwrap(), Value::Exists("foo"));
+        assert_eq!(field_data.get_str(1).unwrap(), Value::Na);
+        assert!(field_data.get_str(3).is_err());
+        assert_eq!(
+            field_data.iter_str().collect::<Vec<_>>(),
+            vec![Value::Exists("foo"), Value::Na, Value::Exists("bar")]
+        );
+        assert_eq!(field_data.to_str_vec(), vec!["foo", "bar"]);
+
+        // permuted access borrows from the original storage
+        let permuted = field_data.permute(&[2, 0]);
+        assert_eq!(permuted.to_str_vec(), vec!["bar", "foo"]);
+    }
+
+    #[test]
+    fn direct_iter() {
+        let dense: FieldData<u64> = vec![3u64, 1, 4].into();
+        assert!(dense.as_field_data().is_some());
+        assert_eq!(dense.iter().size_hint(), (3, Some(3)));
+        assert_eq!(dense.to_vec(), vec![3, 1, 4]);
+
+        let masked =
+            FieldData::from_field_vec(vec![Value::Exists(3u64), Value::Na, Value::Exists(4)]);
+        assert_eq!(
+            masked.iter().collect::<Vec<_>>(),
+            vec![Value::Exists(&3), Value::Na, Value::Exists(&4)]
+        );
+
+        // unpermuted frames are scanned directly; permuted frames fall back to indexed access
+        let framed: Framed<u64, DataRef<u64>> = masked.clone().into();
+        assert!(framed.as_field_data().is_some());
+        assert_eq!(framed.to_value_vec(), masked.to_value_vec());
+        let permuted = masked.permute(&[2, 1, 1]);
+        assert!(permuted.as_field_data().is_none());
+        assert_eq!(permuted.iter().size_hint(), (3, Some(3)));
+        assert_eq!(
+            permuted.to_value_vec(),
+            vec![Value::Exists(4), Value::Na, Value::Na]
+        );
+    }
+
+    #[test]
+    fn reverse_and_chunks() {
+        let masked =
+            FieldData::from_field_vec(vec![Value::Exists(3u64), Value::Na, Value::Exists(4)]);
+        let permuted = masked.clone().permute(&[2, 1, 0, 0, 2]);
+        assert_eq!(
+            masked.iter().rev().collect::<Vec<_>>(),
+            vec![Value::Exists(&4), Value::Na, Value::Exists(&3)]
+        );
+        assert_eq!(
+            permuted.iter().rev().collect::<Vec<_>>(),
+            vec![
+                Value::Exists(&4),
+                Value::Exists(&3),
+                Value::Exists(&3),
+                Value::Na,
+                Value::Exists(&4)
+            ]
+        );
+        assert_eq!(permuted.get_or_panic(1), Value::Na);
+
+        // iterating from both ends meets in the middle
+        let mut iter = masked.iter();
+        assert_eq!(iter.next_back(), Some(Value::Exists(&4)));
+        assert_eq!(iter.next(), Some(Value::Exists(&3)));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next_back(), Some(Value::Na));
+        assert_eq!(iter.next(), None);
+
+        let chunks = permuted
+            .chunks(2)
+            .map(|chunk| chunk.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            chunks,
+            vec![
+                vec![Value::Exists(&4), Value::Na],
+                vec![Value::Exists(&3), Value::Exists(&3)],
+                vec![Value::Exists(&4)],
+            ]
+        );
+        assert_eq!(masked.chunks(2).len(), 2);
+        assert_eq!(
+            masked
+                .chunks(2)
+                .rev()
+                .map(|chunk| chunk.collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![Value::Exists(&4)], vec![Value::Exists(&3), Value::Na]]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_or_panic_out_of_bounds() {
+        let field: FieldData<u64> = vec![3u64, 1, 4].into();
+        field.permute(&[0, 1]).get_or_panic(2);
+    }
 }
@@ -2,9 +2,9 @@
 Basic heterogeneous list ([cons-list](https://en.wikipedia.org/wiki/Cons#Lists)) implementation.
 */
 
-use std::ops::Add;
+use std::ops::{Add, Sub};
 
-use typenum::{Add1, UTerm, Unsigned, B1};
+use typenum::{Add1, Sub1, UInt, UTerm, Unsigned, B1};
 
 /// The end of a heterogeneous type list.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -97,16 +97,306 @@ where
     }
 }
 
-// TODO: idea for macro framework for applying function to each value in a cons-list
-//
-// list_apply![
-//     self.frames; // list to apply this to
-//     |order: &[usize]| { /* closure to apply for recursive case */
-//         head.update_permutation(order);
-//         tail.update_permutation(order);
-//     }
-//     |order: &[usize]| {} /* base-case closure */
-// ]
+/// Trait for reversing the order of the elements of a [heterogeneous list](struct.Cons.html).
+pub trait Reverse {
+    /// The resulting cons-list type with the elements in reverse order.
+    type Output;
+    /// Reverse the order of the elements of this heterogeneous list.
+    fn reverse_list(self) -> Self::Output;
+}
+impl Reverse for Nil {
+    type Output = Nil;
+    fn reverse_list(self) -> Nil {
+        Nil
+    }
+}
+impl<H, T> Reverse for Cons<H, T>
+where
+    T: ReverseOnto<Cons<H, Nil>>,
+{
+    type Output = <T as ReverseOnto<Cons<H, Nil>>>::Output;
+    fn reverse_list(self) -> Self::Output {
+        self.tail.reverse_onto(cons(self.head, Nil))
+    }
+}
+
+/// Helper trait used by [Reverse](trait.Reverse.html) to reverse a
+/// [heterogeneous list](struct.Cons.html). `Acc` is the (reversed) list of elements which have
+/// already been processed.
+pub trait ReverseOnto<Acc> {
+    /// The resulting cons-list type with the elements of `Self` in reverse order, followed by the
+    /// elements of `Acc`.
+    type Output;
+    /// Reverse the order of the elements of this heterogeneous list onto the front of `acc`.
+    fn reverse_onto(self, acc: Acc) -> Self::Output;
+}
+impl<Acc> ReverseOnto<Acc> for Nil {
+    type Output = Acc;
+    fn reverse_onto(self, acc: Acc) -> Acc {
+        acc
+    }
+}
+impl<Acc, H, T> ReverseOnto<Acc> for Cons<H, T>
+where
+    T: ReverseOnto<Cons<H, Acc>>,
+{
+    type Output = <T as ReverseOnto<Cons<H, Acc>>>::Output;
+    fn reverse_onto(self, acc: Acc) -> Self::Output {
+        self.tail.reverse_onto(cons(self.head, acc))
+    }
+}
+
+/// Trait for taking the first `N` elements of a [heterogeneous list](struct.Cons.html), where `N`
+/// is a `typenum` unsigned integer. Only implemented for lists with at least `N` elements.
+pub trait Take<N> {
+    /// The resulting cons-list type containing the first `N` elements.
+    type Output;
+    /// Returns a heterogeneous list containing the first `N` elements of this list.
+    fn take_list(self) -> Self::Output;
+}
+impl Take<UTerm> for Nil {
+    type Output = Nil;
+    fn take_list(self) -> Nil {
+        Nil
+    }
+}
+impl<H, T> Take<UTerm> for Cons<H, T> {
+    type Output = Nil;
+    fn take_list(self) -> Nil {
+        Nil
+    }
+}
+impl<H, T, N, B> Take<UInt<N, B>> for Cons<H, T>
+where
+    UInt<N, B>: Sub<B1>,
+    T: Take<Sub1<UInt<N, B>>>,
+{
+    type Output = Cons<H, <T as Take<Sub1<UInt<N, B>>>>::Output>;
+    fn take_list(self) -> Self::Output {
+        cons(self.head, self.tail.take_list())
+    }
+}
+
+/// Trait for skipping the first `N` elements of a [heterogeneous list](struct.Cons.html), where
+/// `N` is a `typenum` unsigned integer. Only implemented for lists with at least `N` elements.
+pub trait Skip<N> {
+    /// The resulting cons-list type containing the elements after the first `N` elements.
+    type Output;
+    /// Returns a heterogeneous list containing the elements of this list after the first `N`.
+    fn skip_list(self) -> Self::Output;
+}
+impl Skip<UTerm> for Nil {
+    type Output = Nil;
+    fn skip_list(self) -> Nil {
+        Nil
+    }
+}
+impl<H, T> Skip<UTerm> for Cons<H, T> {
+    type Output = Self;
+    fn skip_list(self) -> Self {
+        self
+    }
+}
+impl<H, T, N, B> Skip<UInt<N, B>> for Cons<H, T>
+where
+    UInt<N, B>: Sub<B1>,
+    T: Skip<Sub1<UInt<N, B>>>,
+{
+    type Output = <T as Skip<Sub1<UInt<N, B>>>>::Output;
+    fn skip_list(self) -> Self::Output {
+        self.tail.skip_list()
+    }
+}
+
+/// Function which can be applied to an element of type `T` of a
+/// [heterogeneous list](struct.Cons.html) using [Map](trait.Map.html). A single function type can
+/// implement `MapFunc` for each of the element types of a list.
+pub trait MapFunc<T> {
+    /// Output of this function.
+    type Output;
+    /// Apply this function to `elem`.
+    fn call(&mut self, elem: T) -> Self::Output;
+}
+
+/// Trait for applying a function to each element of a [heterogeneous list](struct.Cons.html),
+/// producing a new heterogeneous list of the results.
+pub trait Map<F> {
+    /// The resulting cons-list type of function outputs.
+    type Output;
+    /// Apply the function `f` to each element of this heterogeneous list, in order.
+    fn map_list(self, f: &mut F) -> Self::Output;
+}
+impl<F> Map<F> for Nil {
+    type Output = Nil;
+    fn map_list(self, _f: &mut F) -> Nil {
+        Nil
+    }
+}
+impl<F, H, T> Map<F> for Cons<H, T>
+where
+    F: MapFunc<H>,
+    T: Map<F>,
+{
+    type Output = Cons<F::Output, T::Output>;
+    fn map_list(self, f: &mut F) -> Self::Output {
+        let head = f.call(self.head);
+        cons(head, self.tail.map_list(f))
+    }
+}
+
+/// Function which can be used to combine an accumulated value of type `Acc` with an element of
+/// type `T` of a [heterogeneous list](struct.Cons.html) using [Fold](trait.Fold.html).
+pub trait FoldFunc<Acc, T> {
+    /// Combine the accumulated value `acc` with `elem`, returning the new accumulated value.
+    fn call(&mut self, acc: Acc, elem: T) -> Acc;
+}
+
+/// Trait for combining the elements of a [heterogeneous list](struct.Cons.html) into a single
+/// value. Implemented both for lists (consuming their elements) and for references to lists
+/// (borrowing their elements).
+pub trait Fold<F, Acc> {
+    /// Combine each element of this heterogeneous list (in order) into an accumulated value,
+    /// starting with `init`, using the function `f`.
+    fn fold_list(self, init: Acc, f: &mut F) -> Acc;
+}
+impl<F, Acc> Fold<F, Acc> for Nil {
+    fn fold_list(self, init: Acc, _f: &mut F) -> Acc {
+        init
+    }
+}
+impl<F, Acc, H, T> Fold<F, Acc> for Cons<H, T>
+where
+    F: FoldFunc<Acc, H>,
+    T: Fold<F, Acc>,
+{
+    fn fold_list(self, init: Acc, f: &mut F) -> Acc {
+        let acc = f.call(init, self.head);
+        self.tail.fold_list(acc, f)
+    }
+}
+impl<'a, F, Acc> Fold<F, Acc> for &'a Nil {
+    fn fold_list(self, init: Acc, _f: &mut F) -> Acc {
+        init
+    }
+}
+impl<'a, F, Acc, H, T> Fold<F, Acc> for &'a Cons<H, T>
+where
+    F: FoldFunc<Acc, &'a H>,
+    &'a T: Fold<F, Acc>,
+{
+    fn fold_list(self, init: Acc, f: &mut F) -> Acc {
+        let acc = f.call(init, &self.head);
+        (&self.tail).fold_list(acc, f)
+    }
+}
+
+/// Trait for combining two [heterogeneous lists](struct.Cons.html) of the same length into a
+/// single heterogeneous list of pairs.
+pub trait Zip<List> {
+    /// The resulting cons-list of `(left, right)` element pairs.
+    type Zipped;
+    /// Pair each element of this heterogeneous list with the corresponding element of `list`.
+    fn zip_list(self, list: List) -> Self::Zipped;
+}
+impl Zip<Nil> for Nil {
+    type Zipped = Nil;
+    fn zip_list(self, _list: Nil) -> Nil {
+        Nil
+    }
+}
+impl<H, T, OtherH, OtherT> Zip<Cons<OtherH, OtherT>> for Cons<H, T>
+where
+    T: Zip<OtherT>,
+{
+    type Zipped = Cons<(H, OtherH), T::Zipped>;
+    fn zip_list(self, list: Cons<OtherH, OtherT>) -> Self::Zipped {
+        cons((self.head, list.head), self.tail.zip_list(list.tail))
+    }
+}
+
+/// Trait for borrowing each element of a [heterogeneous list](struct.Cons.html), so that a list
+/// can be [mapped](trait.Map.html) or [folded](trait.Fold.html) without consuming it.
+pub trait ListRefs<'a> {
+    /// The resulting cons-list of element references.
+    type Refs;
+    /// Returns a heterogeneous list of references to the elements of this list.
+    fn list_refs(&'a self) -> Self::Refs;
+}
+impl<'a> ListRefs<'a> for Nil {
+    type Refs = Nil;
+    fn list_refs(&'a self) -> Nil {
+        Nil
+    }
+}
+impl<'a, H, T> ListRefs<'a> for Cons<H, T>
+where
+    H: 'a,
+    T: 'a + ListRefs<'a>,
+{
+    type Refs = Cons<&'a H, T::Refs>;
+    fn list_refs(&'a self) -> Self::Refs {
+        cons(&self.head, self.tail.list_refs())
+    }
+}
+
+/// Trait for mutably borrowing each element of a [heterogeneous list](struct.Cons.html), so that
+/// the elements of a list can be updated in place using [Map](trait.Map.html).
+pub trait ListMuts<'a> {
+    /// The resulting cons-list of mutable element references.
+    type Muts;
+    /// Returns a heterogeneous list of mutable references to the elements of this list.
+    fn list_muts(&'a mut self) -> Self::Muts;
+}
+impl<'a> ListMuts<'a> for Nil {
+    type Muts = Nil;
+    fn list_muts(&'a mut self) -> Nil {
+        Nil
+    }
+}
+impl<'a, H, T> ListMuts<'a> for Cons<H, T>
+where
+    H: 'a,
+    T: 'a + ListMuts<'a>,
+{
+    type Muts = Cons<&'a mut H, T::Muts>;
+    fn list_muts(&'a mut self) -> Self::Muts {
+        cons(&mut self.head, self.tail.list_muts())
+    }
+}
+
+/// Trait for collecting the results of applying a function to each element of a
+/// [heterogeneous list](struct.Cons.html) into a `Vec`, when the function produces the same
+/// output type `T` for each element. This allows the elements of a heterogeneous list to be
+/// iterated over as values of a common type.
+pub trait MapToVec<F, T> {
+    /// Apply the function `f` to each element of this heterogeneous list, pushing the results
+    /// (in order) onto `out`.
+    fn map_into_vec(self, f: &mut F, out: &mut Vec<T>);
+
+    /// Apply the function `f` to each element of this heterogeneous list, collecting the results
+    /// (in order) into a `Vec`.
+    fn map_to_vec(self, f: &mut F) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        let mut out = vec![];
+        self.map_into_vec(f, &mut out);
+        out
+    }
+}
+impl<F, T> MapToVec<F, T> for Nil {
+    fn map_into_vec(self, _f: &mut F, _out: &mut Vec<T>) {}
+}
+impl<F, T, H, Tail> MapToVec<F, T> for Cons<H, Tail>
+where
+    F: MapFunc<H, Output = T>,
+    Tail: MapToVec<F, T>,
+{
+    fn map_into_vec(self, f: &mut F, out: &mut Vec<T>) {
+        out.push(f.call(self.head));
+        self.tail.map_into_vec(f, out);
+    }
+}
 
 /// Trait providing length (either compile-time or runtime) details of a list.
 pub trait Len {
@@ -143,3 +433,147 @@ macro_rules! length {
         <<$list as $crate::cons::Len>::Len as typenum::Unsigned>::USIZE
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // formats each element of a list
+    struct Describe;
+    impl<'a> MapFunc<&'a u64> for Describe {
+        type Output = String;
+        fn call(&mut self, elem: &'a u64) -> String {
+            format!("u64 {}", elem)
+        }
+    }
+    impl<'a> MapFunc<&'a f64> for Describe {
+        type Output = String;
+        fn call(&mut self, elem: &'a f64) -> String {
+            format!("f64 {:.1}", elem)
+        }
+    }
+    impl<'a> MapFunc<&'a &'static str> for Describe {
+        type Output = String;
+        fn call(&mut self, elem: &'a &'static str) -> String {
+            format!("str {}", elem)
+        }
+    }
+
+    // doubles numeric elements in place, counting the number of updated elements
+    struct Double {
+        count: usize,
+    }
+    impl<'a> MapFunc<&'a mut u64> for Double {
+        type Output = ();
+        fn call(&mut self, elem: &'a mut u64) {
+            *elem *= 2;
+            self.count += 1;
+        }
+    }
+    impl<'a> MapFunc<&'a mut f64> for Double {
+        type Output = ();
+        fn call(&mut self, elem: &'a mut f64) {
+            *elem *= 2.0;
+            self.count += 1;
+        }
+    }
+    impl<'a> MapFunc<&'a mut &'static str> for Double {
+        type Output = ();
+        fn call(&mut self, _elem: &'a mut &'static str) {}
+    }
+
+    // converts numeric elements to `f64`
+    struct ToF64;
+    impl MapFunc<u64> for ToF64 {
+        type Output = f64;
+        fn call(&mut self, elem: u64) -> f64 {
+            elem as f64
+        }
+    }
+    impl MapFunc<f64> for ToF64 {
+        type Output = f64;
+        fn call(&mut self, elem: f64) -> f64 {
+            elem
+        }
+    }
+
+    // sums numeric elements
+    struct Sum;
+    impl FoldFunc<f64, u64> for Sum {
+        fn call(&mut self, acc: f64, elem: u64) -> f64 {
+            acc + elem as f64
+        }
+    }
+    impl FoldFunc<f64, f64> for Sum {
+        fn call(&mut self, acc: f64, elem: f64) -> f64 {
+            acc + elem
+        }
+    }
+    impl<'a> FoldFunc<f64, &'a u64> for Sum {
+        fn call(&mut self, acc: f64, elem: &'a u64) -> f64 {
+            acc + *elem as f64
+        }
+    }
+    impl<'a> FoldFunc<f64, &'a f64> for Sum {
+        fn call(&mut self, acc: f64, elem: &'a f64) -> f64 {
+            acc + *elem
+        }
+    }
+
+    #[test]
+    fn reverse_take_skip() {
+        use typenum::{U0, U1, U2, U3};
+
+        let list = cons(1u64, cons("b", cons(3.0f64, Nil)));
+        assert_eq!(
+            list.clone().reverse_list(),
+            cons(3.0f64, cons("b", cons(1u64, Nil)))
+        );
+        assert_eq!(Nil.reverse_list(), Nil);
+
+        assert_eq!(Take::<U0>::take_list(list.clone()), Nil);
+        assert_eq!(
+            Take::<U2>::take_list(list.clone()),
+            cons(1u64, cons("b", Nil))
+        );
+        assert_eq!(Take::<U3>::take_list(list.clone()), list);
+
+        assert_eq!(Skip::<U0>::skip_list(list.clone()), list);
+        assert_eq!(
+            Skip::<U1>::skip_list(list.clone()),
+            cons("b", cons(3.0f64, Nil))
+        );
+        assert_eq!(Skip::<U3>::skip_list(list.clone()), Nil);
+
+        // type-level slicing of a list
+        type List = Cons<u64, Cons<&'static str, Cons<f64, Nil>>>;
+        type Middle = <<List as Skip<U1>>::Output as Take<U1>>::Output;
+        assert_eq!(<Middle as Len>::len(), 1);
+    }
+
+    #[test]
+    fn map_fold_zip() {
+        let mut list = cons(3u64, cons(1.5f64, cons("a", Nil)));
+        assert_eq!(
+            list.list_refs().map_to_vec(&mut Describe),
+            vec!["u64 3", "f64 1.5", "str a"]
+        );
+
+        let mut double = Double { count: 0 };
+        list.list_muts().map_list(&mut double);
+        assert_eq!(double.count, 2);
+        assert_eq!(list, cons(6u64, cons(3.0f64, cons("a", Nil))));
+
+        let numbers = cons(6u64, cons(3.0f64, Nil));
+        assert_eq!(
+            numbers.clone().map_list(&mut ToF64),
+            cons(6.0, cons(3.0, Nil))
+        );
+        assert_eq!(numbers.clone().fold_list(0.5, &mut Sum), 9.5);
+        assert_eq!((&numbers).fold_list(0.5, &mut Sum), 9.5);
+        assert_eq!(Nil.fold_list(0.5, &mut Sum), 0.5);
+
+        let zipped = numbers.zip_list(cons("b", cons(true, Nil)));
+        assert_eq!(zipped, cons((6u64, "b"), cons((3.0f64, true), Nil)));
+    }
+}
@@ -0,0 +1,761 @@
+/*!
+Date and time field data.
+
+A [DateTime](struct.DateTime.html) is an instant in time (with nanosecond precision). It can be
+loaded from CSV files (parsed from ISO 8601-style strings such as `2018-07-04`,
+`2018-07-04 12:30:00`, or `2018-07-04T12:30:00.250-05:00`), compared, sorted, and used as a join
+or group-by key. CSV columns containing Unix timestamps (in seconds or milliseconds) can also be
+loaded as `DateTime` fields; see the [schema](../macro.schema.html) macro.
+
+Each `DateTime` is presented (displayed, and broken into calendar components) in a fixed
+[UtcOffset](struct.UtcOffset.html) from UTC. Parsed values are normalized to UTC, so values
+recorded in different timezones can be compared, joined, and ordered directly; comparisons only
+consider the instant in time, never the offset. Values can be presented in a different timezone
+with [to_timezone](struct.DateTime.html#method.to_timezone).
+
+The calendar components of a `DateTime` field can be extracted into numeric fields using the
+[dt](trait.DateTimeIndex.html#method.dt) adapter on any field selection containing `DateTime`
+values:
+
+```ignore
+let years: FieldData<i32> = dv.field::<Timestamp>().dt().year();
+```
+*/
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use num_traits::AsPrimitive;
+#[cfg(feature = "serialize")]
+use serde::ser::{Serialize, Serializer};
+
+use access::DataIndex;
+use field::FieldData;
+
+const SECS_PER_DAY: i64 = 86_400;
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// A fixed offset from UTC (such as `+05:30` or `-08:00`) in which a
+/// [DateTime](struct.DateTime.html) is presented. The default `UtcOffset` is UTC itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UtcOffset {
+    // seconds east of UTC
+    secs: i32,
+}
+
+impl UtcOffset {
+    /// Returns the offset of UTC itself.
+    pub fn utc() -> UtcOffset {
+        UtcOffset::default()
+    }
+    /// Creates a new `UtcOffset` which is `secs` seconds east of (ahead of) UTC. Returns `None` if
+    /// the offset is not strictly less than one day in either direction.
+    pub fn east(secs: i32) -> Option<UtcOffset> {
+        if i64::from(secs).abs() < SECS_PER_DAY {
+            Some(UtcOffset { secs })
+        } else {
+            None
+        }
+    }
+    /// Creates a new `UtcOffset` which is `secs` seconds west of (behind) UTC. Returns `None` if
+    /// the offset is not strictly less than one day in either direction.
+    pub fn west(secs: i32) -> Option<UtcOffset> {
+        secs.checked_neg().and_then(UtcOffset::east)
+    }
+    /// Returns the number of seconds east of UTC of this offset (negative for offsets west of UTC).
+    pub fn local_minus_utc(&self) -> i32 {
+        self.secs
+    }
+}
+
+impl Display for UtcOffset {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let sign = if self.secs < 0 { '-' } else { '+' };
+        let secs = self.secs.abs();
+        write!(f, "{}{:02}:{:02}", sign, secs / 3600, secs % 3600 / 60)?;
+        if secs % 60 != 0 {
+            write!(f, ":{:02}", secs % 60)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calendar period used to bucket [DateTime](struct.DateTime.html) values with
+/// [period_start](struct.DateTime.html#method.period_start).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    /// Calendar day.
+    Day,
+    /// ISO 8601 week, starting on Monday.
+    Week,
+    /// Calendar month.
+    Month,
+    /// Calendar quarter (starting in January, April, July, or October).
+    Quarter,
+    /// Calendar year.
+    Year,
+}
+
+/// Unit of a Unix timestamp: a number of seconds or milliseconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochUnit {
+    /// Seconds since the Unix epoch.
+    Seconds,
+    /// Milliseconds since the Unix epoch.
+    Milliseconds,
+}
+
+/// An instant in time with nanosecond precision, presented in a fixed offset from UTC. The default
+/// `DateTime` is the Unix epoch (`1970-01-01 00:00:00` UTC).
+///
+/// Equality, ordering, and hashing only consider the instant in time: the same instant presented in
+/// two different timezones compares equal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateTime {
+    // seconds since the Unix epoch
+    secs: i64,
+    // nanoseconds since the start of the second (always less than one billion)
+    nanos: u32,
+    // offset used for presentation and calendar components
+    offset: UtcOffset,
+}
+
+impl DateTime {
+    /// Creates a new `DateTime` from the number of seconds since the Unix epoch
+    /// (`1970-01-01 00:00:00` UTC).
+    pub fn from_timestamp(secs: i64) -> DateTime {
+        DateTime {
+            secs,
+            nanos: 0,
+            offset: UtcOffset::utc(),
+        }
+    }
+    /// Creates a new `DateTime` from the number of milliseconds since the Unix epoch.
+    pub fn from_timestamp_millis(millis: i64) -> DateTime {
+        DateTime::from_timestamp_nanos(millis as i128 * 1_000_000)
+    }
+    /// Creates a new `DateTime` from a Unix timestamp `value` with the unit `unit`.
+    pub fn from_epoch(value: i64, unit: EpochUnit) -> DateTime {
+        match unit {
+            EpochUnit::Seconds => DateTime::from_timestamp(value),
+            EpochUnit::Milliseconds => DateTime::from_timestamp_millis(value),
+        }
+    }
+    // creates a new `DateTime` from the number of nanoseconds since the Unix epoch
+    fn from_timestamp_nanos(nanos: i128) -> DateTime {
+        DateTime {
+            secs: nanos.div_euclid(NANOS_PER_SEC as i128) as i64,
+            nanos: nanos.rem_euclid(NANOS_PER_SEC as i128) as u32,
+            offset: UtcOffset::utc(),
+        }
+    }
+    /// Creates a new `DateTime` from its calendar date and time of day in UTC. Returns `None` if
+    /// any component is out of range (e.g. February 30th, or an hour of 24).
+    pub fn from_ymd_hms(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> Option<DateTime> {
+        if month < 1
+            || month > 12
+            || day < 1
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(DateTime::from_timestamp(
+            days * SECS_PER_DAY + i64::from(hour * 3600 + minute * 60 + second),
+        ))
+    }
+    // returns this `DateTime` with the nanosecond component `nanos`
+    fn with_nanos(self, nanos: u32) -> DateTime {
+        debug_assert!(i64::from(nanos) < NANOS_PER_SEC);
+        DateTime { nanos, ..self }
+    }
+
+    /// Returns the number of (whole) seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.secs
+    }
+    /// Returns the number of (whole) milliseconds since the Unix epoch.
+    pub fn timestamp_millis(&self) -> i64 {
+        self.secs * 1000 + i64::from(self.nanos / 1_000_000)
+    }
+    /// Returns the number of nanoseconds since the start of the second.
+    pub fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Returns the offset from UTC in which this `DateTime` is presented.
+    pub fn offset(&self) -> UtcOffset {
+        self.offset
+    }
+    /// Returns the same instant in time, presented in the timezone with offset `offset`. Calendar
+    /// components (such as [hour](#method.hour) or [day](#method.day)) of the returned `DateTime`
+    /// are computed in that timezone.
+    pub fn to_timezone(&self, offset: UtcOffset) -> DateTime {
+        DateTime { offset, ..*self }
+    }
+    /// Returns the same instant in time, presented in UTC.
+    pub fn to_utc(&self) -> DateTime {
+        self.to_timezone(UtcOffset::utc())
+    }
+
+    // number of days since the Unix epoch, and number of seconds since the start of that day, in
+    // the presentation timezone
+    fn days_and_secs(&self) -> (i64, u32) {
+        let local_secs = self.secs + i64::from(self.offset.secs);
+        (
+            local_secs.div_euclid(SECS_PER_DAY),
+            local_secs.rem_euclid(SECS_PER_DAY) as u32,
+        )
+    }
+    // calendar year, month, and day
+    fn ymd(&self) -> (i32, u32, u32) {
+        civil_from_days(self.days_and_secs().0)
+    }
+
+    /// Returns the calendar year.
+    pub fn year(&self) -> i32 {
+        self.ymd().0
+    }
+    /// Returns the month of the year, starting at `1` (January).
+    pub fn month(&self) -> u32 {
+        self.ymd().1
+    }
+    /// Returns the day of the month, starting at `1`.
+    pub fn day(&self) -> u32 {
+        self.ymd().2
+    }
+    /// Returns the day of the year, starting at `1` (January 1st).
+    pub fn ordinal(&self) -> u32 {
+        let (year, _, _) = self.ymd();
+        (self.days_and_secs().0 - days_from_civil(year, 1, 1)) as u32 + 1
+    }
+    /// Returns the ISO 8601 day of the week, from `1` (Monday) to `7` (Sunday).
+    pub fn weekday(&self) -> u32 {
+        // the Unix epoch was a Thursday
+        (self.days_and_secs().0 + 3).rem_euclid(7) as u32 + 1
+    }
+    /// Returns the hour of the day, from `0` to `23`.
+    pub fn hour(&self) -> u32 {
+        self.days_and_secs().1 / 3600
+    }
+    /// Returns the minute of the hour, from `0` to `59`.
+    pub fn minute(&self) -> u32 {
+        self.days_and_secs().1 % 3600 / 60
+    }
+    /// Returns the second of the minute, from `0` to `59`.
+    pub fn second(&self) -> u32 {
+        self.days_and_secs().1 % 60
+    }
+
+    /// Returns the start (midnight on the first day) of the calendar period `period` containing
+    /// this `DateTime`. The period is determined in, and the returned `DateTime` is presented in,
+    /// the presentation timezone of this `DateTime`.
+    pub fn period_start(&self, period: Period) -> DateTime {
+        let days = self.days_and_secs().0;
+        let (year, month, _) = self.ymd();
+        let start_days = match period {
+            Period::Day => days,
+            Period::Week => days - i64::from(self.weekday() - 1),
+            Period::Month => days_from_civil(year, month, 1),
+            Period::Quarter => days_from_civil(year, (month - 1) / 3 * 3 + 1, 1),
+            Period::Year => days_from_civil(year, 1, 1),
+        };
+        DateTime {
+            secs: start_days * SECS_PER_DAY - i64::from(self.offset.secs),
+            nanos: 0,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Converts a `DateTime` into the (fractional) number of seconds since the Unix epoch, so that
+/// `DateTime` fields can be used with numeric operations on ordered fields (such as
+/// [gaps](../view/struct.DataView.html#method.gaps) or
+/// [group_consecutive_within](../view/struct.DataView.html#method.group_consecutive_within)).
+impl AsPrimitive<f64> for DateTime {
+    fn as_(self) -> f64 {
+        self.secs as f64 + f64::from(self.nanos) / NANOS_PER_SEC as f64
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &DateTime) -> bool {
+        (self.secs, self.nanos) == (other.secs, other.nanos)
+    }
+}
+impl Eq for DateTime {}
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &DateTime) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for DateTime {
+    fn cmp(&self, other: &DateTime) -> Ordering {
+        (self.secs, self.nanos).cmp(&(other.secs, other.nanos))
+    }
+}
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.secs.hash(state);
+        self.nanos.hash(state);
+    }
+}
+
+// whether or not `year` is a leap year in the proleptic Gregorian calendar
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// number of days in `month` of `year`
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// number of days since the Unix epoch of the specified date in the proleptic Gregorian calendar
+// (see http://howardhinnant.github.io/date_algorithms.html)
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - if month <= 2 { 1 } else { 0 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// date in the proleptic Gregorian calendar of the specified number of days since the Unix epoch
+// (inverse of `days_from_civil`)
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            self.hour(),
+            self.minute(),
+            self.second()
+        )?;
+        if self.nanos != 0 {
+            let frac = format!("{:09}", self.nanos);
+            write!(f, ".{}", frac.trim_end_matches('0'))?;
+        }
+        if self.offset != UtcOffset::utc() {
+            write!(f, "{}", self.offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error which occurs when parsing a [DateTime](struct.DateTime.html) from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateTimeError {
+    input: String,
+}
+
+impl Display for ParseDateTimeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid datetime: '{}'", self.input)
+    }
+}
+
+impl Error for ParseDateTimeError {
+    fn description(&self) -> &str {
+        "invalid datetime"
+    }
+}
+
+// parses an unsigned integer consisting of exactly `len` ASCII digits
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+// parses a date of the form `YYYY-MM-DD`, returning the year, month, and day
+fn parse_date(s: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = s.split('-');
+    let year = parse_digits(parts.next()?, 4)? as i32;
+    let month = parse_digits(parts.next()?, 2)?;
+    let day = parse_digits(parts.next()?, 2)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((year, month, day))
+}
+
+// parses a time of day of the form `HH:MM`, `HH:MM:SS`, or `HH:MM:SS.fff` (with up to nine
+// fractional digits), returning the hour, minute, second, and nanoseconds
+fn parse_time(s: &str) -> Option<(u32, u32, u32, u32)> {
+    let mut parts = s.split(':');
+    let hour = parse_digits(parts.next()?, 2)?;
+    let minute = parse_digits(parts.next()?, 2)?;
+    let (second, nanos) = match parts.next() {
+        Some(seconds) => {
+            let mut seconds = seconds.splitn(2, '.');
+            let second = parse_digits(seconds.next()?, 2)?;
+            let nanos = match seconds.next() {
+                Some(frac) => {
+                    if frac.is_empty() || frac.len() > 9 {
+                        return None;
+                    }
+                    parse_digits(frac, frac.len())? * 10u32.pow(9 - frac.len() as u32)
+                }
+                None => 0,
+            };
+            (second, nanos)
+        }
+        None => (0, 0),
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((hour, minute, second, nanos))
+}
+
+// parses an offset from UTC of the form `+HH:MM`, `+HHMM`, or `+HH` (or the same with `-`)
+fn parse_offset(s: &str) -> Option<UtcOffset> {
+    // offsets are sliced by byte index below, which is only valid for ASCII strings
+    if !s.is_ascii() {
+        return None;
+    }
+    let (sign, s) = match s.chars().next()? {
+        '+' => (1, &s[1..]),
+        '-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match s.len() {
+        2 => (s, "00"),
+        4 => (&s[..2], &s[2..]),
+        5 if &s[2..3] == ":" => (&s[..2], &s[3..]),
+        _ => return None,
+    };
+    let hours = parse_digits(hours, 2)?;
+    let minutes = parse_digits(minutes, 2)?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    UtcOffset::east(sign * (hours * 3600 + minutes * 60) as i32)
+}
+
+impl FromStr for DateTime {
+    type Err = ParseDateTimeError;
+
+    /// Parses a `DateTime` from a string of the form `YYYY-MM-DD`, optionally followed by a time
+    /// of day (separated by `T` or a space) of the form `HH:MM`, `HH:MM:SS`, or `HH:MM:SS.fff`,
+    /// optionally followed by `Z` or an offset from UTC (`+HH:MM`, `+HHMM`, `+HH`, or the same
+    /// with `-`). Times without an offset are taken to be in UTC.
+    ///
+    /// The parsed `DateTime` is normalized to UTC: `2018-07-04 08:30-04:00` is parsed as (and
+    /// presented as) `2018-07-04 12:30:00`.
+    fn from_str(s: &str) -> Result<DateTime, ParseDateTimeError> {
+        let err = || ParseDateTimeError {
+            input: s.to_string(),
+        };
+        let trimmed = s.trim();
+        let (date, time) = match trimmed.find(|c| c == 'T' || c == ' ') {
+            Some(idx) => (&trimmed[..idx], Some(&trimmed[idx + 1..])),
+            None => (trimmed, None),
+        };
+        let (year, month, day) = parse_date(date).ok_or_else(err)?;
+        let ((hour, minute, second, nanos), offset) = match time {
+            Some(time) => {
+                let (time, offset) = if time.ends_with('Z') {
+                    (&time[..time.len() - 1], UtcOffset::utc())
+                } else if let Some(idx) = time.find(|c| c == '+' || c == '-') {
+                    (&time[..idx], parse_offset(&time[idx..]).ok_or_else(err)?)
+                } else {
+                    (time, UtcOffset::utc())
+                };
+                (parse_time(time).ok_or_else(err)?, offset)
+            }
+            None => ((0, 0, 0, 0), UtcOffset::utc()),
+        };
+        DateTime::from_ymd_hms(year, month, day, hour, minute, second)
+            .map(|datetime| DateTime {
+                secs: datetime.secs - i64::from(offset.secs),
+                ..datetime.with_nanos(nanos)
+            })
+            .ok_or_else(err)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl Serialize for DateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Adapter for extracting calendar components from a field of [DateTime](struct.DateTime.html)
+/// values. Each component is returned as a new numeric field with the same number of records, in
+/// which missing datetimes result in missing components. Created with
+/// [dt](trait.DateTimeIndex.html#method.dt).
+#[derive(Debug)]
+pub struct Dt<'a, DI: 'a> {
+    data: &'a DI,
+}
+
+/// Trait providing the [dt](#method.dt) adapter for fields (implementing
+/// [DataIndex](../access/trait.DataIndex.html)) containing [DateTime](struct.DateTime.html) values.
+pub trait DateTimeIndex: DataIndex<DType = DateTime> + Sized {
+    /// Returns an adapter for extracting calendar components from this field.
+    fn dt(&self) -> Dt<Self> {
+        Dt { data: self }
+    }
+}
+impl<DI> DateTimeIndex for DI where DI: DataIndex<DType = DateTime> {}
+
+impl<'a, DI> Dt<'a, DI>
+where
+    DI: DataIndex<DType = DateTime>,
+{
+    // extracts the component computed by `f` from each datetime
+    fn component<T, F>(&self, f: F) -> FieldData<T>
+    where
+        T: Debug + Default,
+        F: Fn(&DateTime) -> T,
+    {
+        self.data.iter().map(|value| value.map(&f)).collect()
+    }
+
+    /// Returns a field containing the calendar year of each datetime.
+    pub fn year(&self) -> FieldData<i32> {
+        self.component(DateTime::year)
+    }
+    /// Returns a field containing the month (`1` to `12`) of each datetime.
+    pub fn month(&self) -> FieldData<u32> {
+        self.component(DateTime::month)
+    }
+    /// Returns a field containing the day of the month (starting at `1`) of each datetime.
+    pub fn day(&self) -> FieldData<u32> {
+        self.component(DateTime::day)
+    }
+    /// Returns a field containing the day of the year (starting at `1`) of each datetime.
+    pub fn ordinal(&self) -> FieldData<u32> {
+        self.component(DateTime::ordinal)
+    }
+    /// Returns a field containing the ISO 8601 day of the week (`1` for Monday to `7` for Sunday)
+    /// of each datetime.
+    pub fn weekday(&self) -> FieldData<u32> {
+        self.component(DateTime::weekday)
+    }
+    /// Returns a field containing the hour of the day (`0` to `23`) of each datetime.
+    pub fn hour(&self) -> FieldData<u32> {
+        self.component(DateTime::hour)
+    }
+    /// Returns a field containing the minute of the hour (`0` to `59`) of each datetime.
+    pub fn minute(&self) -> FieldData<u32> {
+        self.component(DateTime::minute)
+    }
+    /// Returns a field containing the second of the minute (`0` to `59`) of each datetime.
+    pub fn second(&self) -> FieldData<u32> {
+        self.component(DateTime::second)
+    }
+    /// Returns a field containing the start of the calendar period `period` containing each
+    /// datetime (see [period_start](struct.DateTime.html#method.period_start)).
+    pub fn period_start(&self, period: Period) -> FieldData<DateTime> {
+        self.component(|value| value.period_start(period))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use value::Value;
+
+    #[test]
+    fn calendar() {
+        let epoch = DateTime::default();
+        assert_eq!(epoch.to_string(), "1970-01-01 00:00:00");
+        assert_eq!(epoch.weekday(), 4);
+
+        let dt = DateTime::from_ymd_hms(2016, 2, 29, 23, 59, 58).unwrap();
+        assert_eq!(dt.timestamp(), 1_456_790_398);
+        assert_eq!(
+            (dt.year(), dt.month(), dt.day(), dt.ordinal(), dt.weekday()),
+            (2016, 2, 29, 60, 1)
+        );
+        assert_eq!((dt.hour(), dt.minute(), dt.second()), (23, 59, 58));
+        assert_eq!(DateTime::from_ymd_hms(2015, 2, 29, 0, 0, 0), None);
+
+        // before the epoch
+        assert_eq!(
+            DateTime::from_epoch(1_456_790_398_500, EpochUnit::Milliseconds).to_string(),
+            "2016-02-29 23:59:58.5"
+        );
+        let dt = DateTime::from_timestamp_millis(-1);
+        assert_eq!(dt.to_string(), "1969-12-31 23:59:59.999");
+        assert_eq!(dt.timestamp_millis(), -1);
+        assert_eq!(dt.ordinal(), 365);
+        let dt = DateTime::from_ymd_hms(1600, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!((dt.year(), dt.month(), dt.day()), (1600, 3, 1));
+    }
+
+    #[test]
+    fn parse() {
+        let parse = |s: &str| s.parse::<DateTime>();
+        assert_eq!(
+            parse("2018-07-04").unwrap(),
+            DateTime::from_ymd_hms(2018, 7, 4, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse("2018-07-04 12:30").unwrap(),
+            DateTime::from_ymd_hms(2018, 7, 4, 12, 30, 0).unwrap()
+        );
+        let dt = parse("2018-07-04T12:30:05.25Z").unwrap();
+        assert_eq!(dt.subsec_nanos(), 250_000_000);
+        assert_eq!(dt.to_string(), "2018-07-04 12:30:05.25");
+        assert_eq!(parse(&dt.to_string()).unwrap(), dt);
+
+        for invalid in &[
+            "",
+            "2018-7-4",
+            "2018-02-30",
+            "2018-07-04 24:00",
+            "2018-07-04T12",
+        ] {
+            assert!(parse(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn timezones() {
+        let utc = "2018-07-04 12:30:00Z".parse::<DateTime>().unwrap();
+        for local in &[
+            "2018-07-04 08:30-04:00",
+            "2018-07-04T18:00:00+05:30",
+            "2018-07-05 00:30+1200",
+            "2018-07-03T23:30-13",
+        ] {
+            let parsed = local.parse::<DateTime>().unwrap();
+            assert_eq!(parsed, utc);
+            assert_eq!(parsed.offset(), UtcOffset::utc());
+            assert_eq!(parsed.to_string(), "2018-07-04 12:30:00");
+        }
+        assert!("2018-07-04 12:30+24:00".parse::<DateTime>().is_err());
+        assert!("2018-07-04 12:30+5".parse::<DateTime>().is_err());
+        assert!("2018-07-04T08:30+0é0".parse::<DateTime>().is_err());
+        assert!("2018-07-04T08:30-é".parse::<DateTime>().is_err());
+
+        let offset = UtcOffset::west(10 * 3600).unwrap();
+        let local = utc.to_timezone(offset);
+        assert_eq!(local, utc);
+        assert_eq!(local.timestamp(), utc.timestamp());
+        assert_eq!(local.to_string(), "2018-07-04 02:30:00-10:00");
+        assert_eq!(local.to_string().parse::<DateTime>().unwrap(), utc);
+        assert_eq!((local.day(), local.hour()), (4, 2));
+        let local = utc.to_timezone(UtcOffset::east(14 * 3600).unwrap());
+        assert_eq!((local.day(), local.hour(), local.weekday()), (5, 2, 4));
+        assert_eq!(local.to_utc().to_string(), "2018-07-04 12:30:00");
+
+        assert_eq!(UtcOffset::east(86_400), None);
+        assert_eq!(
+            UtcOffset::east(5 * 3600 + 1800).unwrap().to_string(),
+            "+05:30"
+        );
+    }
+
+    #[test]
+    fn period_start() {
+        let dt = "2018-08-16 13:45:10.5".parse::<DateTime>().unwrap();
+        let start = |period| dt.period_start(period).to_string();
+        assert_eq!(start(Period::Day), "2018-08-16 00:00:00");
+        assert_eq!(start(Period::Week), "2018-08-13 00:00:00");
+        assert_eq!(start(Period::Month), "2018-08-01 00:00:00");
+        assert_eq!(start(Period::Quarter), "2018-07-01 00:00:00");
+        assert_eq!(start(Period::Year), "2018-01-01 00:00:00");
+
+        // ISO weeks can start in the previous year
+        let dt = "2019-01-01 12:00".parse::<DateTime>().unwrap();
+        assert_eq!(
+            dt.period_start(Period::Week).to_string(),
+            "2018-12-31 00:00:00"
+        );
+
+        // periods are determined in the presentation timezone
+        let local = dt.to_timezone(UtcOffset::east(13 * 3600).unwrap());
+        assert_eq!(
+            local.period_start(Period::Day).to_string(),
+            "2019-01-02 00:00:00+13:00"
+        );
+        assert_eq!(
+            dt.to_timezone(UtcOffset::west(13 * 3600).unwrap())
+                .period_start(Period::Year)
+                .to_string(),
+            "2018-01-01 00:00:00-13:00"
+        );
+    }
+
+    #[test]
+    fn components() {
+        let field = FieldData::from_field_vec(vec![
+            Value::Exists("2018-07-04 12:30:00".parse::<DateTime>().unwrap()),
+            Value::Na,
+            Value::Exists("2019-12-31 06:00:00".parse::<DateTime>().unwrap()),
+        ]);
+        assert_eq!(
+            field.dt().year().to_value_vec(),
+            vec![Value::Exists(2018), Value::Na, Value::Exists(2019)]
+        );
+        assert_eq!(
+            field.dt().month().to_value_vec(),
+            vec![Value::Exists(7), Value::Na, Value::Exists(12)]
+        );
+        assert_eq!(
+            field.dt().weekday().to_value_vec(),
+            vec![Value::Exists(3), Value::Na, Value::Exists(2)]
+        );
+        assert_eq!(
+            field.dt().hour().to_value_vec(),
+            vec![Value::Exists(12), Value::Na, Value::Exists(6)]
+        );
+        assert_eq!(
+            field.dt().ordinal().to_value_vec(),
+            vec![Value::Exists(185), Value::Na, Value::Exists(365)]
+        );
+    }
+}
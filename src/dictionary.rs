@@ -0,0 +1,263 @@
+/*!
+Dictionary-encoded (categorical) field data.
+
+A [DictData](struct.DictData.html) stores a field as a dictionary of its distinct values along
+with an integer code for each record, which reduces the memory used by fields with few distinct
+values (such as category names) and allows values to be compared by code rather than by value. It
+implements [DataIndex](../access/trait.DataIndex.html), looking up values in the dictionary on
+access, so it can be used anywhere other field data can be read.
+
+Equality joins between dictionary-encoded keys (see
+[join_indices](struct.DictData.html#method.join_indices)) compare integer codes instead of
+values. If both sides share the same dictionary (see
+[encode_shared](struct.DictData.html#method.encode_shared)), codes are compared directly;
+otherwise, one dictionary is first remapped onto the other.
+
+Fields within a [DataStore](../store/struct.DataStore.html) are always stored as
+[FieldData](../field/struct.FieldData.html), so joins between `DataView`s (see
+[SortMergeJoin](../join/trait.SortMergeJoin.html)) do not use dictionary encoding;
+`join_indices` is a helper to be called directly on two dictionary-encoded fields.
+*/
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use access::DataIndex;
+use error::*;
+use field::FieldData;
+use value::Value;
+
+/// Dictionary-encoded field data. Each record is stored as a code into a dictionary of distinct
+/// values (or as NA).
+#[derive(Debug, Clone)]
+pub struct DictData<T> {
+    // distinct values, shared among fields encoded with the same dictionary
+    dictionary: Rc<Vec<T>>,
+    // index into `dictionary` of each record's value
+    codes: Vec<Value<usize>>,
+}
+
+impl<T> DictData<T> {
+    /// Dictionary-encodes the values of `data`. The dictionary contains the distinct values of
+    /// `data` in order of first appearance.
+    pub fn encode<DI>(data: &DI) -> DictData<T>
+    where
+        DI: DataIndex<DType = T>,
+        T: Hash + Eq + Clone,
+    {
+        DictData::encode_into(data, vec![])
+    }
+
+    /// Dictionary-encodes the values of `data` using the dictionary of `other`, so that joins
+    /// between the two fields can compare codes without remapping. If `data` contains values not
+    /// found in the dictionary of `other`, a new dictionary is created (containing the values of
+    /// the dictionary of `other`, followed by the new values), and the dictionary is no longer
+    /// shared.
+    pub fn encode_shared<DI>(data: &DI, other: &DictData<T>) -> DictData<T>
+    where
+        DI: DataIndex<DType = T>,
+        T: Hash + Eq + Clone,
+    {
+        let encoded = DictData::encode_into(data, (*other.dictionary).clone());
+        if encoded.dictionary.len() == other.dictionary.len() {
+            DictData {
+                dictionary: other.dictionary.clone(),
+                codes: encoded.codes,
+            }
+        } else {
+            encoded
+        }
+    }
+
+    fn encode_into<DI>(data: &DI, mut dictionary: Vec<T>) -> DictData<T>
+    where
+        DI: DataIndex<DType = T>,
+        T: Hash + Eq + Clone,
+    {
+        let mut lookup = dictionary
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(code, value)| (value, code))
+            .collect::<HashMap<_, _>>();
+        let codes = data
+            .iter()
+            .map(|value| {
+                value.map(|value| {
+                    if let Some(&code) = lookup.get(value) {
+                        return code;
+                    }
+                    dictionary.push(value.clone());
+                    lookup.insert(value.clone(), dictionary.len() - 1);
+                    dictionary.len() - 1
+                })
+            })
+            .collect();
+        DictData {
+            dictionary: Rc::new(dictionary),
+            codes,
+        }
+    }
+
+    /// Returns the dictionary of distinct values of this field.
+    pub fn dictionary(&self) -> &[T] {
+        &self.dictionary
+    }
+
+    /// Returns the code (index into the [dictionary](#method.dictionary)) of each record of this
+    /// field.
+    pub fn codes(&self) -> &[Value<usize>] {
+        &self.codes
+    }
+
+    /// Returns `true` if this field and `other` share the same dictionary, in which case their
+    /// codes can be compared directly.
+    pub fn shares_dictionary(&self, other: &DictData<T>) -> bool {
+        Rc::ptr_eq(&self.dictionary, &other.dictionary)
+    }
+
+    /// Decodes this field into a new [FieldData](../field/struct.FieldData.html).
+    pub fn decode(&self) -> FieldData<T>
+    where
+        T: Debug + Default + Clone,
+    {
+        self.codes
+            .iter()
+            .map(|code| code.map(|code| &self.dictionary[code]))
+            .collect()
+    }
+
+    /// Computes the indices of the matching records of an equality join between this field and
+    /// `other`. Returns a pair of vectors of equal length, containing the indices into this field
+    /// and into `other` of each matching pair of records, ordered by their index into this field
+    /// (and then by their index into `other`). Missing (NA) values do not match any values.
+    ///
+    /// Records are matched by code. If the two fields do not share a dictionary, the codes of
+    /// `other` are first remapped onto the dictionary of this field (which requires a single
+    /// lookup per dictionary entry, rather than per record).
+    pub fn join_indices(&self, other: &DictData<T>) -> (Vec<usize>, Vec<usize>)
+    where
+        T: Hash + Eq,
+    {
+        // code in this field's dictionary of each code in `other`'s dictionary
+        let remapped = if self.shares_dictionary(other) {
+            (0..self.dictionary.len()).map(Some).collect::<Vec<_>>()
+        } else {
+            let lookup = self
+                .dictionary
+                .iter()
+                .enumerate()
+                .map(|(code, value)| (value, code))
+                .collect::<HashMap<_, _>>();
+            other
+                .dictionary
+                .iter()
+                .map(|value| lookup.get(value).cloned())
+                .collect::<Vec<_>>()
+        };
+        // indices of the records of `other` with each code of this field's dictionary
+        let mut other_indices = vec![vec![]; self.dictionary.len()];
+        for (idx, code) in other.codes.iter().enumerate() {
+            if let Value::Exists(code) = *code {
+                if let Some(code) = remapped[code] {
+                    other_indices[code].push(idx);
+                }
+            }
+        }
+
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        for (idx, code) in self.codes.iter().enumerate() {
+            if let Value::Exists(code) = *code {
+                for &other_idx in &other_indices[code] {
+                    left_indices.push(idx);
+                    right_indices.push(other_idx);
+                }
+            }
+        }
+        (left_indices, right_indices)
+    }
+}
+
+impl<T> DataIndex for DictData<T>
+where
+    T: Debug,
+{
+    type DType = T;
+
+    fn get_datum(&self, idx: usize) -> Result<Value<&T>> {
+        self.codes
+            .get(idx)
+            .map(|code| code.map(|code| &self.dictionary[code]))
+            .ok_or(AgnesError::IndexError {
+                index: idx,
+                len: self.len(),
+                field: None,
+            })
+    }
+    fn len(&self) -> usize {
+        self.codes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(values: Vec<Option<&'static str>>) -> FieldData<&'static str> {
+        values.into_iter().collect()
+    }
+
+    #[test]
+    fn encode_decode() {
+        let data = field(vec![Some("b"), Some("a"), None, Some("b"), Some("c")]);
+        let dict = DictData::encode(&data);
+        assert_eq!(dict.dictionary(), &["b", "a", "c"]);
+        assert_eq!(
+            dict.codes(),
+            &[
+                Value::Exists(0),
+                Value::Exists(1),
+                Value::Na,
+                Value::Exists(0),
+                Value::Exists(2)
+            ]
+        );
+        assert_eq!(dict.len(), 5);
+        assert_eq!(dict.to_value_vec(), data.to_value_vec());
+        assert_eq!(dict.decode().to_value_vec(), data.to_value_vec());
+        assert!(dict.get_datum(5).is_err());
+    }
+
+    #[test]
+    fn join() {
+        let left = DictData::encode(&field(vec![
+            Some("b"),
+            Some("b"),
+            None,
+            Some("a"),
+            Some("b"),
+        ]));
+        let other = field(vec![Some("a"), None, Some("b"), Some("b"), Some("c")]);
+        let expected = (vec![0, 0, 1, 1, 3, 4, 4], vec![2, 3, 2, 3, 0, 2, 3]);
+
+        // separately-encoded dictionaries are remapped
+        let right = DictData::encode(&other);
+        assert!(!left.shares_dictionary(&right));
+        assert_eq!(left.join_indices(&right), expected);
+
+        // 'c' is not in the left dictionary, so the dictionary can't be shared
+        let right = DictData::encode_shared(&other, &left);
+        assert!(!left.shares_dictionary(&right));
+        assert_eq!(right.dictionary(), &["b", "a", "c"]);
+        assert_eq!(left.join_indices(&right), expected);
+
+        let right = DictData::encode_shared(&field(vec![Some("a"), Some("b"), None]), &left);
+        assert!(left.shares_dictionary(&right));
+        assert_eq!(
+            left.join_indices(&right),
+            (vec![0, 1, 3, 4], vec![1, 1, 0, 1])
+        );
+    }
+}
@@ -9,8 +9,11 @@ use csv;
 use csv_sniffer;
 use hyper;
 use native_tls;
+use serde_json;
 
+use datetime::ParseDateTimeError;
 use field::FieldIdent;
+use join::Cardinality;
 
 /// General DataFrame error enum.
 #[derive(Debug)]
@@ -25,6 +28,10 @@ pub enum AgnesError {
     CsvSniffer(csv_sniffer::error::SnifferError),
     /// CSV dialect error
     CsvDialect(String),
+    /// JSON parsing error
+    Json(serde_json::Error),
+    /// JSON structure error (unexpected structure of JSON data source)
+    JsonStructure(String),
     /// Parsing error (failure parsing as specified type).
     Parse(ParseError),
     /// Charset Decoding error.
@@ -39,6 +46,8 @@ pub enum AgnesError {
         index: usize,
         /// length of underlying data structure
         len: usize,
+        /// identifier of the accessed field, if known
+        field: Option<FieldIdent>,
     },
     /// Length mismatch error
     LengthMismatch {
@@ -47,11 +56,50 @@ pub enum AgnesError {
         /// Observed length
         actual: usize,
     },
+    /// Join cardinality mismatch
+    CardinalityMismatch {
+        /// Expected join cardinality
+        expected: Cardinality,
+        /// Number of keys matching more than one left-hand record, when at most one was expected
+        left_violations: usize,
+        /// Number of keys matching more than one right-hand record, when at most one was expected
+        right_violations: usize,
+    },
+    /// Query expression parsing / evaluation error
+    Query(String),
+    /// Missing values in a field declared as required
+    MissingRequired {
+        /// Identifier of the required field
+        field: FieldIdent,
+        /// Number of missing values in the field
+        count: usize,
+    },
+    /// Errors of the failed steps of a [Pipe](../pipe/struct.Pipe.html), in order
+    Pipeline(Vec<AgnesError>),
 }
 
 /// Wrapper for DataFrame-based results.
 pub type Result<T> = ::std::result::Result<T, AgnesError>;
 
+impl AgnesError {
+    /// Attaches the identifier of the accessed field to an `IndexError` which does not already
+    /// identify its field. Other errors are returned unchanged.
+    pub fn in_field<I: Into<FieldIdent>>(self, ident: I) -> AgnesError {
+        match self {
+            AgnesError::IndexError {
+                index,
+                len,
+                field: None,
+            } => AgnesError::IndexError {
+                index,
+                len,
+                field: Some(ident.into()),
+            },
+            err => err,
+        }
+    }
+}
+
 impl fmt::Display for AgnesError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -60,22 +108,60 @@ impl fmt::Display for AgnesError {
             AgnesError::Csv(ref err) => write!(f, "CSV error: {}", err),
             AgnesError::CsvSniffer(ref err) => write!(f, "CSV sniffer error: {}", err),
             AgnesError::CsvDialect(ref s) => write!(f, "CSV structure error: {}", s),
+            AgnesError::Json(ref err) => write!(f, "JSON error: {}", err),
+            AgnesError::JsonStructure(ref s) => write!(f, "JSON structure error: {}", s),
             AgnesError::Parse(ref err) => write!(f, "Parse error: {}", err),
             AgnesError::Decode(ref s) => write!(f, "Decode error: {}", s),
             AgnesError::FieldNotFound(ref ident) => {
                 write!(f, "Missing source field: {}", ident.to_string())
             }
             AgnesError::DimensionMismatch(ref s) => write!(f, "Dimension mismatch: {}", s),
-            AgnesError::IndexError { index, len } => write!(
-                f,
-                "Index error: index {} exceeds data length {}",
-                index, len
-            ),
+            AgnesError::IndexError {
+                index,
+                len,
+                ref field,
+            } => {
+                write!(
+                    f,
+                    "Index error: index {} exceeds data length {}",
+                    index, len
+                )?;
+                match *field {
+                    Some(ref ident) => write!(f, " of field {}", ident.to_string()),
+                    None => Ok(()),
+                }
+            }
             AgnesError::LengthMismatch { expected, actual } => write!(
                 f,
                 "Length mismatch: expected {} does not match actual {}",
                 expected, actual
             ),
+            AgnesError::CardinalityMismatch {
+                expected,
+                left_violations,
+                right_violations,
+            } => write!(
+                f,
+                "Cardinality mismatch: expected {} join, found {} key(s) with multiple left \
+                 matches and {} key(s) with multiple right matches",
+                expected, left_violations, right_violations
+            ),
+            AgnesError::Query(ref s) => write!(f, "Query error: {}", s),
+            AgnesError::MissingRequired { ref field, count } => write!(
+                f,
+                "Missing required values: {} missing value(s) in field {}",
+                count,
+                field.to_string()
+            ),
+            AgnesError::Pipeline(ref errors) => write!(
+                f,
+                "Pipeline errors: {}",
+                errors
+                    .iter()
+                    .map(|err| err.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
         }
     }
 }
@@ -88,12 +174,18 @@ impl Error for AgnesError {
             AgnesError::Csv(ref err) => err.description(),
             AgnesError::CsvSniffer(ref err) => err.description(),
             AgnesError::CsvDialect(ref s) => s,
+            AgnesError::Json(ref err) => err.description(),
+            AgnesError::JsonStructure(ref s) => s,
             AgnesError::Parse(ref err) => err.description(),
             AgnesError::Decode(ref s) => s,
             AgnesError::FieldNotFound(_) => "missing source field",
             AgnesError::DimensionMismatch(ref s) => s,
             AgnesError::IndexError { .. } => "indexing error",
             AgnesError::LengthMismatch { .. } => "length mismatch",
+            AgnesError::CardinalityMismatch { .. } => "join cardinality mismatch",
+            AgnesError::Query(ref s) => s,
+            AgnesError::MissingRequired { .. } => "missing required values",
+            AgnesError::Pipeline(_) => "pipeline errors",
         }
     }
 
@@ -104,12 +196,18 @@ impl Error for AgnesError {
             AgnesError::Csv(ref err) => Some(err),
             AgnesError::CsvSniffer(ref err) => Some(err),
             AgnesError::CsvDialect(_) => None,
+            AgnesError::Json(ref err) => Some(err),
+            AgnesError::JsonStructure(_) => None,
             AgnesError::Parse(ref err) => Some(err),
             AgnesError::Decode(_) => None,
             AgnesError::FieldNotFound(_) => None,
             AgnesError::DimensionMismatch(_) => None,
             AgnesError::IndexError { .. } => None,
             AgnesError::LengthMismatch { .. } => None,
+            AgnesError::CardinalityMismatch { .. } => None,
+            AgnesError::Query(_) => None,
+            AgnesError::MissingRequired { .. } => None,
+            AgnesError::Pipeline(ref errors) => errors.first().map(|err| err as &dyn Error),
         }
     }
 }
@@ -175,6 +273,8 @@ pub enum ParseError {
     Float(std::num::ParseFloatError),
     /// String
     Str(std::string::ParseError),
+    /// Date and time
+    DateTime(ParseDateTimeError),
 }
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -183,6 +283,7 @@ impl fmt::Display for ParseError {
             ParseError::Bool(ref err) => write!(f, "Boolean parse error: {}", err),
             ParseError::Float(ref err) => write!(f, "Float parse error: {}", err),
             ParseError::Str(ref err) => write!(f, "String parse error: {}", err),
+            ParseError::DateTime(ref err) => write!(f, "Datetime parse error: {}", err),
         }
     }
 }
@@ -193,6 +294,7 @@ impl Error for ParseError {
             ParseError::Bool(ref err) => err.description(),
             ParseError::Float(ref err) => err.description(),
             ParseError::Str(ref err) => err.description(),
+            ParseError::DateTime(ref err) => err.description(),
         }
     }
 
@@ -202,6 +304,7 @@ impl Error for ParseError {
             ParseError::Bool(ref err) => Some(err),
             ParseError::Float(ref err) => Some(err),
             ParseError::Str(ref err) => Some(err),
+            ParseError::DateTime(ref err) => Some(err),
         }
     }
 }
@@ -246,6 +349,16 @@ impl From<std::string::ParseError> for AgnesError {
         AgnesError::Parse(err.into())
     }
 }
+impl From<ParseDateTimeError> for ParseError {
+    fn from(err: ParseDateTimeError) -> ParseError {
+        ParseError::DateTime(err)
+    }
+}
+impl From<ParseDateTimeError> for AgnesError {
+    fn from(err: ParseDateTimeError) -> AgnesError {
+        AgnesError::Parse(err.into())
+    }
+}
 impl From<ParseError> for AgnesError {
     fn from(err: ParseError) -> AgnesError {
         AgnesError::Parse(err)
@@ -303,6 +416,12 @@ impl From<csv::Error> for AgnesError {
     }
 }
 
+impl From<serde_json::Error> for AgnesError {
+    fn from(err: serde_json::Error) -> AgnesError {
+        AgnesError::Json(err)
+    }
+}
+
 impl From<csv_sniffer::error::SnifferError> for AgnesError {
     fn from(err: csv_sniffer::error::SnifferError) -> AgnesError {
         AgnesError::CsvSniffer(err)
@@ -10,6 +10,7 @@ use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 #[cfg(feature = "serialize")]
 use serde::ser::{Serialize, SerializeSeq, Serializer};
@@ -41,6 +42,36 @@ impl<T> FieldData<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+    /// Create a new, empty `FieldData` with space for at least `capacity` values, so that pushing
+    /// up to `capacity` values does not reallocate.
+    pub fn with_capacity(capacity: usize) -> FieldData<T> {
+        FieldData {
+            mask: None,
+            data: Vec::with_capacity(capacity),
+        }
+    }
+    /// Returns the number of values this field can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+    /// Reserves space for at least `additional` more values in this field.
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        if let Some(mask) = self.mask.as_mut() {
+            mask.reserve(additional);
+        }
+    }
+    /// Appends the values in `values` (none of which are missing) to this field.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        self.data.extend_from_slice(values);
+        // if mask exists (which means there are NA values), then add a true for each value
+        if let Some(mask) = self.mask.as_mut() {
+            mask.grow(values.len(), true);
+        }
+    }
     fn exists_at(&self, index: usize) -> bool {
         self.mask.as_ref().map_or(true, |mask| mask[index])
     }
@@ -101,6 +132,17 @@ impl<T> FieldData<T> {
             })
             .collect()
     }
+    // returns the stored values of this field, including placeholder values at missing indices
+    pub(crate) fn values(&self) -> &[T] {
+        &self.data
+    }
+    /// Returns a read-only view of the missing value mask of this field.
+    pub fn na_mask(&self) -> NaMask {
+        NaMask {
+            mask: self.mask.as_ref(),
+            len: self.data.len(),
+        }
+    }
     /// Create a new `FieldData` from a slice. Does not clone or reallocate the contained data (but
     /// does allocate the bit mask). Resulting `FieldData` struct will have no `Value::Na` values.
     pub fn from_boxed_slice(orig: Box<[T]>) -> Self {
@@ -141,15 +183,34 @@ where
                 self.mask.as_mut().map(|mask| mask.push(true));
             }
             Value::Na => {
-                let prev_len = self.data.len();
+                let (prev_len, capacity) = (self.data.len(), self.data.capacity());
                 self.data.push(T::default());
                 // either get or create mask, and add a false to the end
                 self.mask
-                    .get_or_insert_with(|| BitVec::from_elem(prev_len, true))
+                    .get_or_insert_with(|| full_mask(prev_len, capacity))
                     .push(false);
             }
         }
     }
+    /// Appends the values in `values` to this field, where `None` indicates a missing value.
+    /// Space for the values is reserved up front using the iterator's size hint.
+    pub fn extend_from_option_iter<I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = Option<T>>,
+    {
+        let values = values.into_iter();
+        self.reserve(values.size_hint().0);
+        for value in values {
+            self.push_val(value.into());
+        }
+    }
+}
+
+// creates a mask of `len` existing values, with space for `capacity` values
+fn full_mask(len: usize, capacity: usize) -> BitVec {
+    let mut mask = BitVec::with_capacity(capacity.max(len));
+    mask.grow(len, true);
+    mask
 }
 impl<T> FieldData<T>
 where
@@ -164,18 +225,18 @@ where
                 self.mask.as_mut().map(|mask| mask.push(true));
             }
             Value::Na => {
-                let prev_len = self.data.len();
+                let (prev_len, capacity) = (self.data.len(), self.data.capacity());
                 self.data.push(T::default());
                 // either get or create mask, and add a false to the end
                 self.mask
-                    .get_or_insert_with(|| BitVec::from_elem(prev_len, true))
+                    .get_or_insert_with(|| full_mask(prev_len, capacity))
                     .push(false);
             }
         }
     }
     /// Create a `FieldData` struct from a vector of field values.
     pub fn from_field_vec(mut v: Vec<Value<T>>) -> FieldData<T> {
-        let mut ret = FieldData::default();
+        let mut ret = FieldData::with_capacity(v.len());
         for elem in v.drain(..) {
             ret.push(elem);
         }
@@ -187,19 +248,29 @@ where
     T: Debug + Default,
 {
     fn from_iter<I: IntoIterator<Item = Value<T>>>(iter: I) -> Self {
-        let mut data = FieldData::default();
+        let iter = iter.into_iter();
+        let mut data = FieldData::with_capacity(iter.size_hint().0);
         for value in iter {
             data.push(value);
         }
         data
     }
 }
+impl<T> FromIterator<Option<T>> for FieldData<T>
+where
+    T: Debug + Default,
+{
+    fn from_iter<I: IntoIterator<Item = Option<T>>>(iter: I) -> Self {
+        iter.into_iter().map(Value::from).collect()
+    }
+}
 impl<'a, T> FromIterator<Value<&'a T>> for FieldData<T>
 where
     T: 'a + Debug + Default + Clone,
 {
     fn from_iter<I: IntoIterator<Item = Value<&'a T>>>(iter: I) -> Self {
-        let mut data = FieldData::default();
+        let iter = iter.into_iter();
+        let mut data = FieldData::with_capacity(iter.size_hint().0);
         for value in iter {
             data.push(value.cloned());
         }
@@ -208,7 +279,8 @@ where
 }
 impl<T> FromIterator<T> for FieldData<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut data = vec![];
+        let iter = iter.into_iter();
+        let mut data = Vec::with_capacity(iter.size_hint().0);
         for value in iter {
             data.push(value);
         }
@@ -221,6 +293,50 @@ impl<T> From<Vec<T>> for FieldData<T> {
     }
 }
 
+/// Read-only view of the missing value (NA) mask of a [FieldData](struct.FieldData.html) object,
+/// as returned by [na_mask](struct.FieldData.html#method.na_mask).
+#[derive(Debug, Clone, Copy)]
+pub struct NaMask<'a> {
+    mask: Option<&'a BitVec>,
+    len: usize,
+}
+impl<'a> NaMask<'a> {
+    /// Returns the number of values covered by this mask.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if this mask covers no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the underlying bit vector, in which set bits denote existing values and unset bits
+    /// denote missing values. Returns `None` if the field has never contained a missing value (in
+    /// which case no mask is allocated).
+    pub fn bits(&self) -> Option<&'a BitVec> {
+        self.mask
+    }
+    /// Returns `true` if the value at `index` is missing.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn is_na(&self, index: usize) -> bool {
+        assert!(index < self.len, "index out of bounds");
+        self.mask.map_or(false, |mask| !mask[index])
+    }
+    /// Returns the number of existing values, computed using a population count of the mask.
+    pub fn num_exists(&self) -> usize {
+        match self.mask {
+            // unused bits in the final block are always unset
+            Some(mask) => mask.blocks().map(|block| block.count_ones() as usize).sum(),
+            None => self.len,
+        }
+    }
+    /// Returns the number of missing values, computed using a population count of the mask.
+    pub fn num_na(&self) -> usize {
+        self.len - self.num_exists()
+    }
+}
+
 impl<T> DataIndex for FieldData<T>
 where
     T: Debug,
@@ -231,11 +347,18 @@ where
         self.get(idx).ok_or(error::AgnesError::IndexError {
             index: idx,
             len: self.len(),
+            field: None,
         })
     }
     fn len(&self) -> usize {
         self.len()
     }
+    fn count_na(&self) -> usize {
+        self.na_mask().num_na()
+    }
+    fn as_field_data(&self) -> Option<&FieldData<T>> {
+        Some(self)
+    }
 }
 impl<T> DataIndexMut for FieldData<T>
 where
@@ -251,6 +374,7 @@ where
         self.take(idx).ok_or(error::AgnesError::IndexError {
             index: idx,
             len: self.len(),
+            field: None,
         })
     }
 }
@@ -285,12 +409,15 @@ where
     }
 }
 /// Identifier for a field in the source.
+///
+/// Field names are stored as shared string slices, so cloning a `FieldIdent` never reallocates its
+/// name.
 #[derive(Debug, Clone)]
 pub enum FieldIdent {
     /// Unnamed field identifier, using the field index in the source file.
     Index(usize),
     /// Field name in the source file
-    Name(String),
+    Name(Arc<str>),
 }
 impl FieldIdent {
     /// Produce a string representation of the field identifier. Either the name if
@@ -299,7 +426,7 @@ impl FieldIdent {
     pub fn to_string(&self) -> String {
         match *self {
             FieldIdent::Index(i) => format!("Field {}", i),
-            FieldIdent::Name(ref s) => s.clone(),
+            FieldIdent::Name(ref s) => s.to_string(),
         }
     }
 }
@@ -310,7 +437,11 @@ impl fmt::Display for FieldIdent {
 }
 impl PartialEq for FieldIdent {
     fn eq(&self, other: &FieldIdent) -> bool {
-        self.to_string().eq(&other.to_string())
+        match (self, other) {
+            (&FieldIdent::Name(ref left), &FieldIdent::Name(ref right)) => left == right,
+            (&FieldIdent::Index(left), &FieldIdent::Index(right)) => left == right,
+            _ => self.to_string().eq(&other.to_string()),
+        }
     }
 }
 impl Eq for FieldIdent {}
@@ -319,7 +450,11 @@ impl Hash for FieldIdent {
     where
         H: Hasher,
     {
-        self.to_string().hash(state)
+        // must be consistent with the string comparison of mixed identifiers in `eq`
+        match *self {
+            FieldIdent::Name(ref s) => s.hash(state),
+            FieldIdent::Index(_) => self.to_string().hash(state),
+        }
     }
 }
 
@@ -330,11 +465,16 @@ impl From<usize> for FieldIdent {
 }
 impl<'a> From<&'a str> for FieldIdent {
     fn from(src: &'a str) -> FieldIdent {
-        FieldIdent::Name(src.to_string())
+        FieldIdent::Name(src.into())
     }
 }
 impl From<String> for FieldIdent {
     fn from(src: String) -> FieldIdent {
+        FieldIdent::Name(src.into())
+    }
+}
+impl From<Arc<str>> for FieldIdent {
+    fn from(src: Arc<str>) -> FieldIdent {
         FieldIdent::Name(src)
     }
 }
@@ -368,7 +508,7 @@ impl RFieldIdent {
     /// simply a clone of the underlying `FieldIdent`.
     pub fn to_renamed_field_ident(&self) -> FieldIdent {
         match self.rename {
-            Some(ref renamed) => FieldIdent::Name(renamed.clone()),
+            Some(ref renamed) => FieldIdent::Name(renamed.as_str().into()),
             None => self.ident.clone(),
         }
     }
@@ -405,4 +545,81 @@ mod tests {
             "[5.0,3.4,-1.3,5.2,6.0,-126.9]"
         );
     }
+
+    #[test]
+    fn na_mask() {
+        let field: FieldData<u64> = vec![1u64, 2, 3].into();
+        assert!(field.na_mask().bits().is_none());
+        assert_eq!(field.na_mask().num_na(), 0);
+        assert_eq!(field.na_mask().num_exists(), 3);
+
+        // span multiple mask blocks
+        let field = (0..100u64)
+            .map(|i| {
+                if i % 3 == 0 {
+                    Value::Na
+                } else {
+                    Value::Exists(i)
+                }
+            })
+            .collect::<FieldData<u64>>();
+        let mask = field.na_mask();
+        assert_eq!(mask.len(), 100);
+        assert!(mask.is_na(0));
+        assert!(!mask.is_na(1));
+        assert!(mask.is_na(99));
+        assert_eq!(mask.num_na(), 34);
+        assert_eq!(mask.num_exists(), 66);
+        assert_eq!(field.count_na(), 34);
+        assert_eq!(field.count_exists(), 66);
+    }
+
+    #[test]
+    fn capacity_extend() {
+        let mut field = FieldData::<u64>::with_capacity(10);
+        assert!(field.is_empty());
+        assert!(field.capacity() >= 10);
+
+        field.extend_from_slice(&[1, 2, 3]);
+        assert!(field.na_mask().bits().is_none());
+        field.extend_from_option_iter(vec![Some(4), None, Some(6)]);
+        field.extend_from_slice(&[7, 8]);
+        assert!(field.capacity() >= 10);
+        assert_eq!(field.len(), 8);
+        assert_eq!(
+            field.to_value_vec(),
+            vec![
+                Value::Exists(1),
+                Value::Exists(2),
+                Value::Exists(3),
+                Value::Exists(4),
+                Value::Na,
+                Value::Exists(6),
+                Value::Exists(7),
+                Value::Exists(8),
+            ]
+        );
+        assert_eq!(field.na_mask().len(), 8);
+        assert_eq!(field.na_mask().num_na(), 1);
+
+        field.reserve(100);
+        assert!(field.capacity() >= 108);
+    }
+
+    #[test]
+    fn field_ident() {
+        use std::collections::HashSet;
+
+        let name = FieldIdent::from("Field 1");
+        assert_eq!(name, name.clone());
+        assert_eq!(name, FieldIdent::from("Field 1".to_string()));
+        assert_ne!(name, FieldIdent::from("Field 2"));
+        // indices compare equal to their string representations
+        assert_eq!(name, FieldIdent::from(1usize));
+
+        let mut set = HashSet::new();
+        set.insert(name);
+        assert!(set.contains(&FieldIdent::from(1usize)));
+        assert!(!set.contains(&FieldIdent::from(2usize)));
+    }
 }
@@ -3,7 +3,11 @@ Type aliases and macro for handling specifications of fields in a data source.
 */
 use std::marker::PhantomData;
 
+use datetime::EpochUnit;
+use error::*;
+use field::FieldIdent;
 use label::*;
+use value::Value;
 
 /// Type alias for a field label and data type.
 pub type FieldSchema<Label, DType> = Labeled<Label, PhantomData<DType>>;
@@ -38,6 +42,275 @@ impl<Label, DType, Tail> SchemaCons<Label, DType, Tail> {
     }
 }
 
+/// Designation of a field in a data source which contains Unix timestamps, to be loaded as a
+/// [DateTime](../datetime/struct.DateTime.html) field.
+#[derive(Debug, Clone)]
+pub struct TimestampDesignator {
+    /// Field / column designator in data source.
+    pub designator: FieldDesignator,
+    /// Unit of the timestamps in the field.
+    pub unit: EpochUnit,
+}
+impl SelfValued for TimestampDesignator {}
+
+/// Type alias for a cons-list containing Unix timestamp fields with their labels, data type, and
+/// source designators.
+pub type TimestampSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, TimestampDesignator, Tail>;
+
+impl<Label, DType, Tail> TimestampSchemaCons<Label, DType, Tail> {
+    /// Create a new `TimestampSchemaCons` cons-list from a
+    /// [FieldDesignator](enum.FieldDesignator.html) and the unit of the timestamps in the field.
+    pub fn new(
+        src_designator: FieldDesignator,
+        unit: EpochUnit,
+        tail: Tail,
+    ) -> TimestampSchemaCons<Label, DType, Tail> {
+        TimestampSchemaCons {
+            head: TypedValue::from(TimestampDesignator {
+                designator: src_designator,
+                unit,
+            })
+            .into(),
+            tail,
+        }
+    }
+}
+
+/// Cleaning rules applied to the values of a field after they are loaded from a data source.
+#[derive(Debug, Clone)]
+pub struct FieldRules<DType> {
+    /// Value substituted for missing (NA) values, if any.
+    pub default: Option<DType>,
+    /// Whether loading fails if the field contains any missing (NA) values (after substituting
+    /// the default value, if any).
+    pub required: bool,
+}
+
+impl<DType> FieldRules<DType> {
+    /// Creates a new `FieldRules` without a default value, which does not require values to
+    /// exist.
+    pub fn new() -> FieldRules<DType> {
+        FieldRules {
+            default: None,
+            required: false,
+        }
+    }
+    /// Substitutes `value` for missing values.
+    pub fn with_default<T: Into<DType>>(mut self, value: T) -> FieldRules<DType> {
+        self.default = Some(value.into());
+        self
+    }
+    /// Requires all values to exist.
+    pub fn required(mut self) -> FieldRules<DType> {
+        self.required = true;
+        self
+    }
+
+    /// Applies these rules to the loaded `values` of the field identified by `ident`, returning
+    /// the cleaned values.
+    ///
+    /// # Error
+    /// Fails with a `MissingRequired` error if the field is required and any values are missing
+    /// after substituting the default value.
+    pub fn apply<I: Into<FieldIdent>>(
+        &self,
+        ident: I,
+        mut values: Vec<Value<DType>>,
+    ) -> Result<Vec<Value<DType>>>
+    where
+        DType: Clone,
+    {
+        if let Some(ref default) = self.default {
+            for value in values.iter_mut().filter(|value| value.is_na()) {
+                *value = Value::Exists(default.clone());
+            }
+        }
+        if self.required {
+            let count = values.iter().filter(|value| value.is_na()).count();
+            if count > 0 {
+                return Err(AgnesError::MissingRequired {
+                    field: ident.into(),
+                    count,
+                });
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl<DType> Default for FieldRules<DType> {
+    fn default() -> FieldRules<DType> {
+        FieldRules::new()
+    }
+}
+
+/// Designation of a field in a data source along with the cleaning rules to apply to its values.
+#[derive(Debug, Clone)]
+pub struct RuledDesignator<DType> {
+    /// Field / column designator in data source.
+    pub designator: FieldDesignator,
+    /// Rules to apply to the values of the field.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for RuledDesignator<DType> {}
+
+/// Type alias for a cons-list containing fields with their labels, data type, source designators,
+/// and cleaning rules.
+pub type RuledSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, RuledDesignator<DType>, Tail>;
+
+impl<Label, DType, Tail> RuledSchemaCons<Label, DType, Tail> {
+    /// Create a new `RuledSchemaCons` cons-list from a
+    /// [FieldDesignator](enum.FieldDesignator.html) and the rules to apply to the field's values.
+    pub fn new(
+        src_designator: FieldDesignator,
+        rules: FieldRules<DType>,
+        tail: Tail,
+    ) -> RuledSchemaCons<Label, DType, Tail> {
+        RuledSchemaCons {
+            head: TypedValue::from(RuledDesignator {
+                designator: src_designator,
+                rules,
+            })
+            .into(),
+            tail,
+        }
+    }
+}
+
+/// Method of combining the values of multiple source fields into a single field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Combine {
+    /// Concatenate the non-missing values, separated by the specified separator.
+    Concat(String),
+    /// Take the first non-missing value.
+    Coalesce,
+}
+
+impl Combine {
+    /// Combines the (unparsed) source `values` of a single record into one value. Empty strings
+    /// are treated as missing; an empty string is returned if all of the values are missing.
+    pub fn combine<'a, I>(&self, values: I) -> String
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut values = values.into_iter().filter(|value| !value.is_empty());
+        match *self {
+            Combine::Concat(ref sep) => values.collect::<Vec<_>>().join(sep),
+            Combine::Coalesce => values.next().unwrap_or("").to_string(),
+        }
+    }
+}
+
+/// Designation of multiple fields in a data source which are combined into a single field, along
+/// with the cleaning rules to apply to the combined values.
+#[derive(Debug, Clone)]
+pub struct CombinedDesignator<DType> {
+    /// Field / column designators in data source.
+    pub designators: Vec<FieldDesignator>,
+    /// Method of combining the values of the designated fields.
+    pub combine: Combine,
+    /// Rules to apply to the combined values.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for CombinedDesignator<DType> {}
+
+/// Type alias for a cons-list containing fields which combine multiple source fields, with their
+/// labels, data type, source designators, combination method, and cleaning rules.
+pub type CombinedSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CombinedDesignator<DType>, Tail>;
+
+impl<Label, DType, Tail> CombinedSchemaCons<Label, DType, Tail> {
+    /// Create a new `CombinedSchemaCons` cons-list from a list of
+    /// [FieldDesignator](enum.FieldDesignator.html)s, the method of combining their values, and
+    /// the rules to apply to the combined values.
+    pub fn new(
+        src_designators: Vec<FieldDesignator>,
+        combine: Combine,
+        rules: FieldRules<DType>,
+        tail: Tail,
+    ) -> CombinedSchemaCons<Label, DType, Tail> {
+        CombinedSchemaCons {
+            head: TypedValue::from(CombinedDesignator {
+                designators: src_designators,
+                combine,
+                rules,
+            })
+            .into(),
+            tail,
+        }
+    }
+}
+
+/// Designation of multiple fields in a data source whose values are stacked into a single field
+/// (one record for each of the designated fields), along with the cleaning rules to apply to the
+/// stacked values.
+#[derive(Debug, Clone)]
+pub struct StackedDesignator<DType> {
+    /// Field / column designators in data source.
+    pub designators: Vec<FieldDesignator>,
+    /// Rules to apply to the stacked values.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for StackedDesignator<DType> {}
+
+/// Type alias for a cons-list containing fields which stack the values of multiple source fields,
+/// with their labels, data type, source designators, and cleaning rules.
+pub type StackedSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, StackedDesignator<DType>, Tail>;
+
+impl<Label, DType, Tail> StackedSchemaCons<Label, DType, Tail> {
+    /// Create a new `StackedSchemaCons` cons-list from a list of
+    /// [FieldDesignator](enum.FieldDesignator.html)s and the rules to apply to the stacked
+    /// values.
+    pub fn new(
+        src_designators: Vec<FieldDesignator>,
+        rules: FieldRules<DType>,
+        tail: Tail,
+    ) -> StackedSchemaCons<Label, DType, Tail> {
+        StackedSchemaCons {
+            head: TypedValue::from(StackedDesignator {
+                designators: src_designators,
+                rules,
+            })
+            .into(),
+            tail,
+        }
+    }
+}
+
+/// Designation of the stacked fields in a data source whose names are loaded into a field
+/// accompanying a stacked field.
+#[derive(Debug, Clone)]
+pub struct StackNamesDesignator {
+    /// Field / column designators in data source.
+    pub designators: Vec<FieldDesignator>,
+}
+impl SelfValued for StackNamesDesignator {}
+
+/// Type alias for a cons-list containing fields which hold the names of the source fields of a
+/// stacked field, with their labels, data type, and source designators.
+pub type StackNamesSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, StackNamesDesignator, Tail>;
+
+impl<Label, DType, Tail> StackNamesSchemaCons<Label, DType, Tail> {
+    /// Create a new `StackNamesSchemaCons` cons-list from the list of
+    /// [FieldDesignator](enum.FieldDesignator.html)s of a stacked field.
+    pub fn new(
+        src_designators: Vec<FieldDesignator>,
+        tail: Tail,
+    ) -> StackNamesSchemaCons<Label, DType, Tail> {
+        StackNamesSchemaCons {
+            head: TypedValue::from(StackNamesDesignator {
+                designators: src_designators,
+            })
+            .into(),
+            tail,
+        }
+    }
+}
+
 /// Macro for creating a source specification structure used to specify how to
 /// extract fields from a data source. It correlates labels (defined using the
 /// [tablespace](macro.tablespace.html) macro) to field / column names or indices in a
@@ -57,6 +330,125 @@ impl<Label, DType, Tail> SchemaCons<Label, DType, Tail> {
 /// This example also shows the usage of the [tablespace](macro.tablespace.html) macro; see that
 /// macro's documentation for its syntax and an example.
 ///
+/// Columns containing Unix timestamps can be loaded directly into fields with the
+/// [DateTime](datetime/struct.DateTime.html) data type by following the field label with
+/// `as unix_seconds` or `as unix_millis` (for timestamps in seconds or milliseconds since the
+/// Unix epoch, respectively). In this example, the `Created` field will take data from the
+/// "created_at" column, which contains timestamps in milliseconds.
+///
+/// ```
+/// # #[macro_use] extern crate agnes;
+/// use agnes::datetime::DateTime;
+///
+/// tablespace![
+///     table events {
+///         EventId: u64,
+///         Created: DateTime,
+///     }
+/// ];
+///
+/// fn main() {
+///     let events_schema = schema![
+///         fieldname events::EventId = "id";
+///         fieldname events::Created as unix_millis = "created_at";
+///     ];
+///     // ...
+/// }
+/// ```
+///
+/// Common cleaning rules can be declared by following a `fieldname` or `fieldindex` declaration
+/// with a comma and a comma-separated list of rules:
+/// * `default <value>` substitutes `value` for missing (empty) values in the field.
+/// * `required` causes loading to fail with a `MissingRequired` error if any values of the field
+///   are missing (after substituting the default value, if any).
+///
+/// In this example, missing values of the `Population` field are replaced with `0`, and loading
+/// fails if any record is missing its `CityName`.
+///
+/// ```
+/// # #[macro_use] extern crate agnes;
+///
+/// tablespace![
+///     table cities {
+///         CityName: String,
+///         Population: u64,
+///     }
+/// ];
+///
+/// fn main() {
+///     let cities_schema = schema![
+///         fieldname cities::CityName = "name", required;
+///         fieldname cities::Population = "population", default 0u64;
+///     ];
+///     // ...
+/// }
+/// ```
+///
+/// A single field can also be computed from multiple source fields with a `fieldnames` or
+/// `fieldindices` declaration, which takes a bracketed list of column titles or column indices,
+/// respectively, followed by a comma and the method used to combine the (unparsed) values of
+/// each record:
+/// * `concat <separator>` concatenates the non-empty values, separated by `separator`.
+/// * `coalesce` takes the first non-empty value.
+///
+/// The combined value is then parsed as the field's data type. Cleaning rules can follow the
+/// combination method. In this example, the `FullName` field is loaded by joining the "first"
+/// and "last" columns with a space, and the `Phone` field is taken from the "mobile" column,
+/// falling back to the "home" column for records without a mobile number.
+///
+/// ```
+/// # #[macro_use] extern crate agnes;
+///
+/// tablespace![
+///     table contacts {
+///         FullName: String,
+///         Phone: String,
+///     }
+/// ];
+///
+/// fn main() {
+///     let contacts_schema = schema![
+///         fieldnames contacts::FullName = ["first", "last"], concat " ";
+///         fieldnames contacts::Phone = ["mobile", "home"], coalesce, required;
+///     ];
+///     // ...
+/// }
+/// ```
+///
+/// Wide data sources with several columns holding different instances of the same quantity
+/// (such as a column for each year) can be loaded in a long format by using `stack <label>` as
+/// the combination method of a `fieldnames` or `fieldindices` declaration. Each source record
+/// is then loaded as one record for each of the listed columns (in order): the declared field
+/// holds the value of the column, and the `label` field holds the column's name (parsed as that
+/// field's data type; columns of files without a header row are named by their index). All
+/// other fields repeat their value for each of these records, and stacked fields which are
+/// declared together must list the same number of columns. This is equivalent to loading the
+/// columns separately and calling [melt](view/struct.DataView.html#method.melt), without
+/// building the wide table first. Cleaning rules can follow the `stack` declaration, and apply
+/// to the stacked values. In this example, each country's record is loaded as three records
+/// with the `Year` field holding 2013, 2014, and 2015, and the `Gdp` field holding the GDP value
+/// for that year.
+///
+/// ```
+/// # #[macro_use] extern crate agnes;
+///
+/// tablespace![
+///     table gdp {
+///         CountryName: String,
+///         Year: u32,
+///         Gdp: f64,
+///     }
+/// ];
+///
+/// fn main() {
+///     let gdp_schema = schema![
+///         fieldname gdp::CountryName = "Country Name";
+///         fieldnames gdp::Gdp = ["2013", "2014", "2015"], stack gdp::Year;
+///     ];
+///     // ...
+/// }
+/// ```
+///
 /// ```
 /// # #[macro_use] extern crate agnes;
 ///
@@ -82,6 +474,128 @@ macro_rules! schema {
     () => {{
         $crate::cons::Nil
     }};
+    (@epoch_unit unix_seconds) => {
+        $crate::datetime::EpochUnit::Seconds
+    };
+    (@epoch_unit unix_millis) => {
+        $crate::datetime::EpochUnit::Milliseconds
+    };
+    (@new_rules $field_label:ty) => {
+        $crate::fieldlist::FieldRules::<<$field_label as $crate::label::Typed>::DType>::new()
+    };
+    (@rules($cons:ident, $field_label:ty)($($args:expr),*)($rules:expr)
+        default $default:expr, $($rest:tt)*
+    ) => {
+        schema![@rules($cons, $field_label)($($args),*)($rules.with_default($default)) $($rest)*]
+    };
+    (@rules($cons:ident, $field_label:ty)($($args:expr),*)($rules:expr)
+        default $default:expr; $($rest:tt)*
+    ) => {
+        schema![@ruled($cons, $field_label)($($args),*)($rules.with_default($default)) $($rest)*]
+    };
+    (@rules($cons:ident, $field_label:ty)($($args:expr),*)($rules:expr)
+        required, $($rest:tt)*
+    ) => {
+        schema![@rules($cons, $field_label)($($args),*)($rules.required()) $($rest)*]
+    };
+    (@rules($cons:ident, $field_label:ty)($($args:expr),*)($rules:expr)
+        required; $($rest:tt)*
+    ) => {
+        schema![@ruled($cons, $field_label)($($args),*)($rules.required()) $($rest)*]
+    };
+    (@ruled($cons:ident, $field_label:ty)($($args:expr),*)($rules:expr) $($rest:tt)*) => {
+        $crate::fieldlist::$cons::<
+            $field_label,
+            <$field_label as $crate::label::Typed>::DType,
+            _,
+        >::new(
+            $($args,)*
+            $rules,
+            schema![$($rest)*]
+        )
+    };
+    (@combine($field_label:ty)($designators:expr) concat $sep:expr, $($rest:tt)*) => {
+        schema![@rules(CombinedSchemaCons, $field_label)
+            ($designators, $crate::fieldlist::Combine::Concat($sep.to_string()))
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (@combine($field_label:ty)($designators:expr) concat $sep:expr; $($rest:tt)*) => {
+        schema![@ruled(CombinedSchemaCons, $field_label)
+            ($designators, $crate::fieldlist::Combine::Concat($sep.to_string()))
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (@combine($field_label:ty)($designators:expr) coalesce, $($rest:tt)*) => {
+        schema![@rules(CombinedSchemaCons, $field_label)
+            ($designators, $crate::fieldlist::Combine::Coalesce)
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (@combine($field_label:ty)($designators:expr) coalesce; $($rest:tt)*) => {
+        schema![@ruled(CombinedSchemaCons, $field_label)
+            ($designators, $crate::fieldlist::Combine::Coalesce)
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (@combine($field_label:ty)($designators:expr) stack $name_label:ty, $($rest:tt)*) => {
+        $crate::fieldlist::StackNamesSchemaCons::<
+            $name_label,
+            <$name_label as $crate::label::Typed>::DType,
+            _,
+        >::new(
+            $designators,
+            schema![@rules(StackedSchemaCons, $field_label)
+                ($designators)
+                (schema![@new_rules $field_label])
+                $($rest)*
+            ]
+        )
+    };
+    (@combine($field_label:ty)($designators:expr) stack $name_label:ty; $($rest:tt)*) => {
+        $crate::fieldlist::StackNamesSchemaCons::<
+            $name_label,
+            <$name_label as $crate::label::Typed>::DType,
+            _,
+        >::new(
+            $designators,
+            schema![@ruled(StackedSchemaCons, $field_label)
+                ($designators)
+                (schema![@new_rules $field_label])
+                $($rest)*
+            ]
+        )
+    };
+    (fieldname $field_label:ty = $header:expr, $($rest:tt)*) => {
+        schema![@rules(RuledSchemaCons, $field_label)
+            ($crate::fieldlist::FieldDesignator::Expr($header.to_string()))
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (fieldindex $field_label:ty = $idx:expr, $($rest:tt)*) => {
+        schema![@rules(RuledSchemaCons, $field_label)
+            ($crate::fieldlist::FieldDesignator::Idx($idx))
+            (schema![@new_rules $field_label])
+            $($rest)*
+        ]
+    };
+    (fieldnames $field_label:ty = [$($header:expr),* $(,)*], $($rest:tt)*) => {
+        schema![@combine($field_label)
+            (vec![$($crate::fieldlist::FieldDesignator::Expr($header.to_string())),*])
+            $($rest)*
+        ]
+    };
+    (fieldindices $field_label:ty = [$($idx:expr),* $(,)*], $($rest:tt)*) => {
+        schema![@combine($field_label)
+            (vec![$($crate::fieldlist::FieldDesignator::Idx($idx)),*])
+            $($rest)*
+        ]
+    };
     (fieldname $field_label:ty = $header:expr; $($rest:tt)*) => {{
         use $crate::fieldlist::{FieldDesignator, SchemaCons};
         SchemaCons::<
@@ -104,4 +618,28 @@ macro_rules! schema {
             schema![$($rest)*]
         )
     }};
+    (fieldname $field_label:ty as $unit:ident = $header:expr; $($rest:tt)*) => {{
+        use $crate::fieldlist::{FieldDesignator, TimestampSchemaCons};
+        TimestampSchemaCons::<
+            $field_label,
+            <$field_label as $crate::label::Typed>::DType,
+            _,
+        >::new(
+            FieldDesignator::Expr($header.to_string()),
+            schema![@epoch_unit $unit],
+            schema![$($rest)*]
+        )
+    }};
+    (fieldindex $field_label:ty as $unit:ident = $idx:expr; $($rest:tt)*) => {{
+        use $crate::fieldlist::{FieldDesignator, TimestampSchemaCons};
+        TimestampSchemaCons::<
+            $field_label,
+            <$field_label as $crate::label::Typed>::DType,
+            _,
+        >::new(
+            FieldDesignator::Idx($idx),
+            schema![@epoch_unit $unit],
+            schema![$($rest)*]
+        )
+    }};
 }
@@ -17,14 +17,15 @@ use std::sync::Arc;
 use typenum::UTerm;
 
 use access::{DataIndex, NRows};
-use cons::Nil;
+use cons::{FoldFunc, MapFunc, Nil};
 use error;
-use field::FieldData;
+use field::{FieldData, FieldIdent};
 use fieldlist::FieldCons;
 use label::*;
 use permute::{self, UpdatePermutation};
 use select::{FieldSelect, SelectFieldByLabel};
-use store::{AssocFrameLookup, AssocStorage, DataRef, DataStore, IntoView};
+use stats::FieldSummary;
+use store::{AssocFrameLookup, AssocStorage, CacheHashes, DataRef, DataStore, IntoView};
 use value::Value;
 use view::{DataView, ViewFrameCons};
 
@@ -178,13 +179,188 @@ impl<FrameFields, FramedStore> StoreRefCount for DataFrame<FrameFields, FramedSt
     }
 }
 impl<FrameFields, FramedStore> UpdatePermutation for DataFrame<FrameFields, FramedStore> {
-    fn update_permutation(mut self, new_permutation: &[usize]) -> Self {
-        let perm = (*self.permutation).clone();
-        self.permutation = Rc::new(perm.update_indices(new_permutation));
+    fn update_permutation(self, new_permutation: &[usize]) -> Self {
+        self.update_shared_permutation(new_permutation, &mut PermutationUpdates::default())
+    }
+}
+
+/// Record of the permutation updates applied during a single update of a `DataView`'s frames, so
+/// that frames which share a permutation are updated only once (and continue to share the
+/// updated permutation).
+#[derive(Debug, Default)]
+pub struct PermutationUpdates {
+    // (previous permutation, updated permutation) pairs
+    updates: Vec<(Rc<Permutation>, Rc<Permutation>)>,
+}
+impl PermutationUpdates {
+    fn update(&mut self, prev: Rc<Permutation>, order: &[usize]) -> Rc<Permutation> {
+        if let Some(&(_, ref updated)) = self
+            .updates
+            .iter()
+            .find(|&&(ref shared, _)| Rc::ptr_eq(shared, &prev))
+        {
+            return Rc::clone(updated);
+        }
+        match Rc::try_unwrap(prev) {
+            // no other frame uses this permutation, so its buffer can be reused
+            Ok(prev) => Rc::new(prev.update_indices(order)),
+            Err(prev) => {
+                let updated = Rc::new(prev.updated_indices(order));
+                self.updates.push((prev, Rc::clone(&updated)));
+                updated
+            }
+        }
+    }
+}
+
+/// Trait for updating the permutations of frames which may share their permutation with other
+/// frames.
+pub trait UpdateSharedPermutation {
+    /// Consumes this object and returns a new object with permutations updated according to the
+    /// provided indices. Permutations which were already updated (as recorded in `updates`) are
+    /// reused instead of being updated again.
+    fn update_shared_permutation(self, order: &[usize], updates: &mut PermutationUpdates)
+        -> Self;
+}
+impl UpdateSharedPermutation for Nil {
+    fn update_shared_permutation(self, _order: &[usize], _updates: &mut PermutationUpdates) -> Nil {
+        Nil
+    }
+}
+impl<FrameFields, FramedStore> UpdateSharedPermutation for DataFrame<FrameFields, FramedStore> {
+    fn update_shared_permutation(
+        self,
+        order: &[usize],
+        updates: &mut PermutationUpdates,
+    ) -> Self {
+        DataFrame {
+            permutation: updates.update(self.permutation, order),
+            fields: PhantomData,
+            store: self.store,
+        }
+    }
+}
+
+/// The underlying stores and permutations of a collection of frames.
+#[derive(Debug, Default)]
+pub struct FramePermutations {
+    // (store address, permutation) pairs
+    frames: Vec<(usize, Rc<Permutation>)>,
+}
+impl FramePermutations {
+    // finds a permutation of a frame of the store at `store` which is equal to `permutation`
+    fn find_equal(&self, store: usize, permutation: &Permutation) -> Option<&Rc<Permutation>> {
+        self.frames
+            .iter()
+            .find(|&&(other_store, ref other)| other_store == store && **other == *permutation)
+            .map(|&(_, ref other)| other)
+    }
+    #[cfg(test)]
+    pub(crate) fn num_distinct(&self) -> usize {
+        let mut distinct: Vec<&Rc<Permutation>> = vec![];
+        for &(_, ref permutation) in &self.frames {
+            if !distinct.iter().any(|other| Rc::ptr_eq(other, permutation)) {
+                distinct.push(permutation);
+            }
+        }
+        distinct.len()
+    }
+}
+
+/// Trait for sharing permutations among frames which reference the same underlying store with
+/// identical permutations, such as the frames of two views derived from the same source which
+/// are merged together. Frames which share a permutation also share its subsequent updates.
+pub trait SharePermutations {
+    /// Adds the store and permutation of each frame in this object to `frames`.
+    fn collect_permutations(&self, frames: &mut FramePermutations);
+    /// Consumes this object and returns it with each frame using the permutation of any frame in
+    /// `frames` which has the same underlying store and an equal permutation.
+    fn share_permutations(self, frames: &FramePermutations) -> Self;
+}
+impl SharePermutations for Nil {
+    fn collect_permutations(&self, _frames: &mut FramePermutations) {}
+    fn share_permutations(self, _frames: &FramePermutations) -> Nil {
+        Nil
+    }
+}
+impl<FrameFields, FramedStore> SharePermutations for DataFrame<FrameFields, FramedStore> {
+    fn collect_permutations(&self, frames: &mut FramePermutations) {
+        frames
+            .frames
+            .push((self.store_addr(), Rc::clone(&self.permutation)));
+    }
+    fn share_permutations(mut self, frames: &FramePermutations) -> Self {
+        if let Some(shared) = frames.find_equal(self.store_addr(), &self.permutation) {
+            self.permutation = Rc::clone(shared);
+        }
         self
     }
 }
 
+/// Function which updates the permutation of each labeled frame of a list of frames when
+/// [mapped](../cons/trait.Map.html) over the list (see
+/// [UpdateSharedPermutation](trait.UpdateSharedPermutation.html)).
+pub struct UpdateFramePermutation<'a> {
+    pub(crate) order: &'a [usize],
+    pub(crate) updates: &'a mut PermutationUpdates,
+}
+impl<'a, L, Frame> MapFunc<Labeled<L, Frame>> for UpdateFramePermutation<'a>
+where
+    Frame: Valued<Value = Frame> + UpdateSharedPermutation,
+{
+    type Output = Labeled<L, Frame>;
+    fn call(&mut self, frame: Labeled<L, Frame>) -> Labeled<L, Frame> {
+        Labeled::from(
+            frame
+                .value()
+                .update_shared_permutation(self.order, self.updates),
+        )
+    }
+}
+
+/// Function which adds the store and permutation of each labeled frame of a list of frames to a
+/// [FramePermutations](struct.FramePermutations.html) when [folded](../cons/trait.Fold.html) over
+/// a reference to the list (see [SharePermutations](trait.SharePermutations.html)).
+pub struct CollectFramePermutations;
+impl<'a, 'b, L, Frame> FoldFunc<&'b mut FramePermutations, &'a Labeled<L, Frame>>
+    for CollectFramePermutations
+where
+    Frame: Valued<Value = Frame> + SharePermutations,
+{
+    fn call(
+        &mut self,
+        frames: &'b mut FramePermutations,
+        frame: &'a Labeled<L, Frame>,
+    ) -> &'b mut FramePermutations {
+        frame.value_ref().collect_permutations(frames);
+        frames
+    }
+}
+
+/// Function which shares the permutations in a
+/// [FramePermutations](struct.FramePermutations.html) with each labeled frame of a list of frames
+/// when [mapped](../cons/trait.Map.html) over the list (see
+/// [SharePermutations](trait.SharePermutations.html)).
+pub struct ShareFramePermutations<'a> {
+    pub(crate) frames: &'a FramePermutations,
+}
+impl<'a, L, Frame> MapFunc<Labeled<L, Frame>> for ShareFramePermutations<'a>
+where
+    Frame: Valued<Value = Frame> + SharePermutations,
+{
+    type Output = Labeled<L, Frame>;
+    fn call(&mut self, frame: Labeled<L, Frame>) -> Labeled<L, Frame> {
+        Labeled::from(frame.value().share_permutations(self.frames))
+    }
+}
+
+impl<FrameFields, FramedStore> DataFrame<FrameFields, FramedStore> {
+    // address of the underlying store, for identifying frames which share a store
+    fn store_addr(&self) -> usize {
+        &*self.store as *const FramedStore as usize
+    }
+}
+
 impl<StoreFields> From<DataStore<StoreFields>>
     for DataFrame<<StoreFields as SimpleFrameFields>::Fields, DataStore<StoreFields>>
 where
@@ -376,6 +552,8 @@ where
 pub struct Framed<T, DI> {
     permutation: Rc<Permutation>,
     data: FrameKind<DI>,
+    // label of the accessed field (if known), used to identify the field in errors
+    label: Option<&'static str>,
     _ty: PhantomData<T>,
 }
 impl<T, DI> Framed<T, DI> {
@@ -384,6 +562,7 @@ impl<T, DI> Framed<T, DI> {
         Framed {
             permutation,
             data: FrameKind::Single(data),
+            label: None,
             _ty: PhantomData,
         }
     }
@@ -393,10 +572,40 @@ impl<T, DI> Framed<T, DI> {
         Framed {
             permutation,
             data: FrameKind::Melt(data),
+            label: None,
             _ty: PhantomData,
         }
     }
 }
+
+/// Trait for field accessors which can be labeled, so that errors accessing the field identify it.
+pub trait LabelField {
+    /// Consumes this field accessor and returns it labeled with `label`.
+    fn with_label(self, label: &'static str) -> Self;
+}
+impl<T, DI> LabelField for Framed<T, DI> {
+    fn with_label(mut self, label: &'static str) -> Framed<T, DI> {
+        self.label = Some(label);
+        self
+    }
+}
+impl<T, DI> Framed<T, DI>
+where
+    T: Debug,
+    DI: DataIndex<DType = T> + Debug,
+{
+    /// Returns the value (possibly NA) at the specified index, without wrapping it in a `Result`.
+    /// Intended for algorithms which have already established that `idx` is within bounds.
+    ///
+    /// # Panics
+    /// Panics if `idx` is out of bounds.
+    pub fn get_or_panic(&self, idx: usize) -> Value<&T> {
+        match self.get_datum(idx) {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
 impl<T, DI> Clone for Framed<T, DI>
 where
     DI: Clone,
@@ -405,6 +614,7 @@ where
         Framed {
             permutation: Rc::clone(&self.permutation),
             data: self.data.clone(),
+            label: self.label,
             _ty: PhantomData,
         }
     }
@@ -414,6 +624,7 @@ impl<T> From<DataRef<T>> for Framed<T, DataRef<T>> {
         Framed {
             permutation: Rc::new(Permutation::default()),
             data: FrameKind::Single(orig),
+            label: None,
             _ty: PhantomData,
         }
     }
@@ -423,6 +634,7 @@ impl<T> From<FieldData<T>> for Framed<T, DataRef<T>> {
         Framed {
             permutation: Rc::new(Permutation::default()),
             data: FrameKind::Single(orig.into()),
+            label: None,
             _ty: PhantomData,
         }
     }
@@ -436,8 +648,16 @@ where
     type DType = T;
 
     fn get_datum(&self, idx: usize) -> error::Result<Value<&T>> {
-        assert!(!self.data.is_empty());
-        match self.data {
+        // check against the framed length, since the permutation itself may not be bounds-checked
+        let len = self.len();
+        if idx >= len {
+            return Err(error::AgnesError::IndexError {
+                index: idx,
+                len,
+                field: self.label.map(FieldIdent::from),
+            });
+        }
+        let datum = match self.data {
             FrameKind::Single(ref field) => field.get_datum(self.permutation.map_index(idx)),
             FrameKind::Melt(ref fields) => {
                 // when we have multiple fields in this Framed struct, we loop through through the
@@ -445,6 +665,10 @@ where
                 let nfields = self.data.nfields();
                 fields[idx % nfields].get_datum(self.permutation.map_index(idx / nfields))
             }
+        };
+        match self.label {
+            Some(label) => datum.map_err(|e| e.in_field(label)),
+            None => datum,
         }
     }
     fn len(&self) -> usize {
@@ -452,6 +676,65 @@ where
         // nfields * nrows
         self.data.nfields() * self.permutation.len().unwrap_or(self.data.nrows())
     }
+    fn count_na(&self) -> usize {
+        if self.permutation.is_permuted() {
+            // permuted data may repeat or exclude records, so each value must be checked
+            (0..self.len())
+                .filter(|&idx| self.get_datum(idx).map_or(false, |value| value.is_na()))
+                .count()
+        } else {
+            match self.data {
+                FrameKind::Single(ref field) => field.count_na(),
+                FrameKind::Melt(ref fields) => fields.iter().map(|field| field.count_na()).sum(),
+            }
+        }
+    }
+    fn cached_summary(&self) -> Option<FieldSummary<T>>
+    where
+        T: Clone + PartialOrd,
+    {
+        // permuted data may exclude records, so the underlying summary would not apply
+        if self.permutation.is_permuted() {
+            return None;
+        }
+        match self.data {
+            FrameKind::Single(ref field) => field.cached_summary(),
+            FrameKind::Melt(_) => None,
+        }
+    }
+    fn as_field_data(&self) -> Option<&FieldData<T>> {
+        if self.permutation.is_permuted() {
+            return None;
+        }
+        match self.data {
+            FrameKind::Single(ref field) => field.as_field_data(),
+            FrameKind::Melt(_) => None,
+        }
+    }
+    fn cached_hash(&self, idx: usize) -> Option<u64> {
+        match self.data {
+            FrameKind::Single(ref field) => field.cached_hash(self.permutation.map_index(idx)),
+            // melted fields may not all have cached hashes, and cached hashes can't be mixed with
+            // computed hashes
+            FrameKind::Melt(_) => None,
+        }
+    }
+}
+
+impl<T, DI> CacheHashes for Framed<T, DI>
+where
+    DI: CacheHashes,
+{
+    fn cache_hashes(&self) {
+        match self.data {
+            FrameKind::Single(ref field) => field.cache_hashes(),
+            FrameKind::Melt(ref fields) => {
+                for field in fields {
+                    field.cache_hashes();
+                }
+            }
+        }
+    }
 }
 
 #[cfg(feature = "serialize")]
@@ -0,0 +1,268 @@
+/*!
+Approximate (fuzzy) matching of string values, for record-linkage tasks such as matching country
+names across data sets.
+
+A [FuzzyMatch](struct.FuzzyMatch.html) specifies how two strings are compared (a
+[Similarity](enum.Similarity.html) measure and threshold, along with an optional
+[StrNormalization](../normalize/struct.StrNormalization.html)) and which pairs of records are
+compared at all (a [Blocking](enum.Blocking.html) strategy). It is used to join two `DataView`s
+with [fuzzy_join](../view/struct.DataView.html#method.fuzzy_join).
+*/
+use std::cmp::{max, min};
+use std::collections::HashMap;
+
+use access::DataIndex;
+use normalize::StrNormalization;
+use value::Value;
+
+/// Computes the Levenshtein (edit) distance between `left` and `right`: the minimum number of
+/// single-character insertions, deletions, and substitutions required to change one into the
+/// other.
+pub fn levenshtein(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut prev_row = (0..=right.len()).collect::<Vec<_>>();
+    let mut curr_row = vec![0; right.len() + 1];
+    for (i, lc) in left.chars().enumerate() {
+        curr_row[0] = i + 1;
+        for (j, &rc) in right.iter().enumerate() {
+            let substitution_cost = if lc == rc { 0 } else { 1 };
+            curr_row[j + 1] = min(
+                min(prev_row[j + 1] + 1, curr_row[j] + 1),
+                prev_row[j] + substitution_cost,
+            );
+        }
+        ::std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+    prev_row[right.len()]
+}
+
+/// Computes the Jaro similarity between `left` and `right`, between `0.0` (no similarity) and
+/// `1.0` (identical strings).
+pub fn jaro(left: &str, right: &str) -> f64 {
+    let left = left.chars().collect::<Vec<_>>();
+    let right = right.chars().collect::<Vec<_>>();
+    if left.is_empty() && right.is_empty() {
+        return 1.0;
+    }
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+
+    // characters only match if they are within this distance of each other
+    let window = (max(left.len(), right.len()) / 2).saturating_sub(1);
+    let mut left_matched = vec![false; left.len()];
+    let mut right_matched = vec![false; right.len()];
+    let mut num_matches = 0usize;
+    for (i, &lc) in left.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = min(i + window + 1, right.len());
+        for j in start..end {
+            if !right_matched[j] && right[j] == lc {
+                left_matched[i] = true;
+                right_matched[j] = true;
+                num_matches += 1;
+                break;
+            }
+        }
+    }
+    if num_matches == 0 {
+        return 0.0;
+    }
+
+    // count matched characters which appear in a different order
+    let left_matches = left
+        .iter()
+        .zip(&left_matched)
+        .filter(|&(_, &matched)| matched)
+        .map(|(c, _)| c);
+    let right_matches = right
+        .iter()
+        .zip(&right_matched)
+        .filter(|&(_, &matched)| matched)
+        .map(|(c, _)| c);
+    let num_transpositions = left_matches
+        .zip(right_matches)
+        .filter(|&(lc, rc)| lc != rc)
+        .count()
+        / 2;
+
+    let m = num_matches as f64;
+    (m / left.len() as f64 + m / right.len() as f64 + (m - num_transpositions as f64) / m) / 3.0
+}
+
+/// Computes the Jaro-Winkler similarity between `left` and `right`, between `0.0` (no
+/// similarity) and `1.0` (identical strings). This is the [Jaro](fn.jaro.html) similarity,
+/// adjusted upwards for strings which share a common prefix (of up to four characters).
+pub fn jaro_winkler(left: &str, right: &str) -> f64 {
+    let jaro = jaro(left, right);
+    let prefix_len = left
+        .chars()
+        .zip(right.chars())
+        .take(4)
+        .take_while(|&(lc, rc)| lc == rc)
+        .count();
+    jaro + prefix_len as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// String similarity measure and threshold used to determine whether two strings match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Similarity {
+    /// Strings match if their [Levenshtein](fn.levenshtein.html) distance is at most the
+    /// specified maximum distance.
+    Levenshtein(usize),
+    /// Strings match if their [Jaro-Winkler](fn.jaro_winkler.html) similarity is at least the
+    /// specified minimum similarity.
+    JaroWinkler(f64),
+}
+
+impl Similarity {
+    /// Returns whether or not `left` and `right` match using this similarity measure.
+    pub fn is_match(&self, left: &str, right: &str) -> bool {
+        match *self {
+            Similarity::Levenshtein(max_distance) => levenshtein(left, right) <= max_distance,
+            Similarity::JaroWinkler(min_similarity) => jaro_winkler(left, right) >= min_similarity,
+        }
+    }
+}
+
+/// Strategy for limiting which pairs of records are compared when fuzzy matching. Comparing every
+/// pair of records is quadratic in the number of records; blocking only compares records which
+/// share a (normalized) blocking key, at the cost of missing matches which differ in that key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blocking {
+    /// Compare every pair of records.
+    Full,
+    /// Only compare records whose (normalized) values share the same first `n` characters.
+    Prefix(usize),
+}
+
+/// Specification of how to match string values approximately: the similarity measure, the
+/// normalization applied to values before comparison, and the blocking strategy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuzzyMatch {
+    similarity: Similarity,
+    normalization: StrNormalization,
+    blocking: Blocking,
+}
+
+impl FuzzyMatch {
+    /// Creates a new `FuzzyMatch` using the similarity measure `similarity`, with no normalization
+    /// and no blocking (every pair of records is compared).
+    pub fn new(similarity: Similarity) -> FuzzyMatch {
+        FuzzyMatch {
+            similarity,
+            normalization: StrNormalization::new(),
+            blocking: Blocking::Full,
+        }
+    }
+    /// Normalizes values with `normalization` before comparing them (and computing blocking keys).
+    pub fn normalization(mut self, normalization: StrNormalization) -> FuzzyMatch {
+        self.normalization = normalization;
+        self
+    }
+    /// Limits the pairs of records compared using the blocking strategy `blocking`.
+    pub fn blocking(mut self, blocking: Blocking) -> FuzzyMatch {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Returns whether or not `left` and `right` match (after normalization).
+    pub fn is_match(&self, left: &str, right: &str) -> bool {
+        self.similarity.is_match(
+            &self.normalization.normalize(left),
+            &self.normalization.normalize(right),
+        )
+    }
+
+    // computes the blocking key of a normalized value
+    fn block_key(&self, value: &str) -> String {
+        match self.blocking {
+            Blocking::Full => String::new(),
+            Blocking::Prefix(n) => value.chars().take(n).collect(),
+        }
+    }
+
+    /// Computes the indices of the matching pairs of records between `left` and `right`. Missing
+    /// values never match. Returns the indices of the left records and the indices of the right
+    /// records, with matches ordered by left index and then by right index.
+    pub fn match_indices<L, R>(&self, left: &L, right: &R) -> (Vec<usize>, Vec<usize>)
+    where
+        L: DataIndex,
+        L::DType: AsRef<str>,
+        R: DataIndex,
+        R::DType: AsRef<str>,
+    {
+        let mut blocks: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+        for (ridx, value) in right.iter().enumerate() {
+            if let Value::Exists(value) = value {
+                let value = self.normalization.normalize(value.as_ref());
+                blocks
+                    .entry(self.block_key(&value))
+                    .or_insert_with(Vec::new)
+                    .push((ridx, value));
+            }
+        }
+
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        for (lidx, value) in left.iter().enumerate() {
+            if let Value::Exists(value) = value {
+                let value = self.normalization.normalize(value.as_ref());
+                if let Some(candidates) = blocks.get(&self.block_key(&value)) {
+                    for &(ridx, ref candidate) in candidates {
+                        if self.similarity.is_match(&value, candidate) {
+                            left_indices.push(lidx);
+                            right_indices.push(ridx);
+                        }
+                    }
+                }
+            }
+        }
+        (left_indices, right_indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use field::FieldData;
+
+    #[test]
+    fn distances() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("flaw", "flaw"), 0);
+
+        assert!((jaro("MARTHA", "MARHTA") - 0.9444).abs() < 1e-4);
+        assert!((jaro_winkler("MARTHA", "MARHTA") - 0.9611).abs() < 1e-4);
+        assert!((jaro_winkler("DIXON", "DICKSONX") - 0.8133).abs() < 1e-4);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+        assert_eq!(jaro_winkler("", ""), 1.0);
+    }
+
+    #[test]
+    fn match_indices() {
+        let left: FieldData<String> = vec!["Viet Nam", "Korea, Rep.", "Bahamas, The", "Egypt"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let right = FieldData::from_field_vec(vec![
+            Value::Exists("egypt".to_string()),
+            Value::Na,
+            Value::Exists("Vietnam".to_string()),
+            Value::Exists("Bahamas".to_string()),
+        ]);
+
+        let fuzzy = FuzzyMatch::new(Similarity::Levenshtein(1))
+            .normalization(StrNormalization::new().case_insensitive());
+        assert_eq!(fuzzy.match_indices(&left, &right), (vec![0, 3], vec![2, 0]));
+
+        let fuzzy = FuzzyMatch::new(Similarity::JaroWinkler(0.85))
+            .normalization(StrNormalization::new().case_insensitive())
+            .blocking(Blocking::Prefix(1));
+        assert_eq!(
+            fuzzy.match_indices(&left, &right),
+            (vec![0, 2, 3], vec![2, 3, 0])
+        );
+    }
+}
@@ -0,0 +1,489 @@
+/*!
+Reusable groupings of the records of a `DataView`.
+
+A [GroupBy](struct.GroupBy.html) (created with
+[group_by](../view/struct.DataView.html#method.group_by)) holds the groups of records of a
+`DataView` which share the same values in a list of key fields, so that several values can be
+computed over the same groups (with [apply](struct.GroupBy.html#method.apply)) without regrouping
+the records each time. Representative records of each group can be inspected with
+[head](struct.GroupBy.html#method.head) and [sample](struct.GroupBy.html#method.sample).
+
+Quantiles of each group (see [quantile](struct.GroupBy.html#method.quantile)) are computed exactly
+by default. For large groups, they can instead be estimated with a streaming
+[QuantileSketch](../stats/struct.QuantileSketch.html) by specifying a
+[QuantileMethod](../stats/enum.QuantileMethod.html) with
+[with_quantile_method](struct.GroupBy.html#method.with_quantile_method).
+Similarly, the number of distinct values in each group can be estimated with a
+[HyperLogLog](../stats/struct.HyperLogLog.html) sketch (see
+[approx_nunique](struct.GroupBy.html#method.approx_nunique)).
+
+Groupings are hierarchical: a grouping by several key fields (e.g.
+`group_by::<Labels![Region, Country]>()`) can be coarsened into a grouping by a subset of those
+fields (e.g. `Labels![Region]`) with [regroup](struct.GroupBy.html#method.regroup), which merges
+the existing groups instead of regrouping the records from scratch.
+*/
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use num_traits::AsPrimitive;
+use rand::Rng;
+
+use access::{DataIndex, NRows};
+use label::HasLabels;
+use permute::UpdatePermutation;
+use rng::operation_rng;
+use select::{FieldSelect, SelectFieldByLabel};
+use stats::{HyperLogLog, Quantile, QuantileMethod, QuantileSketch, StatAccumulator};
+use value::Value;
+use view::{DataView, FieldList, GroupApply, HashIndex, PartialEqIndex, Record, VFieldTypeOf};
+
+/// Groups of the records of a `DataView` sharing the same values in the fields labeled by
+/// `KeyLabels`.
+#[derive(Debug, Clone)]
+pub struct GroupBy<KeyLabels, Labels, Frames> {
+    view: DataView<Labels, Frames>,
+    // row indices (into `view`) of the records in each group, with groups in order of first
+    // appearance and row indices in increasing order
+    groups: Vec<Vec<usize>>,
+    quantile_method: QuantileMethod,
+    _keys: PhantomData<KeyLabels>,
+}
+
+impl<KeyLabels, Labels, Frames> GroupBy<KeyLabels, Labels, Frames> {
+    /// Returns the `DataView` whose records are grouped.
+    pub fn view(&self) -> &DataView<Labels, Frames> {
+        &self.view
+    }
+
+    /// Returns the number of groups.
+    pub fn ngroups(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns the row indices (within the grouped `DataView`) of the records in each group, with
+    /// groups in order of first appearance.
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    /// Computes a single value per group using `f`, which is called with the row indices of the
+    /// records in each group. Returns a new `DataView` containing the fields in `KeyLabels` (one
+    /// row per group) along with the computed values in a new field labeled `OutLabel`. See
+    /// [group_apply](../view/struct.DataView.html#method.group_apply).
+    pub fn apply<OutLabel, T, F>(
+        &self,
+        f: F,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, T>>::Output
+    where
+        DataView<Labels, Frames>: GroupApply<KeyLabels, OutLabel, T>,
+        F: FnMut(&[usize]) -> Value<T>,
+    {
+        GroupApply::<KeyLabels, OutLabel, T>::group_apply_to(&self.view, &self.groups, f)
+    }
+
+    /// Sets the method used to compute the quantiles of each group (with
+    /// [quantile](#method.quantile), [p25](#method.p25), [p50](#method.p50), and
+    /// [p75](#method.p75)). Defaults to `QuantileMethod::Exact`.
+    pub fn with_quantile_method(mut self, method: QuantileMethod) -> Self {
+        self.quantile_method = method;
+        self
+    }
+
+    /// Computes the `q`th quantile (`0.0 <= q <= 1.0`) of the numeric field labeled `ValueLabel`
+    /// for each group, using this grouping's [quantile method](#method.with_quantile_method).
+    ///
+    /// Returns a new `DataView` containing the fields in `KeyLabels` along with the quantiles in a
+    /// new field labeled `OutLabel`. Groups without any existing values have a missing quantile.
+    pub fn quantile<ValueLabel, OutLabel>(
+        &self,
+        q: f64,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        DataView<Labels, Frames>:
+            GroupApply<KeyLabels, OutLabel, f64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<DataView<Labels, Frames>, ValueLabel>: AsPrimitive<f64> + Debug,
+    {
+        let method = self.quantile_method;
+        self.apply::<OutLabel, f64, _>(|rows| {
+            let values = self.view.field::<ValueLabel>();
+            match method {
+                QuantileMethod::Sketch {
+                    compression,
+                    min_group_size,
+                } if rows.len() >= min_group_size => {
+                    let mut sketch = QuantileSketch::with_compression(compression);
+                    sketch.push_iter(rows.iter().map(|&row| values.get_datum(row).unwrap()));
+                    sketch.quantile(q)
+                }
+                _ => values.permute(rows).quantile(q),
+            }
+            .into()
+        })
+    }
+
+    /// Computes the 25th percentile of the numeric field labeled `ValueLabel` for each group. See
+    /// [quantile](#method.quantile).
+    pub fn p25<ValueLabel, OutLabel>(
+        &self,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        DataView<Labels, Frames>:
+            GroupApply<KeyLabels, OutLabel, f64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<DataView<Labels, Frames>, ValueLabel>: AsPrimitive<f64> + Debug,
+    {
+        self.quantile::<ValueLabel, OutLabel>(0.25)
+    }
+
+    /// Computes the 50th percentile (median) of the numeric field labeled `ValueLabel` for each
+    /// group. See [quantile](#method.quantile).
+    pub fn p50<ValueLabel, OutLabel>(
+        &self,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        DataView<Labels, Frames>:
+            GroupApply<KeyLabels, OutLabel, f64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<DataView<Labels, Frames>, ValueLabel>: AsPrimitive<f64> + Debug,
+    {
+        self.quantile::<ValueLabel, OutLabel>(0.5)
+    }
+
+    /// Computes the 75th percentile of the numeric field labeled `ValueLabel` for each group. See
+    /// [quantile](#method.quantile).
+    pub fn p75<ValueLabel, OutLabel>(
+        &self,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        DataView<Labels, Frames>:
+            GroupApply<KeyLabels, OutLabel, f64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<DataView<Labels, Frames>, ValueLabel>: AsPrimitive<f64> + Debug,
+    {
+        self.quantile::<ValueLabel, OutLabel>(0.75)
+    }
+
+    /// Estimates the number of distinct existing values of the field labeled `ValueLabel` in each
+    /// group, using a [HyperLogLog](../stats/struct.HyperLogLog.html) sketch per group instead of
+    /// collecting the distinct values of each group.
+    ///
+    /// Returns a new `DataView` containing the fields in `KeyLabels` along with the estimates in a
+    /// new field labeled `OutLabel`.
+    pub fn approx_nunique<ValueLabel, OutLabel>(
+        &self,
+    ) -> <DataView<Labels, Frames> as GroupApply<KeyLabels, OutLabel, u64>>::Output
+    where
+        DataView<Labels, Frames>:
+            GroupApply<KeyLabels, OutLabel, u64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<DataView<Labels, Frames>, ValueLabel>: Hash + Debug,
+    {
+        self.apply::<OutLabel, u64, _>(|rows| {
+            let values = self.view.field::<ValueLabel>();
+            let mut sketch = HyperLogLog::new();
+            sketch.push_iter(rows.iter().map(|&row| values.get_datum(row).unwrap()));
+            Value::Exists(sketch.result() as u64)
+        })
+    }
+
+    /// Coarsens this grouping into a grouping by the fields labeled by `OuterLabels`, which must
+    /// be a subset of `KeyLabels`. The groups of this grouping are merged into the groups of the
+    /// new grouping, so only one record per existing group is rehashed.
+    ///
+    /// The resulting groups are the same as those produced by grouping the records by
+    /// `OuterLabels` directly.
+    pub fn regroup<OuterLabels>(&self) -> GroupBy<OuterLabels, Labels, Frames>
+    where
+        KeyLabels: HasLabels<OuterLabels>,
+        Labels: FieldList<OuterLabels, Frames>,
+        <Labels as FieldList<OuterLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+        DataView<Labels, Frames>: Clone,
+    {
+        let fl = self.view.field_list::<OuterLabels>();
+        let mut map = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for rows in &self.groups {
+            let group_idx = *map.entry(Record::new(&fl, rows[0])).or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[group_idx].extend(rows);
+        }
+        for rows in &mut groups {
+            rows.sort();
+        }
+        GroupBy {
+            view: self.view.clone(),
+            groups,
+            quantile_method: self.quantile_method,
+            _keys: PhantomData,
+        }
+    }
+}
+
+impl<KeyLabels, Labels, Frames> GroupBy<KeyLabels, Labels, Frames>
+where
+    DataView<Labels, Frames>: Clone,
+    Frames: UpdatePermutation,
+{
+    /// Returns a `DataView` containing the first `n` records of each group (or all records of
+    /// groups with `n` or fewer records). Records remain in their order in the grouped `DataView`.
+    pub fn head(&self, n: usize) -> DataView<Labels, Frames> {
+        self.select_rows(|rows| rows.iter().take(n).cloned().collect())
+    }
+
+    /// Returns a `DataView` containing a uniform random sample (without replacement) of `n`
+    /// records from each group (or all records of groups with `n` or fewer records). Records
+    /// remain in their order in the grouped `DataView`. The sample is reproducible: the same
+    /// `seed` always produces the same sample from the same grouping. A `None` seed uses the
+    /// crate-level default seed (see the [rng](../rng/index.html) module).
+    pub fn sample(&self, n: usize, seed: Option<u64>) -> DataView<Labels, Frames> {
+        let mut rng = operation_rng(seed);
+        self.select_rows(|rows| {
+            let mut reservoir = Vec::with_capacity(n);
+            for (num_seen, &row) in rows.iter().enumerate() {
+                if num_seen < n {
+                    reservoir.push(row);
+                } else {
+                    let replace = rng.gen_range(0, num_seen + 1);
+                    if replace < n {
+                        reservoir[replace] = row;
+                    }
+                }
+            }
+            reservoir
+        })
+    }
+
+    // creates a view of the grouped `DataView` containing the rows chosen from each group by
+    // `choose`, in their original order
+    fn select_rows<F>(&self, mut choose: F) -> DataView<Labels, Frames>
+    where
+        F: FnMut(&[usize]) -> Vec<usize>,
+    {
+        let mut rows = self
+            .groups
+            .iter()
+            .flat_map(|group| choose(group))
+            .collect::<Vec<_>>();
+        rows.sort();
+        self.view.clone().update_permutations(&rows)
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Groups the records of this `DataView` by the unique composite values of the fields labeled
+    /// by `KeyLabels`, returning a [GroupBy](../groupby/struct.GroupBy.html) which can be used to
+    /// compute values over each group.
+    pub fn group_by<KeyLabels>(&self) -> GroupBy<KeyLabels, Labels, Frames>
+    where
+        Self: NRows + Clone,
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+    {
+        GroupBy {
+            view: self.clone(),
+            groups: self.group_rows::<KeyLabels>(),
+            quantile_method: QuantileMethod::default(),
+            _keys: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use stats::ApproxDistinct;
+    use store::IntoView;
+
+    tablespace![
+        @continue(typenum::Add1<::test_utils::dept_table::Table>)
+        table sales {
+            Region: String,
+            Country: String,
+            Amount: f64,
+            NumSales: u64,
+            TotalAmount: f64,
+            AmountQuantile: f64,
+            NumCountries: u64,
+        }
+    ];
+
+    #[test]
+    fn nested_group_by() {
+        use self::sales::*;
+
+        let dv = table![
+            Region = ["europe", "asia", "europe", "europe", "asia", "americas"];
+            Country = ["france", "japan", "spain", "france", "korea", "peru"];
+            Amount = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0];
+        ];
+
+        let by_country = dv.group_by::<Labels![Region, Country]>();
+        assert_eq!(by_country.ngroups(), 5);
+        assert_eq!(
+            by_country.groups(),
+            &[vec![0, 3], vec![1], vec![2], vec![4], vec![5]]
+        );
+        let amounts = dv.field::<Amount>();
+        let totals = by_country.apply::<TotalAmount, f64, _>(|rows| {
+            Value::Exists(
+                rows.iter()
+                    .map(|&row| amounts.get_datum(row).unwrap().unwrap())
+                    .sum(),
+            )
+        });
+        println!("{}", totals);
+        assert_eq!(
+            totals.fieldnames(),
+            vec!["Region", "Country", "TotalAmount"]
+        );
+        assert_eq!(
+            totals.field::<Country>().to_vec(),
+            vec!["france", "japan", "spain", "korea", "peru"]
+        );
+        assert_eq!(
+            totals.field::<TotalAmount>().to_vec(),
+            vec![50.0, 20.0, 30.0, 50.0, 60.0]
+        );
+
+        // re-aggregate at the region level
+        let by_region = by_country.regroup::<Labels![Region]>();
+        assert_eq!(
+            by_region.groups(),
+            dv.group_by::<Labels![Region]>().groups()
+        );
+        assert_eq!(by_region.groups(), &[vec![0, 2, 3], vec![1, 4], vec![5]]);
+        let counts = by_region.apply::<NumSales, u64, _>(|rows| Value::Exists(rows.len() as u64));
+        println!("{}", counts);
+        assert_eq!(counts.fieldnames(), vec!["Region", "NumSales"]);
+        assert_eq!(
+            counts.field::<Region>().to_vec(),
+            vec!["europe", "asia", "americas"]
+        );
+        assert_eq!(counts.field::<NumSales>().to_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn head_sample() {
+        use self::sales::*;
+
+        let dv = table![
+            Region = ["europe", "asia", "europe", "europe", "asia", "americas", "europe"];
+            Amount = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0];
+        ];
+        let by_region = dv.group_by::<Labels![Region]>();
+
+        let head = by_region.head(2);
+        println!("{}", head);
+        assert_eq!(
+            head.field::<Region>().to_vec(),
+            vec!["europe", "asia", "europe", "asia", "americas"]
+        );
+        assert_eq!(
+            head.field::<Amount>().to_vec(),
+            vec![10.0, 20.0, 30.0, 50.0, 60.0]
+        );
+        assert_eq!(by_region.head(0).nrows(), 0);
+        assert_eq!(by_region.head(10).nrows(), dv.nrows());
+
+        let sample = by_region.sample(2, Some(42));
+        println!("{}", sample);
+        assert_eq!(sample.nrows(), 5);
+        let sampled_regions = sample.field::<Region>().to_vec();
+        for &(region, count) in &[("europe", 2), ("asia", 2), ("americas", 1)] {
+            assert_eq!(
+                sampled_regions.iter().filter(|&r| r == region).count(),
+                count
+            );
+        }
+        // sampled records keep their original order
+        let amounts = sample.field::<Amount>().to_vec();
+        assert!(amounts.windows(2).all(|pair| pair[0] < pair[1]));
+        // samples are reproducible
+        assert_eq!(
+            by_region.sample(2, Some(42)).field::<Amount>().to_vec(),
+            amounts
+        );
+        assert_eq!(by_region.sample(10, Some(7)).nrows(), dv.nrows());
+    }
+
+    #[test]
+    fn quantiles() {
+        use self::sales::*;
+
+        let dv = table![
+            Region = ["europe", "asia", "europe", "europe", "asia", "americas", "europe"];
+            Country = ["france", "japan", "spain", "france", "korea", "peru", "spain"];
+            Amount = [10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0];
+        ];
+        let by_region = dv.group_by::<Labels![Region]>();
+        let p25 = by_region.p25::<Amount, AmountQuantile>();
+        println!("{}", p25);
+        assert_eq!(
+            p25.field::<AmountQuantile>().to_vec(),
+            vec![25.0, 27.5, 60.0]
+        );
+        let p50 = by_region.p50::<Amount, AmountQuantile>();
+        assert_eq!(
+            p50.field::<AmountQuantile>().to_vec(),
+            vec![35.0, 35.0, 60.0]
+        );
+        let p75 = by_region.p75::<Amount, AmountQuantile>();
+        assert_eq!(
+            p75.field::<AmountQuantile>().to_vec(),
+            vec![47.5, 42.5, 60.0]
+        );
+        let p10 = by_region.quantile::<Amount, AmountQuantile>(0.1);
+        assert_eq!(
+            p10.field::<AmountQuantile>().to_vec(),
+            vec![16.0, 23.0, 60.0]
+        );
+
+        // sketches of small groups give exact quantiles
+        let sketched =
+            dv.group_by::<Labels![Region]>()
+                .with_quantile_method(QuantileMethod::Sketch {
+                    compression: 100.0,
+                    min_group_size: 2,
+                });
+        assert_eq!(
+            sketched
+                .p50::<Amount, AmountQuantile>()
+                .field::<AmountQuantile>()
+                .to_vec(),
+            vec![35.0, 35.0, 60.0]
+        );
+        // outer groupings keep the quantile method
+        let by_all = dv
+            .group_by::<Labels![Region, Country]>()
+            .with_quantile_method(QuantileMethod::Sketch {
+                compression: 100.0,
+                min_group_size: 2,
+            })
+            .regroup::<Labels![Region]>();
+        assert_eq!(
+            by_all
+                .p75::<Amount, AmountQuantile>()
+                .field::<AmountQuantile>()
+                .to_vec(),
+            vec![47.5, 42.5, 60.0]
+        );
+    }
+
+    #[test]
+    fn approx_nunique() {
+        use self::sales::*;
+
+        let dv = table![
+            Region = ["europe", "asia", "europe", "europe", "asia", "americas", "europe"];
+            Country = ["france", "japan", "spain", "france", "korea", "peru", "spain"];
+        ];
+        let nunique = dv
+            .group_by::<Labels![Region]>()
+            .approx_nunique::<Country, NumCountries>();
+        println!("{}", nunique);
+        assert_eq!(nunique.field::<NumCountries>().to_vec(), vec![2, 2, 1]);
+        assert_eq!(dv.field::<Country>().approx_nunique(), 5);
+    }
+}
@@ -0,0 +1,215 @@
+/*!
+Incremental aggregation over data which arrives in batches.
+
+An [IncrementalAggregate](struct.IncrementalAggregate.html) maintains the aggregation state of
+each group seen so far, and updates those states with each appended batch of records (a
+`DataView`). This avoids recomputing the aggregation over the full history of the data each
+time a new batch arrives, as would be required with
+[aggregate](../view/struct.DataView.html#method.aggregate). The current aggregation results can
+be retrieved as a `DataView` at any time.
+*/
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use access::{DataIndex, NRows};
+use field::FieldData;
+use frame::IntoFrame;
+use join::AddFrame;
+use select::SelectFieldByLabel;
+use store::{IntoStore, IntoView};
+use value::Value;
+use view::{AddedFieldFrame, DataView, SingleFieldView};
+
+/// Aggregation of the values of the field labeled `ValueLabel` (with data type `DType`), grouped
+/// by the values of the field labeled `KeyLabel` (with data type `K`), which can be updated with
+/// appended batches of records. Each group's aggregation state (of type `AggType`) starts as a
+/// clone of the initial value, and is updated with each of the group's values using the
+/// aggregation function `F` (as in [aggregate](../view/struct.DataView.html#method.aggregate)).
+/// Missing (NA) keys form their own group.
+#[derive(Debug, Clone)]
+pub struct IncrementalAggregate<KeyLabel, ValueLabel, AggLabel, K, DType, AggType, F> {
+    init: AggType,
+    f: F,
+    // group keys, in order of first appearance
+    keys: Vec<Value<K>>,
+    // aggregation state of each group, in the same order as `keys`
+    aggregates: Vec<AggType>,
+    // map from group key to index in `keys` and `aggregates`
+    groups: HashMap<Value<K>, usize>,
+    nrows: usize,
+    _marker: PhantomData<(KeyLabel, ValueLabel, AggLabel, DType)>,
+}
+
+impl<KeyLabel, ValueLabel, AggLabel, K, DType, AggType, F>
+    IncrementalAggregate<KeyLabel, ValueLabel, AggLabel, K, DType, AggType, F>
+where
+    K: Hash + Eq + Clone,
+    AggType: Clone,
+    F: Fn(&mut AggType, Value<&DType>),
+{
+    /// Creates a new `IncrementalAggregate` without any records, where each group's aggregation
+    /// state is initialized to `init` and updated with `f`.
+    pub fn new(init: AggType, f: F) -> Self {
+        IncrementalAggregate {
+            init,
+            f,
+            keys: vec![],
+            aggregates: vec![],
+            groups: HashMap::new(),
+            nrows: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Updates the aggregation states with the records of `batch`, creating new groups for any
+    /// keys which have not been seen before. Only the records in `batch` are processed.
+    pub fn push_batch<Labels, Frames>(&mut self, batch: &DataView<Labels, Frames>)
+    where
+        DataView<Labels, Frames>: NRows
+            + SelectFieldByLabel<KeyLabel, DType = K>
+            + SelectFieldByLabel<ValueLabel, DType = DType>,
+    {
+        let keys = SelectFieldByLabel::<KeyLabel>::select_field(batch);
+        let values = SelectFieldByLabel::<ValueLabel>::select_field(batch);
+        for i in 0..batch.nrows() {
+            let key = keys.get_datum(i).unwrap().cloned();
+            let group_idx = match self.groups.get(&key) {
+                Some(&group_idx) => group_idx,
+                None => {
+                    self.keys.push(key.clone());
+                    self.aggregates.push(self.init.clone());
+                    self.groups.insert(key, self.keys.len() - 1);
+                    self.keys.len() - 1
+                }
+            };
+            (self.f)(
+                &mut self.aggregates[group_idx],
+                values.get_datum(i).unwrap(),
+            );
+        }
+        self.nrows += batch.nrows();
+    }
+
+    /// Returns the number of groups seen so far.
+    pub fn ngroups(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns the total number of records processed so far.
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns a new `DataView` with the current aggregation results: the field labeled
+    /// `KeyLabel` (one row per group, in order of first appearance), along with the aggregation
+    /// states in a new field labeled `AggLabel`.
+    pub fn to_view(
+        &self,
+    ) -> <SingleFieldView<KeyLabel, K> as AddFrame<AddedFieldFrame<AggLabel, AggType>>>::Output
+    where
+        FieldData<K>: IntoStore<KeyLabel>,
+        <FieldData<K> as IntoStore<KeyLabel>>::Output: IntoView,
+        FieldData<AggType>: IntoStore<AggLabel>,
+        <FieldData<AggType> as IntoStore<AggLabel>>::Output: IntoFrame,
+        SingleFieldView<KeyLabel, K>: AddFrame<AddedFieldFrame<AggLabel, AggType>>,
+        K: Debug + Default,
+        AggType: Debug + Default,
+    {
+        let keys = FieldData::from_field_vec(self.keys.clone());
+        let aggregates: FieldData<AggType> = self.aggregates.clone().into();
+        IntoStore::<KeyLabel>::into_store(keys)
+            .into_view()
+            .add_frame(IntoStore::<AggLabel>::into_store(aggregates).into_frame())
+    }
+}
+
+#[cfg(feature = "test-utils")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utils::*;
+
+    tablespace![
+        @continue(typenum::Add1<::test_utils::dept_table::Table>)
+        table dept_totals {
+            TotalOffset: i64,
+        }
+    ];
+
+    #[test]
+    fn push_batches() {
+        let full = sample_emp_table_full().into_view();
+        let mut agg = IncrementalAggregate::<
+            full_emp_table::DeptId,
+            full_emp_table::SalaryOffset,
+            dept_totals::TotalOffset,
+            _,
+            _,
+            _,
+            _,
+        >::new(0i64, |total: &mut i64, offset: Value<&i64>| {
+            *total += *offset.unwrap_or(&0)
+        });
+        assert_eq!(agg.ngroups(), 0);
+        assert_eq!(agg.to_view().nrows(), 0);
+
+        // first batch: employees of departments 1 and 2
+        let batch = full
+            .clone()
+            .filter::<full_emp_table::DeptId, _>(|dept: Value<&u64>| {
+                dept.map_or(false, |&dept| dept <= 2)
+            });
+        agg.push_batch(&batch);
+        let totals = agg.to_view();
+        println!("{}", totals);
+        assert_eq!(
+            totals.field::<full_emp_table::DeptId>().to_vec(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            totals.field::<dept_totals::TotalOffset>().to_vec(),
+            vec![-26, 4]
+        );
+
+        // second batch: remaining employees
+        let batch = full.filter::<full_emp_table::DeptId, _>(|dept: Value<&u64>| {
+            dept.map_or(false, |&dept| dept > 2)
+        });
+        agg.push_batch(&batch);
+        assert_eq!((agg.ngroups(), agg.nrows()), (4, 7));
+        let totals = agg.to_view();
+        println!("{}", totals);
+        assert_eq!(
+            totals.field::<full_emp_table::DeptId>().to_vec(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            totals.field::<dept_totals::TotalOffset>().to_vec(),
+            vec![-26, 4, 10, -1]
+        );
+
+        // existing groups are updated by later batches
+        let batch = dept_table(vec![4, 5], vec!["R&D", "Ops"]).into_view();
+        let mut counts = IncrementalAggregate::<
+            dept_table::DeptId,
+            dept_table::DeptName,
+            dept_totals::TotalOffset,
+            _,
+            _,
+            _,
+            _,
+        >::new(0i64, |count: &mut i64, _| *count += 1);
+        counts.push_batch(&batch);
+        counts.push_batch(&batch);
+        assert_eq!(
+            counts
+                .to_view()
+                .field::<dept_totals::TotalOffset>()
+                .to_vec(),
+            vec![2, 2]
+        );
+    }
+}
@@ -4,19 +4,38 @@ Traits and implementations to handle joining or merging two `DataView`s.
 Joining [DataView](../view/struct.DataView.html)s involves finding the rows in each `DataView` which
 satisfy a specific join predicate (much like a `JOIN` in a SQL database). Merging refers to
 combining fields of two `DataView` objects with the same number of rows into a single `DataView`.
+
+Unless otherwise specified (with [JoinOrder](enum.JoinOrder.html)), the records of a joined
+`DataView` are ordered by the record order of the left-hand `DataView`, then by the record order
+of the right-hand `DataView`, regardless of the join predicate.
+
+Semi-joins and anti-joins (see [semi_join](../view/struct.DataView.html#method.semi_join) and
+[anti_join](../view/struct.DataView.html#method.anti_join)) filter the records of a `DataView` by
+whether or not their key appears in another `DataView`, without adding any fields. When the
+right-hand `DataView` is large, a [BloomFilter](struct.BloomFilter.html) of its keys can be used to
+cheaply discard most non-matching records before matching keys exactly (see
+[SemiJoinMethod](enum.SemiJoinMethod.html)).
 */
-use std::cmp::Ordering;
-use std::fmt::Debug;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::ops::Add;
 
+use num_traits::AsPrimitive;
+
 use access::DataIndex;
 use cons::*;
 use error::*;
-use frame::DataFrame;
-use label::{LVCons, Labeled, LookupValuedElemByLabel, Valued};
+use field::FieldData;
+use frame::{DataFrame, FramePermutations, SharePermutations};
+use fuzzy::FuzzyMatch;
+use label::{LVCons, LabelName, Labeled, LookupValuedElemByLabel, Valued};
 use permute::SortOrder;
+use provenance::{Operation, WithOperationLog};
 use select::{FieldSelect, SelectFieldByLabel};
+use stats::hash_pair;
 use store::{DataStore, IntoView, PushBackClonedFromValueIter};
 use value::Value;
 use view::*;
@@ -128,29 +147,31 @@ pub trait AddFrame<Frame> {
     fn add_frame(&self, frame: Frame) -> Self::Output;
 }
 
-type NextFrameIndex<Frames> = <Frames as Len>::Len;
+type NextFrameIndexOf<Frames> = <Frames as NextFrameIndex>::Output;
 
 impl<Labels, Frames, NewFrame> AddFrame<NewFrame> for DataView<Labels, Frames>
 where
-    NewFrame: AsFrameLookup<NextFrameIndex<Frames>>,
-    Frames: Len,
-    Labels: Append<<NewFrame as AsFrameLookup<NextFrameIndex<Frames>>>::Output>,
-    Frames: Clone + PushBack<Labeled<NextFrameIndex<Frames>, NewFrame>>,
+    NewFrame: AsFrameLookup<NextFrameIndexOf<Frames>>,
+    Frames: NextFrameIndex,
+    Labels: Append<<NewFrame as AsFrameLookup<NextFrameIndexOf<Frames>>>::Output>,
+    Frames: Clone + PushBack<Labeled<NextFrameIndexOf<Frames>, NewFrame>>,
 {
     type Output = DataView<
-        <Labels as Append<<NewFrame as AsFrameLookup<NextFrameIndex<Frames>>>::Output>>::Appended,
-        <Frames as PushBack<Labeled<NextFrameIndex<Frames>, NewFrame>>>::Output,
+        <Labels as Append<<NewFrame as AsFrameLookup<NextFrameIndexOf<Frames>>>::Output>>::Appended,
+        <Frames as PushBack<Labeled<NextFrameIndexOf<Frames>, NewFrame>>>::Output,
     >;
 
     fn add_frame(&self, frame: NewFrame) -> Self::Output {
         let frames = self
             .frames
             .clone()
-            .push_back(Labeled::<NextFrameIndex<Frames>, _>::from(frame));
+            .push_back(Labeled::<NextFrameIndexOf<Frames>, _>::from(frame));
 
         DataView {
             _labels: PhantomData,
             frames,
+            log: self.log.clone(),
+            sort_cache: self.sort_cache.detached(),
         }
     }
 }
@@ -172,30 +193,43 @@ pub trait Merge<RLabels, RFrames> {
 }
 impl<LLabels, LFrames, RLabels, RFrames> Merge<RLabels, RFrames> for DataView<LLabels, LFrames>
 where
-    LFrames: Len,
-    RLabels: UpdateFrameIndexMarker<<LFrames as Len>::Len>,
-    LLabels: Append<<RLabels as UpdateFrameIndexMarker<<LFrames as Len>::Len>>::Output>,
-    RFrames: Clone + UpdateFrameIndex<<LFrames as Len>::Len>,
-    LFrames: Append<<RFrames as UpdateFrameIndex<<LFrames as Len>::Len>>::Output> + Clone,
+    LFrames: NextFrameIndex,
+    RLabels: UpdateFrameIndexMarker<NextFrameIndexOf<LFrames>>,
+    LLabels: Append<<RLabels as UpdateFrameIndexMarker<NextFrameIndexOf<LFrames>>>::Output>,
+    RFrames: Clone + SharePermutations + UpdateFrameIndex<NextFrameIndexOf<LFrames>>,
+    LFrames: Append<<RFrames as UpdateFrameIndex<NextFrameIndexOf<LFrames>>>::Output>
+        + Clone
+        + SharePermutations,
 {
     type OutLabels = <LLabels as Append<
-        <RLabels as UpdateFrameIndexMarker<<LFrames as Len>::Len>>::Output,
+        <RLabels as UpdateFrameIndexMarker<NextFrameIndexOf<LFrames>>>::Output,
+    >>::Appended;
+    type OutFrames = <LFrames as Append<
+        <RFrames as UpdateFrameIndex<NextFrameIndexOf<LFrames>>>::Output,
     >>::Appended;
-    type OutFrames =
-        <LFrames as Append<<RFrames as UpdateFrameIndex<<LFrames as Len>::Len>>::Output>>::Appended;
 
     fn merge(
         &self,
         right: &DataView<RLabels, RFrames>,
     ) -> DataView<Self::OutLabels, Self::OutFrames> {
-        let out_frames = self
-            .frames
-            .clone()
-            .append(right.frames.clone().update_frame_label());
+        // frames of the right-hand view which duplicate a left-hand frame share its permutation
+        let mut left_permutations = FramePermutations::default();
+        self.frames.collect_permutations(&mut left_permutations);
+        let out_frames = self.frames.clone().append(
+            right
+                .frames
+                .clone()
+                .share_permutations(&left_permutations)
+                .update_frame_label(),
+        );
 
         DataView {
             _labels: PhantomData,
             frames: out_frames,
+            log: self.log.with(Operation::Merge {
+                right: right.log.to_vec(),
+            }),
+            sort_cache: self.sort_cache.detached(),
         }
     }
 }
@@ -388,15 +422,418 @@ pub enum PredAction {
     },
 }
 
+/// Expected relationship between the records on the left-hand side and the records on the
+/// right-hand side of a join. Used to validate join results with
+/// [join_with_cardinality](../view/struct.DataView.html#method.join_with_cardinality).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Cardinality {
+    /// Each left record matches at most one right record, and each right record matches at most
+    /// one left record (1:1).
+    OneToOne,
+    /// Each right record matches at most one left record (1:m).
+    OneToMany,
+    /// Each left record matches at most one right record (m:1).
+    ManyToOne,
+    /// Records on either side can match any number of records on the other side (m:m).
+    ManyToMany,
+}
+
+impl Cardinality {
+    /// Returns `true` if each right record should match at most one left record.
+    pub fn left_is_one(&self) -> bool {
+        match *self {
+            Cardinality::OneToOne | Cardinality::OneToMany => true,
+            Cardinality::ManyToOne | Cardinality::ManyToMany => false,
+        }
+    }
+    /// Returns `true` if each left record should match at most one right record.
+    pub fn right_is_one(&self) -> bool {
+        match *self {
+            Cardinality::OneToOne | Cardinality::ManyToOne => true,
+            Cardinality::OneToMany | Cardinality::ManyToMany => false,
+        }
+    }
+}
+
+impl Display for Cardinality {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Cardinality::OneToOne => write!(f, "1:1"),
+            Cardinality::OneToMany => write!(f, "1:m"),
+            Cardinality::ManyToOne => write!(f, "m:1"),
+            Cardinality::ManyToMany => write!(f, "m:m"),
+        }
+    }
+}
+
+/// Direction in which to search for a matching key in an as-of (nearest key) lookup, such as
+/// [map_from_nearest](../view/struct.DataView.html#method.map_from_nearest).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AsOf {
+    /// Match the greatest key less than or equal to the search key.
+    Backward,
+    /// Match the least key greater than or equal to the search key.
+    Forward,
+    /// Match the key closest to the search key in either direction, preferring the lesser key when
+    /// two keys are equally close.
+    Nearest,
+}
+
+// finds the index of the record in `right` with the key matching each value of `left` in the
+// direction `direction`, accepting only matches within `tolerance` (if specified) of the search key
+pub(crate) fn asof_indices<L, R>(
+    left: &L,
+    right: &R,
+    direction: AsOf,
+    tolerance: Option<f64>,
+) -> Vec<Option<usize>>
+where
+    L: DataIndex,
+    L::DType: AsPrimitive<f64>,
+    R: DataIndex,
+    R::DType: AsPrimitive<f64>,
+{
+    // existing (and non-NaN) right-hand keys, sorted by key (stable, so equal keys remain in
+    // record order)
+    let mut keys = right
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, key)| {
+            key.into_option()
+                .map(|&key| (AsPrimitive::<f64>::as_(key), idx))
+        })
+        .filter(|&(key, _)| !key.is_nan())
+        .collect::<Vec<_>>();
+    keys.sort_by(|&(left, _), &(right, _)| left.partial_cmp(&right).unwrap());
+
+    left.iter()
+        .map(|key| {
+            let key: f64 = match key {
+                Value::Exists(&key) => key.as_(),
+                Value::Na => return None,
+            };
+            // NaN search keys don't match any key
+            if key.is_nan() {
+                return None;
+            }
+            // last key less than or equal to the search key
+            let backward = match keys.partition_point(|&(k, _)| k <= key) {
+                0 => None,
+                n => Some(keys[n - 1]),
+            };
+            // first key greater than or equal to the search key
+            let forward = keys.get(keys.partition_point(|&(k, _)| k < key)).cloned();
+            let matched = match direction {
+                AsOf::Backward => backward,
+                AsOf::Forward => forward,
+                AsOf::Nearest => match (backward, forward) {
+                    (Some(b), Some(f)) => Some(if f.0 - key < key - b.0 { f } else { b }),
+                    (b, f) => b.or(f),
+                },
+            };
+            matched
+                .filter(|&(k, _)| tolerance.map_or(true, |tolerance| (k - key).abs() <= tolerance))
+                .map(|(_, idx)| idx)
+        })
+        .collect()
+}
+
+/// Default false positive rate of the [BloomFilter](struct.BloomFilter.html) used by a semi-join
+/// or anti-join with `SemiJoinMethod::BloomFilter`.
+pub const DEFAULT_BLOOM_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// A [Bloom filter](https://en.wikipedia.org/wiki/Bloom_filter): a compact, probabilistic set of
+/// values. A Bloom filter can report that a value is possibly in the set when it is not (a false
+/// positive), but never reports that a value is not in the set when it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilter<T> {
+    bits: Vec<u64>,
+    nbits: u64,
+    nhashes: u32,
+    _marker: PhantomData<T>,
+}
+
+impl<T> BloomFilter<T>
+where
+    T: Hash,
+{
+    /// Creates a new, empty `BloomFilter` sized to hold `capacity` values with a false positive
+    /// rate of approximately `false_positive_rate` (between `0.0` and `1.0`, exclusive).
+    pub fn new(capacity: usize, false_positive_rate: f64) -> BloomFilter<T> {
+        let rate = false_positive_rate.max(1e-9).min(0.5);
+        let ln2 = 2f64.ln();
+        let nbits = (-(capacity.max(1) as f64) * rate.ln() / (ln2 * ln2))
+            .ceil()
+            .max(64.0);
+        let nhashes = (nbits / capacity.max(1) as f64 * ln2).round().max(1.0);
+        let nbits = nbits as u64;
+        BloomFilter {
+            bits: vec![0; ((nbits + 63) / 64) as usize],
+            nbits,
+            nhashes: nhashes as u32,
+            _marker: PhantomData,
+        }
+    }
+
+    // index of the `i`th bit corresponding to the value with hashes `h1` and `h2`
+    fn bit_index(&self, h1: u64, h2: u64, i: u32) -> u64 {
+        h1.wrapping_add((i as u64).wrapping_mul(h2)) % self.nbits
+    }
+
+    /// Adds `value` to this filter.
+    pub fn insert(&mut self, value: &T) {
+        let (h1, h2) = hash_pair(value);
+        for i in 0..self.nhashes {
+            let idx = self.bit_index(h1, h2, i);
+            self.bits[(idx / 64) as usize] |= 1u64 << (idx % 64);
+        }
+    }
+
+    /// Returns `true` if `value` is possibly in this filter, or `false` if it is definitely not.
+    pub fn contains(&self, value: &T) -> bool {
+        let (h1, h2) = hash_pair(value);
+        (0..self.nhashes).all(|i| {
+            let idx = self.bit_index(h1, h2, i);
+            self.bits[(idx / 64) as usize] & (1u64 << (idx % 64)) != 0
+        })
+    }
+}
+
+/// Method used to match keys in a semi-join or anti-join (see
+/// [semi_join](../view/struct.DataView.html#method.semi_join)).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SemiJoinMethod {
+    /// Match the keys of each left-hand record exactly against the keys of the right-hand
+    /// `DataView`.
+    Exact,
+    /// Along with the set of right-hand keys used for exact matching, build a compact
+    /// [BloomFilter](struct.BloomFilter.html) of the right-hand keys with the specified
+    /// `false_positive_rate`, and discard the left-hand records whose keys are definitely not in
+    /// the filter before probing the set. This avoids most probes into a large set of right-hand
+    /// keys when most left-hand records have no match, at the cost of building the filter. The
+    /// result is the same as with `Exact`.
+    BloomFilter {
+        /// Approximate false positive rate of the Bloom filter.
+        false_positive_rate: f64,
+    },
+}
+
+impl Default for SemiJoinMethod {
+    fn default() -> SemiJoinMethod {
+        SemiJoinMethod::Exact
+    }
+}
+
+// determines whether each key in `left` matches a key in `right`, using the specified method.
+// Missing values never match.
+pub(crate) fn semi_join_matches<L, R>(left: &L, right: &R, method: SemiJoinMethod) -> Vec<bool>
+where
+    L: DataIndex,
+    L::DType: Hash + Eq,
+    R: DataIndex<DType = L::DType>,
+{
+    let right_keys = right
+        .iter()
+        .filter_map(|key| key.into_option())
+        .collect::<HashSet<_>>();
+    // filter used to skip probing `right_keys` for left-hand keys which definitely don't match
+    let filter = match method {
+        SemiJoinMethod::Exact => None,
+        SemiJoinMethod::BloomFilter {
+            false_positive_rate,
+        } => {
+            let mut filter = BloomFilter::new(right_keys.len(), false_positive_rate);
+            for key in &right_keys {
+                filter.insert(*key);
+            }
+            Some(filter)
+        }
+    };
+    left.iter()
+        .map(|key| {
+            key.map_or(false, |key| {
+                filter.as_ref().map_or(true, |filter| filter.contains(key))
+                    && right_keys.contains(key)
+            })
+        })
+        .collect()
+}
+
+// counts the number of distinct indices which occur more than once
+fn count_repeated(indices: &[usize]) -> usize {
+    let mut counts = HashMap::new();
+    for &idx in indices {
+        *counts.entry(idx).or_insert(0usize) += 1;
+    }
+    counts.values().filter(|&&count| count > 1).count()
+}
+
+fn check_cardinality(
+    cardinality: Cardinality,
+    merge_indices: &(Vec<usize>, Vec<usize>),
+) -> Result<()> {
+    // a right record appearing more than once in the merge indices matched multiple left
+    // records, and vice versa
+    let left_violations = if cardinality.left_is_one() {
+        count_repeated(&merge_indices.1)
+    } else {
+        0
+    };
+    let right_violations = if cardinality.right_is_one() {
+        count_repeated(&merge_indices.0)
+    } else {
+        0
+    };
+    if left_violations > 0 || right_violations > 0 {
+        Err(AgnesError::CardinalityMismatch {
+            expected: cardinality,
+            left_violations,
+            right_violations,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Statistics describing the records matched by a join (see
+/// [join_with_stats](../view/struct.DataView.html#method.join_with_stats)), useful for verifying
+/// the quality of a join without further queries on the joined `DataView`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinStats<K> {
+    matched_rows: usize,
+    unmatched_left: usize,
+    unmatched_right: usize,
+    max_fanout: Option<(K, usize)>,
+}
+
+impl<K> JoinStats<K> {
+    // computes the statistics of a join between the records of `left_keys` and `right_len`
+    // right-hand records which produced the pairs of records in `merge_indices`
+    fn new<L>(
+        left_keys: &L,
+        right_len: usize,
+        merge_indices: &(Vec<usize>, Vec<usize>),
+    ) -> JoinStats<K>
+    where
+        L: DataIndex<DType = K>,
+        K: Clone,
+    {
+        let mut fanouts = HashMap::new();
+        for &idx in &merge_indices.0 {
+            *fanouts.entry(idx).or_insert(0usize) += 1;
+        }
+        let matched_right = merge_indices.1.iter().collect::<HashSet<_>>().len();
+        // ties resolve to the earliest left-hand record
+        let max_fanout = fanouts
+            .iter()
+            .max_by_key(|&(&idx, &fanout)| (fanout, Reverse(idx)))
+            .map(|(&idx, &fanout)| {
+                // matched records always have an existing key
+                (left_keys.get_datum(idx).unwrap().unwrap().clone(), fanout)
+            });
+        JoinStats {
+            matched_rows: merge_indices.0.len(),
+            unmatched_left: left_keys.len() - fanouts.len(),
+            unmatched_right: right_len - matched_right,
+            max_fanout,
+        }
+    }
+
+    /// Returns the number of records in the joined `DataView` (the number of matching pairs of
+    /// left-hand and right-hand records).
+    pub fn matched_rows(&self) -> usize {
+        self.matched_rows
+    }
+    /// Returns the number of left-hand records which did not match any right-hand record.
+    pub fn unmatched_left(&self) -> usize {
+        self.unmatched_left
+    }
+    /// Returns the number of right-hand records which did not match any left-hand record.
+    pub fn unmatched_right(&self) -> usize {
+        self.unmatched_right
+    }
+    /// Returns the key of the left-hand record which matched the most right-hand records (the
+    /// earliest such record, in case of a tie), along with the number of right-hand records it
+    /// matched. Returns `None` if no records matched.
+    pub fn max_fanout(&self) -> Option<(&K, usize)> {
+        self.max_fanout
+            .as_ref()
+            .map(|&(ref key, fanout)| (key, fanout))
+    }
+}
+
+/// Order of the records of a joined `DataView` (see
+/// [join_with_order](../view/struct.DataView.html#method.join_with_order)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinOrder {
+    /// Records are ordered by the record order of the left-hand `DataView`, with records
+    /// matching the same left-hand record ordered by the record order of the right-hand
+    /// `DataView`. This is the order used by all joins unless otherwise specified.
+    Preserved,
+    /// The record order is unspecified (and may change between versions), which avoids sorting
+    /// the matched records. Currently, sort-merge joins produce records ordered by join key.
+    Unspecified,
+}
+
+impl Default for JoinOrder {
+    fn default() -> JoinOrder {
+        JoinOrder::Preserved
+    }
+}
+
+// sorts pairs of matched record indices as specified by `order`
+fn order_merge_indices(
+    merge_indices: (Vec<usize>, Vec<usize>),
+    order: JoinOrder,
+) -> (Vec<usize>, Vec<usize>) {
+    match order {
+        JoinOrder::Preserved => {
+            let mut pairs = merge_indices
+                .0
+                .into_iter()
+                .zip(merge_indices.1)
+                .collect::<Vec<_>>();
+            // pairs are distinct, so an unstable sort gives a deterministic order
+            pairs.sort_unstable();
+            pairs.into_iter().unzip()
+        }
+        JoinOrder::Unspecified => merge_indices,
+    }
+}
+
 /// A trait for merging a [DataView](../view/struct.DataView.html) with the current object using
 /// specified `Join`. `RLabels` and `RFrames` are the `Labels` and `Frames` type parameters for the
 /// `DataView` to merge.
 pub trait SortMergeJoin<RLabels, RFrames, Join> {
     /// Resultant data structure after join.
     type Output;
+    /// Data type of the join key.
+    type Key;
 
     /// Join this object with a `DataView`, using the join details specified with `Join`.
     fn join(&self, right: &DataView<RLabels, RFrames>) -> Self::Output;
+
+    /// Join this object with a `DataView`, using the join details specified with `Join`. Fails
+    /// if the matched records do not have the relationship specified by `cardinality`.
+    fn join_with_cardinality(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        cardinality: Cardinality,
+    ) -> Result<Self::Output>;
+
+    /// Join this object with a `DataView`, using the join details specified with `Join`, with the
+    /// records of the result ordered as specified by `order`.
+    fn join_with_order(&self, right: &DataView<RLabels, RFrames>, order: JoinOrder)
+        -> Self::Output;
+
+    /// Join this object with a `DataView`, using the join details specified with `Join`. Also
+    /// returns statistics describing the matched records.
+    fn join_with_stats(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+    ) -> (Self::Output, JoinStats<Self::Key>)
+    where
+        Self::Key: Clone;
 }
 impl<LLabels, LFrames, RLabels, RFrames, LLabel, RLabel, Pred>
     SortMergeJoin<RLabels, RFrames, Join<LLabel, RLabel, Pred>> for DataView<LLabels, LFrames>
@@ -407,36 +844,294 @@ where
         RLabels,
         <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
     >>::Output: IntoView,
+    <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output: WithOperationLog,
     Self: SelectFieldByLabel<LLabel>,
     <Self as SelectFieldByLabel<LLabel>>::Output: SortOrder,
     VFieldTypeOf<Self, LLabel>: Ord + PartialEq,
     DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel, DType = VFieldTypeOf<Self, LLabel>>,
     <DataView<RLabels, RFrames> as SelectFieldByLabel<RLabel>>::Output: SortOrder,
+    LLabel: LabelName,
+    RLabel: LabelName,
     Pred: Predicate,
 {
     type Output = <<RFrames as JoinIntoStore<
         RLabels,
         <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
     >>::Output as IntoView>::Output;
+    type Key = VFieldTypeOf<Self, LLabel>;
 
     fn join(&self, right: &DataView<RLabels, RFrames>) -> Self::Output {
+        // many-to-many joins place no restrictions on the matched records
+        SortMergeJoin::<RLabels, RFrames, Join<LLabel, RLabel, Pred>>::join_with_cardinality(
+            self,
+            right,
+            Cardinality::ManyToMany,
+        )
+        .unwrap()
+    }
+
+    fn join_with_cardinality(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        cardinality: Cardinality,
+    ) -> Result<Self::Output> {
+        sort_merge_join::<LLabel, RLabel, Pred, _, _, _, _>(
+            self,
+            right,
+            JoinOrder::Preserved,
+            cardinality,
+        )
+        .map(|(joined, _)| joined)
+    }
+
+    fn join_with_order(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        order: JoinOrder,
+    ) -> Self::Output {
+        // many-to-many joins place no restrictions on the matched records
+        sort_merge_join::<LLabel, RLabel, Pred, _, _, _, _>(
+            self,
+            right,
+            order,
+            Cardinality::ManyToMany,
+        )
+        .unwrap()
+        .0
+    }
+
+    fn join_with_stats(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+    ) -> (Self::Output, JoinStats<Self::Key>)
+    where
+        Self::Key: Clone,
+    {
+        // many-to-many joins place no restrictions on the matched records
+        let (joined, merge_indices) = sort_merge_join::<LLabel, RLabel, Pred, _, _, _, _>(
+            self,
+            right,
+            JoinOrder::Preserved,
+            Cardinality::ManyToMany,
+        )
+        .unwrap();
+        let stats = JoinStats::new(&self.field::<LLabel>(), right.nrows(), &merge_indices);
+        (joined, stats)
+    }
+}
+
+// joins `left` and `right` on the fields `LLabel` and `RLabel` using the predicate `Pred`, with the
+// joined records ordered as specified by `order`. Fails if the matched records do not have the
+// relationship specified by `cardinality`. Also returns the pairs of matched record indices.
+fn sort_merge_join<LLabel, RLabel, Pred, LLabels, LFrames, RLabels, RFrames>(
+    left: &DataView<LLabels, LFrames>,
+    right: &DataView<RLabels, RFrames>,
+    order: JoinOrder,
+    cardinality: Cardinality,
+) -> Result<(
+    <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output,
+    (Vec<usize>, Vec<usize>),
+)>
+where
+    LFrames: JoinIntoStore<LLabels, DataStore<Nil>>,
+    RFrames: JoinIntoStore<RLabels, <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output>,
+    <RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output: IntoView,
+    <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output: WithOperationLog,
+    DataView<LLabels, LFrames>: SelectFieldByLabel<LLabel>,
+    <DataView<LLabels, LFrames> as SelectFieldByLabel<LLabel>>::Output: SortOrder,
+    VFieldTypeOf<DataView<LLabels, LFrames>, LLabel>: Ord + PartialEq,
+    DataView<RLabels, RFrames>:
+        SelectFieldByLabel<RLabel, DType = VFieldTypeOf<DataView<LLabels, LFrames>, LLabel>>,
+    <DataView<RLabels, RFrames> as SelectFieldByLabel<RLabel>>::Output: SortOrder,
+    LLabel: LabelName,
+    RLabel: LabelName,
+    Pred: Predicate,
+{
+    //TODO: return empty dataview if left or right is empty
+
+    let merge_indices =
+        merge_indices::<Pred, _, _>(&left.field::<LLabel>(), &right.field::<RLabel>());
+    let merge_indices = order_merge_indices(merge_indices, order);
+    check_cardinality(cardinality, &merge_indices)?;
+
+    let store = DataStore::<Nil>::empty();
+
+    let store = left.frames.join_into_store(store, &merge_indices.0)?;
+    let store = right.frames.join_into_store(store, &merge_indices.1)?;
+    let joined = store
+        .into_view()
+        .with_operation_log(left.log.with(Operation::Join {
+            left_label: LLabel::name(),
+            right_label: RLabel::name(),
+            predicate: predicate_symbol::<Pred>(),
+            cardinality,
+            right: right.log.to_vec(),
+        }));
+    Ok((joined, merge_indices))
+}
+
+/// A trait for joining a [DataView](../view/struct.DataView.html) with the current object using
+/// specified `Join`, where the join keys are computed from the `Join` fields with the
+/// key-extraction functions `LKeyFn` (for the left-hand side) and `RKeyFn` (for the right-hand
+/// side). See the intrinsic method [join_by](../view/struct.DataView.html#method.join_by) for more
+/// details.
+pub trait KeyedJoin<RLabels, RFrames, Join, LKeyFn, RKeyFn> {
+    /// Resultant data structure after join.
+    type Output;
+
+    /// Join this object with a `DataView`, using the join details specified with `Join` and the
+    /// keys computed with `left_key` and `right_key`.
+    fn join_by(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        left_key: LKeyFn,
+        right_key: RKeyFn,
+    ) -> Self::Output;
+}
+impl<LLabels, LFrames, RLabels, RFrames, LLabel, RLabel, Pred, LKeyFn, RKeyFn, Key>
+    KeyedJoin<RLabels, RFrames, Join<LLabel, RLabel, Pred>, LKeyFn, RKeyFn>
+    for DataView<LLabels, LFrames>
+where
+    LFrames: JoinIntoStore<LLabels, DataStore<Nil>>,
+    RFrames: JoinIntoStore<RLabels, <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output>,
+    <RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output: IntoView,
+    <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output: WithOperationLog,
+    Self: SelectFieldByLabel<LLabel>,
+    DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel>,
+    LKeyFn: Fn(&VFieldTypeOf<Self, LLabel>) -> Key,
+    RKeyFn: Fn(&VFieldTypeOf<DataView<RLabels, RFrames>, RLabel>) -> Key,
+    Key: Ord + PartialEq + Debug + Default,
+    LLabel: LabelName,
+    RLabel: LabelName,
+    Pred: Predicate,
+{
+    type Output = <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output;
+
+    fn join_by(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        left_key: LKeyFn,
+        right_key: RKeyFn,
+    ) -> Self::Output {
         let left = self;
-        //TODO: return empty dataview if left or right is empty
 
-        let merge_indices =
-            merge_indices::<Pred, _, _>(&left.field::<LLabel>(), &right.field::<RLabel>());
+        let left_keys: FieldData<Key> = left
+            .field::<LLabel>()
+            .iter()
+            .map(|value| value.map(&left_key))
+            .collect();
+        let right_keys: FieldData<Key> = right
+            .field::<RLabel>()
+            .iter()
+            .map(|value| value.map(&right_key))
+            .collect();
+        let merge_indices = merge_indices::<Pred, _, _>(&left_keys, &right_keys);
+        let merge_indices = order_merge_indices(merge_indices, JoinOrder::Preserved);
 
         let store = DataStore::<Nil>::empty();
 
-        let store = left
-            .frames
-            .join_into_store(store, &merge_indices.0)
-            .unwrap();
-        let store = right
-            .frames
-            .join_into_store(store, &merge_indices.1)
-            .unwrap();
-        store.into_view()
+        // permutations are always within the bounds of the source frames
+        let store = left.frames.join_into_store(store, &merge_indices.0).unwrap();
+        let store = right.frames.join_into_store(store, &merge_indices.1).unwrap();
+        store.into_view().with_operation_log(left.log.with(Operation::Join {
+            left_label: LLabel::name(),
+            right_label: RLabel::name(),
+            predicate: predicate_symbol::<Pred>(),
+            cardinality: Cardinality::ManyToMany,
+            right: right.log.to_vec(),
+        }))
+    }
+}
+
+/// A trait for joining a [DataView](../view/struct.DataView.html) with the current object by
+/// approximately matching the string fields labeled `LLabel` (in this object) and `RLabel` (in the
+/// right-hand `DataView`). See the intrinsic method
+/// [fuzzy_join](../view/struct.DataView.html#method.fuzzy_join) for more details.
+pub trait FuzzyJoin<RLabels, RFrames, LLabel, RLabel> {
+    /// Resultant data structure after join.
+    type Output;
+
+    /// Join this object with a `DataView`, matching records as specified by `fuzzy`.
+    fn fuzzy_join(&self, right: &DataView<RLabels, RFrames>, fuzzy: &FuzzyMatch) -> Self::Output;
+}
+impl<LLabels, LFrames, RLabels, RFrames, LLabel, RLabel> FuzzyJoin<RLabels, RFrames, LLabel, RLabel>
+    for DataView<LLabels, LFrames>
+where
+    LFrames: JoinIntoStore<LLabels, DataStore<Nil>>,
+    RFrames: JoinIntoStore<RLabels, <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output>,
+    <RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output: IntoView,
+    <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output: WithOperationLog,
+    Self: SelectFieldByLabel<LLabel>,
+    VFieldTypeOf<Self, LLabel>: AsRef<str>,
+    DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel>,
+    VFieldTypeOf<DataView<RLabels, RFrames>, RLabel>: AsRef<str>,
+    LLabel: LabelName,
+    RLabel: LabelName,
+{
+    type Output = <<RFrames as JoinIntoStore<
+        RLabels,
+        <LFrames as JoinIntoStore<LLabels, DataStore<Nil>>>::Output,
+    >>::Output as IntoView>::Output;
+
+    fn fuzzy_join(&self, right: &DataView<RLabels, RFrames>, fuzzy: &FuzzyMatch) -> Self::Output {
+        let left = self;
+
+        let merge_indices = fuzzy.match_indices(&left.field::<LLabel>(), &right.field::<RLabel>());
+
+        let store = DataStore::<Nil>::empty();
+
+        // permutations are always within the bounds of the source frames
+        let store = left.frames.join_into_store(store, &merge_indices.0).unwrap();
+        let store = right.frames.join_into_store(store, &merge_indices.1).unwrap();
+        store.into_view().with_operation_log(left.log.with(Operation::Join {
+            left_label: LLabel::name(),
+            right_label: RLabel::name(),
+            predicate: "~",
+            cardinality: Cardinality::ManyToMany,
+            right: right.log.to_vec(),
+        }))
+    }
+}
+
+// returns the comparison symbol for the join predicate `Pred`
+fn predicate_symbol<Pred: Predicate>() -> &'static str {
+    match (
+        Pred::is_equality_pred(),
+        Pred::is_greater_than_pred(),
+        Pred::is_less_than_pred(),
+    ) {
+        (true, true, _) => ">=",
+        (true, _, true) => "<=",
+        (true, _, _) => "==",
+        (false, true, _) => ">",
+        (false, _, _) => "<",
     }
 }
 
@@ -447,6 +1142,7 @@ where
     U: DataIndex<DType = <T as DataIndex>::DType> + SortOrder,
     <T as DataIndex>::DType: PartialEq + Ord,
 {
+    // keys whose type provides a radix key (see `SortKey`) are sorted with a radix sort
     let left_order = left_key_data.sort_order();
     let right_order = right_key_data.sort_order();
 
@@ -589,6 +1285,91 @@ where
     }
 }
 
+/// Trait for adding data to a [DataStore](../store/struct.DataStore.html) by concatenating the
+/// records of two sets of frames with the same structure.
+pub trait ConcatIntoStore<Labels, Store> {
+    /// The output type after augmenting `Store`.
+    type Output;
+
+    /// Augments `store` with data from `self` (as specified with `Labels`) using the permutation
+    /// indices `permutation`, followed by data from `other` using the permutation indices
+    /// `other_permutation`.
+    fn concat_into_store(
+        &self,
+        other: &Self,
+        store: Store,
+        permutation: &[usize],
+        other_permutation: &[usize],
+    ) -> Self::Output;
+}
+impl<Frames, Store> ConcatIntoStore<Nil, Store> for Frames {
+    type Output = Store;
+    fn concat_into_store(
+        &self,
+        _other: &Self,
+        store: Store,
+        _permutation: &[usize],
+        _other_permutation: &[usize],
+    ) -> Store {
+        store
+    }
+}
+impl<Label, FrameIndex, FrameLabel, Tail, Frames, Store>
+    ConcatIntoStore<FrameLookupCons<Label, FrameIndex, FrameLabel, Tail>, Store> for Frames
+where
+    Frames: LookupValuedElemByLabel<FrameIndex>,
+    FrameByFrameIndexOf<Frames, FrameIndex>: SelectFieldByLabel<FrameLabel>,
+    FieldTypeFromFrameDetailsOf<Frames, FrameIndex, FrameLabel>: Debug,
+    Store: PushBackClonedFromValueIter<
+        Label,
+        FieldTypeFromFrameDetailsOf<Frames, FrameIndex, FrameLabel>,
+    >,
+    Frames: ConcatIntoStore<
+        Tail,
+        DataStore<
+            <Store as PushBackClonedFromValueIter<
+                Label,
+                FieldTypeFromFrameDetailsOf<Frames, FrameIndex, FrameLabel>,
+            >>::OutputFields,
+        >,
+    >,
+{
+    type Output = <Frames as ConcatIntoStore<
+        Tail,
+        DataStore<
+            <Store as PushBackClonedFromValueIter<
+                Label,
+                FieldTypeFromFrameDetailsOf<Frames, FrameIndex, FrameLabel>,
+            >>::OutputFields,
+        >,
+    >>::Output;
+
+    fn concat_into_store(
+        &self,
+        other: &Self,
+        store: Store,
+        permutation: &[usize],
+        other_permutation: &[usize],
+    ) -> Self::Output {
+        let field = SelectFieldByLabel::<FrameLabel>::select_field(
+            LookupValuedElemByLabel::<FrameIndex>::elem(self).value_ref(),
+        )
+        .permute(permutation);
+        let other_field = SelectFieldByLabel::<FrameLabel>::select_field(
+            LookupValuedElemByLabel::<FrameIndex>::elem(other).value_ref(),
+        )
+        .permute(other_permutation);
+        let store = store.push_back_cloned_from_value_iter(field.iter().chain(other_field.iter()));
+        ConcatIntoStore::<Tail, _>::concat_into_store(
+            self,
+            other,
+            store,
+            permutation,
+            other_permutation,
+        )
+    }
+}
+
 #[cfg(feature = "test-utils")]
 #[cfg(test)]
 mod tests {
@@ -608,25 +1389,26 @@ mod tests {
         println!("{}", joined_dv);
         assert_eq!(joined_dv.nrows(), 7);
         assert_eq!(joined_dv.nfields(), 5);
+        // records are in employee table order
         assert_eq!(
             joined_dv.field::<emp_table::EmpId>().to_vec(),
-            vec![0u64, 5, 6, 2, 8, 9, 10]
+            vec![0u64, 2, 5, 6, 8, 9, 10]
         );
         assert_eq!(
             joined_dv.field::<emp_table::DeptId>().to_vec(),
-            vec![1u64, 1, 1, 2, 3, 4, 4]
+            vec![1u64, 2, 1, 1, 3, 4, 4]
         );
         assert_eq!(
             joined_dv.field::<emp_table::EmpName>().to_vec(),
-            vec!["Sally", "Bob", "Cara", "Jamie", "Louis", "Louise", "Ann"]
+            vec!["Sally", "Jamie", "Bob", "Cara", "Louis", "Louise", "Ann"]
         );
         assert_eq!(
             joined_dv.field::<dept_table::DeptName>().to_vec(),
             vec![
                 "Marketing",
+                "Sales",
                 "Marketing",
                 "Marketing",
-                "Sales",
                 "Manufacturing",
                 "R&D",
                 "R&D"
@@ -634,6 +1416,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn join_order() {
+        let dv_emp = sample_emp_table().into_view();
+        let dv_dept =
+            dept_table(vec![4u64, 1, 2, 1], vec!["R&D", "Mktg", "Sales", "Mktg2"]).into_view();
+
+        // ordered by employee, then by department record
+        let joined_dv = dv_emp
+            .join_with_order::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(
+                &dv_dept,
+                JoinOrder::Preserved,
+            );
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpId>().to_vec(),
+            vec![0u64, 0, 2, 5, 5, 6, 6, 9, 10]
+        );
+        assert_eq!(
+            joined_dv.field::<dept_table::DeptName>().to_vec(),
+            vec!["Mktg", "Mktg2", "Sales", "Mktg", "Mktg2", "Mktg", "Mktg2", "R&D", "R&D"]
+        );
+        let ordered =
+            dv_emp.join::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(&dv_dept);
+        assert_eq!(
+            ordered.field::<emp_table::EmpId>().to_vec(),
+            joined_dv.field::<emp_table::EmpId>().to_vec()
+        );
+
+        // non-equality joins use the same order
+        let joined_dv =
+            dv_emp.join::<Join<emp_table::DeptId, dept_table::DeptId, LessThan>, _, _>(&dv_dept);
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpId>().to_vec(),
+            vec![0u64, 0, 2, 5, 5, 6, 6, 8]
+        );
+        assert_eq!(
+            joined_dv.field::<dept_table::DeptName>().to_vec(),
+            vec!["R&D", "Sales", "R&D", "R&D", "Sales", "R&D", "Sales", "R&D"]
+        );
+
+        // unspecified order contains the same records
+        let unordered = dv_emp
+            .join_with_order::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(
+                &dv_dept,
+                JoinOrder::Unspecified,
+            );
+        let mut emp_ids = unordered.field::<emp_table::EmpId>().to_vec();
+        emp_ids.sort();
+        assert_eq!(emp_ids, vec![0u64, 0, 2, 5, 5, 6, 6, 9, 10]);
+    }
+
+    #[test]
+    fn keyed_join() {
+        let dv_emp = sample_emp_table().into_view();
+        // department ids offset by 100 in department table
+        let dv_dept = dept_table(
+            vec![101u64, 102, 103, 104],
+            vec!["Marketing", "Sales", "Manufacturing", "R&D"],
+        )
+        .into_view();
+
+        let joined_dv = dv_emp
+            .join_by::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _, _, _>(
+                &dv_dept,
+                |&id: &u64| id,
+                |&id: &u64| id - 100,
+            );
+        println!("{}", joined_dv);
+        assert_eq!(joined_dv.nrows(), 7);
+        assert_eq!(joined_dv.nfields(), 5);
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpId>().to_vec(),
+            vec![0u64, 2, 5, 6, 8, 9, 10]
+        );
+        // original (not derived) key values are retained
+        assert_eq!(
+            joined_dv.field::<dept_table::DeptId>().to_vec(),
+            vec![101u64, 102, 101, 101, 103, 104, 104]
+        );
+    }
+
     #[test]
     fn inner_equi_join_missing_dept_id() {
         // dept id missing from dept table, should remove the entire marketing department from join
@@ -721,22 +1583,22 @@ mod tests {
         assert_eq!(joined_dv.nfields(), 5);
         assert_eq!(
             joined_dv.field::<emp_table::EmpId>().to_vec(),
-            vec![0u64, 6, 2, 8, 9, 10]
+            vec![0u64, 2, 6, 8, 9, 10]
         );
         assert_eq!(
             joined_dv.field::<emp_table::DeptId>().to_vec(),
-            vec![1u64, 1, 2, 3, 4, 4]
+            vec![1u64, 2, 1, 3, 4, 4]
         );
         assert_eq!(
             joined_dv.field::<emp_table::EmpName>().to_vec(),
-            vec!["Sally", "Cara", "Jamie", "Louis", "Louise", "Ann"]
+            vec!["Sally", "Jamie", "Cara", "Louis", "Louise", "Ann"]
         );
         assert_eq!(
             joined_dv.field::<dept_table::DeptName>().to_vec(),
             vec![
-                "Marketing",
                 "Marketing",
                 "Sales",
+                "Marketing",
                 "Manufacturing",
                 "R&D",
                 "R&D"
@@ -778,6 +1640,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn join_cardinality() {
+        let dv_emp = sample_emp_table().into_view();
+        let dv_dept = sample_dept_table().into_view();
+
+        // each employee has a single department
+        let joined_dv = dv_emp
+            .join_with_cardinality::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(
+                &dv_dept,
+                Cardinality::ManyToOne,
+            )
+            .unwrap();
+        assert_eq!(joined_dv.nrows(), 7);
+
+        // but departments 1 and 4 have multiple employees
+        match dv_emp
+            .join_with_cardinality::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(
+                &dv_dept,
+                Cardinality::OneToOne,
+            ) {
+            Err(AgnesError::CardinalityMismatch {
+                expected,
+                left_violations,
+                right_violations,
+            }) => {
+                assert_eq!(expected, Cardinality::OneToOne);
+                assert_eq!(left_violations, 2);
+                assert_eq!(right_violations, 0);
+            }
+            _ => panic!("expected cardinality mismatch"),
+        }
+
+        // swapping sides flips the relationship
+        assert!(dv_dept
+            .join_with_cardinality::<Join<dept_table::DeptId, emp_table::DeptId, Equal>, _, _>(
+                &dv_emp,
+                Cardinality::OneToMany,
+            )
+            .is_ok());
+        assert!(dv_dept
+            .join_with_cardinality::<Join<dept_table::DeptId, emp_table::DeptId, Equal>, _, _>(
+                &dv_emp,
+                Cardinality::ManyToOne,
+            )
+            .is_err());
+    }
+
     tablespace![
         @continue(typenum::Add1<::test_utils::dept_table::Table>)
         table dept_rename {
@@ -785,6 +1694,137 @@ mod tests {
         }
     ];
 
+    #[test]
+    fn join_stats() {
+        let dv_emp = sample_emp_table().into_view();
+        let dv_dept = dept_table(
+            vec![1u64, 1, 3, 7],
+            vec!["Marketing", "Marketing2", "Manufacturing", "Misc"],
+        )
+        .into_view();
+        let (joined_dv, stats) = dv_emp
+            .join_with_stats::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(&dv_dept);
+        println!("{}", joined_dv);
+        assert_eq!(joined_dv.nrows(), 7);
+        assert_eq!(stats.matched_rows(), 7);
+        assert_eq!(stats.unmatched_left(), 3);
+        assert_eq!(stats.unmatched_right(), 1);
+        assert_eq!(stats.max_fanout(), Some((&1, 2)));
+
+        let (joined_dv, stats) = dv_emp
+            .join_with_stats::<Join<emp_table::DeptId, dept_table::DeptId, GreaterThan>, _, _>(
+                &dv_dept,
+            );
+        assert_eq!(joined_dv.nrows(), 10);
+        assert_eq!(stats.matched_rows(), 10);
+        assert_eq!(stats.unmatched_left(), 3);
+        assert_eq!(stats.unmatched_right(), 1);
+        assert_eq!(stats.max_fanout(), Some((&4, 3)));
+
+        let (joined_dv, stats) = dv_emp
+            .join_with_stats::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(
+                &dept_table(vec![5], vec!["None"]).into_view(),
+            );
+        assert_eq!(joined_dv.nrows(), 0);
+        assert_eq!(stats.unmatched_left(), 7);
+        assert_eq!(stats.unmatched_right(), 1);
+        assert_eq!(stats.max_fanout(), None);
+    }
+
+    #[test]
+    fn semi_anti_join() {
+        let dv_dept = dept_table(
+            vec![1u64, 3, 3, 7],
+            vec!["Marketing", "Mfg", "Mfg2", "Misc"],
+        )
+        .into_view()
+        .record_operations("dept.csv");
+        for &method in &[
+            SemiJoinMethod::Exact,
+            SemiJoinMethod::BloomFilter {
+                false_positive_rate: DEFAULT_BLOOM_FALSE_POSITIVE_RATE,
+            },
+        ] {
+            let semi = sample_emp_table()
+                .into_view()
+                .semi_join::<emp_table::DeptId, dept_table::DeptId, _, _>(&dv_dept, method);
+            println!("{}", semi);
+            assert_eq!(semi.nfields(), 3);
+            assert_eq!(
+                semi.field::<emp_table::EmpId>().to_vec(),
+                vec![0u64, 5, 6, 8]
+            );
+            let anti = sample_emp_table()
+                .into_view()
+                .anti_join::<emp_table::DeptId, dept_table::DeptId, _, _>(&dv_dept, method);
+            assert_eq!(anti.field::<emp_table::EmpId>().to_vec(), vec![2u64, 9, 10]);
+        }
+
+        let anti = sample_emp_table()
+            .into_view()
+            .record_operations("emp.csv")
+            .anti_join::<emp_table::DeptId, dept_table::DeptId, _, _>(
+                &dv_dept,
+                SemiJoinMethod::default(),
+            );
+        assert_eq!(
+            anti.operation_log().to_string(),
+            "load emp.csv -> anti join on DeptId == DeptId with [load dept.csv]"
+        );
+    }
+
+    #[test]
+    fn asof_nan_keys() {
+        let left = FieldData::from_vec(vec![1.5, ::std::f64::NAN, 4.0]);
+        let right = FieldData::from_vec(vec![::std::f64::NAN, 3.0, 1.0]);
+        for &direction in &[AsOf::Backward, AsOf::Forward, AsOf::Nearest] {
+            let matches = asof_indices(&left, &right, direction, None);
+            assert_eq!(matches[1], None);
+        }
+        assert_eq!(
+            asof_indices(&left, &right, AsOf::Backward, None),
+            vec![Some(2), None, Some(1)]
+        );
+        assert_eq!(
+            asof_indices(&left, &right, AsOf::Forward, None),
+            vec![Some(1), None, None]
+        );
+    }
+
+    #[test]
+    fn bloom_filter_semi_join() {
+        let mut filter = BloomFilter::new(1000, 0.01);
+        for i in 0..1000u64 {
+            filter.insert(&i);
+        }
+        assert!((0..1000u64).all(|i| filter.contains(&i)));
+        let false_positives = (1000..11000u64).filter(|i| filter.contains(i)).count();
+        assert!(false_positives < 300);
+
+        // missing values never match; Bloom filter false positives are removed by exact matching
+        let left = (0..2000u64)
+            .map(|i| if i % 10 == 0 { None } else { Some(i) })
+            .collect::<FieldData<_>>();
+        let right = (0..50000u64).map(|i| i * 3).collect::<FieldData<_>>();
+        let expected = (0..2000u64)
+            .map(|i| i % 10 != 0 && i % 3 == 0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            semi_join_matches(&left, &right, SemiJoinMethod::Exact),
+            expected
+        );
+        assert_eq!(
+            semi_join_matches(
+                &left,
+                &right,
+                SemiJoinMethod::BloomFilter {
+                    false_positive_rate: 0.2
+                }
+            ),
+            expected
+        );
+    }
+
     #[test]
     fn inner_nonequi_join() {
         // greater than
@@ -840,4 +1880,56 @@ mod tests {
             assert![*value.unwrap() <= 2];
         }
     }
+
+    #[test]
+    fn operation_log() {
+        use provenance::Operation;
+
+        // logging is disabled by default
+        let dv_emp = sample_emp_table().into_view();
+        let dv_dept = sample_dept_table().into_view();
+        let joined_dv =
+            dv_emp.join::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(&dv_dept);
+        assert_eq!(joined_dv.operations(), None);
+
+        let dv_emp = sample_emp_table()
+            .into_view()
+            .record_operations("emp.csv")
+            .filter::<emp_table::DeptId, _>(|val: Value<&u64>| val != 3)
+            .sort_by_label::<emp_table::EmpName>();
+        let dv_dept = sample_dept_table()
+            .into_view()
+            .record_operations("dept.csv")
+            .log_operation("remove test departments");
+        let joined_dv =
+            dv_emp.join::<Join<emp_table::DeptId, dept_table::DeptId, Equal>, _, _>(&dv_dept);
+        assert_eq!(joined_dv.nrows(), 6);
+        assert_eq!(
+            joined_dv.operations().unwrap(),
+            &[
+                Operation::Load {
+                    source: "emp.csv".into()
+                },
+                Operation::Filter { label: "DeptId" },
+                Operation::Sort { label: "EmpName" },
+                Operation::Join {
+                    left_label: "DeptId",
+                    right_label: "DeptId",
+                    predicate: "==",
+                    cardinality: Cardinality::ManyToMany,
+                    right: vec![
+                        Operation::Load {
+                            source: "dept.csv".into()
+                        },
+                        Operation::Custom("remove test departments".into()),
+                    ],
+                },
+            ][..]
+        );
+        assert_eq!(
+            joined_dv.operation_log().to_string(),
+            "load emp.csv -> filter on DeptId -> sort by EmpName -> m:m join on DeptId == DeptId \
+             with [load dept.csv -> remove test departments]"
+        );
+    }
 }
@@ -14,7 +14,7 @@ use typenum::{
 };
 
 use access::NRows;
-use cons::{cons, Cons, Nil};
+use cons::{cons, Append, Cons, Nil};
 use store::DataRef;
 
 /// Trait to provide associated types (table and backing natural) for a field identifier.
@@ -466,19 +466,85 @@ where
     type Set = <LVCons<LLabel, LValue, LTail> as SetDiffStep<RTail, FullRightSet>>::Set;
 }
 
-// heads of both left and right match: continues with tails of both left and right
+// heads of both left and right match: drop left head, recurse into left tail, restart with full
+// right set
 impl<LLabel, LValue, LTail, RLabel, RValue, RTail, FullRightSet>
     SetDiffMatch<LVCons<RLabel, RValue, RTail>, FullRightSet, True>
     for LVCons<LLabel, LValue, LTail>
 where
-    LTail: SetDiffStep<RTail, FullRightSet>,
+    LTail: SetDiffStep<FullRightSet, FullRightSet>,
 {
-    type Set = <LTail as SetDiffStep<RTail, FullRightSet>>::Set;
+    type Set = <LTail as SetDiffStep<FullRightSet, FullRightSet>>::Set;
 }
 
 /// Type alias for the label set that is the set different between `LeftSet` and `RightSet`.
 pub type LabelSetDiff<LeftSet, RightSet> = <LeftSet as SetDiff<RightSet>>::Set;
 
+/// Determines the set intersection between an [LVCons](type.LVCons.html) label set and another
+/// [LVCons](type.LVCons.html) label set `RightSet`.
+pub trait SetIntersect<RightSet> {
+    /// The set of labels that exist in both `Self` and `RightSet`, in the order of `Self`.
+    type Set;
+}
+impl<RightSet> SetIntersect<RightSet> for Nil {
+    type Set = Nil;
+}
+impl<LLabel, LValue, LTail, RightSet> SetIntersect<RightSet> for LVCons<LLabel, LValue, LTail>
+where
+    RightSet: Member<LLabel>,
+    Self: SetIntersectPred<RightSet, <RightSet as Member<LLabel>>::IsMember>,
+{
+    type Set = <Self as SetIntersectPred<RightSet, <RightSet as Member<LLabel>>::IsMember>>::Set;
+}
+
+/// Helper trait used by [SetIntersect](trait.SetIntersect.html) to compute the set intersection
+/// between two label sets. `IsMember` denotes whether or not the head of `Self` is a member of
+/// `RightSet`.
+pub trait SetIntersectPred<RightSet, IsMember> {
+    /// The set of labels that exist in both `Self` and `RightSet`, in the order of `Self`.
+    type Set;
+}
+// head is in right set: keep it
+impl<LLabel, LValue, LTail, RightSet> SetIntersectPred<RightSet, True>
+    for LVCons<LLabel, LValue, LTail>
+where
+    LTail: SetIntersect<RightSet>,
+{
+    type Set = LVCons<LLabel, LValue, <LTail as SetIntersect<RightSet>>::Set>;
+}
+// head is not in right set: skip it
+impl<LLabel, LValue, LTail, RightSet> SetIntersectPred<RightSet, False>
+    for LVCons<LLabel, LValue, LTail>
+where
+    LTail: SetIntersect<RightSet>,
+{
+    type Set = <LTail as SetIntersect<RightSet>>::Set;
+}
+
+/// Determines the set union between an [LVCons](type.LVCons.html) label set and another
+/// [LVCons](type.LVCons.html) label set `RightSet`.
+pub trait SetUnion<RightSet> {
+    /// The set of labels that exist in either `Self` or `RightSet`: the labels of `Self`, followed
+    /// by the labels of `RightSet` which do not exist in `Self`.
+    type Set;
+}
+impl<LeftSet, RightSet> SetUnion<RightSet> for LeftSet
+where
+    RightSet: SetDiff<LeftSet>,
+    LeftSet: Append<LabelSetDiff<RightSet, LeftSet>>,
+{
+    type Set = <LeftSet as Append<LabelSetDiff<RightSet, LeftSet>>>::Appended;
+}
+
+/// Type alias for the union of the label lists `LeftSet` and `RightSet` (as generated by the
+/// [Labels](../macro.Labels.html) macro).
+pub type LabelsUnion<LeftSet, RightSet> = <LeftSet as SetUnion<RightSet>>::Set;
+/// Type alias for the labels in label list `LeftSet` which are not in label list `RightSet`.
+/// Equivalent to [LabelSetDiff](type.LabelSetDiff.html).
+pub type LabelsDiff<LeftSet, RightSet> = LabelSetDiff<LeftSet, RightSet>;
+/// Type alias for the labels in label list `LeftSet` which are also in label list `RightSet`.
+pub type LabelsIntersect<LeftSet, RightSet> = <LeftSet as SetIntersect<RightSet>>::Set;
+
 /// Look up an element from a cons-list by `typenum` natural number.
 pub trait LookupElemByNat<N> {
     /// Type of looked-up element.
@@ -1023,6 +1089,59 @@ where
     }
 }
 
+/// Name and data type of a field, as declared in a [tablespace](../macro.tablespace.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// Name of the field.
+    pub name: &'static str,
+    /// Name of the data type of the field.
+    pub ty: &'static str,
+}
+
+/// Name and fields of a table, as declared in a [tablespace](../macro.tablespace.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableInfo {
+    /// Name of the table.
+    pub name: &'static str,
+    /// Fields of the table, in declaration order.
+    pub fields: Vec<FieldInfo>,
+}
+
+impl TableInfo {
+    /// Creates a new `TableInfo` for the table named `name` with the fields in the `Fields`
+    /// cons-list.
+    pub fn new<Fields>(name: &'static str) -> TableInfo
+    where
+        Fields: StrLabels + StrTypes,
+    {
+        TableInfo {
+            name,
+            fields: Fields::labels()
+                .into_iter()
+                .zip(Fields::str_types())
+                .map(|(name, ty)| FieldInfo { name, ty })
+                .collect(),
+        }
+    }
+
+    /// Returns the field named `name`, or `None` if this table has no such field.
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Runtime registry of the tables declared in a [tablespace](../macro.tablespace.html), generated
+/// by a `registry` declaration within the tablespace.
+pub trait TablespaceRegistry {
+    /// Returns the tables declared in the tablespace, in declaration order.
+    fn tables() -> Vec<TableInfo>;
+
+    /// Returns the table named `name`, or `None` if the tablespace has no such table.
+    fn table(name: &str) -> Option<TableInfo> {
+        Self::tables().into_iter().find(|table| table.name == name)
+    }
+}
+
 /// Declares a set of data tables that all occupy the same tablespace (i.e. can be merged or
 /// joined together). This macro should be used at the beginning of any `agnes`-using code, to
 /// declare the various source and constructed table field labels.
@@ -1061,6 +1180,43 @@ where
 /// -- as well as the specified field labels within those modules. In this case, the `employee`
 /// table will have public visibility, while the `department` table will be private. After declaring
 /// these modules, you can refer to the labels as you would a normal type; e.g., `employee::EmpId`.
+///
+/// Each table module also contains a `NAME` constant with the name of the table, and a
+/// `table_info` function which returns a [TableInfo](label/struct.TableInfo.html) describing the
+/// table's fields at runtime.
+///
+/// # Registry
+///
+/// A tablespace can optionally begin with a `registry` declaration, which declares a struct
+/// (with the specified name and visibility) implementing
+/// [TablespaceRegistry](label/trait.TablespaceRegistry.html). The registry can be used to
+/// enumerate the tables of the tablespace and their fields at runtime, which is useful for
+/// generic tooling over compile-time schemas (such as command-line tools or validators).
+///
+/// ```
+/// # #[macro_use] extern crate agnes;
+/// use agnes::label::TablespaceRegistry;
+///
+/// tablespace![
+///     pub registry Catalog;
+///     pub table employee {
+///         EmpId: u64,
+///         EmpName: String,
+///     }
+///     table department {
+///         DeptId: u64,
+///     }
+/// ];
+///
+/// # fn main() {
+/// let tables = Catalog::tables();
+/// assert_eq!(tables.len(), 2);
+/// assert_eq!(tables[0].name, "employee");
+/// assert_eq!(tables[0].fields[1].name, "EmpName");
+/// assert_eq!(tables[0].fields[1].ty, "String");
+/// assert!(Catalog::table("department").is_some());
+/// # }
+/// ```
 #[macro_export]
 macro_rules! tablespace {
     (@fields() -> ($($out:tt)*)) => {
@@ -1123,13 +1279,46 @@ macro_rules! tablespace {
             /// Extra type alias for `View`.
             pub type DataView = View;
 
+            // allow field types which are in scope where the tablespace is defined
+            #[allow(unused_imports)]
+            use super::*;
+
             tablespace![@body($($body)*)];
+
+            /// Name of this table.
+            pub const NAME: &str = stringify![$tbl_name];
+
+            /// Returns the name of this table along with the names and types of its fields.
+            pub fn table_info() -> $crate::label::TableInfo {
+                $crate::label::TableInfo::new::<Fields>(NAME)
+            }
+        }
+    };
+
+    (@registry($vis:vis $reg_name:ident)($($tbl_name:ident)*)) => {
+        /// Registry of the tables declared in this tablespace.
+        #[derive(Debug, Clone, Copy, Default)]
+        $vis struct $reg_name;
+
+        impl $crate::label::TablespaceRegistry for $reg_name {
+            fn tables() -> Vec<$crate::label::TableInfo> {
+                vec![$($tbl_name::table_info()),*]
+            }
         }
     };
 
     // end case
     (@continue($prev_tbl:ty)) => {};
 
+    // registry declaration (continuing an existing tablespace)
+    (@continue($prev_tbl:ty)
+        $reg_vis:vis registry $reg_name:ident;
+        $($vis:vis table $tbl_name:ident { $($body:tt)* })*
+    ) => {
+        tablespace![@continue($prev_tbl) $($vis table $tbl_name { $($body)* })*];
+        tablespace![@registry($reg_vis $reg_name)($($tbl_name)*)];
+    };
+
     // non-initial case
     (@continue($prev_tbl:ty)
         $vis:vis table $tbl_name:ident {
@@ -1141,6 +1330,15 @@ macro_rules! tablespace {
         tablespace![@continue($crate::typenum::Add1<$prev_tbl>) $($rest)*];
     };
 
+    // entry point with registry declaration
+    (
+        $reg_vis:vis registry $reg_name:ident;
+        $($vis:vis table $tbl_name:ident { $($body:tt)* })*
+    ) => {
+        tablespace![$($vis table $tbl_name { $($body)* })*];
+        tablespace![@registry($reg_vis $reg_name)($($tbl_name)*)];
+    };
+
     // entry point
     (
         $vis:vis table $tbl_name:ident {
@@ -1382,6 +1580,74 @@ macro_rules! table {
     }}
 }
 
+/// Creates a data table with supplied data, specified row-by-row.
+///
+/// Like [table](macro.table.html), `dataview` handles creating the underlying
+/// [DataStore](store/struct.DataStore.html) to store the data, and returns a
+/// [DataView](view/struct.DataView.html). The data is instead laid out as it would appear in the
+/// resulting table: the macro should be called with a comma-separated list of field labels
+/// followed by a semi-colon, and then one '`<comma-separated list of values>;`' row per record.
+/// Each row should have one value per field label, and at least one row must be provided. Since the
+/// rows are rearranged into fields during macro expansion, this macro is best suited to small
+/// tables (such as in examples, tests, and lookup tables); larger tables may exceed the default
+/// macro `recursion_limit`.
+///
+/// # Example
+///
+/// This produces the same table as the example for the [table](macro.table.html) macro:
+/// ```
+/// # #[macro_use] extern crate agnes;
+/// tablespace![
+///     pub table employee {
+///         EmpId: u64,
+///         DeptId: u64,
+///         EmpName: String,
+///     }
+/// ];
+/// fn main() {
+///     use employee::*;
+///     let emp_table = dataview![
+///         EmpId, DeptId, EmpName;
+///         0,     0,      "Astrid";
+///         1,     2,      "Bob";
+///         2,     1,      "Calvin";
+///         3,     1,      "Deborah";
+///         4,     1,      "Eliza";
+///         5,     0,      "Franklin";
+///         6,     1,      "Gunther";
+///     ];
+///     assert_eq!((emp_table.nrows(), emp_table.nfields()), (7, 3));
+///     println!("{}", emp_table);
+/// }
+/// ```
+#[macro_export]
+macro_rules! dataview {
+    // all rows processed
+    (@rows[$(($label:ty)[$($value:expr),*])*]) => {
+        table![$($label = [$($value),*];)*]
+    };
+    // append each value of the next row onto its field's values
+    (@rows[$($cols:tt)*] ($($row:expr),*) $($rest:tt)*) => {
+        dataview![@zip[][$($cols)*][$($row),*] $($rest)*]
+    };
+    (@zip
+        [$($done:tt)*]
+        [($label:ty)[$($value:expr),*] $($cols:tt)*]
+        [$next:expr $(, $row:expr)*]
+        $($rest:tt)*
+    ) => {
+        dataview![@zip[$($done)* ($label)[$($value,)* $next]][$($cols)*][$($row),*] $($rest)*]
+    };
+    (@zip[$($done:tt)*][][] $($rest:tt)*) => {
+        dataview![@rows[$($done)*] $($rest)*]
+    };
+
+    // entry point
+    ($($label:ty),+; $($($value:expr),+;)+) => {
+        dataview![@rows[$(($label)[])+] $(($($value),+))+]
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1645,5 +1911,101 @@ mod tests {
             [] as [&str; 0],
             <LabelSetDiff<LSet2, LSet2> as StrLabels>::labels_vec()[..]
         );
+        assert_eq!(
+            [] as [&str; 0],
+            <LabelSetDiff<Labels![F2, F1], Labels![F1, F2]> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            ["F0"],
+            <LabelSetDiff<Labels![F2, F0, F1], Labels![F1, F2]> as StrLabels>::labels_vec()[..]
+        );
+    }
+
+    #[test]
+    fn set_union_intersect() {
+        type LSet1 = Labels![F0, F1, F2];
+        type LSet2 = Labels![F4, F2, F1];
+        type LSet3 = Labels![F3];
+
+        assert_eq!(
+            ["F0", "F1", "F2", "F4"],
+            <LabelsUnion<LSet1, LSet2> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            ["F4", "F2", "F1", "F0"],
+            <LabelsUnion<LSet2, LSet1> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            ["F0", "F1", "F2", "F3"],
+            <LabelsUnion<LSet1, LSet3> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            ["F3"],
+            <LabelsUnion<Nil, LSet3> as StrLabels>::labels_vec()[..]
+        );
+
+        assert_eq!(
+            ["F1", "F2"],
+            <LabelsIntersect<LSet1, LSet2> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            ["F2", "F1"],
+            <LabelsIntersect<LSet2, LSet1> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            [] as [&str; 0],
+            <LabelsIntersect<LSet1, LSet3> as StrLabels>::labels_vec()[..]
+        );
+        assert_eq!(
+            [] as [&str; 0],
+            <LabelsIntersect<Nil, LSet3> as StrLabels>::labels_vec()[..]
+        );
+
+        assert_eq!(
+            ["F0"],
+            <LabelsDiff<LSet1, LSet2> as StrLabels>::labels_vec()[..]
+        );
+    }
+
+    tablespace![
+        @continue(Add1<NumberTable>)
+        registry SampleRegistry;
+        table orders {
+            OrderId: u64,
+            Customer: String = {"Customer Name"},
+        }
+        table line_items {
+            OrderId: u64,
+            Qty: i32,
+        }
+    ];
+
+    #[test]
+    fn registry() {
+        assert_eq!(orders::NAME, "orders");
+        let tables = SampleRegistry::tables();
+        assert_eq!(
+            tables.iter().map(|table| table.name).collect::<Vec<_>>(),
+            vec!["orders", "line_items"]
+        );
+        assert_eq!(
+            tables[0].fields,
+            vec![
+                FieldInfo {
+                    name: "OrderId",
+                    ty: "u64"
+                },
+                FieldInfo {
+                    name: "Customer Name",
+                    ty: "String"
+                },
+            ]
+        );
+
+        let line_items = SampleRegistry::table("line_items").unwrap();
+        assert_eq!(line_items, line_items::table_info());
+        assert_eq!(line_items.field("Qty").unwrap().ty, "i32");
+        assert!(line_items.field("Customer Name").is_none());
+        assert!(SampleRegistry::table("customers").is_none());
     }
 }
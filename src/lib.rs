@@ -61,15 +61,20 @@ in the [label](label/index.html) module.
 extern crate bit_vec;
 extern crate csv;
 extern crate encoding;
+#[cfg(feature = "gzip")]
+extern crate flate2;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
 extern crate indexmap;
 extern crate native_tls;
 extern crate num_traits;
+extern crate rand;
 extern crate serde;
 extern crate tokio_core;
 extern crate tokio_io;
+#[cfg(feature = "zstandard")]
+extern crate zstd;
 #[macro_use]
 extern crate prettytable;
 extern crate csv_sniffer;
@@ -77,9 +82,6 @@ extern crate tempfile;
 // re-export typenum (since it's used in exported macros)
 pub extern crate typenum;
 
-#[cfg(test)]
-extern crate rand;
-#[cfg(test)]
 extern crate serde_json;
 
 #[macro_use]
@@ -102,17 +104,30 @@ pub mod value;
 pub mod test_utils;
 
 pub mod access;
+pub mod datetime;
+pub mod dictionary;
 pub mod error;
 pub mod frame;
+pub mod fuzzy;
+pub mod groupby;
+pub mod incremental;
 pub mod join;
+pub mod normalize;
 #[cfg(feature = "ops")]
 pub mod ops;
 pub mod permute;
+pub mod pipe;
+pub mod provenance;
+pub mod query;
+pub mod rle;
+pub mod rng;
 pub mod select;
 pub mod source;
 pub mod stats;
+pub mod validate;
 pub mod view;
 pub mod view_stats;
+pub mod window;
 
 #[cfg(feature = "experimental")]
 pub mod experimental;
@@ -0,0 +1,109 @@
+/*!
+Normalization of string keys, for matching messy real-world string values (differing in case or
+surrounding whitespace) in joins and group-bys without cleaning the source fields.
+
+A [StrNormalization](struct.StrNormalization.html) can be used to compute join keys with
+[join_by](../view/struct.DataView.html#method.join_by), or to add a normalized key field to a
+`DataView` for use in group-by operations with
+[normalize_str](../view/struct.DataView.html#method.normalize_str).
+*/
+
+/// Options for normalizing string keys before comparison. By default, no normalization is
+/// performed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StrNormalization {
+    case_fold: bool,
+    trim: bool,
+    collapse_whitespace: bool,
+}
+
+impl StrNormalization {
+    /// Creates a new `StrNormalization` which performs no normalization.
+    pub fn new() -> StrNormalization {
+        StrNormalization::default()
+    }
+    /// Enables case folding: strings are compared without regard to (Unicode) case.
+    pub fn case_insensitive(mut self) -> StrNormalization {
+        self.case_fold = true;
+        self
+    }
+    /// Enables trimming of leading and trailing whitespace.
+    pub fn trimmed(mut self) -> StrNormalization {
+        self.trim = true;
+        self
+    }
+    /// Enables collapsing of each run of internal whitespace into a single space.
+    pub fn collapse_whitespace(mut self) -> StrNormalization {
+        self.collapse_whitespace = true;
+        self
+    }
+
+    /// Returns the normalized form of `value`.
+    pub fn normalize(&self, value: &str) -> String {
+        let value = if self.trim { value.trim() } else { value };
+        let value = if self.collapse_whitespace {
+            let mut collapsed = String::with_capacity(value.len());
+            let mut prev_whitespace = false;
+            for c in value.chars() {
+                if c.is_whitespace() {
+                    if !prev_whitespace {
+                        collapsed.push(' ');
+                    }
+                    prev_whitespace = true;
+                } else {
+                    collapsed.push(c);
+                    prev_whitespace = false;
+                }
+            }
+            collapsed
+        } else {
+            value.to_string()
+        };
+        if self.case_fold {
+            value.to_lowercase()
+        } else {
+            value
+        }
+    }
+
+    /// Returns a key-extraction function which normalizes string values, for use with
+    /// [join_by](../view/struct.DataView.html#method.join_by).
+    pub fn key_fn(self) -> impl Fn(&String) -> String {
+        move |value: &String| self.normalize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let value = "  United \t States  ";
+        assert_eq!(StrNormalization::new().normalize(value), value);
+        assert_eq!(
+            StrNormalization::new().trimmed().normalize(value),
+            "United \t States"
+        );
+        assert_eq!(
+            StrNormalization::new()
+                .trimmed()
+                .collapse_whitespace()
+                .case_insensitive()
+                .normalize(value),
+            "united states"
+        );
+        assert_eq!(
+            StrNormalization::new()
+                .collapse_whitespace()
+                .normalize(value),
+            " United States "
+        );
+        assert_eq!(
+            StrNormalization::new()
+                .case_insensitive()
+                .normalize("ÉCOLE"),
+            "école"
+        );
+    }
+}
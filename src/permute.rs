@@ -8,9 +8,14 @@ included) or filtering (where a strict subset of the indices are included) of th
 This module also contains traits and methods for sorting data sets.
 */
 use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::iter::Peekable;
+use std::mem;
 
 use access::DataIndex;
 use cons::Nil;
+use datetime::DateTime;
 use value::Value;
 
 /// A structure containing information about the permutation status of a field. `I` represents the
@@ -39,20 +44,36 @@ impl Permutation<Vec<usize>> {
     /// Consumes this `Permutation` and returns a new `Permutation` with new values from
     /// `new_permutation`.
     pub fn update_indices(mut self, new_permutation: &[usize]) -> Permutation<Vec<usize>> {
+        if let Some(ref mut prev_perm) = self.perm {
+            // strictly increasing indices (e.g. from a filter) never refer to an already-updated
+            // position, so the existing buffer can be updated in place
+            let increasing = new_permutation.windows(2).all(|pair| pair[0] < pair[1]);
+            if increasing && new_permutation.last().map_or(true, |&idx| idx < prev_perm.len()) {
+                for (i, &new_idx) in new_permutation.iter().enumerate() {
+                    prev_perm[i] = prev_perm[new_idx];
+                }
+                prev_perm.truncate(new_permutation.len());
+                return self;
+            }
+        }
+        self.updated_indices(new_permutation)
+    }
+
+    /// Returns a new `Permutation` with new values from `new_permutation`, leaving this
+    /// `Permutation` unchanged.
+    pub fn updated_indices(&self, new_permutation: &[usize]) -> Permutation<Vec<usize>> {
         // check if we already have a permutation
-        self.perm = match self.perm {
+        let perm = match self.perm {
             Some(ref prev_perm) => {
                 // we already have a permutation, map the filter indices through it
-                Some(
-                    new_permutation
-                        .iter()
-                        .map(|&new_idx| prev_perm[new_idx])
-                        .collect(),
-                )
+                new_permutation
+                    .iter()
+                    .map(|&new_idx| prev_perm[new_idx])
+                    .collect()
             }
-            None => Some(new_permutation.iter().map(|&idx| idx).collect()),
+            None => new_permutation.to_vec(),
         };
-        self
+        Permutation { perm: Some(perm) }
     }
 }
 
@@ -92,6 +113,10 @@ impl UpdatePermutation for Nil {
 
 /// Trait providing function to compute and return the sorted permutation order. This sort is stable
 /// (preserves original order of equal elements).
+///
+/// Fields whose data type provides a radix key (see [SortKey](trait.SortKey.html)), such as the
+/// built-in integer types, are sorted using a radix sort (see
+/// [SortOrderRadix](trait.SortOrderRadix.html)), and other fields using a comparison sort.
 pub trait SortOrder {
     /// Returns the stable sorted permutation order as `Vec<usize>`
     fn sort_order(&self) -> Vec<usize>;
@@ -100,9 +125,19 @@ pub trait SortOrder {
 impl<DI> SortOrder for DI
 where
     DI: DataIndex,
-    <DI as DataIndex>::DType: Ord,
+    <DI as DataIndex>::DType: SortKey,
 {
     fn sort_order(&self) -> Vec<usize> {
+        // a data type either provides radix keys for all of its values or for none of them
+        let radix = self
+            .iter()
+            .filter_map(|value| value.into_option())
+            .next()
+            .map_or(true, |value| value.sort_radix_key().is_some());
+        if radix {
+            // only called for existing values, which all have radix keys
+            return radix_sort_order(self, |value| value.sort_radix_key().unwrap());
+        }
         let mut order = (0..self.len()).collect::<Vec<_>>();
         order.sort_by(|&left, &right| {
             // a, b are always in range, so unwraps are safe
@@ -114,6 +149,132 @@ where
     }
 }
 
+/// Trait for integer types which can be sorted using a radix sort. The radix key of a value is an
+/// unsigned integer whose ordering matches the ordering of the original values.
+pub trait RadixKey {
+    /// Returns the radix key of this value.
+    fn radix_key(&self) -> u64;
+}
+macro_rules! impl_radix_key_unsigned {
+    ($($dtype:ty)*) => {$(
+        impl RadixKey for $dtype {
+            fn radix_key(&self) -> u64 {
+                *self as u64
+            }
+        }
+    )*}
+}
+impl_radix_key_unsigned![u64 u32 u16 u8 usize];
+macro_rules! impl_radix_key_signed {
+    ($($dtype:ty)*) => {$(
+        impl RadixKey for $dtype {
+            fn radix_key(&self) -> u64 {
+                // flip the sign bit so that negative values order before positive values
+                (*self as i64 as u64) ^ (1 << 63)
+            }
+        }
+    )*}
+}
+impl_radix_key_signed![i64 i32 i16 i8 isize];
+
+/// Trait for data types which can be sorted with
+/// [sort_order](trait.SortOrder.html#tymethod.sort_order). Types which provide a radix key (the
+/// built-in integer types) are sorted using a radix sort; other types fall back to a comparison
+/// sort. Other `Ord` types can be made sortable with an empty implementation, which uses the
+/// comparison sort.
+pub trait SortKey: Ord {
+    /// Returns the radix key of this value (see [RadixKey](trait.RadixKey.html)), or `None` if
+    /// values of this type are sorted using a comparison sort.
+    fn sort_radix_key(&self) -> Option<u64> {
+        None
+    }
+}
+macro_rules! impl_sort_key_radix {
+    ($($dtype:ty)*) => {$(
+        impl SortKey for $dtype {
+            fn sort_radix_key(&self) -> Option<u64> {
+                Some(self.radix_key())
+            }
+        }
+    )*}
+}
+impl_sort_key_radix![u64 u32 u16 u8 usize i64 i32 i16 i8 isize];
+macro_rules! impl_sort_key_comparison {
+    ($($dtype:ty)*) => {$(
+        impl SortKey for $dtype {}
+    )*}
+}
+impl_sort_key_comparison![bool char String TotalF64 DateTime];
+impl<'a> SortKey for &'a str {}
+
+/// Trait providing function to compute and return the sorted permutation order of an integer
+/// field using a radix sort, which avoids comparison-based sorting and is typically faster for
+/// large fields. This sort is stable, and produces the same order as
+/// [sort_order](trait.SortOrder.html#tymethod.sort_order) (which uses this sort for fields of the
+/// types whose [SortKey](trait.SortKey.html) provides a radix key).
+pub trait SortOrderRadix {
+    /// Returns the stable sorted permutation order as `Vec<usize>`
+    fn sort_order_radix(&self) -> Vec<usize>;
+}
+
+impl<DI> SortOrderRadix for DI
+where
+    DI: DataIndex,
+    <DI as DataIndex>::DType: RadixKey,
+{
+    fn sort_order_radix(&self) -> Vec<usize> {
+        radix_sort_order(self, RadixKey::radix_key)
+    }
+}
+
+// computes the stable sort order of `data` using a radix sort on the keys computed by `key`
+fn radix_sort_order<DI, F>(data: &DI, key: F) -> Vec<usize>
+where
+    DI: DataIndex,
+    F: Fn(&DI::DType) -> u64,
+{
+    // missing values are placed at the beginning of the order
+    let mut order = Vec::with_capacity(data.len());
+    let mut keyed = Vec::with_capacity(data.len());
+    for (idx, value) in data.iter().enumerate() {
+        match value {
+            Value::Exists(value) => keyed.push((key(value), idx)),
+            Value::Na => order.push(idx),
+        }
+    }
+    order.extend(radix_sort(keyed).into_iter().map(|(_, idx)| idx));
+    order
+}
+
+// stable least-significant-digit radix sort of (key, index) pairs, one byte per pass
+fn radix_sort(mut keyed: Vec<(u64, usize)>) -> Vec<(u64, usize)> {
+    let mut buffer = vec![(0u64, 0usize); keyed.len()];
+    for pass in 0..8 {
+        let shift = pass * 8;
+        let mut counts = [0usize; 256];
+        for &(key, _) in &keyed {
+            counts[((key >> shift) & 0xFF) as usize] += 1;
+        }
+        // skip passes where every key has the same digit, since they wouldn't change the order
+        if counts.iter().any(|&count| count == keyed.len()) {
+            continue;
+        }
+        let mut offsets = [0usize; 256];
+        let mut total = 0;
+        for (offset, &count) in offsets.iter_mut().zip(counts.iter()) {
+            *offset = total;
+            total += count;
+        }
+        for &(key, idx) in &keyed {
+            let digit = ((key >> shift) & 0xFF) as usize;
+            buffer[offsets[digit]] = (key, idx);
+            offsets[digit] += 1;
+        }
+        mem::swap(&mut keyed, &mut buffer);
+    }
+    keyed
+}
+
 /// Trait providing function to compute and return the sorted permutation order. This sort is
 /// unstable (does not preserve original order of equal elements, but may be faster than the stable
 /// version).
@@ -230,6 +391,314 @@ pub fn sort_f64_values(left: Value<&f64>, right: Value<&f64>) -> Ordering {
     }
 }
 
+/// Placement of a class of values (such as missing values or NaNs) within a sort order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Values are placed at the beginning of the order.
+    First,
+    /// Values are placed at the end of the order.
+    Last,
+}
+
+impl Placement {
+    // ordering of a value of this placement class relative to a value outside of it
+    fn ordering(&self) -> Ordering {
+        match *self {
+            Placement::First => Ordering::Less,
+            Placement::Last => Ordering::Greater,
+        }
+    }
+}
+
+/// Policy for placing missing (NA) values and floating-point NaN values when sorting. The default
+/// policy places missing values first, followed by NaN values, followed by all other values (the
+/// same order as [sort_f64_values](fn.sort_f64_values.html)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortPolicy {
+    na: Placement,
+    nan: Placement,
+}
+
+impl Default for SortPolicy {
+    fn default() -> SortPolicy {
+        SortPolicy {
+            na: Placement::First,
+            nan: Placement::First,
+        }
+    }
+}
+
+impl SortPolicy {
+    /// Creates a new `SortPolicy` with missing values first, then NaN values.
+    pub fn new() -> SortPolicy {
+        SortPolicy::default()
+    }
+    /// Places missing values at the beginning of the order.
+    pub fn na_first(mut self) -> SortPolicy {
+        self.na = Placement::First;
+        self
+    }
+    /// Places missing values at the end of the order.
+    pub fn na_last(mut self) -> SortPolicy {
+        self.na = Placement::Last;
+        self
+    }
+    /// Places NaN values before all other existing values.
+    pub fn nan_first(mut self) -> SortPolicy {
+        self.nan = Placement::First;
+        self
+    }
+    /// Places NaN values after all other existing values.
+    pub fn nan_last(mut self) -> SortPolicy {
+        self.nan = Placement::Last;
+        self
+    }
+
+    /// Compares `left` and `right` using this policy. Missing values are always placed before
+    /// or after (as specified by this policy) existing values, including NaN values.
+    pub fn compare_values<T>(&self, left: Value<&T>, right: Value<&T>) -> Ordering
+    where
+        T: PolicyOrd,
+    {
+        match (left, right) {
+            (Value::Na, Value::Na) => Ordering::Equal,
+            (Value::Na, Value::Exists(_)) => self.na.ordering(),
+            (Value::Exists(_), Value::Na) => self.na.ordering().reverse(),
+            (Value::Exists(left), Value::Exists(right)) => left.policy_cmp(right, self),
+        }
+    }
+}
+
+/// Trait for types which can be ordered using a [SortPolicy](struct.SortPolicy.html). Implemented
+/// for floating-point types (placing NaN values as specified by the policy) and types with a total
+/// order.
+pub trait PolicyOrd {
+    /// Compares this value with `other` using `policy`.
+    fn policy_cmp(&self, other: &Self, policy: &SortPolicy) -> Ordering;
+}
+
+macro_rules! impl_policy_ord_float {
+    ($($ty:ty)*) => {$(
+        impl PolicyOrd for $ty {
+            fn policy_cmp(&self, other: &$ty, policy: &SortPolicy) -> Ordering {
+                match (self.is_nan(), other.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => policy.nan.ordering(),
+                    (false, true) => policy.nan.ordering().reverse(),
+                    // partial_cmp only fails for NaN
+                    (false, false) => self.partial_cmp(other).unwrap(),
+                }
+            }
+        }
+    )*}
+}
+impl_policy_ord_float![f64 f32];
+
+macro_rules! impl_policy_ord_total {
+    ($($ty:ty)*) => {$(
+        impl PolicyOrd for $ty {
+            fn policy_cmp(&self, other: &$ty, _policy: &SortPolicy) -> Ordering {
+                self.cmp(other)
+            }
+        }
+    )*}
+}
+impl_policy_ord_total![u64 u32 u16 u8 usize i64 i32 i16 i8 isize bool char String DateTime];
+
+impl PolicyOrd for TotalF64 {
+    fn policy_cmp(&self, other: &TotalF64, policy: &SortPolicy) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (false, false) => self.cmp(other),
+            // NaN values are placed as specified by the policy
+            _ => self.0.policy_cmp(&other.0, policy),
+        }
+    }
+}
+
+/// Ordering used when sorting string values. By default, strings are compared byte-wise (as with
+/// `Ord` for `String`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Collation {
+    case_insensitive: bool,
+    numeric: bool,
+}
+
+impl Collation {
+    /// Creates a new `Collation` which compares strings byte-wise.
+    pub fn new() -> Collation {
+        Collation::default()
+    }
+    /// Compares strings without regard to (Unicode) case.
+    pub fn case_insensitive(mut self) -> Collation {
+        self.case_insensitive = true;
+        self
+    }
+    /// Compares runs of ASCII digits within strings by their numeric value ('natural' ordering),
+    /// so that (for example) `"item2"` is ordered before `"item10"`.
+    pub fn numeric(mut self) -> Collation {
+        self.numeric = true;
+        self
+    }
+
+    /// Compares `left` and `right` using this collation.
+    pub fn compare(&self, left: &str, right: &str) -> Ordering {
+        if self.case_insensitive {
+            self.compare_folded(&left.to_lowercase(), &right.to_lowercase())
+        } else {
+            self.compare_folded(left, right)
+        }
+    }
+
+    /// Compares the string values `left` and `right` using this collation, with missing values
+    /// considered to be of 'lesser' value than existing values.
+    pub fn compare_values<T>(&self, left: Value<&T>, right: Value<&T>) -> Ordering
+    where
+        T: AsRef<str>,
+    {
+        match (left, right) {
+            (Value::Na, Value::Na) => Ordering::Equal,
+            (Value::Na, Value::Exists(_)) => Ordering::Less,
+            (Value::Exists(_), Value::Na) => Ordering::Greater,
+            (Value::Exists(left), Value::Exists(right)) => {
+                self.compare(left.as_ref(), right.as_ref())
+            }
+        }
+    }
+
+    // compares strings which have already been case-folded (if necessary)
+    fn compare_folded(&self, left: &str, right: &str) -> Ordering {
+        if !self.numeric {
+            return left.cmp(right);
+        }
+        let mut left = left.chars().peekable();
+        let mut right = right.chars().peekable();
+        loop {
+            match (left.peek().cloned(), right.peek().cloned()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                    let left_digits = take_digits(&mut left);
+                    let right_digits = take_digits(&mut right);
+                    let left_digits = left_digits.trim_start_matches('0');
+                    let right_digits = right_digits.trim_start_matches('0');
+                    // without leading zeros, longer digit runs are larger numbers
+                    let ordering = left_digits
+                        .len()
+                        .cmp(&right_digits.len())
+                        .then_with(|| left_digits.cmp(right_digits));
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                (Some(l), Some(r)) => {
+                    if l != r {
+                        return l.cmp(&r);
+                    }
+                    left.next();
+                    right.next();
+                }
+            }
+        }
+    }
+}
+
+// consumes and returns the run of ASCII digits at the front of `chars`
+fn take_digits<I>(chars: &mut Peekable<I>) -> String
+where
+    I: Iterator<Item = char>,
+{
+    let mut digits = String::new();
+    while let Some(&c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        digits.push(c);
+        chars.next();
+    }
+    digits
+}
+
+/// Treatment of signed zeros when constructing a [TotalF64](struct.TotalF64.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedZero {
+    /// `-0.0` and `0.0` are considered equal.
+    Equal,
+    /// `-0.0` and `0.0` are considered distinct, with `-0.0` ordered before `0.0`.
+    Distinct,
+}
+
+/// Wrapper for `f64` values which provides a total order, equality, and hashing, so that
+/// floating-point values can be used as join or group-by keys deterministically. All NaN values are
+/// considered equal to each other and less than all other values (as with
+/// [sort_f64](fn.sort_f64.html)), and signed zeros are compared as specified at construction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TotalF64(f64);
+
+impl TotalF64 {
+    /// Creates a new `TotalF64` from `value`, treating signed zeros as specified by
+    /// `signed_zero`.
+    pub fn new(value: f64, signed_zero: SignedZero) -> TotalF64 {
+        if value.is_nan() {
+            TotalF64(::std::f64::NAN)
+        } else if value == 0.0 && signed_zero == SignedZero::Equal {
+            TotalF64(0.0)
+        } else {
+            TotalF64(value)
+        }
+    }
+    /// Returns the wrapped value.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl From<f64> for TotalF64 {
+    /// Creates a new `TotalF64` from `value`, considering `-0.0` and `0.0` to be equal.
+    fn from(value: f64) -> TotalF64 {
+        TotalF64::new(value, SignedZero::Equal)
+    }
+}
+
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &TotalF64) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // partial_cmp only fails for NaN; zeros which compare equal are ordered by sign
+            (false, false) => self
+                .0
+                .partial_cmp(&other.0)
+                .unwrap()
+                .then_with(|| other.0.is_sign_negative().cmp(&self.0.is_sign_negative())),
+        }
+    }
+}
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &TotalF64) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &TotalF64) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for TotalF64 {}
+impl Hash for TotalF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // NaNs and (if considered equal) zeros are canonicalized on construction, so equal values
+        // have equal bit patterns
+        self.0.to_bits().hash(state);
+    }
+}
+impl Display for TotalF64 {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
 /// Trait providing method to provide an index permutation of values that match a predicate.
 pub trait FilterPerm<P> {
     /// Returns the permutation indices of this field which match the specified `predicate`.
@@ -256,6 +725,23 @@ mod tests {
     use super::*;
     use field::FieldData;
 
+    #[test]
+    fn update_indices() {
+        let perm: Permutation<Vec<usize>> = Permutation::default();
+        let perm = perm.update_indices(&[4, 2, 0, 1]);
+        assert_eq!(perm, Permutation::from(vec![4, 2, 0, 1]));
+
+        // filter (in-place) update
+        let filtered = perm.clone().update_indices(&[1, 3]);
+        assert_eq!(filtered, Permutation::from(vec![2, 1]));
+        assert_eq!(perm.updated_indices(&[1, 3]), filtered);
+
+        // reordering update
+        let reordered = perm.clone().update_indices(&[3, 3, 0]);
+        assert_eq!(reordered, Permutation::from(vec![1, 1, 4]));
+        assert_eq!(perm.updated_indices(&[3, 3, 0]), reordered);
+    }
+
     #[test]
     fn sort_order_no_na() {
         let field_data: FieldData<u64> = FieldData::from_vec(vec![2u64, 5, 3, 1, 8]);
@@ -319,4 +805,151 @@ mod tests {
         let sorted_order = field_data.sort_order_by(sort_f64_values);
         assert_eq!(sorted_order, vec![2, 1, 0, 4, 3]);
     }
+
+    #[test]
+    fn sort_policy() {
+        use std::f64::NAN;
+
+        let field_data = FieldData::from_field_vec(vec![
+            Value::Exists(2.0),
+            Value::Na,
+            Value::Exists(NAN),
+            Value::Exists(-1.0),
+        ]);
+        let sorted_order = |policy: SortPolicy| {
+            field_data.sort_order_by(|left: Value<&f64>, right: Value<&f64>| {
+                policy.compare_values(left, right)
+            })
+        };
+        // default policy matches sort_f64_values
+        assert_eq!(
+            sorted_order(SortPolicy::new()),
+            field_data.sort_order_by(sort_f64_values)
+        );
+        assert_eq!(sorted_order(SortPolicy::new()), vec![1, 2, 3, 0]);
+        assert_eq!(sorted_order(SortPolicy::new().na_last()), vec![2, 3, 0, 1]);
+        assert_eq!(sorted_order(SortPolicy::new().nan_last()), vec![1, 3, 0, 2]);
+        assert_eq!(
+            sorted_order(SortPolicy::new().na_last().nan_last()),
+            vec![3, 0, 2, 1]
+        );
+
+        let field_data: FieldData<u64> = vec![3u64, 1, 2].into();
+        assert_eq!(
+            field_data.sort_order_by(|left: Value<&u64>, right: Value<&u64>| {
+                SortPolicy::new().compare_values(left, right)
+            }),
+            field_data.sort_order()
+        );
+
+        // total-ordered floats place NaN values as specified by the policy
+        let field_data: FieldData<TotalF64> = vec![
+            TotalF64::from(2.0),
+            TotalF64::from(NAN),
+            TotalF64::from(-1.0),
+        ]
+        .into();
+        let sorted_order = |policy: SortPolicy| {
+            field_data.sort_order_by(|left: Value<&TotalF64>, right: Value<&TotalF64>| {
+                policy.compare_values(left, right)
+            })
+        };
+        assert_eq!(sorted_order(SortPolicy::new()), vec![1, 2, 0]);
+        assert_eq!(sorted_order(SortPolicy::new().nan_last()), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn total_f64() {
+        use std::collections::HashSet;
+        use std::f64::NAN;
+
+        assert_eq!(TotalF64::from(NAN), TotalF64::from(-NAN));
+        assert_eq!(TotalF64::from(-0.0), TotalF64::from(0.0));
+        assert_ne!(
+            TotalF64::new(-0.0, SignedZero::Distinct),
+            TotalF64::new(0.0, SignedZero::Distinct)
+        );
+        assert!(TotalF64::new(-0.0, SignedZero::Distinct) < TotalF64::from(0.0));
+        assert!(TotalF64::from(NAN) < TotalF64::from(::std::f64::NEG_INFINITY));
+
+        let keys = vec![1.0, NAN, -0.0, 0.0, NAN, 1.0]
+            .into_iter()
+            .map(TotalF64::from)
+            .collect::<HashSet<_>>();
+        assert_eq!(keys.len(), 3);
+
+        let field_data: FieldData<TotalF64> = vec![2.0, NAN, -1.0, 0.0]
+            .into_iter()
+            .map(TotalF64::from)
+            .collect();
+        assert_eq!(field_data.sort_order(), vec![1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn collation() {
+        let field_data: FieldData<String> = vec!["item10", "Item2", "item1", "item02", "apple"]
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let sorted_values = |collation: Collation| {
+            let order = field_data.sort_order_by(|left: Value<&String>, right: Value<&String>| {
+                collation.compare_values(left, right)
+            });
+            order
+                .iter()
+                .map(|&idx| field_data.get_datum(idx).unwrap().unwrap().as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted_values(Collation::new()),
+            vec!["Item2", "apple", "item02", "item1", "item10"]
+        );
+        assert_eq!(
+            sorted_values(Collation::new().case_insensitive()),
+            vec!["apple", "item02", "item1", "item10", "Item2"]
+        );
+        assert_eq!(
+            sorted_values(Collation::new().numeric()),
+            vec!["Item2", "apple", "item1", "item02", "item10"]
+        );
+        // equal values (item2, item02) retain original order
+        assert_eq!(
+            sorted_values(Collation::new().case_insensitive().numeric()),
+            vec!["apple", "item1", "Item2", "item02", "item10"]
+        );
+    }
+
+    #[test]
+    fn sort_order_radix() {
+        let field_data = FieldData::from_field_vec(vec![
+            Value::Exists(2u64),
+            Value::Exists(5),
+            Value::Na,
+            Value::Exists(1),
+            Value::Exists(8),
+        ]);
+        assert_eq!(field_data.sort_order_radix(), vec![2, 3, 0, 1, 4]);
+
+        let field_data: FieldData<i64> =
+            FieldData::from_vec(vec![300i64, -2, 70_000, -70_000, 0, -2, ::std::i64::MIN]);
+        let compared =
+            field_data.sort_order_by(|left: Value<&i64>, right: Value<&i64>| left.cmp(&right));
+        assert_eq!(field_data.sort_order_radix(), compared);
+        assert_eq!(field_data.sort_order(), compared);
+        assert_eq!(field_data.sort_order_radix(), vec![6, 3, 1, 5, 4, 0, 2]);
+
+        let field_data: FieldData<u32> = FieldData::from_vec(
+            (0..1000u32)
+                .map(|i| (i * 7919) % 1013 + ((i % 3) << 20))
+                .collect::<Vec<_>>(),
+        );
+        let compared =
+            field_data.sort_order_by(|left: Value<&u32>, right: Value<&u32>| left.cmp(&right));
+        assert_eq!(field_data.sort_order_radix(), compared);
+        assert_eq!(field_data.sort_order(), compared);
+
+        let field_data: FieldData<u64> = FieldData::from_vec(Vec::<u64>::new());
+        assert!(field_data.sort_order_radix().is_empty());
+    }
 }
@@ -0,0 +1,251 @@
+/*!
+Fluent chaining of `DataView` transformations.
+
+A [Pipe](struct.Pipe.html) (created with [pipe](../view/struct.DataView.html#method.pipe)) wraps a
+`DataView` and applies a sequence of transformations (filters, sorts, subviews, queries, or
+arbitrary functions) to it, without requiring intermediate bindings for each step. Steps which can
+fail (such as [query](struct.Pipe.html#method.query)) do not interrupt the pipeline: the error is
+recorded, the step is skipped, and the remaining steps are applied. All recorded errors are
+reported together when the result is retrieved with [collect](struct.Pipe.html#method.collect).
+
+The [Apply](trait.Apply.html) trait (implemented for all types) allows user-defined transformation
+functions to be used within method chains, either singly (with
+[apply](trait.Apply.html#method.apply)) or as a sequence of steps (with
+[apply_chain](trait.Apply.html#method.apply_chain)).
+*/
+use access::NRows;
+use error::*;
+use label::{LabelName, StrLabels};
+use permute::{FilterPerm, SortOrder};
+use query::QueryFn;
+use select::SelectFieldByLabel;
+use view::{DataView, FieldMap, FrameFieldId, Subview};
+
+/// Sequence of transformations applied to a value (typically a `DataView`), along with the errors
+/// of any steps which have failed.
+#[derive(Debug)]
+pub struct Pipe<V> {
+    view: V,
+    errors: Vec<AgnesError>,
+}
+
+impl<V> Pipe<V> {
+    /// Creates a new `Pipe` starting with `view`.
+    pub fn new(view: V) -> Pipe<V> {
+        Pipe {
+            view,
+            errors: vec![],
+        }
+    }
+
+    /// Applies the function `f` to the current value of this pipeline.
+    pub fn then<W, F>(self, f: F) -> Pipe<W>
+    where
+        F: FnOnce(V) -> W,
+    {
+        Pipe {
+            view: f(self.view),
+            errors: self.errors,
+        }
+    }
+
+    /// Applies the fallible function `f` to the current value of this pipeline. If `f` fails, the
+    /// error is recorded and the current value is left unchanged.
+    pub fn try_then<F>(self, f: F) -> Pipe<V>
+    where
+        F: FnOnce(V) -> Result<V>,
+        V: Clone,
+    {
+        let Pipe { view, mut errors } = self;
+        let view = match f(view.clone()) {
+            Ok(updated) => updated,
+            Err(e) => {
+                errors.push(e);
+                view
+            }
+        };
+        Pipe { view, errors }
+    }
+
+    /// Returns the errors of the steps of this pipeline which have failed so far.
+    pub fn errors(&self) -> &[AgnesError] {
+        &self.errors
+    }
+
+    /// Returns the result of this pipeline.
+    ///
+    /// # Error
+    /// Fails with a `Pipeline` error containing the errors of every failed step (in order) if any
+    /// steps failed.
+    pub fn collect(self) -> Result<V> {
+        if self.errors.is_empty() {
+            Ok(self.view)
+        } else {
+            Err(AgnesError::Pipeline(self.errors))
+        }
+    }
+}
+
+impl<Labels, Frames> Pipe<DataView<Labels, Frames>> {
+    /// Filters the `DataView` by `predicate`, applied to the field with label `Label`. See
+    /// [filter](../view/struct.DataView.html#method.filter).
+    pub fn filter<Label, P>(self, predicate: P) -> Self
+    where
+        DataView<Labels, Frames>: SelectFieldByLabel<Label>,
+        <DataView<Labels, Frames> as SelectFieldByLabel<Label>>::Output: FilterPerm<P>,
+        Label: LabelName,
+    {
+        self.then(|dv| dv.filter::<Label, P>(predicate))
+    }
+
+    /// Sorts the `DataView` by the field with label `Label`. See
+    /// [sort_by_label](../view/struct.DataView.html#method.sort_by_label).
+    pub fn sort<Label>(self) -> Self
+    where
+        DataView<Labels, Frames>: SelectFieldByLabel<Label>,
+        <DataView<Labels, Frames> as SelectFieldByLabel<Label>>::Output: SortOrder,
+        Labels: FrameFieldId<Label>,
+        Label: LabelName,
+    {
+        self.then(|dv| dv.sort_by_label::<Label>())
+    }
+
+    /// Replaces the `DataView` with a subview containing the fields with labels in `LabelList`.
+    /// See [v](../view/struct.DataView.html#method.v).
+    pub fn select<LabelList>(self) -> Pipe<<DataView<Labels, Frames> as Subview<LabelList>>::Output>
+    where
+        DataView<Labels, Frames>: Subview<LabelList>,
+    {
+        self.then(|dv| dv.subview::<LabelList>())
+    }
+
+    /// Filters the `DataView` using a [query](../query/index.html) expression. See
+    /// [query](../view/struct.DataView.html#method.query). If the query fails, the error is
+    /// recorded and the `DataView` is left unfiltered.
+    pub fn query(self, expression: &str) -> Self
+    where
+        DataView<Labels, Frames>: FieldMap<QueryFn> + NRows + Clone,
+        Labels: StrLabels,
+    {
+        self.try_then(|dv| dv.query(expression))
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Starts a [Pipe](../pipe/struct.Pipe.html) of transformations with this `DataView`, allowing
+    /// multiple steps to be chained together (e.g.
+    /// `dv.pipe().filter::<A, _>(...).sort::<B>().select::<Labels![A, B]>().collect()`).
+    pub fn pipe(self) -> Pipe<Self> {
+        Pipe::new(self)
+    }
+}
+
+/// Trait for applying user-defined transformation functions within method chains. Implemented for
+/// all types.
+pub trait Apply: Sized {
+    /// Applies the function `f` to this value, returning the result. Equivalent to `f(self)`, but
+    /// usable as part of a method chain (e.g. `dv.filter::<A, _>(...).apply(my_transform)`).
+    fn apply<R, F>(self, f: F) -> R
+    where
+        F: FnOnce(Self) -> R,
+    {
+        f(self)
+    }
+
+    /// Applies each function in `steps` to this value in turn, returning the final result.
+    fn apply_chain<I>(self, steps: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: FnOnce(Self) -> Self,
+    {
+        steps.into_iter().fold(self, |value, step| step(value))
+    }
+}
+
+impl<T> Apply for T {}
+
+#[cfg(feature = "test-utils")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use access::DataIndex;
+    use select::FieldSelect;
+    use store::IntoView;
+    use test_utils::*;
+    use value::Value;
+
+    #[test]
+    fn pipe() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+
+        let dv = sample_merged_emp_table()
+            .pipe()
+            .filter::<DeptId, _>(|dept: Value<&u64>| dept.map_or(false, |&dept| dept != 2))
+            .query("VacationHrs > 0")
+            .sort::<EmpName>()
+            .select::<Labels![EmpName, DeptId]>()
+            .collect()
+            .unwrap();
+        println!("{}", dv);
+        assert_eq!(dv.fieldnames(), vec!["EmpName", "DeptId"]);
+        assert_eq!(
+            dv.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Louise", "Sally"]
+        );
+
+        // failed steps are skipped, and their errors are collected together
+        let pipe = sample_merged_emp_table()
+            .pipe()
+            .query("Salary > 10")
+            .filter::<DidTraining, _>(|trained: Value<&bool>| trained.map_or(false, |&t| t))
+            .query("DeptId >");
+        assert_eq!(pipe.errors().len(), 2);
+        match pipe.collect() {
+            Err(AgnesError::Pipeline(errors)) => {
+                assert_eq!(errors.len(), 2);
+                match errors[0] {
+                    AgnesError::FieldNotFound(_) => {}
+                    _ => panic!("expected missing field error"),
+                }
+            }
+            _ => panic!("expected pipeline error"),
+        }
+    }
+
+    #[test]
+    fn apply() {
+        use test_utils::emp_table::*;
+
+        fn drop_dept_2(dv: View) -> View {
+            dv.filter::<DeptId, _>(|dept: Value<&u64>| dept.map_or(false, |&dept| dept != 2))
+        }
+
+        let dv = sample_emp_table()
+            .into_view()
+            .apply(drop_dept_2)
+            .sort_by_label::<EmpName>();
+        assert_eq!(
+            dv.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Louis", "Louise", "Sally"]
+        );
+        assert_eq!(dv.apply(|dv| dv.nrows()), 6);
+        assert_eq!(3.apply(|x| x * 2), 6);
+
+        let steps: Vec<Box<dyn Fn(View) -> View>> = vec![
+            Box::new(drop_dept_2),
+            Box::new(|dv: View| dv.sort_by_label::<EmpId>()),
+            Box::new(|dv: View| {
+                dv.filter::<EmpId, _>(|id: Value<&u64>| id.map_or(false, |&id| id > 5))
+            }),
+        ];
+        let dv = sample_emp_table().into_view().apply_chain(steps);
+        assert_eq!(dv.field::<EmpId>().to_vec(), vec![6, 8, 9, 10]);
+
+        // an empty chain leaves the value unchanged
+        let no_steps: Vec<fn(View) -> View> = vec![];
+        let dv = sample_emp_table().into_view().apply_chain(no_steps);
+        assert_eq!(dv.nrows(), 7);
+    }
+}
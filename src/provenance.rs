@@ -0,0 +1,227 @@
+/*!
+Optional recording of the operations which produced a `DataView`.
+
+Operation logging is disabled by default, and can be enabled on a `DataView` with
+[record_operations](../view/struct.DataView.html#method.record_operations). Once enabled, filters,
+sorts, relabelings, merges, joins, semi-joins, and set operations performed on the `DataView` (and
+the `DataView`s derived from it) are appended to its [OperationLog](struct.OperationLog.html),
+which can be retrieved with [operations](../view/struct.DataView.html#method.operations) in order
+to audit or reproduce a pipeline.
+*/
+use std::fmt::{self, Display, Formatter};
+
+use join::Cardinality;
+
+/// A single recorded operation on a `DataView`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    /// Creation of a `DataView` from a data source.
+    Load {
+        /// Description of the data source (e.g. a file path or URI).
+        source: String,
+    },
+    /// Filter applied to the field with label `label`.
+    Filter {
+        /// Name of the filtered field.
+        label: &'static str,
+    },
+    /// Filter specified by a [query](../query/index.html) expression.
+    Query {
+        /// The query expression.
+        expression: String,
+    },
+    /// Sort by the field with label `label`.
+    Sort {
+        /// Name of the sorted field.
+        label: &'static str,
+    },
+    /// Relabeling of a field.
+    Relabel {
+        /// Previous field name.
+        from: &'static str,
+        /// New field name.
+        to: &'static str,
+    },
+    /// Merge with another `DataView`.
+    Merge {
+        /// Operations recorded for the merged (right-hand) `DataView`.
+        right: Vec<Operation>,
+    },
+    /// Join with another `DataView`.
+    Join {
+        /// Name of the join field in the left-hand `DataView`.
+        left_label: &'static str,
+        /// Name of the join field in the right-hand `DataView`.
+        right_label: &'static str,
+        /// Join predicate (e.g. `==`).
+        predicate: &'static str,
+        /// Required join cardinality.
+        cardinality: Cardinality,
+        /// Operations recorded for the right-hand `DataView`.
+        right: Vec<Operation>,
+    },
+    /// Semi-join (or anti-join) with another `DataView`.
+    SemiJoin {
+        /// Name of the join field in the left-hand `DataView`.
+        left_label: &'static str,
+        /// Name of the join field in the right-hand `DataView`.
+        right_label: &'static str,
+        /// Whether records without a match (rather than with a match) were kept.
+        anti: bool,
+        /// Operations recorded for the right-hand `DataView`.
+        right: Vec<Operation>,
+    },
+    /// Row-wise set operation (union, intersection, or difference) with another `DataView`.
+    SetOperation {
+        /// Name of the set operation (`union`, `intersect`, or `difference`).
+        operation: &'static str,
+        /// Operations recorded for the other (right-hand) `DataView`.
+        right: Vec<Operation>,
+    },
+    /// A user-specified operation.
+    Custom(String),
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Operation::Load { ref source } => write!(f, "load {}", source),
+            Operation::Filter { label } => write!(f, "filter on {}", label),
+            Operation::Query { ref expression } => write!(f, "query {}", expression),
+            Operation::Sort { label } => write!(f, "sort by {}", label),
+            Operation::Relabel { from, to } => write!(f, "relabel {} as {}", from, to),
+            Operation::Merge { ref right } => {
+                write!(f, "merge with [{}]", DisplayOps(right))
+            }
+            Operation::Join {
+                left_label,
+                right_label,
+                predicate,
+                cardinality,
+                ref right,
+            } => write!(
+                f,
+                "{} join on {} {} {} with [{}]",
+                cardinality,
+                left_label,
+                predicate,
+                right_label,
+                DisplayOps(right)
+            ),
+            Operation::SemiJoin {
+                left_label,
+                right_label,
+                anti,
+                ref right,
+            } => write!(
+                f,
+                "{} join on {} == {} with [{}]",
+                if anti { "anti" } else { "semi" },
+                left_label,
+                right_label,
+                DisplayOps(right)
+            ),
+            Operation::SetOperation {
+                operation,
+                ref right,
+            } => write!(f, "{} rows with [{}]", operation, DisplayOps(right)),
+            Operation::Custom(ref description) => write!(f, "{}", description),
+        }
+    }
+}
+
+// displays a list of operations separated by arrows
+struct DisplayOps<'a>(&'a [Operation]);
+
+impl<'a> Display for DisplayOps<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, op) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", op)?;
+        }
+        Ok(())
+    }
+}
+
+/// The sequence of operations which produced a `DataView`. When logging is disabled (the default),
+/// operations are not recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationLog {
+    ops: Option<Vec<Operation>>,
+}
+
+impl OperationLog {
+    /// Creates a new, empty, enabled `OperationLog`.
+    pub fn new() -> OperationLog {
+        OperationLog { ops: Some(vec![]) }
+    }
+    /// Returns whether or not operations are being recorded in this log.
+    pub fn is_enabled(&self) -> bool {
+        self.ops.is_some()
+    }
+    /// Returns the recorded operations, in order of application, or `None` if logging is
+    /// disabled.
+    pub fn operations(&self) -> Option<&[Operation]> {
+        self.ops.as_ref().map(|ops| &ops[..])
+    }
+    /// Records `op` in this log. Does nothing if logging is disabled.
+    pub fn record(&mut self, op: Operation) {
+        if let Some(ref mut ops) = self.ops {
+            ops.push(op);
+        }
+    }
+    /// Returns a copy of this log with `op` recorded.
+    pub(crate) fn with(&self, op: Operation) -> OperationLog {
+        let mut log = self.clone();
+        log.record(op);
+        log
+    }
+    // returns the recorded operations, or an empty list if logging is disabled
+    pub(crate) fn to_vec(&self) -> Vec<Operation> {
+        self.ops.clone().unwrap_or_default()
+    }
+}
+
+impl Display for OperationLog {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.ops {
+            Some(ref ops) => write!(f, "{}", DisplayOps(ops)),
+            None => write!(f, "(operation logging disabled)"),
+        }
+    }
+}
+
+/// Trait for replacing the [OperationLog](struct.OperationLog.html) of a data structure.
+/// Implemented by [DataView](../view/struct.DataView.html), and used to attach logs to the results
+/// of operations which create new `DataView`s.
+pub trait WithOperationLog {
+    /// Consumes this object and returns it with its operation log replaced by `log`.
+    fn with_operation_log(self, log: OperationLog) -> Self;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let mut log = OperationLog::default();
+        log.record(Operation::Custom("ignored".into()));
+        assert!(!log.is_enabled());
+        assert_eq!(log.operations(), None);
+
+        let mut log = OperationLog::new();
+        log.record(Operation::Load {
+            source: "emp.csv".into(),
+        });
+        log.record(Operation::Filter { label: "DeptId" });
+        let log = log.with(Operation::Sort { label: "EmpName" });
+        assert_eq!(log.operations().unwrap().len(), 3);
+        assert_eq!(
+            log.to_string(),
+            "load emp.csv -> filter on DeptId -> sort by EmpName"
+        );
+    }
+}
@@ -0,0 +1,753 @@
+/*!
+A small expression language for filtering `DataView`s with predicates specified at runtime (e.g.
+from command-line arguments or configuration files), for use with
+[query](../view/struct.DataView.html#method.query).
+
+A query expression consists of comparisons between a field (referred to by its label name) and a
+literal value, combined with the logical operators `&&`, `||`, and `!` and grouped using
+parentheses. For example:
+
+```text
+DeptId == 1 && (VacationHrs >= 10.5 || !(EmpName == "Sally"))
+```
+
+The supported comparison operators are `==`, `!=`, `<`, `<=`, `>`, and `>=`. Literals can be
+numbers (compared against integer and floating-point fields), double- or single-quoted strings
+(compared against string fields, or against datetime fields if the string is a valid
+[DateTime](../datetime/struct.DateTime.html)), `true` or `false` (compared against boolean
+fields), or `NA`.
+Missing (NA) field values never satisfy a comparison with a non-`NA` literal; `Field == NA` matches
+only missing values, and `Field != NA` matches only existing values. Field names which are not
+valid identifiers (e.g. CSV column headers containing spaces) can be enclosed in backticks:
+
+```text
+`Country Code` == "USA" && `1983` > 1e12
+```
+*/
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use access::DataIndex;
+use datetime::DateTime;
+use error::*;
+use partial::{Func, FuncDefault, Implemented, IsImplemented};
+use permute::TotalF64;
+use stats::FieldSummary;
+use value::Value;
+
+/// A literal value in a query expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// Numeric literal.
+    Number(f64),
+    /// String literal.
+    Str(String),
+    /// Boolean literal.
+    Bool(bool),
+    /// Missing value literal (`NA`).
+    Na,
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Literal::Number(n) => write!(f, "{}", n),
+            Literal::Str(ref s) => write!(f, "{:?}", s),
+            Literal::Bool(b) => write!(f, "{}", b),
+            Literal::Na => write!(f, "NA"),
+        }
+    }
+}
+
+/// Comparison operator in a query expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    /// Equal (`==`).
+    Eq,
+    /// Not equal (`!=`).
+    Ne,
+    /// Less than (`<`).
+    Lt,
+    /// Less than or equal (`<=`).
+    Le,
+    /// Greater than (`>`).
+    Gt,
+    /// Greater than or equal (`>=`).
+    Ge,
+}
+
+impl CmpOp {
+    fn matches(self, ordering: Ordering) -> bool {
+        match self {
+            CmpOp::Eq => ordering == Ordering::Equal,
+            CmpOp::Ne => ordering != Ordering::Equal,
+            CmpOp::Lt => ordering == Ordering::Less,
+            CmpOp::Le => ordering != Ordering::Greater,
+            CmpOp::Gt => ordering == Ordering::Greater,
+            CmpOp::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl Display for CmpOp {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match *self {
+                CmpOp::Eq => "==",
+                CmpOp::Ne => "!=",
+                CmpOp::Lt => "<",
+                CmpOp::Le => "<=",
+                CmpOp::Gt => ">",
+                CmpOp::Ge => ">=",
+            }
+        )
+    }
+}
+
+/// A comparison between the field named `field` and a literal value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    /// Field (label) name.
+    pub field: String,
+    /// Comparison operator.
+    pub op: CmpOp,
+    /// Literal value to compare against.
+    pub literal: Literal,
+}
+
+/// A parsed query expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// A single field comparison.
+    Compare(Comparison),
+    /// Logical negation.
+    Not(Box<Query>),
+    /// Logical conjunction.
+    And(Box<Query>, Box<Query>),
+    /// Logical disjunction.
+    Or(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    /// Parses a query expression. Fails with a `Query` error if the expression is malformed.
+    pub fn parse(expression: &str) -> Result<Query> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(query_error(format!("unexpected token '{}'", token))),
+        }
+    }
+
+    /// Returns the comparisons within this query, in order of appearance.
+    pub fn comparisons(&self) -> Vec<&Comparison> {
+        let mut comparisons = vec![];
+        self.collect_comparisons(&mut comparisons);
+        comparisons
+    }
+    fn collect_comparisons<'a>(&'a self, comparisons: &mut Vec<&'a Comparison>) {
+        match *self {
+            Query::Compare(ref comparison) => comparisons.push(comparison),
+            Query::Not(ref query) => query.collect_comparisons(comparisons),
+            Query::And(ref left, ref right) | Query::Or(ref left, ref right) => {
+                left.collect_comparisons(comparisons);
+                right.collect_comparisons(comparisons);
+            }
+        }
+    }
+
+    /// Evaluates this query over `nrows` records, using `compare` to compute the record-wise
+    /// result of each comparison.
+    pub(crate) fn eval<F>(&self, nrows: usize, compare: &mut F) -> Result<Vec<bool>>
+    where
+        F: FnMut(&Comparison) -> Result<Vec<bool>>,
+    {
+        match *self {
+            Query::Compare(ref comparison) => {
+                let mask = compare(comparison)?;
+                debug_assert_eq!(mask.len(), nrows);
+                Ok(mask)
+            }
+            Query::Not(ref query) => Ok(query
+                .eval(nrows, compare)?
+                .into_iter()
+                .map(|b| !b)
+                .collect()),
+            Query::And(ref left, ref right) => {
+                let left = left.eval(nrows, compare)?;
+                let right = right.eval(nrows, compare)?;
+                Ok(left
+                    .iter()
+                    .zip(right.iter())
+                    .map(|(&l, &r)| l && r)
+                    .collect())
+            }
+            Query::Or(ref left, ref right) => {
+                let left = left.eval(nrows, compare)?;
+                let right = right.eval(nrows, compare)?;
+                Ok(left
+                    .iter()
+                    .zip(right.iter())
+                    .map(|(&l, &r)| l || r)
+                    .collect())
+            }
+        }
+    }
+
+    /// Evaluates this query on a single record, using `compare` to compute the result of each
+    /// comparison.
+    pub(crate) fn matches<F>(&self, compare: &mut F) -> Result<bool>
+    where
+        F: FnMut(&Comparison) -> Result<bool>,
+    {
+        match *self {
+            Query::Compare(ref comparison) => compare(comparison),
+            Query::Not(ref query) => Ok(!query.matches(compare)?),
+            Query::And(ref left, ref right) => {
+                Ok(left.matches(compare)? && right.matches(compare)?)
+            }
+            Query::Or(ref left, ref right) => Ok(left.matches(compare)? || right.matches(compare)?),
+        }
+    }
+}
+
+impl Comparison {
+    /// Evaluates this comparison on a raw (unparsed) text value, such as a CSV cell. Empty (or
+    /// whitespace-only) text is considered missing. Fails if `text` cannot be parsed as the type
+    /// of this comparison's literal.
+    pub(crate) fn matches_text(&self, text: &str) -> Result<bool> {
+        let text = text.trim();
+        if self.literal == Literal::Na {
+            return Ok(text.is_empty() == (self.op == CmpOp::Eq));
+        }
+        if text.is_empty() {
+            return Ok(false);
+        }
+        let ordering = match self.literal {
+            Literal::Number(_) => text
+                .parse::<f64>()
+                .map_err(|_| self.parse_error(text))?
+                .query_cmp(&self.literal),
+            Literal::Bool(_) => text
+                .parse::<bool>()
+                .map_err(|_| self.parse_error(text))?
+                .query_cmp(&self.literal),
+            _ => text.query_cmp(&self.literal),
+        };
+        // incomparable values (e.g. NaN) never match
+        Ok(ordering.map_or(false, |ordering| self.op.matches(ordering)))
+    }
+
+    // returns whether it can be determined from `summary` alone that no value in the summarized
+    // field satisfies this comparison
+    fn excludes<T>(&self, summary: &FieldSummary<T>) -> bool
+    where
+        T: QueryCompare,
+    {
+        if self.literal == Literal::Na {
+            return match self.op {
+                CmpOp::Eq => summary.num_na == 0,
+                _ => summary.min.is_none(),
+            };
+        }
+        let (min, max) = match (&summary.min, &summary.max) {
+            (&Some(ref min), &Some(ref max)) => {
+                (min.query_cmp(&self.literal), max.query_cmp(&self.literal))
+            }
+            // no existing values, which never satisfy comparisons with non-NA literals
+            _ => return true,
+        };
+        let (less, equal, greater) = (
+            Some(Ordering::Less),
+            Some(Ordering::Equal),
+            Some(Ordering::Greater),
+        );
+        match self.op {
+            CmpOp::Eq => min == greater || max == less,
+            CmpOp::Ne => min == equal && max == equal,
+            CmpOp::Lt => min == greater || min == equal,
+            CmpOp::Le => min == greater,
+            CmpOp::Gt => max == less || max == equal,
+            CmpOp::Ge => max == less,
+        }
+    }
+    fn parse_error(&self, text: &str) -> AgnesError {
+        query_error(format!(
+            "value '{}' of field '{}' cannot be compared with {}",
+            text, self.field, self.literal
+        ))
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Query::Compare(ref cmp) => write!(f, "{} {} {}", cmp.field, cmp.op, cmp.literal),
+            Query::Not(ref query) => write!(f, "!({})", query),
+            Query::And(ref left, ref right) => write!(f, "({} && {})", left, right),
+            Query::Or(ref left, ref right) => write!(f, "({} || {})", left, right),
+        }
+    }
+}
+
+fn query_error<S: Into<String>>(msg: S) -> AgnesError {
+    AgnesError::Query(msg.into())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Literal),
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Token::Ident(ref ident) => write!(f, "{}", ident),
+            Token::Literal(ref literal) => write!(f, "{}", literal),
+            Token::Cmp(op) => write!(f, "{}", op),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let chars = expression.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).cloned();
+        match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Cmp(CmpOp::Eq));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Cmp(CmpOp::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' | '>' => {
+                let (op, len) = match (c, next) {
+                    ('<', Some('=')) => (CmpOp::Le, 2),
+                    ('<', _) => (CmpOp::Lt, 1),
+                    ('>', Some('=')) => (CmpOp::Ge, 2),
+                    _ => (CmpOp::Gt, 1),
+                };
+                tokens.push(Token::Cmp(op));
+                i += len;
+            }
+            '"' | '\'' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(query_error("unterminated string literal")),
+                        Some(&ch) if ch == c => break,
+                        Some(&'\\') if i + 1 < chars.len() => {
+                            s.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&ch) => {
+                            s.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Literal(Literal::Str(s)));
+                i += 1;
+            }
+            '`' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && chars[i] != '`' {
+                    i += 1;
+                }
+                if i == chars.len() {
+                    return Err(query_error("unterminated field name"));
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                i += 1;
+            }
+            _ if c.is_ascii_digit() || c == '.' || c == '-' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    let exp_sign =
+                        (ch == '-' || ch == '+') && (chars[i - 1] == 'e' || chars[i - 1] == 'E');
+                    if ch.is_ascii_digit() || ch == '.' || ch == 'e' || ch == 'E' || exp_sign {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let s = chars[start..i].iter().collect::<String>();
+                let n = s
+                    .parse::<f64>()
+                    .map_err(|_| query_error(format!("invalid number '{}'", s)))?;
+                tokens.push(Token::Literal(Literal::Number(n)));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident = chars[start..i].iter().collect::<String>();
+                tokens.push(match ident.as_str() {
+                    "true" => Token::Literal(Literal::Bool(true)),
+                    "false" => Token::Literal(Literal::Bool(false)),
+                    "NA" => Token::Literal(Literal::Na),
+                    _ => Token::Ident(ident),
+                });
+            }
+            _ => {
+                return Err(query_error(format!("unexpected character '{}'", c)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// recursive-descent parser over the token stream; `&&` binds more tightly than `||`
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+    fn parse_or(&mut self) -> Result<Query> {
+        let mut query = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            query = Query::Or(Box::new(query), Box::new(self.parse_and()?));
+        }
+        Ok(query)
+    }
+    fn parse_and(&mut self) -> Result<Query> {
+        let mut query = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            query = Query::And(Box::new(query), Box::new(self.parse_unary()?));
+        }
+        Ok(query)
+    }
+    fn parse_unary(&mut self) -> Result<Query> {
+        match self.next() {
+            Some(Token::Not) => Ok(Query::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let query = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(query),
+                    _ => Err(query_error("expected ')'")),
+                }
+            }
+            Some(Token::Ident(field)) => {
+                let op = match self.next() {
+                    Some(Token::Cmp(op)) => op,
+                    _ => {
+                        return Err(query_error(format!(
+                            "expected comparison operator after '{}'",
+                            field
+                        )));
+                    }
+                };
+                let literal = match self.next() {
+                    Some(Token::Literal(literal)) => literal,
+                    _ => {
+                        return Err(query_error(format!(
+                            "expected literal value after '{} {}'",
+                            field, op
+                        )));
+                    }
+                };
+                if literal == Literal::Na && op != CmpOp::Eq && op != CmpOp::Ne {
+                    return Err(query_error("NA can only be compared with '==' or '!='"));
+                }
+                Ok(Query::Compare(Comparison { field, op, literal }))
+            }
+            Some(token) => Err(query_error(format!("unexpected token '{}'", token))),
+            None => Err(query_error("unexpected end of expression")),
+        }
+    }
+}
+
+/// Trait for data types which can be compared against query [Literal](enum.Literal.html)s.
+pub trait QueryCompare {
+    /// Returns whether or not values of this type can be compared with `literal`.
+    fn accepts(literal: &Literal) -> bool;
+    /// Compares this value with `literal`. Returns `None` if the two are not comparable.
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering>;
+}
+
+macro_rules! impl_query_compare_numeric {
+    ($($dtype:ty)*) => {$(
+        impl QueryCompare for $dtype {
+            fn accepts(literal: &Literal) -> bool {
+                match *literal {
+                    Literal::Number(_) => true,
+                    _ => false,
+                }
+            }
+            fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+                match *literal {
+                    Literal::Number(n) => (*self as f64).partial_cmp(&n),
+                    _ => None,
+                }
+            }
+        }
+    )*}
+}
+impl_query_compare_numeric![f64 f32 u64 u32 usize i64 i32 isize];
+
+impl QueryCompare for TotalF64 {
+    fn accepts(literal: &Literal) -> bool {
+        <f64 as QueryCompare>::accepts(literal)
+    }
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+        self.value().query_cmp(literal)
+    }
+}
+
+impl QueryCompare for DateTime {
+    fn accepts(literal: &Literal) -> bool {
+        match *literal {
+            Literal::Str(ref s) => s.parse::<DateTime>().is_ok(),
+            _ => false,
+        }
+    }
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+        match *literal {
+            Literal::Str(ref s) => s.parse::<DateTime>().ok().map(|other| self.cmp(&other)),
+            _ => None,
+        }
+    }
+}
+
+impl QueryCompare for String {
+    fn accepts(literal: &Literal) -> bool {
+        <&str as QueryCompare>::accepts(literal)
+    }
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+        self.as_str().query_cmp(literal)
+    }
+}
+impl<'a> QueryCompare for &'a str {
+    fn accepts(literal: &Literal) -> bool {
+        match *literal {
+            Literal::Str(_) => true,
+            _ => false,
+        }
+    }
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+        match *literal {
+            Literal::Str(ref s) => Some((*self).cmp(s.as_str())),
+            _ => None,
+        }
+    }
+}
+impl QueryCompare for bool {
+    fn accepts(literal: &Literal) -> bool {
+        match *literal {
+            Literal::Bool(_) => true,
+            _ => false,
+        }
+    }
+    fn query_cmp(&self, literal: &Literal) -> Option<Ordering> {
+        match *literal {
+            Literal::Bool(b) => Some(self.cmp(&b)),
+            _ => None,
+        }
+    }
+}
+
+/// Function (implementing [Func](../partial/trait.Func.html)) that evaluates a
+/// [Comparison](struct.Comparison.html) on the field at a specific index within a `DataView`.
+#[derive(Debug, Clone)]
+pub struct QueryFn {
+    comparison: Comparison,
+    target: usize,
+    current: usize,
+    result: Option<Result<Vec<bool>>>,
+}
+
+impl QueryFn {
+    pub(crate) fn new(comparison: Comparison, target: usize) -> QueryFn {
+        QueryFn {
+            comparison,
+            target,
+            current: 0,
+            result: None,
+        }
+    }
+    pub(crate) fn into_result(self) -> Result<Vec<bool>> {
+        self.result
+            .unwrap_or_else(|| Err(query_error("field not found in view")))
+    }
+    fn type_error(&self) -> AgnesError {
+        query_error(format!(
+            "field '{}' cannot be compared with {}",
+            self.comparison.field, self.comparison.literal
+        ))
+    }
+}
+
+impl<DType> Func<DType> for QueryFn
+where
+    DType: QueryCompare + Clone + PartialOrd,
+{
+    type Output = ();
+    fn call<DI>(&mut self, data: &DI) -> Self::Output
+    where
+        DI: DataIndex<DType = DType>,
+    {
+        if self.current == self.target {
+            let op = self.comparison.op;
+            let literal = &self.comparison.literal;
+            let excluded = (*literal == Literal::Na || DType::accepts(literal))
+                && data
+                    .cached_summary()
+                    .map_or(false, |summary| self.comparison.excludes(&summary));
+            self.result = Some(if excluded {
+                // no values can match, so skip the comparisons entirely
+                Ok(vec![false; data.len()])
+            } else if *literal == Literal::Na {
+                Ok(data
+                    .iter()
+                    .map(|value| value.exists() == (op == CmpOp::Ne))
+                    .collect())
+            } else if DType::accepts(literal) {
+                Ok(data
+                    .iter()
+                    .map(|value| match value {
+                        // incomparable values (e.g. NaN) never match
+                        Value::Exists(value) => value
+                            .query_cmp(literal)
+                            .map_or(false, |ordering| op.matches(ordering)),
+                        Value::Na => false,
+                    })
+                    .collect())
+            } else {
+                Err(self.type_error())
+            });
+        }
+        self.current += 1;
+    }
+}
+impl FuncDefault for QueryFn {
+    type Output = ();
+    fn call(&mut self) -> Self::Output {
+        if self.current == self.target {
+            self.result = Some(Err(self.type_error()));
+        }
+        self.current += 1;
+    }
+}
+macro_rules! impl_query_is_impl {
+    ($($dtype:ty)*) => {$(
+        impl IsImplemented<QueryFn> for $dtype {
+            type IsImpl = Implemented;
+        }
+    )*}
+}
+impl_query_is_impl![String &str f64 f32 u64 u32 usize i64 i32 isize bool TotalF64 DateTime];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let query =
+            Query::parse("DeptId == 1 && (VacationHrs >= -2.5 || !(Name != \"Bob\"))").unwrap();
+        assert_eq!(
+            query.to_string(),
+            "(DeptId == 1 && (VacationHrs >= -2.5 || !(Name != \"Bob\")))"
+        );
+        assert_eq!(
+            query
+                .comparisons()
+                .iter()
+                .map(|cmp| cmp.field.as_str())
+                .collect::<Vec<_>>(),
+            vec!["DeptId", "VacationHrs", "Name"]
+        );
+
+        // '&&' binds more tightly than '||'
+        let query = Query::parse("A == true || B < 1e3 && C == 'x'").unwrap();
+        assert_eq!(query.to_string(), "(A == true || (B < 1000 && C == \"x\"))");
+
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("DeptId 1").is_err());
+        assert!(Query::parse("DeptId == ").is_err());
+        assert!(Query::parse("(DeptId == 1").is_err());
+        assert!(Query::parse("DeptId == 1)").is_err());
+        assert!(Query::parse("Name == \"Bob").is_err());
+        assert!(Query::parse("DeptId < NA").is_err());
+        assert!(Query::parse("DeptId = 1").is_err());
+
+        let query = Query::parse("`Country Code` == 'USA'").unwrap();
+        assert_eq!(query.comparisons()[0].field, "Country Code");
+        assert!(Query::parse("`Country Code == 'USA'").is_err());
+    }
+
+    #[test]
+    fn matches_text() {
+        let query = Query::parse("`1983` > 1e9 && Name != NA").unwrap();
+        let eval = |gdp: &str, name: &str| {
+            query.matches(&mut |cmp: &Comparison| match cmp.field.as_str() {
+                "1983" => cmp.matches_text(gdp),
+                _ => cmp.matches_text(name),
+            })
+        };
+        assert!(eval("1330167597.76", "Aruba").unwrap());
+        assert!(!eval("537777811.1", "Afghanistan").unwrap());
+        assert!(!eval("", "Aruba").unwrap());
+        assert!(!eval("1330167597.76", " ").unwrap());
+        assert!(eval("abc", "Aruba").is_err());
+    }
+}
@@ -0,0 +1,276 @@
+/*!
+Run-length encoded field data.
+
+An [RleData](struct.RleData.html) stores a field as a sequence of runs of consecutive equal values,
+which can greatly reduce the memory used by highly repetitive fields (such as sorted keys or
+constant fields). It implements [DataIndex](../access/trait.DataIndex.html), decoding values on
+access, so it can be read anywhere other field data can be read.
+
+`RleData` is a standalone container: fields within a [DataStore](../store/struct.DataStore.html)
+are always stored as [FieldData](../field/struct.FieldData.html), so `DataView` operations (such
+as group-bys, aggregations, and joins) do not use run-length encoding. Grouping and equality joins
+on run-length encoded keys can instead be computed run-by-run, rather than record-by-record, by
+calling [group_indices](struct.RleData.html#method.group_indices) and
+[join_indices](struct.RleData.html#method.join_indices) directly. An `RleData` can be converted
+into stored field data with [decode](struct.RleData.html#method.decode).
+*/
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Range;
+
+use access::DataIndex;
+use error::*;
+use field::FieldData;
+use value::Value;
+
+/// Run-length encoded field data. Each run holds a single value (possibly NA) which is repeated
+/// for every record in the run.
+#[derive(Debug, Clone)]
+pub struct RleData<T> {
+    // value of each run
+    values: Vec<Value<T>>,
+    // (exclusive) end index of each run, in increasing order
+    ends: Vec<usize>,
+}
+
+impl<T> RleData<T> {
+    /// Run-length encodes the values of `data`. Consecutive values are merged into a single run
+    /// if they are equal, or if they are both missing (NA).
+    pub fn encode<DI>(data: &DI) -> RleData<T>
+    where
+        DI: DataIndex<DType = T>,
+        T: PartialEq + Clone,
+    {
+        let mut values: Vec<Value<T>> = vec![];
+        let mut ends = vec![];
+        for (idx, value) in data.iter().enumerate() {
+            match values.last() {
+                Some(last) if last.as_ref() == value => {
+                    *ends.last_mut().unwrap() = idx + 1;
+                    continue;
+                }
+                _ => {}
+            }
+            values.push(value.cloned());
+            ends.push(idx + 1);
+        }
+        RleData { values, ends }
+    }
+
+    /// Returns the number of runs in this field.
+    pub fn nruns(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns an iterator over the runs in this field, yielding each run's value along with the
+    /// range of indices the run covers.
+    pub fn runs(&self) -> Runs<T> {
+        Runs { data: self, run: 0 }
+    }
+
+    /// Decodes this field into a new [FieldData](../field/struct.FieldData.html).
+    pub fn decode(&self) -> FieldData<T>
+    where
+        T: Debug + Default + Clone,
+    {
+        self.runs()
+            .flat_map(|(value, range)| range.map(move |_| value))
+            .collect()
+    }
+
+    /// Groups the indices of this field by value. Returns the distinct values of this field (in
+    /// order of first appearance), along with the indices of the records with each value. Missing
+    /// (NA) values form their own group.
+    ///
+    /// Each run is looked up only once, so this is faster than grouping record-by-record when
+    /// this field has long runs.
+    pub fn group_indices(&self) -> (Vec<Value<&T>>, Vec<Vec<usize>>)
+    where
+        T: Hash + Eq,
+    {
+        let mut keys = vec![];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        let mut group_idxs = HashMap::new();
+        for (value, range) in self.runs() {
+            let group_idx = *group_idxs.entry(value).or_insert_with(|| {
+                keys.push(value);
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[group_idx].extend(range);
+        }
+        (keys, groups)
+    }
+
+    /// Computes the indices of the matching records of an equality join between this field and
+    /// `other`. Returns a pair of vectors of equal length, containing the indices into this field
+    /// and into `other` of each matching pair of records, ordered by their index into this field
+    /// (and then by their index into `other`). Missing (NA) values do not match any values.
+    ///
+    /// Matches are found run-by-run, so this is faster than joining record-by-record when these
+    /// fields have long runs.
+    pub fn join_indices(&self, other: &RleData<T>) -> (Vec<usize>, Vec<usize>)
+    where
+        T: Hash + Eq,
+    {
+        let mut other_runs: HashMap<&T, Vec<Range<usize>>> = HashMap::new();
+        for (value, range) in other.runs() {
+            if let Value::Exists(value) = value {
+                other_runs.entry(value).or_insert_with(Vec::new).push(range);
+            }
+        }
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        for (value, range) in self.runs() {
+            let matching = match value {
+                Value::Exists(value) => match other_runs.get(value) {
+                    Some(matching) => matching,
+                    None => continue,
+                },
+                Value::Na => continue,
+            };
+            for left_idx in range {
+                for right_range in matching {
+                    for right_idx in right_range.clone() {
+                        left_indices.push(left_idx);
+                        right_indices.push(right_idx);
+                    }
+                }
+            }
+        }
+        (left_indices, right_indices)
+    }
+}
+
+impl<T> DataIndex for RleData<T>
+where
+    T: Debug,
+{
+    type DType = T;
+
+    fn get_datum(&self, idx: usize) -> Result<Value<&T>> {
+        if idx >= self.len() {
+            return Err(AgnesError::IndexError {
+                index: idx,
+                len: self.len(),
+                field: None,
+            });
+        }
+        // find the first run which ends after `idx`
+        let run = match self.ends.binary_search(&idx) {
+            Ok(run) => run + 1,
+            Err(run) => run,
+        };
+        Ok(self.values[run].as_ref())
+    }
+    fn len(&self) -> usize {
+        self.ends.last().cloned().unwrap_or(0)
+    }
+    fn count_na(&self) -> usize {
+        self.runs()
+            .filter(|&(ref value, _)| value.is_na())
+            .map(|(_, range)| range.len())
+            .sum()
+    }
+}
+
+/// Iterator over the runs of an [RleData](struct.RleData.html), created with
+/// [runs](struct.RleData.html#method.runs).
+#[derive(Debug)]
+pub struct Runs<'a, T: 'a> {
+    data: &'a RleData<T>,
+    run: usize,
+}
+
+impl<'a, T> Iterator for Runs<'a, T> {
+    type Item = (Value<&'a T>, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.run >= self.data.values.len() {
+            return None;
+        }
+        let start = if self.run == 0 {
+            0
+        } else {
+            self.data.ends[self.run - 1]
+        };
+        let item = (
+            self.data.values[self.run].as_ref(),
+            start..self.data.ends[self.run],
+        );
+        self.run += 1;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let data = FieldData::from_field_vec(vec![
+            Value::Exists(1u64),
+            Value::Exists(1),
+            Value::Na,
+            Value::Na,
+            Value::Exists(3),
+            Value::Exists(1),
+            Value::Exists(1),
+            Value::Exists(1),
+        ]);
+        let rle = RleData::encode(&data);
+        assert_eq!(rle.nruns(), 4);
+        assert_eq!(rle.len(), 8);
+        assert_eq!(rle.count_na(), 2);
+        assert_eq!(
+            rle.runs()
+                .map(|(value, range)| (value.cloned(), range))
+                .collect::<Vec<_>>(),
+            vec![
+                (Value::Exists(1), 0..2),
+                (Value::Na, 2..4),
+                (Value::Exists(3), 4..5),
+                (Value::Exists(1), 5..8),
+            ]
+        );
+        assert_eq!(rle.to_value_vec(), data.to_value_vec());
+        assert_eq!(rle.decode().to_value_vec(), data.to_value_vec());
+        assert_eq!(rle.get_datum(4).unwrap(), Value::Exists(&3));
+        assert!(rle.get_datum(8).is_err());
+
+        let empty = RleData::encode(&FieldData::<u64>::default());
+        assert_eq!((empty.nruns(), empty.len()), (0, 0));
+        assert!(empty.get_datum(0).is_err());
+    }
+
+    #[test]
+    fn group_join() {
+        let keys = RleData::encode(&FieldData::from_field_vec(vec![
+            Value::Exists("b"),
+            Value::Exists("b"),
+            Value::Na,
+            Value::Exists("a"),
+            Value::Exists("b"),
+        ]));
+        let (values, groups) = keys.group_indices();
+        assert_eq!(
+            values,
+            vec![Value::Exists(&"b"), Value::Na, Value::Exists(&"a")]
+        );
+        assert_eq!(groups, vec![vec![0, 1, 4], vec![2], vec![3]]);
+
+        let other = RleData::encode(&FieldData::from_field_vec(vec![
+            Value::Exists("a"),
+            Value::Na,
+            Value::Exists("b"),
+            Value::Exists("b"),
+            Value::Exists("c"),
+        ]));
+        assert_eq!(
+            keys.join_indices(&other),
+            (vec![0, 0, 1, 1, 3, 4, 4], vec![2, 3, 2, 3, 0, 2, 3])
+        );
+    }
+}
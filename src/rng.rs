@@ -0,0 +1,85 @@
+/*!
+Seedable random number generation for stochastic operations.
+
+All randomized operations in this crate (such as sampling the records of each group with
+[GroupBy::sample](../groupby/struct.GroupBy.html#method.sample), assigning records to buckets with
+[assign_buckets](../view/struct.DataView.html#method.assign_buckets), or sampling CSV records
+with [with_sample](../source/csv/struct.CsvReader.html#method.with_sample)) take an optional
+`seed` parameter, and draw their random numbers from a [SeededRng](type.SeededRng.html) created
+with [seeded_rng](fn.seeded_rng.html). The same seed always produces the same results, so these
+operations are reproducible.
+
+Pipelines which don't need separate seeds for each operation can instead pass `None`, which uses
+the crate-level [default_seed](fn.default_seed.html) (as does [default_rng](fn.default_rng.html)),
+and change it in a single place with [set_default_seed](fn.set_default_seed.html).
+*/
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rand::prng::XorShiftRng;
+use rand::SeedableRng;
+
+/// The random number generator used by stochastic operations.
+pub type SeededRng = XorShiftRng;
+
+/// Initial value of the crate-level [default_seed](fn.default_seed.html).
+pub const DEFAULT_SEED: u64 = 0;
+
+static CURRENT_DEFAULT_SEED: AtomicU64 = AtomicU64::new(DEFAULT_SEED);
+
+/// Creates a random number generator whose output is determined entirely by `seed`.
+pub fn seeded_rng(seed: u64) -> SeededRng {
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        // spread the seed over both halves of the generator seed (and avoid an all-zero seed)
+        let word = if i < 8 {
+            seed
+        } else {
+            !seed ^ 0x9E37_79B9_7F4A_7C15
+        };
+        *byte = (word >> (8 * (i % 8))) as u8;
+    }
+    XorShiftRng::from_seed(bytes)
+}
+
+/// Returns the crate-level default seed (initially [DEFAULT_SEED](constant.DEFAULT_SEED.html)).
+pub fn default_seed() -> u64 {
+    CURRENT_DEFAULT_SEED.load(Ordering::SeqCst)
+}
+
+/// Sets the crate-level default seed returned by [default_seed](fn.default_seed.html). This
+/// setting is shared by all threads.
+pub fn set_default_seed(seed: u64) {
+    CURRENT_DEFAULT_SEED.store(seed, Ordering::SeqCst);
+}
+
+/// Creates a random number generator seeded with the crate-level
+/// [default_seed](fn.default_seed.html).
+pub fn default_rng() -> SeededRng {
+    seeded_rng(default_seed())
+}
+
+// creates the random number generator for a stochastic operation with an optional `seed`, falling
+// back to the crate-level default seed
+pub(crate) fn operation_rng(seed: Option<u64>) -> SeededRng {
+    seed.map_or_else(default_rng, seeded_rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn reproducible() {
+        let draws = |mut rng: SeededRng| (0..10).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+        assert_eq!(draws(seeded_rng(42)), draws(seeded_rng(42)));
+        assert_ne!(draws(seeded_rng(42)), draws(seeded_rng(43)));
+        // a zero seed is valid
+        assert_ne!(draws(seeded_rng(0)), vec![0; 10]);
+
+        // the default seed is left unchanged, since it is shared with concurrently-running tests
+        assert_eq!(draws(default_rng()), draws(seeded_rng(default_seed())));
+        assert_eq!(draws(operation_rng(None)), draws(default_rng()));
+        assert_eq!(draws(operation_rng(Some(42))), draws(seeded_rng(42)));
+    }
+}
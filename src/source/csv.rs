@@ -1,19 +1,30 @@
 //! CSV-based source and reader objects and implentation.
 
-use std::collections::HashMap;
-use std::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io::Read;
+use std::iter;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use csv::{ByteRecord, Reader, StringRecord};
 use csv_sniffer::metadata::Metadata;
-use csv_sniffer::Sniffer;
+use csv_sniffer::{Sniffer, Type};
+use rand::Rng;
 
 use cons::*;
+use datetime::{DateTime, EpochUnit};
 use error::*;
 use field::FieldIdent;
-use fieldlist::{FieldDesignator, FieldPayloadCons, FieldSchema, SchemaCons};
+use fieldlist::{
+    Combine, CombinedSchemaCons, FieldDesignator, FieldPayloadCons, FieldRules, FieldSchema,
+    RuledSchemaCons, SchemaCons, StackNamesSchemaCons, StackedSchemaCons, TimestampSchemaCons,
+};
 use frame::SimpleFrameFields;
-use label::{TypedValue, Valued};
+use label::{LabelName, SelfValued, TypedValue, Valued};
+use query::{Comparison, Query};
+use rng::operation_rng;
 use source::decode::decode;
 use source::file::{FileLocator, LocalFileReader, Uri};
 use store::{AssocFrameLookup, AssocStorage, DataStore, IntoView, PushFrontFromValueIter};
@@ -21,14 +32,35 @@ use value::Value;
 
 /// CSV Data source. Contains location of data file, and computes CSV metadata. Can be turned into
 /// `CsvReader` object.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct CsvSource {
     // File source object for the CSV file
     src: FileLocator,
     // CSV file metadata (from `csv-sniffer` crate)
     metadata: Metadata,
+    // number of header rows in the CSV file
+    header_rows: usize,
+    // number of records in the CSV file, or 0 if not yet known (used to preallocate loaded
+    // fields)
+    nrecords: AtomicUsize,
+}
+
+impl Clone for CsvSource {
+    fn clone(&self) -> CsvSource {
+        CsvSource {
+            src: self.src.clone(),
+            metadata: self.metadata.clone(),
+            header_rows: self.header_rows,
+            nrecords: AtomicUsize::new(self.nrecords.load(Ordering::Relaxed)),
+        }
+    }
 }
 
+/// Separator between the values of a column in each header row of a CSV source with multiple
+/// header rows, used to form the column's name (see
+/// [with_header_rows](struct.CsvSource.html#method.with_header_rows)).
+pub const HEADER_LEVEL_SEPARATOR: &str = ".";
+
 impl CsvSource {
     /// Create a new `CsvSource` object with provided file location. This constructor will analyze
     /// (sniff) the file to detect its metadata (delimiter, quote character, preamble, etc.)
@@ -41,18 +73,350 @@ impl CsvSource {
         let mut file_reader = LocalFileReader::new(&loc)?;
         let metadata = Sniffer::new().sniff_reader(&mut file_reader)?;
 
-        Ok(CsvSource { src: loc, metadata })
+        let header_rows = if metadata.dialect.header.has_header_row {
+            1
+        } else {
+            0
+        };
+        Ok(CsvSource {
+            src: loc,
+            metadata,
+            header_rows,
+            nrecords: AtomicUsize::new(0),
+        })
+    }
+
+    /// Declares the number of header rows at the start of this CSV source (after any preamble),
+    /// overriding whether a header row was detected when the file was analyzed.
+    ///
+    /// Files with multiple header rows have hierarchical column headers, such as a category row
+    /// above a year row. Each column is then named by its (non-empty) values in each header row,
+    /// separated by [HEADER_LEVEL_SEPARATOR](constant.HEADER_LEVEL_SEPARATOR.html) (e.g.
+    /// `"GDP.2015"`), and designated by this name in a [schema](../../macro.schema.html). Empty
+    /// values in all but the last header row are taken from the previous column, since category
+    /// headers commonly only appear above the first column of the category. The values of each
+    /// column in each header row are available from [columns](#method.columns), as
+    /// [CsvColumn::levels](struct.CsvColumn.html#structfield.levels); loaded fields are only
+    /// identified by their label and combined name, and do not carry this hierarchy.
+    ///
+    /// Column data types are sniffed when the file is analyzed by [new](#method.new), before the
+    /// number of header rows is declared, so header rows after the first are sniffed as data.
+    /// The sniffed types reported by [columns](#method.columns) may therefore be more general
+    /// (e.g. text instead of integer) than the values below the headers. Loaded fields are parsed
+    /// according to the types declared in the schema, and are unaffected.
+    pub fn with_header_rows(mut self, header_rows: usize) -> CsvSource {
+        self.metadata.dialect.header.has_header_row = header_rows > 0;
+        self.header_rows = header_rows;
+        self
     }
     /// Return the compute `Metadata` for this CSV source.
     pub fn metadata(&self) -> &Metadata {
         &self.metadata
     }
+
+    /// Returns the name and sniffed data type of each column of this CSV source, in order. If the
+    /// file does not have a header row, columns are named by their index (`"0"`, `"1"`, etc.).
+    ///
+    /// # Error
+    /// Fails if unable to open the file or read its header rows.
+    pub fn columns(&self) -> Result<Vec<CsvColumn>> {
+        let levels = match self.header_levels()? {
+            Some(levels) => levels,
+            None => (0..self.metadata.num_fields)
+                .map(|idx| vec![idx.to_string()])
+                .collect(),
+        };
+        Ok(levels
+            .into_iter()
+            .zip(self.metadata.types.iter().cloned())
+            .map(|(levels, ty)| CsvColumn {
+                name: header_name(&levels),
+                ty,
+                levels,
+            })
+            .collect())
+    }
+
+    /// Compares the columns of this CSV source against `expected` (for example, the
+    /// [columns](#method.columns) of a previously-loaded version of the file), reporting columns
+    /// which were added or removed, columns whose sniffed data type changed, and whether the
+    /// columns were reordered. This can be used to detect changes to the structure of a file
+    /// before its data is loaded.
+    ///
+    /// # Error
+    /// Fails if unable to open the file or read its header row.
+    pub fn schema_drift(&self, expected: &[CsvColumn]) -> Result<SchemaDrift> {
+        Ok(SchemaDrift::compare(expected, &self.columns()?))
+    }
+
+    // reads the header rows of this CSV source (if any), returning the values of each column in
+    // each header row
+    fn header_levels(&self) -> Result<Option<Vec<Vec<String>>>> {
+        if !self.metadata.dialect.header.has_header_row {
+            return Ok(None);
+        }
+        let file_reader = LocalFileReader::new(&self.src)?;
+        let mut csv_reader = self.metadata.dialect.open_reader(file_reader)?;
+        let mut rows = vec![csv_reader.headers()?.clone()];
+        for _ in 1..self.header_rows {
+            let mut row = StringRecord::new();
+            if !csv_reader.read_record(&mut row)? {
+                return Err(AgnesError::CsvDialect(format!(
+                    "expected {} header rows in CSV file",
+                    self.header_rows
+                )));
+            }
+            rows.push(row);
+        }
+
+        let ncolumns = rows[0].len();
+        let mut levels = vec![vec![]; ncolumns];
+        for (row_idx, row) in rows.iter().enumerate() {
+            let is_last_row = row_idx + 1 == rows.len();
+            let mut prev = "";
+            for (idx, column_levels) in levels.iter_mut().enumerate() {
+                let mut value = row.get(idx).unwrap_or("");
+                // fill in empty category headers from the previous column
+                if value.is_empty() && !is_last_row {
+                    value = prev;
+                }
+                prev = value;
+                column_levels.push(value.to_string());
+            }
+        }
+        Ok(Some(levels))
+    }
+
+    // opens a reader of the records of this CSV source, skipping any header rows
+    fn open_reader(&self) -> Result<Reader<LocalFileReader>> {
+        let file_reader = LocalFileReader::new(&self.src)?;
+        // the first header row (if any) is skipped by the reader itself
+        let mut csv_reader = self.metadata.dialect.open_reader(file_reader)?;
+        let mut record = ByteRecord::new();
+        for _ in 1..self.header_rows {
+            csv_reader.read_byte_record(&mut record)?;
+        }
+        Ok(csv_reader)
+    }
+}
+
+// forms the name of a column from its values in each header row
+fn header_name(levels: &[String]) -> String {
+    levels
+        .iter()
+        .filter(|level| !level.is_empty())
+        .map(|level| level.as_str())
+        .collect::<Vec<_>>()
+        .join(HEADER_LEVEL_SEPARATOR)
+}
+
+/// Name and (sniffed) data type of a column in a CSV source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvColumn {
+    /// Column name (from the header row, or the column index if there is no header row).
+    pub name: String,
+    /// Data type of the column.
+    pub ty: Type,
+    /// Values of the column in each header row of the CSV source (see
+    /// [with_header_rows](struct.CsvSource.html#method.with_header_rows)). Contains only the
+    /// column name for sources with a single header row (or no header row).
+    pub levels: Vec<String>,
+}
+
+impl CsvColumn {
+    /// Creates a new `CsvColumn` with name `name` and data type `ty`.
+    pub fn new<S: Into<String>>(name: S, ty: Type) -> CsvColumn {
+        let name = name.into();
+        CsvColumn {
+            levels: vec![name.clone()],
+            name,
+            ty,
+        }
+    }
+}
+
+/// Differences between the expected columns of a CSV source and its actual columns, as computed
+/// by [schema_drift](struct.CsvSource.html#method.schema_drift).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDrift {
+    /// Names of columns which exist in the CSV source but were not expected, in file order.
+    pub added: Vec<String>,
+    /// Names of expected columns which do not exist in the CSV source, in expected order.
+    pub removed: Vec<String>,
+    /// Columns whose data type differs from the expected data type, as (name, expected type,
+    /// actual type), in file order.
+    pub type_changes: Vec<(String, Type, Type)>,
+    /// Whether the columns which exist in both are in a different order than expected.
+    pub reordered: bool,
+}
+
+impl SchemaDrift {
+    // computes the differences between the `expected` and `actual` columns
+    fn compare(expected: &[CsvColumn], actual: &[CsvColumn]) -> SchemaDrift {
+        let expected_types = expected
+            .iter()
+            .map(|column| (column.name.as_str(), &column.ty))
+            .collect::<HashMap<_, _>>();
+        let actual_names = actual
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect::<HashSet<_>>();
+
+        let mut added = vec![];
+        let mut type_changes = vec![];
+        for column in actual {
+            match expected_types.get(column.name.as_str()) {
+                Some(&expected_ty) => {
+                    if *expected_ty != column.ty {
+                        type_changes.push((
+                            column.name.clone(),
+                            expected_ty.clone(),
+                            column.ty.clone(),
+                        ));
+                    }
+                }
+                None => added.push(column.name.clone()),
+            }
+        }
+        let removed = expected
+            .iter()
+            .filter(|column| !actual_names.contains(column.name.as_str()))
+            .map(|column| column.name.clone())
+            .collect();
+
+        // compare the order of the columns which exist in both
+        let common_expected = expected
+            .iter()
+            .map(|column| column.name.as_str())
+            .filter(|name| actual_names.contains(name));
+        let common_actual = actual
+            .iter()
+            .map(|column| column.name.as_str())
+            .filter(|name| expected_types.contains_key(name));
+        let reordered = !common_expected.eq(common_actual);
+
+        SchemaDrift {
+            added,
+            removed,
+            type_changes,
+            reordered,
+        }
+    }
+
+    /// Returns `true` if the CSV source's columns match the expected columns exactly.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.type_changes.is_empty()
+            && !self.reordered
+    }
+}
+
+impl Display for SchemaDrift {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no schema changes");
+        }
+        for name in &self.added {
+            writeln!(f, "added column: {}", name)?;
+        }
+        for name in &self.removed {
+            writeln!(f, "removed column: {}", name)?;
+        }
+        for &(ref name, ref expected, ref actual) in &self.type_changes {
+            writeln!(
+                f,
+                "changed column type: {} ({:?} -> {:?})",
+                name, expected, actual
+            )?;
+        }
+        if self.reordered {
+            writeln!(f, "columns reordered")?;
+        }
+        Ok(())
+    }
 }
 
 /// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
 /// index information of a CSV data source.
 pub type CsvSrcSchemaCons<Label, DType, Tail> = FieldPayloadCons<Label, DType, usize, Tail>;
 
+/// Source index and timestamp unit of a CSV column containing Unix timestamps.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvTimestampColumn {
+    /// Column index in the CSV data source.
+    pub idx: usize,
+    /// Unit of the timestamps in the column.
+    pub unit: EpochUnit,
+}
+impl SelfValued for CsvTimestampColumn {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// column information of a CSV column containing Unix timestamps.
+pub type CsvTimestampSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CsvTimestampColumn, Tail>;
+
+/// Source index and cleaning rules of a CSV column.
+#[derive(Debug, Clone)]
+pub struct CsvRuledColumn<DType> {
+    /// Column index in the CSV data source.
+    pub idx: usize,
+    /// Rules to apply to the values of the column.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for CsvRuledColumn<DType> {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// column information of a CSV column with cleaning rules.
+pub type CsvRuledSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CsvRuledColumn<DType>, Tail>;
+
+/// Source indices, combination method, and cleaning rules of a field combined from multiple CSV
+/// columns.
+#[derive(Debug, Clone)]
+pub struct CsvCombinedColumns<DType> {
+    /// Column indices in the CSV data source.
+    pub idxs: Vec<usize>,
+    /// Method of combining the values of the columns.
+    pub combine: Combine,
+    /// Rules to apply to the combined values.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for CsvCombinedColumns<DType> {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// column information of a field combined from multiple CSV columns.
+pub type CsvCombinedSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CsvCombinedColumns<DType>, Tail>;
+
+/// Source indices and cleaning rules of a field stacked from multiple CSV columns.
+#[derive(Debug, Clone)]
+pub struct CsvStackedColumns<DType> {
+    /// Column indices in the CSV data source.
+    pub idxs: Vec<usize>,
+    /// Rules to apply to the stacked values.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for CsvStackedColumns<DType> {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// column information of a field stacked from multiple CSV columns.
+pub type CsvStackedSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CsvStackedColumns<DType>, Tail>;
+
+/// Names of the CSV columns of a stacked field.
+#[derive(Debug, Clone)]
+pub struct CsvStackNames {
+    /// Column names (from the header row, or the column index if there is no header row).
+    pub names: Vec<String>,
+}
+impl SelfValued for CsvStackNames {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and
+/// source column names of a field holding the column names of a stacked field.
+pub type CsvStackNamesSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, CsvStackNames, Tail>;
+
 /// A trait for converting an object into a [CsvSrcSchemaCons](type.CsvSrcSchemaCons.html).
 pub trait IntoCsvSrcSchema {
     /// Resultant `CsvSrcSchemaCons` object.
@@ -90,20 +454,7 @@ where
         headers: &HashMap<String, usize>,
         num_fields: usize,
     ) -> Result<CsvSrcSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
-        let idx = match *self.head.value_ref() {
-            FieldDesignator::Expr(ref s) => *headers
-                .get(s)
-                .ok_or(AgnesError::FieldNotFound(FieldIdent::Name(s.to_string())))?,
-            FieldDesignator::Idx(idx) => {
-                if idx >= num_fields {
-                    return Err(AgnesError::IndexError {
-                        index: idx,
-                        len: num_fields,
-                    });
-                };
-                idx
-            }
-        };
+        let idx = source_index(self.head.value_ref(), headers, num_fields)?;
         Ok(Cons {
             head: TypedValue::from(idx).into(),
             tail: self.tail.into_csv_src_schema(headers, num_fields)?,
@@ -111,20 +462,204 @@ where
     }
 }
 
+impl<Label, DType, Tail> IntoCsvSrcSchema for TimestampSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoCsvSrcSchema,
+{
+    type CsvSrcSchema = CsvTimestampSchemaCons<Label, DType, Tail::CsvSrcSchema>;
+
+    fn into_csv_src_schema(
+        self,
+        headers: &HashMap<String, usize>,
+        num_fields: usize,
+    ) -> Result<CsvTimestampSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
+        let designator = self.head.value_ref();
+        let column = CsvTimestampColumn {
+            idx: source_index(&designator.designator, headers, num_fields)?,
+            unit: designator.unit,
+        };
+        Ok(Cons {
+            head: TypedValue::from(column).into(),
+            tail: self.tail.into_csv_src_schema(headers, num_fields)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoCsvSrcSchema for RuledSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoCsvSrcSchema,
+    DType: Clone,
+{
+    type CsvSrcSchema = CsvRuledSchemaCons<Label, DType, Tail::CsvSrcSchema>;
+
+    fn into_csv_src_schema(
+        self,
+        headers: &HashMap<String, usize>,
+        num_fields: usize,
+    ) -> Result<CsvRuledSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
+        let designator = self.head.value_ref();
+        let column = CsvRuledColumn {
+            idx: source_index(&designator.designator, headers, num_fields)?,
+            rules: designator.rules.clone(),
+        };
+        Ok(Cons {
+            head: TypedValue::from(column).into(),
+            tail: self.tail.into_csv_src_schema(headers, num_fields)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoCsvSrcSchema for CombinedSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoCsvSrcSchema,
+    DType: Clone,
+{
+    type CsvSrcSchema = CsvCombinedSchemaCons<Label, DType, Tail::CsvSrcSchema>;
+
+    fn into_csv_src_schema(
+        self,
+        headers: &HashMap<String, usize>,
+        num_fields: usize,
+    ) -> Result<CsvCombinedSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
+        let designator = self.head.value_ref();
+        let columns = CsvCombinedColumns {
+            idxs: designator
+                .designators
+                .iter()
+                .map(|designator| source_index(designator, headers, num_fields))
+                .collect::<Result<_>>()?,
+            combine: designator.combine.clone(),
+            rules: designator.rules.clone(),
+        };
+        Ok(Cons {
+            head: TypedValue::from(columns).into(),
+            tail: self.tail.into_csv_src_schema(headers, num_fields)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoCsvSrcSchema for StackedSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoCsvSrcSchema,
+    DType: Clone,
+{
+    type CsvSrcSchema = CsvStackedSchemaCons<Label, DType, Tail::CsvSrcSchema>;
+
+    fn into_csv_src_schema(
+        self,
+        headers: &HashMap<String, usize>,
+        num_fields: usize,
+    ) -> Result<CsvStackedSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
+        let designator = self.head.value_ref();
+        let columns = CsvStackedColumns {
+            idxs: designator
+                .designators
+                .iter()
+                .map(|designator| source_index(designator, headers, num_fields))
+                .collect::<Result<_>>()?,
+            rules: designator.rules.clone(),
+        };
+        Ok(Cons {
+            head: TypedValue::from(columns).into(),
+            tail: self.tail.into_csv_src_schema(headers, num_fields)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoCsvSrcSchema for StackNamesSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoCsvSrcSchema,
+{
+    type CsvSrcSchema = CsvStackNamesSchemaCons<Label, DType, Tail::CsvSrcSchema>;
+
+    fn into_csv_src_schema(
+        self,
+        headers: &HashMap<String, usize>,
+        num_fields: usize,
+    ) -> Result<CsvStackNamesSchemaCons<Label, DType, Tail::CsvSrcSchema>> {
+        let names = self
+            .head
+            .value_ref()
+            .designators
+            .iter()
+            .map(|designator| {
+                let idx = source_index(designator, headers, num_fields)?;
+                Ok(match *designator {
+                    FieldDesignator::Expr(ref name) => name.clone(),
+                    // use the header name of the indexed column, if any
+                    FieldDesignator::Idx(_) => headers
+                        .iter()
+                        .find(|&(_, &header_idx)| header_idx == idx)
+                        .map(|(name, _)| name.clone())
+                        .unwrap_or_else(|| idx.to_string()),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Cons {
+            head: TypedValue::from(CsvStackNames { names }).into(),
+            tail: self.tail.into_csv_src_schema(headers, num_fields)?,
+        })
+    }
+}
+
+// finds the column index in a CSV file of the column designated by `designator`
+fn source_index(
+    designator: &FieldDesignator,
+    headers: &HashMap<String, usize>,
+    num_fields: usize,
+) -> Result<usize> {
+    match *designator {
+        FieldDesignator::Expr(ref s) => headers
+            .get(s)
+            .cloned()
+            .ok_or(AgnesError::FieldNotFound(FieldIdent::from(s.as_str()))),
+        FieldDesignator::Idx(idx) => {
+            if idx >= num_fields {
+                return Err(AgnesError::IndexError {
+                    index: idx,
+                    len: num_fields,
+                    field: None,
+                });
+            };
+            Ok(idx)
+        }
+    }
+}
+
 /// A trait for building a [DataStore](../../store/struct.DataStore.html) from a
 /// [CsvSrcSchemaCons](type.CsvSrcSchemaCons.html).
 pub trait BuildDStore {
     /// `Fields` type parameter of the resultant `DataStore`.
     type OutputFields: AssocStorage;
 
-    /// Builds a `DataStore` from the source schema (`self`) and a CSV source `src`.
-    fn build(&mut self, src: &CsvSource) -> Result<DataStore<Self::OutputFields>>;
+    /// Builds a `DataStore` from the source schema (`self`) and a CSV source `src`. If `rows` is
+    /// provided, only records whose corresponding entry in `rows` is `true` are loaded. Each
+    /// loaded record results in `stack` records in the `DataStore` (see
+    /// [stack_len](#tymethod.stack_len)).
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>>;
+
+    /// Returns the number of CSV columns stacked by the stacked fields of the source schema
+    /// (`self`), or `None` if the source schema does not have any stacked fields.
+    fn stack_len(&self) -> Option<usize>;
 }
 impl BuildDStore for Nil {
     type OutputFields = Nil;
-    fn build(&mut self, _src: &CsvSource) -> Result<DataStore<Nil>> {
+    fn build(
+        &mut self,
+        _src: &CsvSource,
+        _rows: Option<&[bool]>,
+        _stack: usize,
+    ) -> Result<DataStore<Nil>> {
         Ok(DataStore::<Nil>::empty())
     }
+    fn stack_len(&self) -> Option<usize> {
+        None
+    }
 }
 impl<Label, DType, Tail> BuildDStore for CsvSrcSchemaCons<Label, DType, Tail>
 where
@@ -141,38 +676,338 @@ where
         DType,
     >>::OutputFields;
 
-    fn build(&mut self, src: &CsvSource) -> Result<DataStore<Self::OutputFields>> {
-        let file_reader = LocalFileReader::new(&src.src)?;
-        let mut csv_reader = src.metadata.dialect.open_reader(file_reader)?;
-        let ds = self.tail.build(src)?;
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let values = read_column(src, rows, stack, *self.head.value_ref().value_ref(), |s| {
+            s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()))
+        })?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+
+    fn stack_len(&self) -> Option<usize> {
+        self.tail.stack_len()
+    }
+}
+impl<Label, Tail> BuildDStore for CsvTimestampSchemaCons<Label, DateTime, Tail>
+where
+    Tail: BuildDStore,
+    DataStore<<Tail as BuildDStore>::OutputFields>: PushFrontFromValueIter<Label, DateTime>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DateTime>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DateTime>>>::Output: AssocStorage,
+    Label: Debug,
+{
+    type OutputFields = <DataStore<<Tail as BuildDStore>::OutputFields> as PushFrontFromValueIter<
+        Label,
+        DateTime,
+    >>::OutputFields;
+
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let column = *self.head.value_ref().value_ref();
+        let values = read_column(src, rows, stack, column.idx, |s| {
+            Ok(DateTime::from_epoch(s.parse::<i64>()?, column.unit))
+        })?;
+        let ds = ds.push_front_from_value_iter::<Label, DateTime, _, _>(values);
+
+        Ok(ds)
+    }
+
+    fn stack_len(&self) -> Option<usize> {
+        self.tail.stack_len()
+    }
+}
+
+impl<Label, DType, Tail> BuildDStore for CsvRuledSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildDStore,
+    DataStore<<Tail as BuildDStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug + LabelName,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields = <DataStore<<Tail as BuildDStore>::OutputFields> as PushFrontFromValueIter<
+        Label,
+        DType,
+    >>::OutputFields;
+
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let column = self.head.value_ref().value_ref();
+        let values = read_column(src, rows, stack, column.idx, |s| {
+            s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()))
+        })?;
+        let values = column.rules.apply(Label::name(), values)?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
 
-        let values: Vec<Value<DType>> = csv_reader
-            .byte_records()
-            .map(|row| {
-                let record = row?;
-                let value = decode(record.get(*self.head.value_ref().value_ref()).ok_or_else(
-                    || AgnesError::FieldNotFound(FieldIdent::Name(stringify![Field].to_string())),
-                )?)?;
-                Ok(value)
-            })
-            .map(|sresult| {
-                sresult.and_then(|s| {
-                    let trimmed = s.trim();
-                    if trimmed.is_empty() {
-                        Ok(Value::Na)
-                    } else {
-                        trimmed
-                            .parse::<DType>()
-                            .map(|value| Value::Exists(value))
-                            .map_err(|e| AgnesError::Parse(e.into()))
-                    }
+        Ok(ds)
+    }
+
+    fn stack_len(&self) -> Option<usize> {
+        self.tail.stack_len()
+    }
+}
+
+impl<Label, DType, Tail> BuildDStore for CsvCombinedSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildDStore,
+    DataStore<<Tail as BuildDStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug + LabelName,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields = <DataStore<<Tail as BuildDStore>::OutputFields> as PushFrontFromValueIter<
+        Label,
+        DType,
+    >>::OutputFields;
+
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let columns = self.head.value_ref().value_ref();
+        let values = read_values(
+            src,
+            rows,
+            stack,
+            |record| {
+                let cells = columns
+                    .idxs
+                    .iter()
+                    .map(|&idx| read_cell(record, idx).map(|cell| cell.trim().to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(columns
+                    .combine
+                    .combine(cells.iter().map(|cell| cell.as_str())))
+            },
+            |s| s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into())),
+        )?;
+        let values = columns.rules.apply(Label::name(), values)?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+
+    fn stack_len(&self) -> Option<usize> {
+        self.tail.stack_len()
+    }
+}
+
+impl<Label, DType, Tail> BuildDStore for CsvStackedSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildDStore,
+    DataStore<<Tail as BuildDStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug + LabelName,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields = <DataStore<<Tail as BuildDStore>::OutputFields> as PushFrontFromValueIter<
+        Label,
+        DType,
+    >>::OutputFields;
+
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let columns = self.head.value_ref().value_ref();
+        if columns.idxs.len() != stack {
+            return Err(AgnesError::LengthMismatch {
+                expected: stack,
+                actual: columns.idxs.len(),
+            });
+        }
+        let parse = |s: &str| s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()));
+        let values = read_records(src, rows, stack, |record, values| {
+            for &idx in &columns.idxs {
+                values.push(parse_value(&read_cell(record, idx)?, &parse)?);
+            }
+            Ok(())
+        })?;
+        let values = columns.rules.apply(Label::name(), values)?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+
+    fn stack_len(&self) -> Option<usize> {
+        Some(self.head.value_ref().value_ref().idxs.len())
+    }
+}
+
+impl<Label, DType, Tail> BuildDStore for CsvStackNamesSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildDStore,
+    DataStore<<Tail as BuildDStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields = <DataStore<<Tail as BuildDStore>::OutputFields> as PushFrontFromValueIter<
+        Label,
+        DType,
+    >>::OutputFields;
+
+    fn build(
+        &mut self,
+        src: &CsvSource,
+        rows: Option<&[bool]>,
+        stack: usize,
+    ) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src, rows, stack)?;
+        let column = self.head.value_ref().value_ref();
+        if column.names.len() != stack {
+            return Err(AgnesError::LengthMismatch {
+                expected: stack,
+                actual: column.names.len(),
+            });
+        }
+        let names = column
+            .names
+            .iter()
+            .map(|name| {
+                parse_value(name, &|s: &str| {
+                    s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()))
                 })
             })
-            .collect::<Result<_>>()?;
+            .collect::<Result<Vec<_>>>()?;
+        let values = read_records(src, rows, stack, |_, values| {
+            values.extend(names.iter().cloned());
+            Ok(())
+        })?;
         let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
 
         Ok(ds)
     }
+
+    fn stack_len(&self) -> Option<usize> {
+        Some(self.head.value_ref().value_ref().names.len())
+    }
+}
+
+// reads the values of the column with index `idx` from the CSV source `src` (skipping records
+// whose corresponding entry in `rows` is `false`, if provided), using `parse` to parse each
+// non-empty value and repeating each value `stack` times
+fn read_column<T, F>(
+    src: &CsvSource,
+    rows: Option<&[bool]>,
+    stack: usize,
+    idx: usize,
+    parse: F,
+) -> Result<Vec<Value<T>>>
+where
+    T: Clone,
+    F: Fn(&str) -> Result<T>,
+{
+    read_values(src, rows, stack, |record| read_cell(record, idx), parse)
+}
+
+// decodes the value of the column with index `idx` in `record`
+fn read_cell(record: &ByteRecord, idx: usize) -> Result<String> {
+    decode(
+        record
+            .get(idx)
+            .ok_or_else(|| AgnesError::FieldNotFound(FieldIdent::from(stringify![Field])))?,
+    )
+}
+
+// reads a value from each record of the CSV source `src` (skipping records whose corresponding
+// entry in `rows` is `false`, if provided), using `extract` to compute the unparsed value from
+// the record and `parse` to parse each non-empty value, and repeating each value `stack` times
+fn read_values<T, E, F>(
+    src: &CsvSource,
+    rows: Option<&[bool]>,
+    stack: usize,
+    extract: E,
+    parse: F,
+) -> Result<Vec<Value<T>>>
+where
+    T: Clone,
+    E: Fn(&ByteRecord) -> Result<String>,
+    F: Fn(&str) -> Result<T>,
+{
+    read_records(src, rows, stack, |record, values| {
+        let value = parse_value(&extract(record)?, &parse)?;
+        values.extend(iter::repeat(value).take(stack));
+        Ok(())
+    })
+}
+
+// parses the unparsed value `s` using `parse`, treating empty values as missing
+fn parse_value<T, F>(s: &str, parse: &F) -> Result<Value<T>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    let trimmed = s.trim();
+    Ok(if trimmed.is_empty() {
+        Value::Na
+    } else {
+        Value::Exists(parse(trimmed)?)
+    })
+}
+
+// reads the records of the CSV source `src` (skipping records whose corresponding entry in `rows`
+// is `false`, if provided), using `push` to push the `stack` values loaded from each record
+fn read_records<T, P>(
+    src: &CsvSource,
+    rows: Option<&[bool]>,
+    stack: usize,
+    mut push: P,
+) -> Result<Vec<Value<T>>>
+where
+    P: FnMut(&ByteRecord, &mut Vec<Value<T>>) -> Result<()>,
+{
+    let mut csv_reader = src.open_reader()?;
+    // preallocate space for the loaded values if the number of records is known (either from the
+    // selected rows, or from a previously-loaded column)
+    let nrecords = match rows {
+        Some(rows) => rows.iter().filter(|&&keep| keep).count(),
+        None => src.nrecords.load(Ordering::Relaxed),
+    };
+    let mut values = Vec::with_capacity(nrecords * stack);
+    let mut nrecords = 0;
+    for (i, row) in csv_reader.byte_records().enumerate() {
+        // skip filtered-out records before decoding or parsing them
+        if !rows.map_or(true, |rows| rows[i]) {
+            continue;
+        }
+        push(&row?, &mut values)?;
+        nrecords += 1;
+    }
+    if rows.is_none() {
+        src.nrecords.store(nrecords, Ordering::Relaxed);
+    }
+    Ok(values)
 }
 
 /// Object for reading CSV sources.
@@ -180,6 +1015,10 @@ where
 pub struct CsvReader<CsvSchema> {
     src: CsvSource,
     csv_src_schema: CsvSchema,
+    headers: HashMap<String, usize>,
+    predicate: Option<Query>,
+    // sample size and random seed
+    sample: Option<(usize, Option<u64>)>,
 }
 
 impl<CsvSrcSchema> CsvReader<CsvSrcSchema>
@@ -192,25 +1031,22 @@ where
     where
         Schema: IntoCsvSrcSchema<CsvSrcSchema = CsvSrcSchema>,
     {
-        let file_reader = LocalFileReader::new(&src.src)?;
-        let mut csv_reader = src.metadata.dialect.open_reader(file_reader)?;
-
         debug_assert_eq!(src.metadata.num_fields, src.metadata.types.len());
 
-        let headers = if src.metadata.dialect.header.has_header_row {
-            let headers = csv_reader.headers()?;
-            if headers.len() != src.metadata.num_fields {
-                return Err(AgnesError::CsvDialect(
-                    "header row does not match sniffed number of fields in CSV file".into(),
-                ));
+        let headers = match src.header_levels()? {
+            Some(levels) => {
+                if levels.len() != src.metadata.num_fields {
+                    return Err(AgnesError::CsvDialect(
+                        "header row does not match sniffed number of fields in CSV file".into(),
+                    ));
+                }
+                levels
+                    .iter()
+                    .enumerate()
+                    .map(|(i, levels)| (header_name(levels), i))
+                    .collect::<HashMap<_, _>>()
             }
-            headers
-                .iter()
-                .enumerate()
-                .map(|(i, s)| (s.to_string(), i))
-                .collect::<HashMap<_, _>>()
-        } else {
-            HashMap::new()
+            None => HashMap::new(),
         };
         let csv_src_schema = schema.into_csv_src_schema(&headers, src.metadata.num_fields)?;
 
@@ -218,20 +1054,131 @@ where
             //TODO: remove source from here
             src: src.clone(),
             csv_src_schema,
+            headers,
+            predicate: None,
+            sample: None,
         })
     }
 
+    /// Attaches a row predicate to this reader, specified as a
+    /// [query](../../query/index.html) expression over the CSV column header names. Records which
+    /// do not satisfy the predicate are skipped while reading, and are never parsed into the
+    /// resultant `DataStore`. Predicate columns need not be part of the reader's schema.
+    ///
+    /// Fails if the expression is malformed, or if it refers to a column header which does not
+    /// exist in this CSV file.
+    pub fn with_predicate(mut self, expression: &str) -> Result<Self> {
+        let predicate = Query::parse(expression)?;
+        for comparison in predicate.comparisons() {
+            if !self.headers.contains_key(&comparison.field) {
+                return Err(AgnesError::FieldNotFound(FieldIdent::from(
+                    comparison.field.as_str(),
+                )));
+            }
+        }
+        self.predicate = Some(predicate);
+        Ok(self)
+    }
+
+    /// Limits this reader to a uniform random sample of (at most) `n` records, chosen using
+    /// reservoir sampling. Only the sampled records are parsed into the resultant `DataStore`
+    /// (in their original order), so this can be used to profile large files without loading
+    /// them fully. If a predicate is attached with [with_predicate](#method.with_predicate), the
+    /// sample is chosen from the records which satisfy the predicate. The sample is reproducible:
+    /// the same `seed` always produces the same sample from the same file. A `None` seed uses the
+    /// crate-level default seed (see the [rng](../../rng/index.html) module).
+    pub fn with_sample(mut self, n: usize, seed: Option<u64>) -> Self {
+        self.sample = Some((n, seed));
+        self
+    }
+
     /// Read a `CsvSource` into a `DataStore` object.
     pub fn read(&mut self) -> Result<DataStore<CsvSrcSchema::OutputFields>>
     where
         CsvSrcSchema: BuildDStore,
     {
-        self.csv_src_schema.build(&self.src)
+        let rows = match self.predicate {
+            Some(ref predicate) => Some(self.filter_rows(predicate)?),
+            None => None,
+        };
+        let rows = match self.sample {
+            Some((n, seed)) => Some(self.sample_rows(rows, n, seed)?),
+            None => rows,
+        };
+        let stack = self.csv_src_schema.stack_len().unwrap_or(1);
+        self.csv_src_schema
+            .build(&self.src, rows.as_ref().map(|rows| &rows[..]), stack)
+    }
+
+    // evaluates `predicate` on each record of the CSV source
+    fn filter_rows(&self, predicate: &Query) -> Result<Vec<bool>> {
+        let mut csv_reader = self.src.open_reader()?;
+        csv_reader
+            .byte_records()
+            .map(|row| {
+                let record = row?;
+                predicate.matches(&mut |comparison: &Comparison| {
+                    let cell = record.get(self.headers[&comparison.field]).ok_or_else(|| {
+                        AgnesError::FieldNotFound(FieldIdent::from(comparison.field.as_str()))
+                    })?;
+                    comparison.matches_text(&decode(cell)?)
+                })
+            })
+            .collect()
+    }
+
+    // chooses a uniform random sample of `n` records from the records whose corresponding entry
+    // in `rows` is `true` (or from all records if `rows` is not provided)
+    fn sample_rows(
+        &self,
+        rows: Option<Vec<bool>>,
+        n: usize,
+        seed: Option<u64>,
+    ) -> Result<Vec<bool>> {
+        let rows = match rows {
+            Some(rows) => rows,
+            None => {
+                let mut csv_reader = self.src.open_reader()?;
+                let mut num_records = 0;
+                for row in csv_reader.byte_records() {
+                    row?;
+                    num_records += 1;
+                }
+                vec![true; num_records]
+            }
+        };
+
+        let mut rng = operation_rng(seed);
+        let mut reservoir = Vec::with_capacity(n);
+        let candidates = rows
+            .iter()
+            .enumerate()
+            .filter(|&(_, &keep)| keep)
+            .map(|(idx, _)| idx);
+        for (num_seen, idx) in candidates.enumerate() {
+            if num_seen < n {
+                reservoir.push(idx);
+            } else {
+                let replace = rng.gen_range(0, num_seen + 1);
+                if replace < n {
+                    reservoir[replace] = idx;
+                }
+            }
+        }
+
+        let mut sampled = vec![false; rows.len()];
+        for idx in reservoir {
+            sampled[idx] = true;
+        }
+        Ok(sampled)
     }
 }
 
 /// Utility function for loading a CSV file from a [FileLocator](../file/enum.FileLocator.html).
 ///
+/// gzip- or Zstandard-compressed files are decompressed transparently (see
+/// [LocalFileReader](../file/struct.LocalFileReader.html)).
+///
 /// Fails if unable to find or read file at location specified.
 pub fn load_csv<L: Into<FileLocator>, Schema>(
     loc: L,
@@ -277,3 +1224,21 @@ where
 {
     load_csv(path.into(), schema)
 }
+
+/// Utility function for loading a CSV file from a reader, such as standard input (`io::stdin()`)
+/// or an in-memory buffer. The contents of `reader` are read into memory before loading (see
+/// [FileLocator::from_reader](../file/enum.FileLocator.html#method.from_reader)).
+///
+/// Fails if unable to read from `reader`.
+pub fn load_csv_from_reader<R, Schema>(
+    reader: R,
+    schema: Schema,
+) -> Result<<DataStore<<Schema::CsvSrcSchema as BuildDStore>::OutputFields> as IntoView>::Output>
+where
+    R: Read,
+    Schema: IntoCsvSrcSchema,
+    Schema::CsvSrcSchema: BuildDStore + Debug,
+    <Schema::CsvSrcSchema as BuildDStore>::OutputFields: AssocFrameLookup + SimpleFrameFields,
+{
+    load_csv(FileLocator::from_reader(reader)?, schema)
+}
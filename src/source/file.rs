@@ -1,10 +1,13 @@
 //! Types and implementations for reading files, both locally and over HTTP.
 
 use std::fs::File;
-use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
 use std::mem;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+#[cfg(feature = "gzip")]
+use flate2::read::MultiGzDecoder;
 use futures::stream::StreamFuture;
 use futures::Stream;
 use hyper;
@@ -13,6 +16,8 @@ use tempfile;
 
 use hyper_tls::HttpsConnector;
 use tokio_core::reactor::Core;
+#[cfg(feature = "zstandard")]
+use zstd::stream::read::Decoder as ZstdDecoder;
 
 use error::*;
 
@@ -56,6 +61,22 @@ pub enum FileLocator {
     Web(Uri),
     /// A local file
     File(PathBuf),
+    /// File contents held in memory
+    Buffer(Arc<Vec<u8>>),
+}
+
+impl FileLocator {
+    /// Create a file locator for the contents of `reader` (such as standard input, an in-memory
+    /// buffer, or a network stream). Since data sources may read a file multiple times, the
+    /// contents are read fully into memory (without writing a temporary file).
+    ///
+    /// # Errors
+    /// Fails if unable to read from `reader`.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<FileLocator> {
+        let mut contents = vec![];
+        reader.read_to_end(&mut contents)?;
+        Ok(FileLocator::from(contents))
+    }
 }
 
 impl<'a> From<&'a Path> for FileLocator {
@@ -78,60 +99,175 @@ impl From<Uri> for FileLocator {
         FileLocator::Web(orig)
     }
 }
+impl From<Vec<u8>> for FileLocator {
+    fn from(orig: Vec<u8>) -> FileLocator {
+        FileLocator::Buffer(Arc::new(orig))
+    }
+}
+
+/// Compression formats of files which are transparently decompressed by
+/// [LocalFileReader](struct.LocalFileReader.html). Each format is only available with its
+/// corresponding feature (`gzip` or `zstandard`, both on by default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCompression {
+    /// gzip-compressed file (typically with a `.gz` extension)
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Zstandard-compressed file (typically with a `.zst` extension)
+    #[cfg(feature = "zstandard")]
+    Zstd,
+}
 
-/// File reader for reading from files locally.
+// leading bytes of files of each supported compression format
+const MAGIC: &[(&[u8], FileCompression)] = &[
+    #[cfg(feature = "gzip")]
+    (&[0x1f, 0x8b], FileCompression::Gzip),
+    #[cfg(feature = "zstandard")]
+    (&[0x28, 0xb5, 0x2f, 0xfd], FileCompression::Zstd),
+];
+// number of leading bytes needed to detect the compression format of a file
+const MAGIC_LEN: usize = 4;
+
+impl FileCompression {
+    /// Detects the compression format of a file from the leading bytes of its contents. Returns
+    /// `None` if the contents are not compressed with a supported format.
+    pub fn detect(header: &[u8]) -> Option<FileCompression> {
+        MAGIC
+            .iter()
+            .find(|&&(magic, _)| header.starts_with(magic))
+            .map(|&(_, compression)| compression)
+    }
+
+    /// Decompresses all of `src` into `dest`, returning the number of decompressed bytes written.
+    #[cfg_attr(
+        not(any(feature = "gzip", feature = "zstandard")),
+        allow(unused_variables)
+    )]
+    pub fn decompress<R: Read, W: Write>(self, src: R, dest: &mut W) -> io::Result<u64> {
+        match self {
+            // multi-member decoder, since gzip files may consist of several concatenated members
+            #[cfg(feature = "gzip")]
+            FileCompression::Gzip => io::copy(&mut MultiGzDecoder::new(src), dest),
+            #[cfg(feature = "zstandard")]
+            FileCompression::Zstd => io::copy(&mut ZstdDecoder::new(src)?, dest),
+        }
+    }
+}
+
+/// File reader for reading from files locally (or from file contents held in memory).
+///
+/// Files compressed with gzip or Zstandard (detected by the leading bytes of the file, regardless
+/// of file extension) are decompressed into a temporary local file (or into memory, for file
+/// contents held in memory) before reading.
 #[derive(Debug)]
 pub struct LocalFileReader {
-    file: File,
+    contents: LocalContents,
 }
+
+#[derive(Debug)]
+enum LocalContents {
+    File(File),
+    Buffer(Cursor<SharedBuffer>),
+}
+
+// file contents held in memory, shared between all readers of the file
+#[derive(Debug)]
+struct SharedBuffer(Arc<Vec<u8>>);
+impl AsRef<[u8]> for SharedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl LocalFileReader {
     /// Create new reader from a file locator, creating a temporary local file if the file specified
-    /// by the locator is non-local.
+    /// by the locator is non-local or compressed.
     ///
     /// # Errors
     /// Can fail if there are problems accessing local files, if unable to download a remote file,
-    /// or if unable to properly write to a temporary local file.
+    /// if unable to decompress a compressed file, or if unable to properly write to a temporary
+    /// local file.
     pub fn new(loc: &FileLocator) -> Result<LocalFileReader> {
-        match *loc {
-            FileLocator::File(ref path) => {
-                let file = File::open(path)?;
-                Ok(LocalFileReader { file })
+        let mut file = match *loc {
+            FileLocator::File(ref path) => File::open(path)?,
+            FileLocator::Web(_) => download(loc)?,
+            FileLocator::Buffer(ref buffer) => {
+                let buffer = match FileCompression::detect(buffer) {
+                    Some(compression) => {
+                        let mut decompressed = vec![];
+                        compression.decompress(&buffer[..], &mut decompressed)?;
+                        Arc::new(decompressed)
+                    }
+                    None => buffer.clone(),
+                };
+                return Ok(LocalFileReader {
+                    contents: LocalContents::Buffer(Cursor::new(SharedBuffer(buffer))),
+                });
             }
-            FileLocator::Web(_) => {
-                // download file up to nbytes and save it to temp directory
-                const BUF_SIZE: usize = 1 << 13; // 8 * 1024
-                let mut buffer = vec![0; BUF_SIZE];
-                let mut file_reader = HttpFileReader::new(loc)?;
+        };
+
+        let mut header = Vec::with_capacity(MAGIC_LEN);
+        (&mut file)
+            .take(MAGIC_LEN as u64)
+            .read_to_end(&mut header)?;
+        file.seek(SeekFrom::Start(0))?;
+
+        match FileCompression::detect(&header) {
+            Some(compression) => {
                 //TODO: change this to tempfile_in(..) to allow for configurable temp directory
                 let mut temp_file: File = tempfile::tempfile()?;
-                loop {
-                    let n_read = file_reader.read(&mut buffer)?;
-                    if n_read == 0 {
-                        break;
-                    }
-                    let n_wrote = temp_file.write(&buffer[0..n_read])?;
-                    if n_read != n_wrote {
-                        return Err(io::Error::new(
-                            io::ErrorKind::WriteZero,
-                            "unable to write to temporary file",
-                        )
-                        .into());
-                    }
-                }
+                compression.decompress(file, &mut temp_file)?;
                 temp_file.seek(SeekFrom::Start(0))?;
-                Ok(LocalFileReader { file: temp_file })
+                Ok(LocalFileReader {
+                    contents: LocalContents::File(temp_file),
+                })
             }
+            None => Ok(LocalFileReader {
+                contents: LocalContents::File(file),
+            }),
+        }
+    }
+}
+
+/// Downloads the remote file specified by `loc` into a temporary local file.
+fn download(loc: &FileLocator) -> Result<File> {
+    // download file up to nbytes and save it to temp directory
+    const BUF_SIZE: usize = 1 << 13; // 8 * 1024
+    let mut buffer = vec![0; BUF_SIZE];
+    let mut file_reader = HttpFileReader::new(loc)?;
+    //TODO: change this to tempfile_in(..) to allow for configurable temp directory
+    let mut temp_file: File = tempfile::tempfile()?;
+    loop {
+        let n_read = file_reader.read(&mut buffer)?;
+        if n_read == 0 {
+            break;
+        }
+        let n_wrote = temp_file.write(&buffer[0..n_read])?;
+        if n_read != n_wrote {
+            return Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "unable to write to temporary file",
+            )
+            .into());
         }
     }
+    temp_file.seek(SeekFrom::Start(0))?;
+    Ok(temp_file)
 }
 impl Read for LocalFileReader {
     fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
-        self.file.read(out)
+        match self.contents {
+            LocalContents::File(ref mut file) => file.read(out),
+            LocalContents::Buffer(ref mut buffer) => buffer.read(out),
+        }
     }
 }
 impl Seek for LocalFileReader {
     fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        self.file.seek(pos)
+        match self.contents {
+            LocalContents::File(ref mut file) => file.seek(pos),
+            LocalContents::Buffer(ref mut buffer) => buffer.seek(pos),
+        }
     }
 }
 
@@ -145,11 +281,11 @@ impl HttpFileReader {
     /// Create a new reader from a file locator.
     ///
     /// # Errors
-    /// Fails if `FileLocator` points to a local file, or if there are errors connecting retrieving
-    /// the remote file.
+    /// Fails if `FileLocator` points to a local file (or to file contents held in memory), or if
+    /// there are errors connecting retrieving the remote file.
     pub fn new(loc: &FileLocator) -> Result<HttpFileReader> {
         match *loc {
-            FileLocator::File(_) => Err(NetError::LocalFile.into()),
+            FileLocator::File(_) | FileLocator::Buffer(_) => Err(NetError::LocalFile.into()),
             FileLocator::Web(Uri {
                 ref uri,
                 scheme: UriScheme::Http,
@@ -276,7 +412,7 @@ enum State {
 /// Abstract general file reader, implementing `Read`.
 #[derive(Debug)]
 pub enum FileReader {
-    /// Implements `Read` for local files
+    /// Implements `Read` for local files (and file contents held in memory)
     Local(LocalFileReader),
     /// Implements `Read` for http-served files (boxed since HttpFileReader is large)
     Http(Box<HttpFileReader>),
@@ -286,7 +422,9 @@ impl FileReader {
     /// Create new reader from a file locator.
     pub fn new(loc: &FileLocator) -> Result<FileReader> {
         match *loc {
-            FileLocator::File(_) => Ok(FileReader::Local(LocalFileReader::new(loc)?)),
+            FileLocator::File(_) | FileLocator::Buffer(_) => {
+                Ok(FileReader::Local(LocalFileReader::new(loc)?))
+            }
             FileLocator::Web(_) => Ok(FileReader::Http(Box::new(HttpFileReader::new(loc)?))),
         }
     }
@@ -0,0 +1,740 @@
+//! JSON-based source and reader objects and implementation.
+//!
+//! A JSON data source is a file containing an array of records, each of which is a JSON object (or
+//! a JSON array). Fields are designated in a [schema](../../macro.schema.html) by dotted paths into
+//! each record (e.g. `"dept.name"` designates the `name` member of the object in the `dept` member
+//! of each record), or by index for records which are arrays. Path segments which index into an
+//! array are interpreted as array indices (e.g. `"tags.0"`).
+//!
+//! Values which are missing (or `null`) are loaded as missing (NA) values. Strings, numbers, and
+//! booleans are parsed into the field's data type from their text, as with CSV sources; empty
+//! strings are also loaded as missing values.
+//!
+//! Timestamp fields (`as unix_seconds` / `as unix_millis`) and fields combined from multiple source
+//! fields (`concat` / `coalesce`) are supported as with CSV sources. Stacked fields (`stack`) are
+//! not currently supported for JSON sources.
+
+use std::fmt::Debug;
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use serde_json;
+
+use cons::*;
+use datetime::{DateTime, EpochUnit};
+use error::*;
+use field::FieldIdent;
+use fieldlist::{
+    Combine, CombinedSchemaCons, FieldDesignator, FieldPayloadCons, FieldRules, FieldSchema,
+    RuledSchemaCons, SchemaCons, TimestampSchemaCons,
+};
+use frame::SimpleFrameFields;
+use label::{LabelName, SelfValued, TypedValue, Valued};
+use source::file::{FileLocator, LocalFileReader, Uri};
+use store::{AssocFrameLookup, AssocStorage, DataStore, IntoView, PushFrontFromValueIter};
+use value::Value;
+
+/// JSON Data source. Contains location of data file, and the records loaded from it. Can be turned
+/// into `JsonReader` object. Clones of a `JsonSource` share the loaded records.
+#[derive(Debug, Clone)]
+pub struct JsonSource {
+    // File source object for the JSON file
+    src: FileLocator,
+    // records (elements of the top-level array) of the JSON file
+    records: Rc<Vec<serde_json::Value>>,
+}
+
+impl JsonSource {
+    /// Create a new `JsonSource` object with provided file location. This constructor will parse
+    /// the file, which must contain a single array of records.
+    ///
+    /// # Error
+    /// Fails if unable to open the file at the provided location, if the file is not valid JSON,
+    /// or if it does not contain an array.
+    pub fn new<L: Into<FileLocator>>(loc: L) -> Result<JsonSource> {
+        let loc = loc.into();
+        let file_reader = LocalFileReader::new(&loc)?;
+        match serde_json::from_reader(BufReader::new(file_reader))? {
+            serde_json::Value::Array(records) => Ok(JsonSource {
+                src: loc,
+                records: Rc::new(records),
+            }),
+            _ => Err(AgnesError::JsonStructure(
+                "expected an array of records".into(),
+            )),
+        }
+    }
+
+    /// Returns the number of records in this JSON source.
+    pub fn nrecords(&self) -> usize {
+        self.records.len()
+    }
+}
+
+/// Path to a value within a record of a JSON data source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<String>,
+}
+impl SelfValued for JsonPath {}
+
+impl JsonPath {
+    /// Creates the path designated by `designator`: a dotted path for field names, or a single
+    /// array index for field indices.
+    pub fn new(designator: &FieldDesignator) -> JsonPath {
+        let segments = match *designator {
+            FieldDesignator::Expr(ref s) => {
+                s.split('.').map(|segment| segment.to_string()).collect()
+            }
+            FieldDesignator::Idx(idx) => vec![idx.to_string()],
+        };
+        JsonPath { segments }
+    }
+
+    /// Returns the value at this path within `record`, or `None` if this path is not found.
+    pub fn lookup<'a>(&self, record: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.segments
+            .iter()
+            .fold(Some(record), |value, segment| match value {
+                Some(&serde_json::Value::Object(ref members)) => members.get(segment),
+                Some(&serde_json::Value::Array(ref elems)) => {
+                    segment.parse::<usize>().ok().and_then(|idx| elems.get(idx))
+                }
+                _ => None,
+            })
+    }
+
+    // name of the designated field (for errors)
+    fn ident(&self) -> FieldIdent {
+        FieldIdent::from(self.segments.join(".").as_str())
+    }
+}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// path information of a JSON data source.
+pub type JsonSrcSchemaCons<Label, DType, Tail> = FieldPayloadCons<Label, DType, JsonPath, Tail>;
+
+/// Source path and cleaning rules of a JSON field.
+#[derive(Debug, Clone)]
+pub struct JsonRuledField<DType> {
+    /// Path of the field in each record of the JSON data source.
+    pub path: JsonPath,
+    /// Rules to apply to the values of the field.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for JsonRuledField<DType> {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// field information of a JSON field with cleaning rules.
+pub type JsonRuledSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, JsonRuledField<DType>, Tail>;
+
+/// Source path and timestamp unit of a JSON field containing Unix timestamps.
+#[derive(Debug, Clone)]
+pub struct JsonTimestampField {
+    /// Path of the field in each record of the JSON data source.
+    pub path: JsonPath,
+    /// Unit of the timestamps in the field.
+    pub unit: EpochUnit,
+}
+impl SelfValued for JsonTimestampField {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// field information of a JSON field containing Unix timestamps.
+pub type JsonTimestampSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, JsonTimestampField, Tail>;
+
+/// Source paths, combination method, and cleaning rules of a field combined from multiple JSON
+/// fields.
+#[derive(Debug, Clone)]
+pub struct JsonCombinedFields<DType> {
+    /// Paths of the fields in each record of the JSON data source.
+    pub paths: Vec<JsonPath>,
+    /// Method of combining the values of the fields.
+    pub combine: Combine,
+    /// Rules to apply to the combined values.
+    pub rules: FieldRules<DType>,
+}
+impl<DType> SelfValued for JsonCombinedFields<DType> {}
+
+/// Type alias for [Cons](../../cons/struct.Cons.html)-list specifying label, data type, and source
+/// field information of a field combined from multiple JSON fields.
+pub type JsonCombinedSchemaCons<Label, DType, Tail> =
+    FieldPayloadCons<Label, DType, JsonCombinedFields<DType>, Tail>;
+
+/// A trait for converting an object into a [JsonSrcSchemaCons](type.JsonSrcSchemaCons.html).
+pub trait IntoJsonSrcSchema {
+    /// Resultant `JsonSrcSchemaCons` object.
+    type JsonSrcSchema;
+
+    /// Convert this into a `JsonSrcSchemaCons` cons-list. `src` is the JSON source the schema
+    /// applies to (for checking that the designated fields exist).
+    fn into_json_src_schema(self, src: &JsonSource) -> Result<Self::JsonSrcSchema>;
+}
+impl IntoJsonSrcSchema for Nil {
+    type JsonSrcSchema = Nil;
+
+    fn into_json_src_schema(self, _src: &JsonSource) -> Result<Nil> {
+        Ok(Nil)
+    }
+}
+
+impl<Label, DType, Tail> IntoJsonSrcSchema for SchemaCons<Label, DType, Tail>
+where
+    Tail: IntoJsonSrcSchema,
+{
+    type JsonSrcSchema = JsonSrcSchemaCons<Label, DType, Tail::JsonSrcSchema>;
+
+    fn into_json_src_schema(
+        self,
+        src: &JsonSource,
+    ) -> Result<JsonSrcSchemaCons<Label, DType, Tail::JsonSrcSchema>> {
+        let path = source_path(self.head.value_ref(), src)?;
+        Ok(Cons {
+            head: TypedValue::from(path).into(),
+            tail: self.tail.into_json_src_schema(src)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoJsonSrcSchema for RuledSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoJsonSrcSchema,
+    DType: Clone,
+{
+    type JsonSrcSchema = JsonRuledSchemaCons<Label, DType, Tail::JsonSrcSchema>;
+
+    fn into_json_src_schema(
+        self,
+        src: &JsonSource,
+    ) -> Result<JsonRuledSchemaCons<Label, DType, Tail::JsonSrcSchema>> {
+        let designator = self.head.value_ref();
+        let field = JsonRuledField {
+            path: source_path(&designator.designator, src)?,
+            rules: designator.rules.clone(),
+        };
+        Ok(Cons {
+            head: TypedValue::from(field).into(),
+            tail: self.tail.into_json_src_schema(src)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoJsonSrcSchema for TimestampSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoJsonSrcSchema,
+{
+    type JsonSrcSchema = JsonTimestampSchemaCons<Label, DType, Tail::JsonSrcSchema>;
+
+    fn into_json_src_schema(
+        self,
+        src: &JsonSource,
+    ) -> Result<JsonTimestampSchemaCons<Label, DType, Tail::JsonSrcSchema>> {
+        let designator = self.head.value_ref();
+        let field = JsonTimestampField {
+            path: source_path(&designator.designator, src)?,
+            unit: designator.unit,
+        };
+        Ok(Cons {
+            head: TypedValue::from(field).into(),
+            tail: self.tail.into_json_src_schema(src)?,
+        })
+    }
+}
+
+impl<Label, DType, Tail> IntoJsonSrcSchema for CombinedSchemaCons<Label, DType, Tail>
+where
+    Tail: IntoJsonSrcSchema,
+    DType: Clone,
+{
+    type JsonSrcSchema = JsonCombinedSchemaCons<Label, DType, Tail::JsonSrcSchema>;
+
+    fn into_json_src_schema(
+        self,
+        src: &JsonSource,
+    ) -> Result<JsonCombinedSchemaCons<Label, DType, Tail::JsonSrcSchema>> {
+        let designator = self.head.value_ref();
+        let fields = JsonCombinedFields {
+            paths: designator
+                .designators
+                .iter()
+                .map(|designator| source_path(designator, src))
+                .collect::<Result<_>>()?,
+            combine: designator.combine.clone(),
+            rules: designator.rules.clone(),
+        };
+        Ok(Cons {
+            head: TypedValue::from(fields).into(),
+            tail: self.tail.into_json_src_schema(src)?,
+        })
+    }
+}
+
+// finds the path in a JSON source designated by `designator`, checking that it is found in at
+// least one record (unless the source has no records)
+fn source_path(designator: &FieldDesignator, src: &JsonSource) -> Result<JsonPath> {
+    let path = JsonPath::new(designator);
+    if !src.records.is_empty()
+        && src
+            .records
+            .iter()
+            .all(|record| path.lookup(record).is_none())
+    {
+        return Err(AgnesError::FieldNotFound(path.ident()));
+    }
+    Ok(path)
+}
+
+/// A trait for building a [DataStore](../../store/struct.DataStore.html) from a
+/// [JsonSrcSchemaCons](type.JsonSrcSchemaCons.html).
+pub trait BuildJsonStore {
+    /// `Fields` type parameter of the resultant `DataStore`.
+    type OutputFields: AssocStorage;
+
+    /// Builds a `DataStore` from the source schema (`self`) and a JSON source `src`.
+    fn build(&mut self, src: &JsonSource) -> Result<DataStore<Self::OutputFields>>;
+}
+impl BuildJsonStore for Nil {
+    type OutputFields = Nil;
+    fn build(&mut self, _src: &JsonSource) -> Result<DataStore<Nil>> {
+        Ok(DataStore::<Nil>::empty())
+    }
+}
+impl<Label, DType, Tail> BuildJsonStore for JsonSrcSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildJsonStore,
+    DataStore<<Tail as BuildJsonStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields =
+        <DataStore<<Tail as BuildJsonStore>::OutputFields> as PushFrontFromValueIter<
+            Label,
+            DType,
+        >>::OutputFields;
+
+    fn build(&mut self, src: &JsonSource) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src)?;
+        let values = read_field(src, self.head.value_ref().value_ref(), |s| {
+            s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()))
+        })?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+}
+
+impl<Label, DType, Tail> BuildJsonStore for JsonRuledSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildJsonStore,
+    DataStore<<Tail as BuildJsonStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug + LabelName,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields =
+        <DataStore<<Tail as BuildJsonStore>::OutputFields> as PushFrontFromValueIter<
+            Label,
+            DType,
+        >>::OutputFields;
+
+    fn build(&mut self, src: &JsonSource) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src)?;
+        let field = self.head.value_ref().value_ref();
+        let values = read_field(src, &field.path, |s| {
+            s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into()))
+        })?;
+        let values = field.rules.apply(Label::name(), values)?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+}
+
+impl<Label, Tail> BuildJsonStore for JsonTimestampSchemaCons<Label, DateTime, Tail>
+where
+    Tail: BuildJsonStore,
+    DataStore<<Tail as BuildJsonStore>::OutputFields>: PushFrontFromValueIter<Label, DateTime>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DateTime>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DateTime>>>::Output: AssocStorage,
+    Label: Debug,
+{
+    type OutputFields =
+        <DataStore<<Tail as BuildJsonStore>::OutputFields> as PushFrontFromValueIter<
+            Label,
+            DateTime,
+        >>::OutputFields;
+
+    fn build(&mut self, src: &JsonSource) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src)?;
+        let field = self.head.value_ref().value_ref();
+        let values = read_field(src, &field.path, |s| {
+            Ok(DateTime::from_epoch(s.parse::<i64>()?, field.unit))
+        })?;
+        let ds = ds.push_front_from_value_iter::<Label, DateTime, _, _>(values);
+
+        Ok(ds)
+    }
+}
+
+impl<Label, DType, Tail> BuildJsonStore for JsonCombinedSchemaCons<Label, DType, Tail>
+where
+    Tail: BuildJsonStore,
+    DataStore<<Tail as BuildJsonStore>::OutputFields>: PushFrontFromValueIter<Label, DType>,
+    Tail::OutputFields: PushBack<FieldSchema<Label, DType>>,
+    <Tail::OutputFields as PushBack<FieldSchema<Label, DType>>>::Output: AssocStorage,
+    Label: Debug + LabelName,
+    DType: FromStr + Debug + Default + Clone,
+    ParseError: From<<DType as FromStr>::Err>,
+{
+    type OutputFields =
+        <DataStore<<Tail as BuildJsonStore>::OutputFields> as PushFrontFromValueIter<
+            Label,
+            DType,
+        >>::OutputFields;
+
+    fn build(&mut self, src: &JsonSource) -> Result<DataStore<Self::OutputFields>> {
+        let ds = self.tail.build(src)?;
+        let fields = self.head.value_ref().value_ref();
+        let values = read_values(
+            src,
+            |record| {
+                let texts = fields
+                    .paths
+                    .iter()
+                    .map(|path| read_text(record, path))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(fields
+                    .combine
+                    .combine(texts.iter().map(|text| text.as_str())))
+            },
+            |s| s.parse::<DType>().map_err(|e| AgnesError::Parse(e.into())),
+        )?;
+        let values = fields.rules.apply(Label::name(), values)?;
+        let ds = ds.push_front_from_value_iter::<Label, DType, _, _>(values);
+
+        Ok(ds)
+    }
+}
+
+// reads the values at `path` from each record of the JSON source `src`, using `parse` to parse the
+// text of each non-missing value
+fn read_field<T, F>(src: &JsonSource, path: &JsonPath, parse: F) -> Result<Vec<Value<T>>>
+where
+    F: Fn(&str) -> Result<T>,
+{
+    read_values(src, |record| read_text(record, path), parse)
+}
+
+// reads a value from each record of the JSON source `src`, using `text` to find the (unparsed)
+// text of the value within a record and `parse` to parse each non-empty text
+fn read_values<T, R, F>(src: &JsonSource, text: R, parse: F) -> Result<Vec<Value<T>>>
+where
+    R: Fn(&serde_json::Value) -> Result<String>,
+    F: Fn(&str) -> Result<T>,
+{
+    let mut values = Vec::with_capacity(src.nrecords());
+    for record in src.records.iter() {
+        let text = text(record)?;
+        values.push(if text.is_empty() {
+            Value::Na
+        } else {
+            Value::Exists(parse(&text)?)
+        });
+    }
+    Ok(values)
+}
+
+// returns the text of the value at `path` within `record`, which is empty if the value is missing
+fn read_text(record: &serde_json::Value, path: &JsonPath) -> Result<String> {
+    match path.lookup(record) {
+        None | Some(&serde_json::Value::Null) => Ok(String::new()),
+        Some(&serde_json::Value::String(ref s)) => Ok(s.trim().to_string()),
+        Some(&serde_json::Value::Bool(b)) => Ok(b.to_string()),
+        Some(&serde_json::Value::Number(ref n)) => Ok(n.to_string()),
+        Some(_) => Err(AgnesError::JsonStructure(format!(
+            "non-scalar value in field {}",
+            path.ident().to_string()
+        ))),
+    }
+}
+
+/// Object for reading JSON sources.
+#[derive(Debug)]
+pub struct JsonReader<JsonSchema> {
+    src: JsonSource,
+    json_src_schema: JsonSchema,
+}
+
+impl<JsonSrcSchema> JsonReader<JsonSrcSchema>
+where
+    JsonSrcSchema: Debug,
+{
+    /// Create a new JSON reader from a JSON source specification. This will verify the fields
+    /// specified in the schema exist in this JSON source.
+    pub fn new<Schema>(
+        src: &JsonSource,
+        schema: Schema,
+    ) -> Result<JsonReader<Schema::JsonSrcSchema>>
+    where
+        Schema: IntoJsonSrcSchema<JsonSrcSchema = JsonSrcSchema>,
+    {
+        let json_src_schema = schema.into_json_src_schema(src)?;
+        Ok(JsonReader {
+            src: src.clone(),
+            json_src_schema,
+        })
+    }
+
+    /// Read a `JsonSource` into a `DataStore` object.
+    pub fn read(&mut self) -> Result<DataStore<JsonSrcSchema::OutputFields>>
+    where
+        JsonSrcSchema: BuildJsonStore,
+    {
+        self.json_src_schema.build(&self.src)
+    }
+}
+
+/// Type of the `DataView` loaded from a JSON file using the schema `Schema`.
+pub type JsonView<Schema> = <DataStore<
+    <<Schema as IntoJsonSrcSchema>::JsonSrcSchema as BuildJsonStore>::OutputFields,
+> as IntoView>::Output;
+
+/// Utility function for loading a JSON file from a [FileLocator](../file/enum.FileLocator.html).
+///
+/// Fails if unable to find or read file at location specified.
+pub fn load_json<L: Into<FileLocator>, Schema>(loc: L, schema: Schema) -> Result<JsonView<Schema>>
+where
+    Schema: IntoJsonSrcSchema,
+    Schema::JsonSrcSchema: BuildJsonStore + Debug,
+    <Schema::JsonSrcSchema as BuildJsonStore>::OutputFields: AssocFrameLookup + SimpleFrameFields,
+{
+    let source = JsonSource::new(loc)?;
+    let mut json_reader = JsonReader::new(&source, schema)?;
+    Ok(json_reader.read()?.into_view())
+}
+
+/// Utility function for loading a JSON file from a URI string.
+///
+/// Fails if unable to parse `uri`, or if unable to find or read file at the location specified.
+pub fn load_json_from_uri<Schema>(uri: &str, schema: Schema) -> Result<JsonView<Schema>>
+where
+    Schema: IntoJsonSrcSchema,
+    Schema::JsonSrcSchema: BuildJsonStore + Debug,
+    <Schema::JsonSrcSchema as BuildJsonStore>::OutputFields: AssocFrameLookup + SimpleFrameFields,
+{
+    load_json(Uri::from_uri(uri.parse::<hyper::Uri>()?)?, schema)
+}
+
+/// Utility function for loading a JSON file from a local file path.
+///
+/// Fails if unable to find or read file at the location specified.
+pub fn load_json_from_path<P, Schema>(path: P, schema: Schema) -> Result<JsonView<Schema>>
+where
+    P: Into<PathBuf>,
+    Schema: IntoJsonSrcSchema,
+    Schema::JsonSrcSchema: BuildJsonStore + Debug,
+    <Schema::JsonSrcSchema as BuildJsonStore>::OutputFields: AssocFrameLookup + SimpleFrameFields,
+{
+    load_json(path.into(), schema)
+}
+
+/// Utility function for loading a JSON file from a reader, such as standard input (`io::stdin()`)
+/// or an in-memory buffer. The contents of `reader` are read into memory before loading (see
+/// [FileLocator::from_reader](../file/enum.FileLocator.html#method.from_reader)).
+///
+/// Fails if unable to read from `reader`.
+pub fn load_json_from_reader<R, Schema>(reader: R, schema: Schema) -> Result<JsonView<Schema>>
+where
+    R: Read,
+    Schema: IntoJsonSrcSchema,
+    Schema::JsonSrcSchema: BuildJsonStore + Debug,
+    <Schema::JsonSrcSchema as BuildJsonStore>::OutputFields: AssocFrameLookup + SimpleFrameFields,
+{
+    load_json(FileLocator::from_reader(reader)?, schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use access::DataIndex;
+    use select::FieldSelect;
+    use stats::NaCount;
+
+    tablespace![
+        table employees {
+            EmpId: u64,
+            EmpName: String,
+            DeptId: u64,
+            DeptName: String,
+            Salary: f64,
+            EmpLabel: String,
+            Hired: DateTime,
+        }
+    ];
+
+    fn data_path(filename: &str) -> PathBuf {
+        Path::new(file!()) // start as this file
+            .parent()
+            .unwrap() // navigate up to source directory
+            .parent()
+            .unwrap() // navigate up to src directory
+            .parent()
+            .unwrap() // navigate up to root directory
+            .join("tests") // navigate into integration tests directory
+            .join("data") // navigate into data directory
+            .join(filename) // navigate to target file
+    }
+
+    #[test]
+    fn load() {
+        use self::employees::*;
+
+        let dv = load_json_from_path(
+            data_path("employees.json"),
+            schema![
+                fieldname EmpId = "id";
+                fieldname EmpName = "name";
+                fieldname DeptId = "dept.id";
+                fieldname DeptName = "dept.name";
+                fieldname Salary = "salary";
+            ],
+        )
+        .unwrap();
+        println!("{}", dv);
+        assert_eq!(dv.nrows(), 5);
+        assert_eq!(dv.field::<EmpId>().to_vec(), vec![0u64, 2, 5, 6, 8]);
+        assert_eq!(
+            dv.field::<EmpName>().to_value_vec(),
+            vec![
+                Value::Exists("Sally".to_string()),
+                Value::Exists("Jamie".to_string()),
+                Value::Exists("Bob".to_string()),
+                Value::Na,
+                Value::Exists("Louise".to_string()),
+            ]
+        );
+        assert_eq!(
+            dv.field::<DeptId>().to_value_vec(),
+            vec![
+                Value::Exists(1u64),
+                Value::Exists(2),
+                Value::Exists(1),
+                Value::Exists(3),
+                Value::Na,
+            ]
+        );
+        assert_eq!(dv.field::<DeptName>().num_na(), 2);
+        assert_eq!(
+            dv.field::<Salary>().to_value_vec(),
+            vec![
+                Value::Exists(1000.5),
+                Value::Na,
+                Value::Exists(1200.0),
+                Value::Exists(980.25),
+                Value::Na,
+            ]
+        );
+
+        // rules are applied as with CSV sources
+        let dv = load_json_from_path(
+            data_path("employees.json"),
+            schema![
+                fieldname EmpId = "id";
+                fieldname Salary = "salary", default 0.0;
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            dv.field::<Salary>().to_vec(),
+            vec![1000.5, 0.0, 1200.0, 980.25, 0.0]
+        );
+    }
+
+    #[test]
+    fn load_reader() {
+        use self::employees::*;
+
+        let json = br#"[{"id": 3, "name": "Sally"}, {"id": 4, "salary": 1000.5}]"#;
+        let dv = load_json_from_reader(
+            &json[..],
+            schema![
+                fieldname EmpId = "id";
+                fieldname EmpName = "name";
+            ],
+        )
+        .unwrap();
+        assert_eq!(dv.field::<EmpId>().to_vec(), vec![3u64, 4]);
+        assert_eq!(dv.field::<EmpName>().num_na(), 1);
+    }
+
+    #[test]
+    fn load_timestamps_combined() {
+        use self::employees::*;
+
+        let json = br#"[
+            {"id": 3, "name": "Sally", "hired": 1530707400, "dept": {"name": "Sales"}},
+            {"id": 4, "hired": null, "dept": {"name": "Marketing"}},
+            {"id": 5, "name": "Bob", "hired": "1530793800"}
+        ]"#;
+        let dv = load_json_from_reader(
+            &json[..],
+            schema![
+                fieldname Hired as unix_seconds = "hired";
+                fieldnames EmpLabel = ["name", "dept.name"], concat " - ";
+                fieldnames EmpName = ["name", "id"], coalesce;
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            dv.field::<Hired>().to_value_vec(),
+            vec![
+                Value::Exists("2018-07-04 12:30:00".parse::<DateTime>().unwrap()),
+                Value::Na,
+                Value::Exists("2018-07-05 12:30:00".parse::<DateTime>().unwrap()),
+            ]
+        );
+        assert_eq!(
+            dv.field::<EmpLabel>().to_vec(),
+            vec![
+                "Sally - Sales".to_string(),
+                "Marketing".to_string(),
+                "Bob".to_string(),
+            ]
+        );
+        assert_eq!(
+            dv.field::<EmpName>().to_vec(),
+            vec!["Sally".to_string(), "4".to_string(), "Bob".to_string()]
+        );
+    }
+
+    #[test]
+    fn load_errors() {
+        use self::employees::*;
+
+        let source = JsonSource::new(data_path("employees.json")).unwrap();
+        assert_eq!(source.nrecords(), 5);
+        match JsonReader::new(&source, schema![fieldname EmpId = "dept.code";]) {
+            Err(AgnesError::FieldNotFound(_)) => {}
+            _ => panic!("expected missing field error"),
+        }
+        let mut reader = JsonReader::new(&source, schema![fieldname EmpName = "dept";]).unwrap();
+        match reader.read() {
+            Err(AgnesError::JsonStructure(_)) => {}
+            _ => panic!("expected JSON structure error"),
+        }
+        match JsonSource::new(data_path("gdp.csv")) {
+            Err(AgnesError::Json(_)) => {}
+            _ => panic!("expected JSON parse error"),
+        }
+    }
+}
@@ -2,5 +2,6 @@
 
 pub mod csv;
 pub mod file;
+pub mod json;
 
 pub(crate) mod decode;
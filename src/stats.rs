@@ -1,6 +1,12 @@
 /*!
 Useful statistics-calculating traits for fields with numeric data.
 */
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::mem;
 use std::ops::{Add, Mul};
 
 use num_traits::{AsPrimitive, Zero};
@@ -21,16 +27,10 @@ where
     DI: DataIndex,
 {
     fn num_na(&self) -> usize {
-        self.iter().fold(
-            0usize,
-            |count, value| if value.exists() { count } else { count + 1 },
-        )
+        self.count_na()
     }
     fn num_exists(&self) -> usize {
-        self.iter().fold(
-            0usize,
-            |count, value| if value.exists() { count + 1 } else { count },
-        )
+        self.count_exists()
     }
 }
 
@@ -220,6 +220,1020 @@ where
     }
 }
 
+/// Summary statistics of a field: its extrema and number of missing values. Fields stored in a
+/// [DataStore](../store/struct.DataStore.html) cache their summary, which is available via
+/// [cached_summary](../access/trait.DataIndex.html#method.cached_summary).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSummary<T> {
+    /// The minimum value in the field, or `None` if no values exist in the field.
+    pub min: Option<T>,
+    /// The maximum value in the field, or `None` if no values exist in the field.
+    pub max: Option<T>,
+    /// The number of NA (missing) values in the field.
+    pub num_na: usize,
+}
+
+impl<T> FieldSummary<T>
+where
+    T: Clone + PartialOrd,
+{
+    /// Computes the summary statistics of `field`.
+    pub fn compute<DI>(field: &DI) -> FieldSummary<T>
+    where
+        DI: DataIndex<DType = T>,
+    {
+        FieldSummary {
+            min: field.min().cloned(),
+            max: field.max().cloned(),
+            num_na: field.num_na(),
+        }
+    }
+}
+
+/// A trait for finding the indices of the upper and lower extrema values of a field.
+pub trait ArgExtrema {
+    /// The index of the minimum value in this field. Missing values are ignored, and ties resolve
+    /// to the earliest index. Returns `None` if no values exist in this field.
+    fn idxmin(&self) -> Option<usize>;
+    /// The index of the maximum value in this field. Missing values are ignored, and ties resolve
+    /// to the earliest index. Returns `None` if no values exist in this field.
+    fn idxmax(&self) -> Option<usize>;
+}
+
+impl<DI> ArgExtrema for DI
+where
+    DI: DataIndex,
+    DI::DType: PartialOrd,
+{
+    fn idxmin(&self) -> Option<usize> {
+        arg_extreme(self, |value, extreme| value < extreme)
+    }
+    fn idxmax(&self) -> Option<usize> {
+        arg_extreme(self, |value, extreme| value > extreme)
+    }
+}
+
+// finds the index of the existing value which is most extreme according to `more_extreme`
+fn arg_extreme<DI, F>(field: &DI, more_extreme: F) -> Option<usize>
+where
+    DI: DataIndex,
+    F: Fn(&DI::DType, &DI::DType) -> bool,
+{
+    let mut extreme: Option<(usize, &DI::DType)> = None;
+    for (idx, value) in field.iter().enumerate() {
+        if let Value::Exists(value) = value {
+            extreme = match extreme {
+                Some((_, curr)) if !more_extreme(value, curr) => extreme,
+                _ => Some((idx, value)),
+            };
+        }
+    }
+    extreme.map(|(idx, _)| idx)
+}
+
+/// A trait for computing quantiles of values in a field.
+pub trait Quantile {
+    /// Computes the `q`th quantile (`0.0 <= q <= 1.0`) of values in this field, linearly
+    /// interpolating between values when the quantile falls between two of them. Ignores missing
+    /// values in this computation. Returns `None` if `q` is outside of `[0, 1]` or if no values
+    /// exist in this field.
+    fn quantile(&self, q: f64) -> Option<f64>;
+    /// Computes the median (`0.5` quantile) of values in this field.
+    fn median(&self) -> Option<f64> {
+        self.quantile(0.5)
+    }
+}
+
+impl<DI> Quantile for DI
+where
+    DI: DataIndex,
+    DI::DType: AsPrimitive<f64>,
+{
+    fn quantile(&self, q: f64) -> Option<f64> {
+        let pairs = self
+            .iter()
+            .filter_map(|value| value.map(|&value| (value.as_(), 1.0)).into_option())
+            .collect();
+        weighted_quantile(pairs, q)
+    }
+}
+
+/// A trait for computing quantiles of values in a field using frequency weights from another field.
+pub trait WeightedQuantile<W> {
+    /// Computes the `q`th quantile (`0.0 <= q <= 1.0`) of values in this field, where each value is
+    /// weighted by the value at the same index in `weights`. The result is the same as computing
+    /// the unweighted [quantile](trait.Quantile.html) over a field in which each value is repeated
+    /// a number of times equal to its (integral) weight.
+    ///
+    /// Values that are missing, or whose weights are missing or non-positive, are ignored. Returns
+    /// `None` if `q` is outside of `[0, 1]` or if no weighted values exist.
+    fn weighted_quantile(&self, weights: &W, q: f64) -> Option<f64>;
+}
+
+impl<DI, W> WeightedQuantile<W> for DI
+where
+    DI: DataIndex,
+    DI::DType: AsPrimitive<f64>,
+    W: DataIndex,
+    W::DType: AsPrimitive<f64>,
+{
+    fn weighted_quantile(&self, weights: &W, q: f64) -> Option<f64> {
+        let pairs = self
+            .iter()
+            .zip(weights.iter())
+            .filter_map(|(value, weight)| match (value, weight) {
+                (Value::Exists(&value), Value::Exists(&weight)) => {
+                    Some((value.as_(), weight.as_()))
+                }
+                _ => None,
+            })
+            .collect();
+        weighted_quantile(pairs, q)
+    }
+}
+
+// computes the quantile over (value, weight) pairs, using linear interpolation between the
+// closest ranks of the weight-expanded values
+fn weighted_quantile(mut pairs: Vec<(f64, f64)>, q: f64) -> Option<f64> {
+    if !(q >= 0.0 && q <= 1.0) {
+        return None;
+    }
+    pairs.retain(|&(value, weight)| weight > 0.0 && !value.is_nan());
+    if pairs.is_empty() {
+        return None;
+    }
+    pairs.sort_by(|left, right| left.0.partial_cmp(&right.0).unwrap());
+
+    let total_weight: f64 = pairs.iter().map(|&(_, weight)| weight).sum();
+    let value_at = |rank: f64| {
+        let mut cumulative = 0.0;
+        for &(value, weight) in &pairs {
+            cumulative += weight;
+            if rank < cumulative {
+                return value;
+            }
+        }
+        pairs[pairs.len() - 1].0
+    };
+
+    let rank = (total_weight - 1.0).max(0.0) * q;
+    let (lower, upper) = (value_at(rank.floor()), value_at(rank.ceil()));
+    Some(lower + (rank - rank.floor()) * (upper - lower))
+}
+
+/// A trait for statistics which are computed incrementally from a stream of values, such as
+/// the values of a field loaded (or processed) in chunks. Accumulators computed over separate
+/// portions of the data (for example, in parallel) can be combined with `merge`.
+pub trait StatAccumulator<T> {
+    /// The type of the computed statistic.
+    type Output;
+
+    /// Updates this accumulator with a single value.
+    fn push(&mut self, value: Value<&T>);
+    /// Combines the values accumulated by `other` into this accumulator. The result is the same
+    /// as if all of the values pushed to `other` had been pushed to this accumulator.
+    fn merge(&mut self, other: &Self);
+    /// Returns the statistic computed over all values accumulated so far.
+    fn result(&self) -> Self::Output;
+
+    /// Updates this accumulator with each of the values in `values` (for example, a field's
+    /// [iter](../access/trait.DataIndex.html#method.iter) or a single chunk from its
+    /// [chunks](../access/trait.DataIndex.html#method.chunks)).
+    fn push_iter<'a, I>(&mut self, values: I)
+    where
+        I: IntoIterator<Item = Value<&'a T>>,
+        T: 'a,
+    {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+/// Accumulator computing the sum of values. Missing values are treated as `0`, as in
+/// [Sum](trait.Sum.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumAccumulator<T> {
+    sum: T,
+}
+
+impl<T> SumAccumulator<T>
+where
+    T: Zero,
+{
+    /// Creates a new `SumAccumulator` without any values.
+    pub fn new() -> SumAccumulator<T> {
+        SumAccumulator { sum: T::zero() }
+    }
+}
+
+impl<T> Default for SumAccumulator<T>
+where
+    T: Zero,
+{
+    fn default() -> SumAccumulator<T> {
+        SumAccumulator::new()
+    }
+}
+
+impl<T> StatAccumulator<T> for SumAccumulator<T>
+where
+    T: for<'a> Add<&'a T, Output = T> + Zero + Clone,
+{
+    type Output = T;
+
+    fn push(&mut self, value: Value<&T>) {
+        if let Value::Exists(value) = value {
+            let sum = mem::replace(&mut self.sum, T::zero());
+            self.sum = sum + value;
+        }
+    }
+    fn merge(&mut self, other: &Self) {
+        let sum = mem::replace(&mut self.sum, T::zero());
+        self.sum = sum + &other.sum;
+    }
+    fn result(&self) -> T {
+        self.sum.clone()
+    }
+}
+
+/// Count, mean, and variance of values, as computed by a
+/// [MomentsAccumulator](struct.MomentsAccumulator.html).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moments {
+    /// The number of existing (non-missing) values.
+    pub num_exists: usize,
+    /// The number of NA (missing) values.
+    pub num_na: usize,
+    /// The arithmetic mean of existing values, or `0.0` if no values exist.
+    pub mean: f64,
+    /// The sample variance of existing values, or `0.0` if no values exist.
+    pub var: f64,
+    /// The population variance of existing values, or `0.0` if no values exist.
+    pub varp: f64,
+}
+
+/// Accumulator computing the count, mean, and variance of numeric values. Missing values are
+/// counted but otherwise ignored, as in [Mean](trait.Mean.html) and
+/// [Variance](trait.Variance.html). The mean and variance are updated with each value (and
+/// combined when merging) in a numerically stable manner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MomentsAccumulator<T> {
+    num_exists: usize,
+    num_na: usize,
+    mean: f64,
+    // sum of squared differences from the mean
+    m2: f64,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MomentsAccumulator<T> {
+    /// Creates a new `MomentsAccumulator` without any values.
+    pub fn new() -> MomentsAccumulator<T> {
+        MomentsAccumulator {
+            num_exists: 0,
+            num_na: 0,
+            mean: 0.0,
+            m2: 0.0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for MomentsAccumulator<T> {
+    fn default() -> MomentsAccumulator<T> {
+        MomentsAccumulator::new()
+    }
+}
+
+impl<T> StatAccumulator<T> for MomentsAccumulator<T>
+where
+    T: AsPrimitive<f64>,
+{
+    type Output = Moments;
+
+    fn push(&mut self, value: Value<&T>) {
+        match value {
+            Value::Exists(value) => {
+                let value: f64 = value.as_();
+                self.num_exists += 1;
+                let delta = value - self.mean;
+                self.mean += delta / self.num_exists as f64;
+                self.m2 += delta * (value - self.mean);
+            }
+            Value::Na => {
+                self.num_na += 1;
+            }
+        }
+    }
+    fn merge(&mut self, other: &Self) {
+        self.num_na += other.num_na;
+        if other.num_exists == 0 {
+            return;
+        }
+        let num_exists = self.num_exists + other.num_exists;
+        let (n_self, n_other, n) = (
+            self.num_exists as f64,
+            other.num_exists as f64,
+            num_exists as f64,
+        );
+        let delta = other.mean - self.mean;
+        self.mean += delta * n_other / n;
+        self.m2 += other.m2 + delta * delta * n_self * n_other / n;
+        self.num_exists = num_exists;
+    }
+    fn result(&self) -> Moments {
+        let (var, varp) = match self.num_exists {
+            0 => (0.0, 0.0),
+            n => (self.m2 / (n - 1) as f64, self.m2 / n as f64),
+        };
+        Moments {
+            num_exists: self.num_exists,
+            num_na: self.num_na,
+            mean: self.mean,
+            var,
+            varp,
+        }
+    }
+}
+
+/// Accumulator computing the [FieldSummary](struct.FieldSummary.html) (extrema and number of
+/// missing values) of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryAccumulator<T> {
+    summary: FieldSummary<T>,
+}
+
+impl<T> SummaryAccumulator<T> {
+    /// Creates a new `SummaryAccumulator` without any values.
+    pub fn new() -> SummaryAccumulator<T> {
+        SummaryAccumulator {
+            summary: FieldSummary {
+                min: None,
+                max: None,
+                num_na: 0,
+            },
+        }
+    }
+}
+
+impl<T> Default for SummaryAccumulator<T> {
+    fn default() -> SummaryAccumulator<T> {
+        SummaryAccumulator::new()
+    }
+}
+
+impl<T> SummaryAccumulator<T>
+where
+    T: Clone + PartialOrd,
+{
+    // updates the extrema with an existing value
+    fn push_exists(&mut self, value: &T) {
+        if self.summary.min.as_ref().map_or(true, |min| value < min) {
+            self.summary.min = Some(value.clone());
+        }
+        if self.summary.max.as_ref().map_or(true, |max| value > max) {
+            self.summary.max = Some(value.clone());
+        }
+    }
+}
+
+impl<T> StatAccumulator<T> for SummaryAccumulator<T>
+where
+    T: Clone + PartialOrd,
+{
+    type Output = FieldSummary<T>;
+
+    fn push(&mut self, value: Value<&T>) {
+        match value {
+            Value::Exists(value) => self.push_exists(value),
+            Value::Na => {
+                self.summary.num_na += 1;
+            }
+        }
+    }
+    fn merge(&mut self, other: &Self) {
+        if let Some(ref min) = other.summary.min {
+            self.push_exists(min);
+        }
+        if let Some(ref max) = other.summary.max {
+            self.push_exists(max);
+        }
+        self.summary.num_na += other.summary.num_na;
+    }
+    fn result(&self) -> FieldSummary<T> {
+        self.summary.clone()
+    }
+}
+
+/// Default compression used by a [QuantileSketch](struct.QuantileSketch.html).
+pub const DEFAULT_SKETCH_COMPRESSION: f64 = 100.0;
+
+/// Accumulator computing an approximation of the distribution of numeric values (a
+/// [t-digest](https://github.com/tdunning/t-digest)), from which quantiles can be estimated using
+/// [quantile](#method.quantile). The sketch summarizes values as a bounded number of weighted
+/// centroids, so it uses a fixed amount of memory regardless of the number of values
+/// accumulated, and is most accurate for quantiles near `0` or `1`. Missing and NaN values are
+/// ignored.
+///
+/// The `compression` of the sketch controls the tradeoff between accuracy and size: the sketch
+/// holds roughly `compression` centroids at most. When fewer values than this have been
+/// accumulated, quantiles are exact (and computed as with [Quantile](trait.Quantile.html)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantileSketch<T> {
+    compression: f64,
+    // (mean, weight) of each centroid, sorted by mean
+    centroids: Vec<(f64, f64)>,
+    // (mean, weight) of values and centroids not yet merged into `centroids`
+    buffer: Vec<(f64, f64)>,
+    min: f64,
+    max: f64,
+    _marker: PhantomData<T>,
+}
+
+impl<T> QuantileSketch<T> {
+    /// Creates a new `QuantileSketch` without any values, using the
+    /// [default compression](constant.DEFAULT_SKETCH_COMPRESSION.html).
+    pub fn new() -> QuantileSketch<T> {
+        QuantileSketch::with_compression(DEFAULT_SKETCH_COMPRESSION)
+    }
+
+    /// Creates a new `QuantileSketch` without any values, with the specified `compression`
+    /// (which should be at least `1`).
+    pub fn with_compression(compression: f64) -> QuantileSketch<T> {
+        QuantileSketch {
+            compression: compression.max(1.0),
+            centroids: vec![],
+            buffer: vec![],
+            min: 0.0,
+            max: 0.0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the total number of (non-missing) values accumulated by this sketch.
+    pub fn count(&self) -> usize {
+        self.centroids
+            .iter()
+            .chain(&self.buffer)
+            .map(|&(_, weight)| weight)
+            .sum::<f64>() as usize
+    }
+
+    /// Estimates the `q`th quantile (`0.0 <= q <= 1.0`) of the values accumulated by this
+    /// sketch, linearly interpolating between centroids. Returns `None` if `q` is outside of
+    /// `[0, 1]` or if no values have been accumulated.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if !(q >= 0.0 && q <= 1.0) {
+            return None;
+        }
+        if !self.buffer.is_empty() {
+            let mut compressed = self.clone();
+            compressed.compress();
+            return compressed.quantile(q);
+        }
+        if self.centroids.is_empty() {
+            return None;
+        }
+
+        // each centroid is placed at the (possibly fractional) rank of the middle of the values
+        // it represents, bounded by the extrema at the first and last ranks
+        let mut points = vec![(0.0, self.min)];
+        let mut cumulative = 0.0;
+        for &(mean, weight) in &self.centroids {
+            points.push((cumulative + (weight - 1.0) / 2.0, mean));
+            cumulative += weight;
+        }
+        points.push(((cumulative - 1.0).max(0.0), self.max));
+
+        let rank = (cumulative - 1.0).max(0.0) * q;
+        for pair in points.windows(2) {
+            let ((lower_rank, lower), (upper_rank, upper)) = (pair[0], pair[1]);
+            if rank <= upper_rank {
+                if upper_rank <= lower_rank {
+                    return Some(upper);
+                }
+                return Some(
+                    lower + (rank - lower_rank) / (upper_rank - lower_rank) * (upper - lower),
+                );
+            }
+        }
+        Some(self.max)
+    }
+
+    // adds a centroid to the buffer, compressing the sketch if the buffer is full
+    fn push_centroid(&mut self, mean: f64, weight: f64) {
+        if self.centroids.is_empty() && self.buffer.is_empty() {
+            self.min = mean;
+            self.max = mean;
+        } else {
+            self.min = self.min.min(mean);
+            self.max = self.max.max(mean);
+        }
+        self.buffer.push((mean, weight));
+        if self.buffer.len() >= 5 * self.compression.ceil() as usize {
+            self.compress();
+        }
+    }
+
+    // merges the buffer into the centroids, combining neighboring centroids as long as the
+    // combined centroid stays within the size limit for its position in the distribution
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut items = mem::replace(&mut self.centroids, vec![]);
+        items.append(&mut self.buffer);
+        items.sort_by(|left, right| left.0.partial_cmp(&right.0).unwrap());
+
+        let total_weight: f64 = items.iter().map(|&(_, weight)| weight).sum();
+        // sketches of no more than `compression` values keep every value as its own centroid, so
+        // their quantiles are exact
+        if total_weight <= self.compression {
+            self.centroids = items;
+            return;
+        }
+        let mut current = items[0];
+        let mut preceding_weight = 0.0;
+        let mut lower_limit = self.scale(0.0);
+        for &(mean, weight) in &items[1..] {
+            let q = (preceding_weight + current.1 + weight) / total_weight;
+            if self.scale(q) - lower_limit <= 1.0 {
+                current.1 += weight;
+                current.0 += (mean - current.0) * weight / current.1;
+            } else {
+                preceding_weight += current.1;
+                lower_limit = self.scale(preceding_weight / total_weight);
+                self.centroids.push(current);
+                current = (mean, weight);
+            }
+        }
+        self.centroids.push(current);
+    }
+
+    // scale function mapping quantiles to centroid indices, which limits centroids near the
+    // extremes of the distribution to small sizes
+    fn scale(&self, q: f64) -> f64 {
+        self.compression * (2.0 * q - 1.0).asin() / (2.0 * PI)
+    }
+}
+
+impl<T> Default for QuantileSketch<T> {
+    fn default() -> QuantileSketch<T> {
+        QuantileSketch::new()
+    }
+}
+
+impl<T> StatAccumulator<T> for QuantileSketch<T>
+where
+    T: AsPrimitive<f64>,
+{
+    /// A compressed copy of the sketch, which can be queried for any number of quantiles.
+    type Output = QuantileSketch<T>;
+
+    fn push(&mut self, value: Value<&T>) {
+        if let Value::Exists(value) = value {
+            let value: f64 = value.as_();
+            if !value.is_nan() {
+                self.push_centroid(value, 1.0);
+            }
+        }
+    }
+    fn merge(&mut self, other: &Self) {
+        for &(mean, weight) in other.centroids.iter().chain(&other.buffer) {
+            self.push_centroid(mean, weight);
+        }
+        if !(other.centroids.is_empty() && other.buffer.is_empty()) {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+    }
+    fn result(&self) -> QuantileSketch<T> {
+        let mut compressed = self.clone();
+        compressed.compress();
+        compressed
+    }
+}
+
+/// Default precision used by a [HyperLogLog](struct.HyperLogLog.html) sketch.
+pub const DEFAULT_HLL_PRECISION: u8 = 12;
+
+/// Accumulator estimating the number of distinct values using a
+/// [HyperLogLog](https://en.wikipedia.org/wiki/HyperLogLog) sketch. The sketch stores a fixed
+/// number of small registers (`2^precision` bytes) regardless of the number of values
+/// accumulated, rather than a hash set of all distinct values. Missing values are ignored.
+///
+/// The relative standard error of the estimate is approximately `1.04 / sqrt(2^precision)`
+/// (about 1.6% with the [default precision](constant.DEFAULT_HLL_PRECISION.html)); estimates of
+/// small numbers of distinct values are typically exact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperLogLog<T> {
+    precision: u8,
+    // maximum rank observed for the hashes assigned to each register
+    registers: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> HyperLogLog<T> {
+    /// Creates a new `HyperLogLog` without any values, using the
+    /// [default precision](constant.DEFAULT_HLL_PRECISION.html).
+    pub fn new() -> HyperLogLog<T> {
+        HyperLogLog::with_precision(DEFAULT_HLL_PRECISION)
+    }
+
+    /// Creates a new `HyperLogLog` without any values, with the specified `precision` (clamped
+    /// to between `4` and `16`). Higher precisions give more accurate estimates, but use more
+    /// memory.
+    pub fn with_precision(precision: u8) -> HyperLogLog<T> {
+        let precision = precision.max(4).min(16);
+        HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+            _marker: PhantomData,
+        }
+    }
+
+    // updates the register chosen by the leading bits of `hash` with the position of the first set
+    // bit of the remaining bits
+    fn push_hash(&mut self, hash: u64) {
+        let idx = (hash >> (64 - self.precision)) as usize;
+        let rank = ((hash << self.precision).leading_zeros() + 1).min(65 - self.precision as u32);
+        if rank as u8 > self.registers[idx] {
+            self.registers[idx] = rank as u8;
+        }
+    }
+}
+
+impl<T> Default for HyperLogLog<T> {
+    fn default() -> HyperLogLog<T> {
+        HyperLogLog::new()
+    }
+}
+
+impl<T> StatAccumulator<T> for HyperLogLog<T>
+where
+    T: Hash,
+{
+    /// Estimated number of distinct values.
+    type Output = usize;
+
+    fn push(&mut self, value: Value<&T>) {
+        if let Value::Exists(value) = value {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            self.push_hash(hasher.finish());
+        }
+    }
+    /// Combines the values accumulated by `other` into this accumulator.
+    ///
+    /// # Panics
+    /// Panics if `other` has a different precision than this accumulator.
+    fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            self.precision, other.precision,
+            "cannot merge HyperLogLog sketches of different precisions"
+        );
+        for (register, &other_register) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(other_register);
+        }
+    }
+    fn result(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&register| 2f64.powi(-(register as i32)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+        let num_zero = self
+            .registers
+            .iter()
+            .filter(|&&register| register == 0)
+            .count();
+        let estimate = if estimate <= 2.5 * m && num_zero > 0 {
+            // linear counting for small cardinalities
+            m * (m / num_zero as f64).ln()
+        } else {
+            estimate
+        };
+        estimate.round() as usize
+    }
+}
+
+/// A trait for estimating the number of distinct values in a field.
+pub trait ApproxDistinct {
+    /// Estimates the number of distinct existing values in this field using a
+    /// [HyperLogLog](struct.HyperLogLog.html) sketch with the default precision, without
+    /// collecting the distinct values themselves. Ignores missing values.
+    fn approx_nunique(&self) -> usize;
+}
+
+impl<DI> ApproxDistinct for DI
+where
+    DI: DataIndex,
+    DI::DType: Hash,
+{
+    fn approx_nunique(&self) -> usize {
+        let mut sketch = HyperLogLog::new();
+        sketch.push_iter(self.iter());
+        sketch.result()
+    }
+}
+
+/// Default width (number of counters per row) of a [CountMinSketch](struct.CountMinSketch.html).
+pub const DEFAULT_CMS_WIDTH: usize = 2048;
+/// Default depth (number of rows of counters) of a [CountMinSketch](struct.CountMinSketch.html).
+pub const DEFAULT_CMS_DEPTH: usize = 4;
+
+// hashes `value` into a pair of hashes, which are combined to choose a counter in each row of a
+// count-min sketch (or a bit of a Bloom filter)
+pub(crate) fn hash_pair<T: Hash>(value: &T) -> (u64, u64) {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    let hash = hasher.finish();
+    (hash & 0xffff_ffff, (hash >> 32) | 1)
+}
+
+/// Accumulator estimating the frequency of each value using a
+/// [count-min sketch](https://en.wikipedia.org/wiki/Count%E2%80%93min_sketch). The sketch stores
+/// a fixed number of counters (`width * depth`) regardless of the number of distinct values
+/// accumulated. Missing values are ignored.
+///
+/// Frequency estimates (see [estimate](#method.estimate)) never undercount; with probability of
+/// at least `1 - e^-depth`, they overcount by at most `e / width` times the total number of values
+/// accumulated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountMinSketch<T> {
+    width: usize,
+    depth: usize,
+    // `depth` rows of `width` counters each
+    counters: Vec<u64>,
+    total: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<T> CountMinSketch<T> {
+    /// Creates a new `CountMinSketch` without any values, using the
+    /// [default width](constant.DEFAULT_CMS_WIDTH.html) and
+    /// [default depth](constant.DEFAULT_CMS_DEPTH.html).
+    pub fn new() -> CountMinSketch<T> {
+        CountMinSketch::with_dimensions(DEFAULT_CMS_WIDTH, DEFAULT_CMS_DEPTH)
+    }
+
+    /// Creates a new `CountMinSketch` without any values, with `depth` rows of `width` counters
+    /// (both at least `1`). Wider sketches give more accurate estimates; deeper sketches make
+    /// large overestimates less likely.
+    pub fn with_dimensions(width: usize, depth: usize) -> CountMinSketch<T> {
+        let width = width.max(1);
+        let depth = depth.max(1);
+        CountMinSketch {
+            width,
+            depth,
+            counters: vec![0; width * depth],
+            total: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the total number of (existing) values accumulated by this sketch.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Estimates the number of times `value` has been accumulated by this sketch.
+    pub fn estimate(&self, value: &T) -> u64
+    where
+        T: Hash,
+    {
+        let (h1, h2) = hash_pair(value);
+        (0..self.depth)
+            .map(|row| self.counters[self.counter_index(row, h1, h2)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn counter_index(&self, row: usize, h1: u64, h2: u64) -> usize {
+        let col = h1.wrapping_add((row as u64).wrapping_mul(h2)) % self.width as u64;
+        row * self.width + col as usize
+    }
+
+    // increments the counters of `value`, returning its new estimated frequency
+    fn increment(&mut self, value: &T) -> u64
+    where
+        T: Hash,
+    {
+        let (h1, h2) = hash_pair(value);
+        self.total += 1;
+        let mut estimate = u64::max_value();
+        for row in 0..self.depth {
+            let idx = self.counter_index(row, h1, h2);
+            self.counters[idx] += 1;
+            estimate = estimate.min(self.counters[idx]);
+        }
+        estimate
+    }
+}
+
+impl<T> Default for CountMinSketch<T> {
+    fn default() -> CountMinSketch<T> {
+        CountMinSketch::new()
+    }
+}
+
+impl<T> StatAccumulator<T> for CountMinSketch<T>
+where
+    T: Hash,
+{
+    /// A copy of the sketch, which can be queried for the frequency of any value.
+    type Output = CountMinSketch<T>;
+
+    fn push(&mut self, value: Value<&T>) {
+        if let Value::Exists(value) = value {
+            self.increment(value);
+        }
+    }
+    /// Combines the values accumulated by `other` into this accumulator.
+    ///
+    /// # Panics
+    /// Panics if `other` has different dimensions than this accumulator.
+    fn merge(&mut self, other: &Self) {
+        assert!(
+            self.width == other.width && self.depth == other.depth,
+            "cannot merge count-min sketches of different dimensions"
+        );
+        for (counter, &other_counter) in self.counters.iter_mut().zip(&other.counters) {
+            *counter += other_counter;
+        }
+        self.total += other.total;
+    }
+    fn result(&self) -> CountMinSketch<T> {
+        self.clone()
+    }
+}
+
+/// Accumulator estimating the `k` most frequent values (the 'heavy hitters'), using a
+/// [CountMinSketch](struct.CountMinSketch.html) to estimate frequencies. Only the `k` values
+/// with the highest estimated frequencies (rather than every distinct value) are kept, so memory
+/// use is bounded for high-cardinality fields. Missing values are ignored.
+///
+/// The resulting frequencies are estimates, and may overcount values (see
+/// [CountMinSketch](struct.CountMinSketch.html)).
+#[derive(Debug, Clone)]
+pub struct TopK<T>
+where
+    T: Hash + Eq,
+{
+    k: usize,
+    sketch: CountMinSketch<T>,
+    // candidate heavy hitters, with their estimated frequency and the order in which they were
+    // first tracked (used to break ties)
+    candidates: HashMap<T, (u64, u64)>,
+    next_order: u64,
+}
+
+impl<T> TopK<T>
+where
+    T: Hash + Eq,
+{
+    /// Creates a new `TopK` accumulator of the `k` most frequent values, using a
+    /// [CountMinSketch](struct.CountMinSketch.html) with the default dimensions.
+    pub fn new(k: usize) -> TopK<T> {
+        TopK::with_sketch(k, CountMinSketch::new())
+    }
+
+    /// Creates a new `TopK` accumulator of the `k` most frequent values, using the specified
+    /// (empty) `sketch` to estimate frequencies.
+    pub fn with_sketch(k: usize, sketch: CountMinSketch<T>) -> TopK<T> {
+        TopK {
+            k,
+            sketch,
+            candidates: HashMap::new(),
+            next_order: 0,
+        }
+    }
+
+    // updates the estimated frequency of `value`, tracking it if it is one of the `k` most
+    // frequent values
+    fn track(&mut self, value: &T, estimate: u64)
+    where
+        T: Clone,
+    {
+        if let Some(entry) = self.candidates.get_mut(value) {
+            entry.0 = estimate;
+            return;
+        }
+        if self.candidates.len() >= self.k {
+            // replace the least-frequent (and most recently tracked) candidate, if less frequent
+            let least = self
+                .candidates
+                .iter()
+                .min_by_key(|&(_, &(count, order))| (count, !order))
+                .map(|(candidate, &(count, _))| (candidate.clone(), count));
+            match least {
+                Some((ref least, count)) if count < estimate => {
+                    self.candidates.remove(least);
+                }
+                _ => return,
+            }
+        }
+        self.candidates
+            .insert(value.clone(), (estimate, self.next_order));
+        self.next_order += 1;
+    }
+}
+
+impl<T> StatAccumulator<T> for TopK<T>
+where
+    T: Hash + Eq + Clone,
+{
+    /// The (at most `k`) most frequent values with their estimated frequencies, in decreasing
+    /// order of frequency. Values with equal frequencies are in order of first appearance.
+    type Output = Vec<(T, u64)>;
+
+    fn push(&mut self, value: Value<&T>) {
+        if let Value::Exists(value) = value {
+            let estimate = self.sketch.increment(value);
+            self.track(value, estimate);
+        }
+    }
+    /// Combines the values accumulated by `other` into this accumulator.
+    ///
+    /// # Panics
+    /// Panics if the sketch of `other` has different dimensions than the sketch of this
+    /// accumulator.
+    fn merge(&mut self, other: &Self) {
+        self.sketch.merge(&other.sketch);
+        let mut others = other.candidates.iter().collect::<Vec<_>>();
+        others.sort_by_key(|&(_, &(_, order))| order);
+        let existing = self.candidates.keys().cloned().collect::<Vec<_>>();
+        for value in existing {
+            let estimate = self.sketch.estimate(&value);
+            self.track(&value, estimate);
+        }
+        for (value, _) in others {
+            let estimate = self.sketch.estimate(value);
+            self.track(value, estimate);
+        }
+    }
+    fn result(&self) -> Vec<(T, u64)> {
+        let mut top = self.candidates.iter().collect::<Vec<_>>();
+        top.sort_by_key(|&(_, &(count, order))| (!count, order));
+        top.into_iter()
+            .map(|(value, &(count, _))| (value.clone(), count))
+            .collect()
+    }
+}
+
+/// A trait for estimating the most frequent values in a field.
+pub trait ApproxTopK: DataIndex {
+    /// Estimates the `k` most frequent existing values in this field, along with their
+    /// frequencies, using a [TopK](struct.TopK.html) accumulator with a default-sized
+    /// [CountMinSketch](struct.CountMinSketch.html). This avoids counting every distinct value of
+    /// high-cardinality fields. Values are returned in decreasing order of estimated frequency.
+    fn approx_top_k(&self, k: usize) -> Vec<(Self::DType, u64)>;
+}
+
+impl<DI> ApproxTopK for DI
+where
+    DI: DataIndex,
+    DI::DType: Hash + Eq + Clone,
+{
+    fn approx_top_k(&self, k: usize) -> Vec<(DI::DType, u64)> {
+        let mut top_k = TopK::new(k);
+        top_k.push_iter(self.iter());
+        top_k.result()
+    }
+}
+
+/// Method used for computing quantiles over groups of records.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantileMethod {
+    /// Compute exact quantiles (as with [Quantile](trait.Quantile.html)), which requires sorting
+    /// the values of each group.
+    Exact,
+    /// Estimate quantiles of groups with at least `min_group_size` records using a
+    /// [QuantileSketch](struct.QuantileSketch.html) with the specified `compression`. Quantiles
+    /// of smaller groups are computed exactly.
+    Sketch {
+        /// Compression of the sketch.
+        compression: f64,
+        /// Minimum number of records in a group for its quantiles to be estimated.
+        min_group_size: usize,
+    },
+}
+
+impl Default for QuantileMethod {
+    fn default() -> QuantileMethod {
+        QuantileMethod::Exact
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,7 +1245,8 @@ mod tests {
 
     tablespace![
         pub table foo {
-            Foo: f64
+            Foo: f64,
+            Weight: f64,
         }
     ];
 
@@ -442,4 +1457,299 @@ mod tests {
             .into_view();
         assert_eq!(dv.field::<foo::Foo>().max(), None);
     }
+
+    #[test]
+    fn quantile() {
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, _, _, _>(vec![
+                Value::Exists(4u32),
+                Value::Na,
+                Value::Exists(1),
+                Value::Exists(3),
+                Value::Exists(2),
+            ])
+            .into_view();
+        assert_eq!(dv.field::<foo::Foo>().quantile(0.0), Some(1.0));
+        assert_eq!(dv.field::<foo::Foo>().quantile(0.25), Some(1.75));
+        assert_eq!(dv.field::<foo::Foo>().median(), Some(2.5));
+        assert_eq!(dv.field::<foo::Foo>().quantile(1.0), Some(4.0));
+        assert_eq!(dv.field::<foo::Foo>().quantile(1.5), None);
+
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, f64, _, _>(vec![Value::Na, Value::Na])
+            .into_view();
+        assert_eq!(dv.field::<foo::Foo>().median(), None);
+    }
+
+    #[test]
+    fn weighted_quantile() {
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, _, _, _>(vec![
+                Value::Exists(10.0),
+                Value::Exists(20.0),
+                Value::Exists(30.0),
+                Value::Exists(40.0),
+                Value::Na,
+            ])
+            .push_back_from_value_iter::<foo::Weight, _, _, _>(vec![
+                Value::Exists(1u64),
+                Value::Exists(2),
+                Value::Na,
+                Value::Exists(1),
+                Value::Exists(5),
+            ])
+            .into_view();
+        // equivalent to the unweighted values [10, 20, 20, 40]
+        let values = dv.field::<foo::Foo>();
+        let weights = dv.field::<foo::Weight>();
+        assert_eq!(values.weighted_quantile(&weights, 0.0), Some(10.0));
+        assert_eq!(values.weighted_quantile(&weights, 0.5), Some(20.0));
+        assert_eq!(values.weighted_quantile(&weights, 0.75), Some(25.0));
+        assert_eq!(values.weighted_quantile(&weights, 1.0), Some(40.0));
+    }
+
+    #[test]
+    fn idxmin_idxmax() {
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, _, _, _>(vec![
+                Value::Na,
+                Value::Exists(3i32),
+                Value::Exists(-9),
+                Value::Exists(12),
+                Value::Exists(-9),
+                Value::Exists(12),
+            ])
+            .into_view();
+        assert_eq!(dv.field::<foo::Foo>().idxmin(), Some(2));
+        assert_eq!(dv.field::<foo::Foo>().idxmax(), Some(3));
+
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, f64, _, _>(vec![Value::Na, Value::Na])
+            .into_view();
+        assert_eq!(dv.field::<foo::Foo>().idxmin(), None);
+        assert_eq!(dv.field::<foo::Foo>().idxmax(), None);
+    }
+
+    #[test]
+    fn accumulators() {
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, _, _, _>(vec![
+                Value::Exists(-5.0),
+                Value::Exists(-4.0),
+                Value::Na,
+                Value::Exists(12.0),
+                Value::Exists(3.0),
+                Value::Na,
+                Value::Exists(6.0),
+                Value::Exists(0.0),
+                Value::Exists(-3.1),
+            ])
+            .into_view();
+        let field = dv.field::<foo::Foo>();
+
+        // accumulate each chunk separately, then merge the partial results
+        let mut sums = vec![];
+        let mut moments = vec![];
+        let mut summaries = vec![];
+        for chunk in field.chunks(4) {
+            let chunk = chunk.collect::<Vec<_>>();
+            let mut sum = SumAccumulator::new();
+            sum.push_iter(chunk.iter().cloned());
+            sums.push(sum);
+            let mut moment = MomentsAccumulator::new();
+            moment.push_iter(chunk.iter().cloned());
+            moments.push(moment);
+            let mut summary = SummaryAccumulator::new();
+            summary.push_iter(chunk.iter().cloned());
+            summaries.push(summary);
+        }
+        assert_eq!(moments.len(), 3);
+
+        let mut sum = SumAccumulator::new();
+        let mut moment = MomentsAccumulator::new();
+        let mut summary = SummaryAccumulator::new();
+        for ((partial_sum, partial_moment), partial_summary) in
+            sums.iter().zip(&moments).zip(&summaries)
+        {
+            sum.merge(partial_sum);
+            moment.merge(partial_moment);
+            summary.merge(partial_summary);
+        }
+        assert!((sum.result() - field.sum()).abs() < 1e-10);
+        let result = moment.result();
+        assert_eq!((result.num_exists, result.num_na), (7, 2));
+        assert!((result.mean - field.mean()).abs() < 1e-10);
+        assert!((result.var - field.var()).abs() < 1e-10);
+        assert!((result.varp - field.varp()).abs() < 1e-10);
+        assert_eq!(summary.result(), FieldSummary::compute(&field));
+
+        // accumulating the whole field at once gives the same result
+        let mut whole = MomentsAccumulator::new();
+        whole.push_iter(field.iter());
+        assert!((whole.result().var - result.var).abs() < 1e-10);
+
+        // empty accumulators
+        assert_eq!(MomentsAccumulator::<f64>::new().result().var, 0.0);
+        assert_eq!(QuantileSketch::<f64>::new().result().quantile(0.5), None);
+        assert_eq!(
+            SummaryAccumulator::<f64>::new().result(),
+            FieldSummary {
+                min: None,
+                max: None,
+                num_na: 0
+            }
+        );
+    }
+
+    #[test]
+    fn quantile_sketch() {
+        let dv = DataStore::<Nil>::empty()
+            .push_back_from_value_iter::<foo::Foo, _, _, _>(vec![
+                Value::Exists(-5.0),
+                Value::Exists(-4.0),
+                Value::Na,
+                Value::Exists(12.0),
+                Value::Exists(3.0),
+                Value::Exists(6.0),
+                Value::Exists(0.0),
+            ])
+            .into_view();
+        let field = dv.field::<foo::Foo>();
+
+        // small sketches are exact
+        let mut sketch = QuantileSketch::new();
+        sketch.push_iter(field.iter());
+        assert_eq!(sketch.count(), 6);
+        for &q in &[0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0] {
+            assert!((sketch.quantile(q).unwrap() - field.quantile(q).unwrap()).abs() < 1e-10);
+        }
+        assert_eq!(sketch.quantile(1.5), None);
+        let values = (0..90u64)
+            .map(|i| ((i * 37) % 90) as f64 / 4.0)
+            .collect::<FieldData<_>>();
+        let mut sketch = QuantileSketch::new();
+        sketch.push_iter(values.iter());
+        let sketch = sketch.result();
+        assert_eq!(sketch.centroids.len(), 90);
+        for &q in &[0.0, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 1.0] {
+            assert!((sketch.quantile(q).unwrap() - values.quantile(q).unwrap()).abs() < 1e-10);
+        }
+
+        // large sketches are approximate, and much smaller than the data
+        let values = (0..10000u64)
+            .map(|i| ((i * 7919) % 10000) as f64)
+            .collect::<FieldData<_>>();
+        let mut halves = vec![];
+        for half in values.chunks(5000) {
+            let mut sketch = QuantileSketch::with_compression(50.0);
+            sketch.push_iter(half);
+            halves.push(sketch);
+        }
+        let mut sketch = QuantileSketch::with_compression(50.0);
+        for half in &halves {
+            sketch.merge(half);
+        }
+        let sketch = sketch.result();
+        assert!(sketch.centroids.len() < 60);
+        assert_eq!(sketch.count(), 10000);
+        assert_eq!(sketch.quantile(0.0), Some(0.0));
+        assert_eq!(sketch.quantile(1.0), Some(9999.0));
+        for &q in &[0.01, 0.25, 0.5, 0.75, 0.99] {
+            let (estimate, exact) = (sketch.quantile(q).unwrap(), values.quantile(q).unwrap());
+            assert!((estimate - exact).abs() < 100.0);
+        }
+    }
+
+    #[test]
+    fn approx_nunique() {
+        let data = vec![Some("a"), Some("b"), None, Some("a"), Some("c"), Some("b")]
+            .into_iter()
+            .collect::<FieldData<_>>();
+        assert_eq!(data.approx_nunique(), 3);
+        assert_eq!(FieldData::<u64>::default().approx_nunique(), 0);
+
+        // large cardinalities are estimated
+        let values = (0..100000u64).map(|i| i % 60000).collect::<Vec<_>>();
+        let estimate = values
+            .iter()
+            .cloned()
+            .collect::<FieldData<_>>()
+            .approx_nunique();
+        assert!((estimate as f64 - 60000.0).abs() < 0.05 * 60000.0);
+
+        // merging sketches of overlapping chunks gives the same estimate as the whole field
+        let mut sketch = HyperLogLog::new();
+        for chunk in values.chunks(30000) {
+            let mut partial = HyperLogLog::new();
+            partial.push_iter(chunk.iter().map(Value::Exists));
+            sketch.merge(&partial);
+        }
+        assert_eq!(sketch.result(), estimate);
+
+        // lower precisions use fewer registers
+        let mut sketch = HyperLogLog::with_precision(6);
+        sketch.push_iter(values.iter().map(Value::Exists));
+        assert_eq!(sketch.registers.len(), 64);
+        assert!((sketch.result() as f64 - 60000.0).abs() < 0.5 * 60000.0);
+    }
+
+    #[test]
+    fn approx_top_k() {
+        let data = vec![
+            Some("a"),
+            Some("b"),
+            None,
+            Some("c"),
+            Some("b"),
+            Some("a"),
+            Some("b"),
+            Some("d"),
+        ]
+        .into_iter()
+        .collect::<FieldData<_>>();
+        assert_eq!(data.approx_top_k(2), vec![("b", 3), ("a", 2)]);
+        // ties are ordered by first appearance
+        assert_eq!(
+            data.approx_top_k(5),
+            vec![("b", 3), ("a", 2), ("c", 1), ("d", 1)]
+        );
+        assert!(data.approx_top_k(0).is_empty());
+
+        let mut sketch = CountMinSketch::new();
+        sketch.push_iter(data.iter());
+        assert_eq!(sketch.total(), 7);
+        assert_eq!(sketch.estimate(&"b"), 3);
+        assert_eq!(sketch.estimate(&"e"), 0);
+
+        // heavy hitters are found among many infrequent values, even with a small sketch
+        let values = (0..20000u64)
+            .map(|i| if i % 4 == 0 { i % 3 } else { i })
+            .collect::<Vec<_>>();
+        let mut top_k = TopK::with_sketch(3, CountMinSketch::with_dimensions(256, 4));
+        top_k.push_iter(values.iter().map(Value::Exists));
+        let top = top_k.result();
+        let mut top_values = top.iter().map(|&(value, _)| value).collect::<Vec<_>>();
+        top_values.sort();
+        assert_eq!(top_values, vec![0, 1, 2]);
+        for &(_, count) in &top {
+            // each heavy hitter appears ~1667 times; estimates never undercount
+            assert!(count >= 1666 && count < 1666 + 200);
+        }
+
+        // merging accumulators of chunks finds the same heavy hitters
+        let mut merged = TopK::with_sketch(3, CountMinSketch::with_dimensions(256, 4));
+        for chunk in values.chunks(5000) {
+            let mut partial = TopK::with_sketch(3, CountMinSketch::with_dimensions(256, 4));
+            partial.push_iter(chunk.iter().map(Value::Exists));
+            merged.merge(&partial);
+        }
+        assert_eq!(merged.sketch, top_k.sketch);
+        let mut merged_values = merged
+            .result()
+            .iter()
+            .map(|&(value, _)| value)
+            .collect::<Vec<_>>();
+        merged_values.sort();
+        assert_eq!(merged_values, vec![0, 1, 2]);
+    }
 }
@@ -3,7 +3,10 @@ Data storage struct and implementation.
 
 [DataStore](struct.DataStore.html) represents and stores the data from a single data source.
 */
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -19,22 +22,72 @@ use fieldlist::{FieldCons, FieldPayloadCons, FieldSchema};
 use frame::{DataFrame, SimpleFrameFields};
 use label::*;
 use select::{FieldSelect, SelectFieldByLabel};
-use value::Value;
+use stats::FieldSummary;
+use value::{IntoValue, Value};
 use view::{DataView, FrameLookupCons, ViewFrameCons};
 
 /// Local `Rc` wrapper type for [FieldData](../field/struct.FieldData.html) objects.
-#[derive(Debug, Hash, PartialEq, Eq)]
-pub struct DataRef<DType>(pub Rc<FieldData<DType>>);
+///
+/// A `DataRef` also holds a lazily-computed [FieldSummary](../stats/struct.FieldSummary.html) of
+/// its data, as well as (optionally) the hash of each of its values, both of which are shared
+/// among all clones of the `DataRef`. Since stored field data is never mutated, these remain valid
+/// for the lifetime of the data.
+#[derive(Debug)]
+pub struct DataRef<DType> {
+    data: Rc<FieldData<DType>>,
+    summary: Rc<RefCell<Option<FieldSummary<DType>>>>,
+    hashes: Rc<RefCell<Option<Vec<u64>>>>,
+}
 
 impl<DType> DataRef<DType> {
-    fn new(field: FieldData<DType>) -> DataRef<DType> {
-        DataRef(Rc::new(field))
+    /// Creates a new `DataRef` holding the provided field data.
+    pub fn new(field: FieldData<DType>) -> DataRef<DType> {
+        DataRef::from(Rc::new(field))
+    }
+}
+
+impl<DType> DataRef<DType>
+where
+    DType: Debug + Clone + PartialOrd,
+{
+    /// Returns the summary statistics of this field's data. The summary is computed on first
+    /// access, and cached thereafter.
+    pub fn summary(&self) -> FieldSummary<DType> {
+        if let Some(ref summary) = *self.summary.borrow() {
+            return summary.clone();
+        }
+        let summary = FieldSummary::compute(&*self.data);
+        *self.summary.borrow_mut() = Some(summary.clone());
+        summary
     }
 }
 
 impl<DType> Clone for DataRef<DType> {
     fn clone(&self) -> DataRef<DType> {
-        DataRef(Rc::clone(&self.0))
+        DataRef {
+            data: Rc::clone(&self.data),
+            summary: Rc::clone(&self.summary),
+            hashes: Rc::clone(&self.hashes),
+        }
+    }
+}
+
+impl<DType> PartialEq for DataRef<DType>
+where
+    DType: PartialEq,
+{
+    fn eq(&self, other: &DataRef<DType>) -> bool {
+        self.data == other.data
+    }
+}
+impl<DType> Eq for DataRef<DType> where DType: Eq {}
+
+impl<DType> Hash for DataRef<DType>
+where
+    DType: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state)
     }
 }
 
@@ -42,13 +95,23 @@ impl<T> Deref for DataRef<T> {
     type Target = FieldData<T>;
 
     fn deref(&self) -> &FieldData<T> {
-        &self.0.deref()
+        &self.data.deref()
     }
 }
 
 impl<T> From<FieldData<T>> for DataRef<T> {
     fn from(orig: FieldData<T>) -> DataRef<T> {
-        DataRef(Rc::new(orig))
+        DataRef::new(orig)
+    }
+}
+
+impl<T> From<Rc<FieldData<T>>> for DataRef<T> {
+    fn from(orig: Rc<FieldData<T>>) -> DataRef<T> {
+        DataRef {
+            data: orig,
+            summary: Rc::new(RefCell::new(None)),
+            hashes: Rc::new(RefCell::new(None)),
+        }
     }
 }
 
@@ -60,10 +123,85 @@ where
     type DType = T;
 
     fn get_datum(&self, idx: usize) -> error::Result<Value<&T>> {
-        <FieldData<T> as DataIndex>::get_datum(&self.0, idx)
+        <FieldData<T> as DataIndex>::get_datum(&self.data, idx)
     }
     fn len(&self) -> usize {
-        <FieldData<T> as DataIndex>::len(&self.0)
+        <FieldData<T> as DataIndex>::len(&self.data)
+    }
+    fn count_na(&self) -> usize {
+        <FieldData<T> as DataIndex>::count_na(&self.data)
+    }
+    fn as_field_data(&self) -> Option<&FieldData<T>> {
+        Some(&self.data)
+    }
+    fn cached_summary(&self) -> Option<FieldSummary<T>>
+    where
+        T: Clone + PartialOrd,
+    {
+        Some(self.summary())
+    }
+    fn cached_hash(&self, idx: usize) -> Option<u64> {
+        self.hashes
+            .borrow()
+            .as_ref()
+            .and_then(|hashes| hashes.get(idx).cloned())
+    }
+}
+
+/// Trait for computing and caching the hashes of the values of a data field (or a list of data
+/// fields), so that operations which group records by these fields (such as
+/// [aggregate](../view/struct.DataView.html#method.aggregate) or
+/// [group_apply](../view/struct.DataView.html#method.group_apply)) can reuse the cached hashes
+/// instead of rehashing every value. Hashes are cached with the stored field data, so they remain
+/// available to all views of the data (including filtered and sorted views).
+pub trait CacheHashes {
+    /// Computes and caches the hashes of the values of this field (or list of fields). Does nothing
+    /// if the hashes have already been cached.
+    fn cache_hashes(&self);
+}
+impl<T> CacheHashes for DataRef<T>
+where
+    T: Debug,
+    for<'a> Value<&'a T>: Hash,
+{
+    fn cache_hashes(&self) {
+        if self.hashes.borrow().is_some() {
+            return;
+        }
+        let hashes = self
+            .data
+            .iter()
+            .map(|value| {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        *self.hashes.borrow_mut() = Some(hashes);
+    }
+}
+impl CacheHashes for Nil {
+    fn cache_hashes(&self) {}
+}
+impl<Head, Tail> CacheHashes for Cons<Head, Tail>
+where
+    for<'a> &'a Self: Fold<CacheFieldHashes, ()>,
+{
+    fn cache_hashes(&self) {
+        self.fold_list((), &mut CacheFieldHashes);
+    }
+}
+
+/// Function which computes and caches the hashes of each field of a list of fields when
+/// [folded](../cons/trait.Fold.html) over a reference to the list (see
+/// [CacheHashes](trait.CacheHashes.html)).
+pub struct CacheFieldHashes;
+impl<'a, T> FoldFunc<(), &'a T> for CacheFieldHashes
+where
+    T: CacheHashes,
+{
+    fn call(&mut self, _acc: (), field: &'a T) {
+        field.cache_hashes();
     }
 }
 
@@ -76,7 +214,7 @@ where
     where
         S: Serializer,
     {
-        self.0.serialize(serializer)
+        self.data.serialize(serializer)
     }
 }
 
@@ -203,8 +341,9 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<Self::OutputFields>
             where
-                Iter: Iterator<Item = Value<NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<NewDType>>;
+                Iter: Iterator,
+                Iter::Item: IntoValue<NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>;
         }
         impl<PrevFields, NewLabel, NewDType> $add_valiter_trait<NewLabel, NewDType>
             for DataStore<PrevFields>
@@ -225,13 +364,16 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<Self::OutputFields>
             where
-                Iter: Iterator<Item = Value<NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<NewDType>>,
+                Iter: Iterator,
+                Iter::Item: IntoValue<NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>,
             {
                 DataStore {
                     data: self.data.$push_fn(
                         TypedValue::from(DataRef::new(
-                            iter.into_iter().collect::<FieldData<NewDType>>(),
+                            iter.into_iter()
+                                .map(IntoValue::<NewDType>::into_value)
+                                .collect::<FieldData<NewDType>>(),
                         ))
                         .into(),
                     ),
@@ -291,8 +433,9 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<Self::OutputFields>
             where
-                Iter: Iterator<Item = Value<&'a NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<&'a NewDType>>,
+                Iter: Iterator,
+                Iter::Item: IntoValue<&'a NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>,
                 NewDType: 'a;
         }
         impl<PrevFields, NewLabel, NewDType> $add_cloned_valiter_trait<NewLabel, NewDType>
@@ -314,15 +457,16 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<Self::OutputFields>
             where
-                Iter: Iterator<Item = Value<&'a NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<&'a NewDType>>,
+                Iter: Iterator,
+                Iter::Item: IntoValue<&'a NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>,
                 NewDType: 'a,
             {
                 DataStore {
                     data: self.data.$push_fn(
                         TypedValue::from(DataRef::new(
                             iter.into_iter()
-                                .map(|x| x.clone())
+                                .map(|x| IntoValue::<&'a NewDType>::into_value(x).cloned())
                                 .collect::<FieldData<NewDType>>(),
                         ))
                         .into(),
@@ -434,8 +578,9 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<<Self as $add_valiter_trait<NewLabel, NewDType>>::OutputFields>
             where
-                Iter: Iterator<Item = Value<NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<NewDType>>,
+                Iter: Iterator,
+                Iter::Item: IntoValue<NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>,
                 Self: $add_valiter_trait<NewLabel, NewDType>,
             {
                 $add_valiter_trait::$add_valiter_fn(self, iter)
@@ -460,8 +605,9 @@ macro_rules! make_add_field {
                 iter: IntoIter,
             ) -> DataStore<<Self as $add_cloned_valiter_trait<NewLabel, NewDType>>::OutputFields>
             where
-                Iter: Iterator<Item = Value<&'a NewDType>>,
-                IntoIter: IntoIterator<IntoIter = Iter, Item = Value<&'a NewDType>>,
+                Iter: Iterator,
+                Iter::Item: IntoValue<&'a NewDType>,
+                IntoIter: IntoIterator<IntoIter = Iter, Item = Iter::Item>,
                 Self: $add_cloned_valiter_trait<NewLabel, NewDType>,
                 NewDType: 'a,
             {
@@ -503,24 +649,28 @@ make_add_field![
     fn push_front_field;
 
     /// Trait for pushing a field onto the front of a [DataStore](struct.DataStore.html)'s fields
-    /// cons-list using data from an iterator of [Value](../field/enum.Value.html) objects.
+    /// cons-list using data from an iterator of [Value](../field/enum.Value.html) (or `Option`)
+    /// objects.
     trait PushFrontFromValueIter;
     /// Push a field onto the front of this store's fields cons-list using data from an iterator
-    /// of [Value](../field/enum.Value.html) objects.
+    /// of [Value](../field/enum.Value.html) (or `Option`) objects.
     fn push_front_from_value_iter;
 
     /// Trait for pushing a field onto the front of a [DataStore](struct.DataStore.html)'s fields
     /// cons-list using data from an iterator of objects. Field is assumed to have no missing data.
     trait PushFrontFromIter;
     /// Push a field onto the front of this store's fields cons-list using data from an iterator
-    /// of objects.
+    /// of objects. Use
+    /// [push_front_from_value_iter](struct.DataStore.html#method.push_front_from_value_iter)
+    /// for iterators of `Option`s.
     fn push_front_from_iter;
 
     /// Trait for pushing a field onto the front of a [DataStore](struct.DataStore.html)'s fields
-    /// cons-list cloning data from an iterator of [Value](../field/enum.Value.html) objects.
+    /// cons-list cloning data from an iterator of [Value](../field/enum.Value.html) (or `Option`)
+    /// objects.
     trait PushFrontClonedFromValueIter;
     /// Push a field onto the front of this store's fields cons-list cloning data from an iterator
-    /// of [Value](../field/enum.Value.html) objects.
+    /// of [Value](../field/enum.Value.html) (or `Option`) objects.
     fn push_front_cloned_from_value_iter;
 
     /// Trait for pushing a field onto the front of a [DataStore](struct.DataStore.html)'s fields
@@ -549,24 +699,28 @@ make_add_field![
     fn push_back_field;
 
     /// Trait for pushing a field onto the back of a [DataStore](struct.DataStore.html)'s fields
-    /// cons-list using data from an iterator of [Value](../field/enum.Value.html) objects.
+    /// cons-list using data from an iterator of [Value](../field/enum.Value.html) (or `Option`)
+    /// objects.
     trait PushBackFromValueIter;
     /// Push a field onto the back of this store's fields cons-list using data from an iterator
-    /// of [Value](../field/enum.Value.html) objects.
+    /// of [Value](../field/enum.Value.html) (or `Option`) objects.
     fn push_back_from_value_iter;
 
     /// Trait for pushing a field onto the back of a [DataStore](struct.DataStore.html)'s fields
     /// cons-list using data from an iterator of objects. Field is assumed to have no missing data.
     trait PushBackFromIter;
     /// Push a field onto the back of this store's fields cons-list using data from an iterator
-    /// of objects.
+    /// of objects. Use
+    /// [push_back_from_value_iter](struct.DataStore.html#method.push_back_from_value_iter)
+    /// for iterators of `Option`s.
     fn push_back_from_iter;
 
     /// Trait for pushing a field onto the back of a [DataStore](struct.DataStore.html)'s fields
-    /// cons-list cloning data from an iterator of [Value](../field/enum.Value.html) objects.
+    /// cons-list cloning data from an iterator of [Value](../field/enum.Value.html) (or `Option`)
+    /// objects.
     trait PushBackClonedFromValueIter;
     /// Push a field onto the back of this store's fields cons-list cloning data from an iterator
-    /// of [Value](../field/enum.Value.html) objects.
+    /// of [Value](../field/enum.Value.html) (or `Option`) objects.
     fn push_back_cloned_from_value_iter;
 
     /// Trait for pushing a field onto the back of a [DataStore](struct.DataStore.html)'s fields
@@ -704,6 +858,219 @@ where
     }
 }
 
+/// Trait for merging two [DataStore](struct.DataStore.html)s side-by-side, resulting in a single
+/// `DataStore` containing the fields of both.
+///
+/// The two stores should have the same number of rows. Their fields must also have distinct
+/// labels: merging stores with a label in common fails to compile (such fields should be relabeled
+/// or dropped before merging).
+pub trait StoreMerge<OtherFields>
+where
+    OtherFields: AssocStorage,
+{
+    /// [FieldCons](../fieldlist/type.FieldCons.html) cons-list of the merged store.
+    type OutputFields: AssocStorage;
+
+    /// Merge this `DataStore` with another `DataStore`. Fails if the stores do not have the same
+    /// number of rows.
+    fn merge_stores(
+        self,
+        other: DataStore<OtherFields>,
+    ) -> error::Result<DataStore<Self::OutputFields>>;
+}
+impl<Fields, OtherFields> StoreMerge<OtherFields> for DataStore<Fields>
+where
+    Fields: AssocStorage + Append<OtherFields>,
+    OtherFields: AssocStorage,
+    <Fields as Append<OtherFields>>::Appended: AssocStorage + IsLabelSet<IsSet = True>,
+    Fields::Storage: Append<
+        OtherFields::Storage,
+        Appended = <<Fields as Append<OtherFields>>::Appended as AssocStorage>::Storage,
+    >,
+    DataStore<Fields>: NRows,
+    DataStore<OtherFields>: NRows,
+{
+    type OutputFields = <Fields as Append<OtherFields>>::Appended;
+
+    fn merge_stores(
+        self,
+        other: DataStore<OtherFields>,
+    ) -> error::Result<DataStore<Self::OutputFields>> {
+        if self.nrows() != other.nrows() {
+            return Err(error::AgnesError::DimensionMismatch(
+                "number of rows mismatch in store merge".into(),
+            ));
+        }
+        Ok(DataStore {
+            data: self.data.append(other.data),
+        })
+    }
+}
+
+impl<Fields> DataStore<Fields>
+where
+    Fields: AssocStorage,
+{
+    /// Merge this `DataStore` with another `DataStore`, creating a new `DataStore` containing the
+    /// fields of this store followed by the fields of `other`. Field data is not copied. Unlike
+    /// [merge](../view/struct.DataView.html#method.merge)-ing views of the two stores, the
+    /// resulting fields all reside in a single `DataStore`.
+    ///
+    /// Fails if the two stores have a different number of rows. Stores with a field label in
+    /// common cannot be merged.
+    pub fn merge_stores<OtherFields>(
+        self,
+        other: DataStore<OtherFields>,
+    ) -> error::Result<DataStore<<Self as StoreMerge<OtherFields>>::OutputFields>>
+    where
+        OtherFields: AssocStorage,
+        Self: StoreMerge<OtherFields>,
+    {
+        StoreMerge::merge_stores(self, other)
+    }
+}
+
+/// Trait for converting a collection of values into a [FieldData](../field/struct.FieldData.html)
+/// with data type `DType`. Implemented for vectors of values (`Vec<T>`), as well as vectors of
+/// possibly-missing values (`Vec<Option<T>>` and `Vec<Value<T>>`).
+pub trait IntoFieldData<DType> {
+    /// Converts this collection into a `FieldData`.
+    fn into_field_data(self) -> FieldData<DType>;
+}
+impl<T> IntoFieldData<T> for FieldData<T> {
+    fn into_field_data(self) -> FieldData<T> {
+        self
+    }
+}
+impl<T> IntoFieldData<T> for Vec<T> {
+    fn into_field_data(self) -> FieldData<T> {
+        FieldData::from_vec(self)
+    }
+}
+impl<T> IntoFieldData<T> for Vec<Option<T>>
+where
+    T: Debug + Default,
+{
+    fn into_field_data(self) -> FieldData<T> {
+        self.into_iter().collect()
+    }
+}
+impl<T> IntoFieldData<T> for Vec<Value<T>>
+where
+    T: Debug + Default,
+{
+    fn into_field_data(self) -> FieldData<T> {
+        FieldData::from_field_vec(self)
+    }
+}
+
+/// Trait for constructing a [DataStore](struct.DataStore.html) from a list of columns. Implemented
+/// for cons-lists and tuples of `(Label, Values)` pairs, where `Label` is a field label and
+/// `Values` implements [IntoFieldData](trait.IntoFieldData.html) for the label's data type (e.g. a
+/// `Vec<T>` or `Vec<Option<T>>`). Fields are stored in the same order as the columns.
+///
+/// Note that a single column must still be provided as a tuple (i.e. `((Label, values),)`).
+pub trait IntoColumnStore {
+    /// [FieldCons](../fieldlist/type.FieldCons.html) cons-list of the resulting store.
+    type Fields: AssocStorage;
+
+    /// Converts these columns into a `DataStore`.
+    fn into_column_store(self) -> DataStore<Self::Fields>;
+}
+impl IntoColumnStore for Nil {
+    type Fields = Nil;
+
+    fn into_column_store(self) -> DataStore<Nil> {
+        DataStore::<Nil>::empty()
+    }
+}
+impl<Label, Values, Tail> IntoColumnStore for Cons<(Label, Values), Tail>
+where
+    Tail: IntoColumnStore,
+    Label: Typed,
+    Values: IntoFieldData<Label::DType>,
+    DataStore<Tail::Fields>: PushFrontField<Label, Label::DType>,
+{
+    type Fields = <DataStore<Tail::Fields> as PushFrontField<Label, Label::DType>>::OutputFields;
+
+    fn into_column_store(self) -> DataStore<Self::Fields> {
+        let (_, values) = self.head;
+        PushFrontField::<Label, Label::DType>::push_front_field(
+            self.tail.into_column_store(),
+            values.into_field_data(),
+        )
+    }
+}
+
+macro_rules! column_cons {
+    (type) => { Nil };
+    (type $head:ident $(, $rest:ident)*) => { Cons<$head, column_cons![type $($rest),*]> };
+    (expr) => { Nil };
+    (expr $head:ident $(, $rest:ident)*) => { cons($head, column_cons![expr $($rest),*]) };
+}
+
+macro_rules! impl_tuple_column_store {
+    ($($col:ident),*) => {
+        impl<$($col),*> IntoColumnStore for ($($col,)*)
+        where
+            column_cons![type $($col),*]: IntoColumnStore,
+        {
+            type Fields = <column_cons![type $($col),*] as IntoColumnStore>::Fields;
+
+            #[allow(non_snake_case)]
+            fn into_column_store(self) -> DataStore<Self::Fields> {
+                let ($($col,)*) = self;
+                column_cons![expr $($col),*].into_column_store()
+            }
+        }
+    };
+}
+impl_tuple_column_store![A];
+impl_tuple_column_store![A, B];
+impl_tuple_column_store![A, B, C];
+impl_tuple_column_store![A, B, C, D];
+impl_tuple_column_store![A, B, C, D, E];
+impl_tuple_column_store![A, B, C, D, E, F];
+impl_tuple_column_store![A, B, C, D, E, F, G];
+impl_tuple_column_store![A, B, C, D, E, F, G, H];
+impl_tuple_column_store![A, B, C, D, E, F, G, H, I];
+impl_tuple_column_store![A, B, C, D, E, F, G, H, I, J];
+
+impl DataStore<Nil> {
+    /// Constructs a new `DataStore` from a list of columns: a tuple (or cons-list) of
+    /// `(Label, Values)` pairs, where `Values` is a `Vec<T>` or `Vec<Option<T>>` of the data type
+    /// of the field labeled `Label`. See [IntoColumnStore](trait.IntoColumnStore.html).
+    pub fn from_columns<Columns>(columns: Columns) -> DataStore<Columns::Fields>
+    where
+        Columns: IntoColumnStore,
+    {
+        columns.into_column_store()
+    }
+}
+
+impl DataView<Nil, Nil> {
+    /// Constructs a new `DataView` from a list of columns: a tuple (or cons-list) of
+    /// `(Label, Values)` pairs, where `Values` is a `Vec<T>` or `Vec<Option<T>>` of the data type
+    /// of the field labeled `Label`. See [IntoColumnStore](../store/trait.IntoColumnStore.html).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let dv = DataView::from_columns((
+    ///     (emp_table::EmpId, vec![0u64, 2, 5]),
+    ///     (emp_table::EmpName, vec![Some("Sally".to_string()), None, Some("Bob".to_string())]),
+    /// ));
+    /// ```
+    pub fn from_columns<Columns>(
+        columns: Columns,
+    ) -> <DataStore<Columns::Fields> as IntoView>::Output
+    where
+        Columns: IntoColumnStore,
+        DataStore<Columns::Fields>: IntoView,
+    {
+        IntoView::into_view(columns.into_column_store())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -714,10 +1081,14 @@ mod tests {
     use csv_sniffer::metadata::Metadata;
 
     use super::{DataStore, NRows};
+    use access::DataIndex;
     use cons::*;
+    use error::AgnesError;
     use select::FieldSelect;
     use source::csv::{CsvReader, CsvSource, IntoCsvSrcSchema};
+    use stats::FieldSummary;
     use value::Value;
+    use view::DataView;
 
     fn load_csv_file<Schema>(
         filename: &str,
@@ -749,6 +1120,10 @@ mod tests {
             CountryCode: String,
             Year1983: f64,
         }
+        pub table timesheet {
+            Hours: u64,
+            Name: String,
+        }
     ];
 
     #[test]
@@ -786,4 +1161,144 @@ mod tests {
         assert_eq!(ds.nrows(), EXPECTED_GDP_NROWS);
         assert_eq!(ds.field::<gdp::CountryName>().len(), EXPECTED_GDP_NROWS);
     }
+
+    #[test]
+    fn field_summary() {
+        let ds =
+            DataStore::<Nil>::empty().push_back_from_value_iter::<timesheet::Hours, _, _, _>(vec![
+                Value::Exists(4u64),
+                Value::Exists(1),
+                Value::Na,
+                Value::Exists(8),
+            ]);
+        let field = ds.field::<timesheet::Hours>();
+        assert_eq!(
+            field.summary(),
+            FieldSummary {
+                min: Some(1),
+                max: Some(8),
+                num_na: 1
+            }
+        );
+
+        // views share the summary cached in the store
+        let dv = ds.into_view();
+        assert_eq!(
+            dv.field::<timesheet::Hours>().cached_summary(),
+            Some(field.summary())
+        );
+        assert_eq!(dv.clone().query("Hours > 8").unwrap().nrows(), 0);
+        assert_eq!(dv.clone().query("Hours == NA").unwrap().nrows(), 1);
+        let dv = dv.query("Hours >= 4").unwrap();
+        assert_eq!(dv.nrows(), 2);
+        // the summary does not apply to filtered fields
+        assert_eq!(dv.field::<timesheet::Hours>().cached_summary(), None);
+    }
+
+    #[test]
+    fn push_plain_iter() {
+        let ds = DataStore::<Nil>::empty()
+            .push_back_from_iter::<timesheet::Hours, _, _, _>((1u64..4).map(|x| x * 2))
+            .push_front_from_iter::<timesheet::Name, _, _, _>(
+                vec!["Sally", "Jamie", "Bob"].into_iter().map(String::from),
+            );
+        assert_eq!(ds.nrows(), 3);
+        assert_eq!(
+            ds.field::<timesheet::Hours>().to_value_vec(),
+            vec![Value::Exists(2), Value::Exists(4), Value::Exists(6)]
+        );
+        assert_eq!(ds.field::<timesheet::Name>().summary().num_na, 0);
+    }
+
+    #[test]
+    fn push_option_iter() {
+        let ds =
+            DataStore::<Nil>::empty().push_back_from_value_iter::<timesheet::Hours, _, _, _>(vec![
+                Some(4u64),
+                None,
+                Some(8),
+            ]);
+        assert_eq!(
+            ds.field::<timesheet::Hours>().to_value_vec(),
+            vec![Value::Exists(4), Value::Na, Value::Exists(8)]
+        );
+
+        let names = vec!["Sally".to_string(), "Jamie".to_string()];
+        let ds =
+            DataStore::<Nil>::empty().push_back_cloned_from_value_iter::<timesheet::Name, _, _, _>(
+                vec![None, Some(&names[1]), Some(&names[0])],
+            );
+        assert_eq!(
+            ds.field::<timesheet::Name>().to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists("Jamie".to_string()),
+                Value::Exists("Sally".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn from_columns() {
+        let ds = DataStore::from_columns((
+            (timesheet::Hours, vec![4u64, 1, 8]),
+            (
+                timesheet::Name,
+                vec![Some("Sally".to_string()), None, Some("Jamie".to_string())],
+            ),
+        ));
+        assert_eq!(ds.nrows(), 3);
+        assert_eq!(ds.field::<timesheet::Hours>().to_vec(), vec![4, 1, 8]);
+        assert_eq!(
+            ds.field::<timesheet::Name>().to_value_vec(),
+            vec![
+                Value::Exists("Sally".to_string()),
+                Value::Na,
+                Value::Exists("Jamie".to_string())
+            ]
+        );
+
+        // single column, as a cons-list
+        let dv = DataView::from_columns(cons(
+            (timesheet::Hours, vec![Value::Exists(4u64), Value::Na]),
+            Nil,
+        ));
+        assert_eq!(dv.nrows(), 2);
+        assert_eq!(dv.fieldnames(), vec!["Hours"]);
+        assert_eq!(
+            dv.field::<timesheet::Hours>().to_value_vec(),
+            vec![Value::Exists(4), Value::Na]
+        );
+    }
+
+    #[test]
+    fn merge_stores() {
+        let hours = DataStore::from_columns(((timesheet::Hours, vec![4u64, 1, 8]),));
+        let names = DataStore::from_columns((
+            (
+                timesheet::Name,
+                vec!["Sally".to_string(), "Jamie".to_string(), "Bob".to_string()],
+            ),
+            (gdp::Year1983, vec![Some(1.5), None, Some(3.0)]),
+        ));
+        let ds = hours.merge_stores(names).unwrap();
+        assert_eq!(ds.nrows(), 3);
+        let dv = ds.into_view();
+        assert_eq!(dv.fieldnames(), vec!["Hours", "Name", "Year1983"]);
+        assert_eq!(dv.field::<timesheet::Hours>().to_vec(), vec![4, 1, 8]);
+        assert_eq!(
+            dv.field::<timesheet::Name>().to_vec(),
+            vec!["Sally", "Jamie", "Bob"]
+        );
+        assert_eq!(
+            dv.field::<gdp::Year1983>().to_value_vec(),
+            vec![Value::Exists(1.5), Value::Na, Value::Exists(3.0)]
+        );
+
+        let short = DataStore::from_columns(((gdp::CountryCode, vec!["USA".to_string()]),));
+        match DataStore::from_columns(((timesheet::Hours, vec![4u64, 1]),)).merge_stores(short) {
+            Err(AgnesError::DimensionMismatch(_)) => {}
+            _ => panic!("expected dimension mismatch error"),
+        }
+    }
 }
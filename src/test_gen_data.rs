@@ -1,12 +1,12 @@
 use field::FieldData;
 use rand::distributions as rdists;
 use rand::distributions::Distribution;
-use rand::StdRng;
 
+use rng::SeededRng;
 use value::Value;
 
 pub trait Generate<T> {
-    fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<T>;
+    fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<T>;
 }
 
 pub struct Normal {
@@ -20,7 +20,7 @@ impl Normal {
 }
 
 impl Generate<u64> for Normal {
-    fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<u64> {
+    fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<u64> {
         let normal = rdists::Normal::new(self.mean, self.stdev);
         normal
             .sample_iter(rng)
@@ -32,7 +32,7 @@ impl Generate<u64> for Normal {
 }
 
 impl Generate<i64> for Normal {
-    fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<i64> {
+    fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<i64> {
         let normal = rdists::Normal::new(self.mean, self.stdev);
         normal
             .sample_iter(rng)
@@ -44,7 +44,7 @@ impl Generate<i64> for Normal {
 }
 
 impl Generate<f64> for Normal {
-    fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<f64> {
+    fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<f64> {
         let normal = rdists::Normal::new(self.mean, self.stdev);
         normal
             .sample_iter(rng)
@@ -69,7 +69,7 @@ macro_rules! impl_uniform_generate {
     ($($t:ty)*) => {$(
 
         impl Generate<$t> for Uniform<$t> {
-            fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<$t> {
+            fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<$t> {
 
                 let uniform = rdists::Uniform::new(self.low, self.high);
                 uniform
@@ -99,7 +99,7 @@ macro_rules! impl_uniform_choice_generate {
     ($($t:ty)*) => {$(
 
         impl Generate<$t> for UniformChoice<$t> {
-            fn generate(&self, sz: usize, rng: &mut StdRng) -> FieldData<$t> {
+            fn generate(&self, sz: usize, rng: &mut SeededRng) -> FieldData<$t> {
                 let uniform = rdists::Uniform::new(0, self.choices.len());
                 uniform
                     .sample_iter(rng)
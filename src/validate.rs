@@ -0,0 +1,230 @@
+/*!
+Validation checks for the contents of `DataView` fields.
+
+These checks are primarily intended to be used on key fields before performing a
+[join](../view/struct.DataView.html#method.join): a key that is unexpectedly non-unique will
+silently multiply the number of rows in the joined result, and keys without a matching value in the
+other data structure will silently drop rows.
+*/
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+
+use access::DataIndex;
+use select::{FieldSelect, SelectFieldByLabel};
+use value::Value;
+use view::{DataView, VFieldTypeOf};
+
+/// A key value which occurs in more than one row of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateKey<T> {
+    /// The duplicated key value.
+    pub key: T,
+    /// Indices of the rows containing this key value, in ascending order.
+    pub indices: Vec<usize>,
+}
+
+/// Details of a failed uniqueness check, as returned by
+/// [assert_unique](../view/struct.DataView.html#method.assert_unique).
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniquenessViolation<T> {
+    /// The duplicated key values, in order of first occurrence.
+    pub duplicates: Vec<DuplicateKey<T>>,
+}
+
+impl<T> UniquenessViolation<T> {
+    /// Returns the number of distinct key values which occur in more than one row.
+    pub fn num_duplicate_keys(&self) -> usize {
+        self.duplicates.len()
+    }
+    /// Returns the total number of rows which share their key value with at least one other row.
+    pub fn num_duplicate_rows(&self) -> usize {
+        self.duplicates.iter().map(|dup| dup.indices.len()).sum()
+    }
+}
+
+impl<T> Display for UniquenessViolation<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} key value(s) not unique ({} rows affected)",
+            self.num_duplicate_keys(),
+            self.num_duplicate_rows()
+        )?;
+        for dup in &self.duplicates {
+            write!(f, "\n  {}: rows {:?}", dup.key, dup.indices)?;
+        }
+        Ok(())
+    }
+}
+
+/// A key value which does not exist in the referenced field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingKey<T> {
+    /// The key value (possibly NA) which was not found.
+    pub key: Value<T>,
+    /// Index of the row containing this key value.
+    pub index: usize,
+}
+
+/// Details of a failed referential integrity check, as returned by
+/// [assert_subset_of](../view/struct.DataView.html#method.assert_subset_of).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsetViolation<T> {
+    /// The rows whose key values were not found, in ascending order of row index.
+    pub missing: Vec<MissingKey<T>>,
+}
+
+impl<T> SubsetViolation<T> {
+    /// Returns the number of rows whose key value was not found.
+    pub fn num_missing_rows(&self) -> usize {
+        self.missing.len()
+    }
+}
+
+impl<T> Display for SubsetViolation<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} row(s) with key values not found in referenced field",
+            self.num_missing_rows()
+        )?;
+        for missing in &self.missing {
+            write!(f, "\n  row {}: {}", missing.index, missing.key)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Checks that every existing value in the field labeled `Label` is unique. Missing (NA)
+    /// values are ignored, since they never match in a join.
+    ///
+    /// Returns a [UniquenessViolation](../validate/struct.UniquenessViolation.html) listing each
+    /// duplicated value and the rows it appears in if the check fails.
+    pub fn assert_unique<Label>(
+        &self,
+    ) -> Result<(), UniquenessViolation<VFieldTypeOf<Self, Label>>>
+    where
+        Self: SelectFieldByLabel<Label>,
+        VFieldTypeOf<Self, Label>: Hash + Eq + Clone,
+    {
+        let field = self.field::<Label>();
+        let mut positions = HashMap::new();
+        let mut keys = vec![];
+        for (idx, value) in field.iter().enumerate() {
+            if let Value::Exists(value) = value {
+                let pos = *positions.entry(value).or_insert_with(|| {
+                    keys.push((value, vec![]));
+                    keys.len() - 1
+                });
+                keys[pos].1.push(idx);
+            }
+        }
+
+        let duplicates = keys
+            .into_iter()
+            .filter(|&(_, ref indices)| indices.len() > 1)
+            .map(|(key, indices)| DuplicateKey {
+                key: key.clone(),
+                indices,
+            })
+            .collect::<Vec<_>>();
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(UniquenessViolation { duplicates })
+        }
+    }
+
+    /// Checks that every value in the field labeled `LLabel` exists in the field labeled `RLabel`
+    /// of `other` (i.e. that every record in this `DataView` would find a match in an equality
+    /// join on these fields). Missing (NA) values in this `DataView` never match, and are always
+    /// reported.
+    ///
+    /// Returns a [SubsetViolation](../validate/struct.SubsetViolation.html) listing the rows whose
+    /// values were not found if the check fails.
+    pub fn assert_subset_of<LLabel, Other, RLabel>(
+        &self,
+        other: &Other,
+    ) -> Result<(), SubsetViolation<VFieldTypeOf<Self, LLabel>>>
+    where
+        Self: SelectFieldByLabel<LLabel>,
+        Other: SelectFieldByLabel<RLabel, DType = VFieldTypeOf<Self, LLabel>>,
+        VFieldTypeOf<Self, LLabel>: Hash + Eq + Clone,
+    {
+        let right_field = other.select_field();
+        let right_keys = right_field
+            .iter()
+            .filter_map(Value::into_option)
+            .collect::<HashSet<_>>();
+
+        let left_field = self.field::<LLabel>();
+        let missing = left_field
+            .iter()
+            .enumerate()
+            .filter(|&(_, ref value)| match *value {
+                Value::Exists(value) => !right_keys.contains(value),
+                Value::Na => true,
+            })
+            .map(|(index, value)| MissingKey {
+                key: value.cloned(),
+                index,
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(SubsetViolation { missing })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "test-utils")]
+    use test_utils::*;
+
+    use value::Value;
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn unique() {
+        use test_utils::emp_table::*;
+
+        let dv = sample_emp_table().into_view();
+        assert!(dv.assert_unique::<EmpId>().is_ok());
+
+        let violation = dv.assert_unique::<DeptId>().unwrap_err();
+        assert_eq!(violation.num_duplicate_keys(), 2);
+        assert_eq!(violation.num_duplicate_rows(), 5);
+        assert_eq!(violation.duplicates[0].key, 1);
+        assert_eq!(violation.duplicates[0].indices, vec![0, 2, 3]);
+        assert_eq!(violation.duplicates[1].key, 4);
+        assert_eq!(violation.duplicates[1].indices, vec![5, 6]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn subset_of() {
+        let emp_dv = sample_emp_table().into_view();
+        let dept_dv = sample_dept_table().into_view();
+        assert!(emp_dv
+            .assert_subset_of::<emp_table::DeptId, _, dept_table::DeptId>(&dept_dv)
+            .is_ok());
+
+        let dept_dv = dept_table(vec![1, 2, 4], vec!["Marketing", "Sales", "R&D"]).into_view();
+        let violation = emp_dv
+            .assert_subset_of::<emp_table::DeptId, _, dept_table::DeptId>(&dept_dv)
+            .unwrap_err();
+        assert_eq!(violation.num_missing_rows(), 1);
+        assert_eq!(violation.missing[0].index, 4);
+        assert_eq!(violation.missing[0].key, Value::Exists(3));
+    }
+}
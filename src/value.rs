@@ -99,6 +99,93 @@ impl<T> Value<T> {
             Value::Na => def(),
         }
     }
+    /// Unwrap a `Value`, returning the contained value or the default value of `T`.
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(T::default)
+    }
+    /// Returns `Value::Na` if this `Value` is NA, otherwise calls `f` with the contained value and
+    /// returns the result.
+    pub fn and_then<U, F>(self, f: F) -> Value<U>
+    where
+        F: FnOnce(T) -> Value<U>,
+    {
+        match self {
+            Value::Exists(val) => f(val),
+            Value::Na => Value::Na,
+        }
+    }
+    /// Returns this `Value` if it exists, otherwise returns `other`.
+    pub fn or(self, other: Value<T>) -> Value<T> {
+        match self {
+            Value::Exists(val) => Value::Exists(val),
+            Value::Na => other,
+        }
+    }
+    /// Returns this `Value` if it exists, otherwise returns the result of calling `f`.
+    pub fn or_else<F>(self, f: F) -> Value<T>
+    where
+        F: FnOnce() -> Value<T>,
+    {
+        match self {
+            Value::Exists(val) => Value::Exists(val),
+            Value::Na => f(),
+        }
+    }
+    /// Returns this `Value` if it exists and its contained value satisfies `predicate`, otherwise
+    /// returns `Value::Na`.
+    pub fn filter<P>(self, predicate: P) -> Value<T>
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        match self {
+            Value::Exists(val) => {
+                if predicate(&val) {
+                    Value::Exists(val)
+                } else {
+                    Value::Na
+                }
+            }
+            Value::Na => Value::Na,
+        }
+    }
+    /// Combines this `Value` with `other` into a `Value` containing both values. Returns
+    /// `Value::Na` if either value is NA.
+    pub fn zip<U>(self, other: Value<U>) -> Value<(T, U)> {
+        match (self, other) {
+            (Value::Exists(left), Value::Exists(right)) => Value::Exists((left, right)),
+            _ => Value::Na,
+        }
+    }
+    /// Converts this `Value` into an `Option`, mapping existing values to `Some` and NA values
+    /// to `None`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Value::Exists(val) => Some(val),
+            Value::Na => None,
+        }
+    }
+    /// Converts this `Value` into a `Result`, mapping existing values to `Ok` and NA values to
+    /// `Err(err)`.
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        match self {
+            Value::Exists(val) => Ok(val),
+            Value::Na => Err(err),
+        }
+    }
+    /// Converts this `Value` into a `Result`, mapping existing values to `Ok` and NA values to
+    /// the `Err` computed by `err`.
+    pub fn ok_or_else<E, F>(self, err: F) -> Result<T, E>
+    where
+        F: FnOnce() -> E,
+    {
+        match self {
+            Value::Exists(val) => Ok(val),
+            Value::Na => Err(err()),
+        }
+    }
 }
 impl<'a, T: Clone> Value<&'a T> {
     /// Create a owner `Value` out of a reference-holding `Value` using `clone()`.
@@ -177,10 +264,7 @@ where
 
 impl<T> Into<Option<T>> for Value<T> {
     fn into(self) -> Option<T> {
-        match self {
-            Value::Exists(value) => Some(value),
-            Value::Na => None,
-        }
+        self.into_option()
     }
 }
 impl<T> From<Option<T>> for Value<T> {
@@ -192,6 +276,25 @@ impl<T> From<Option<T>> for Value<T> {
     }
 }
 
+/// Trait for types which can be converted into a (possibly missing)
+/// [Value](enum.Value.html). Implemented for `Value` itself and for `Option` (with `None`
+/// denoting a missing value), so that data from standard Rust sources can be used wherever an
+/// iterator of `Value`s is expected.
+pub trait IntoValue<T> {
+    /// Converts this object into a `Value`.
+    fn into_value(self) -> Value<T>;
+}
+impl<T> IntoValue<T> for Value<T> {
+    fn into_value(self) -> Value<T> {
+        self
+    }
+}
+impl<T> IntoValue<T> for Option<T> {
+    fn into_value(self) -> Value<T> {
+        self.into()
+    }
+}
+
 macro_rules! impl_value_op {
     ($trait_name:tt $trait_fn:tt) => {
         impl<T, U> $trait_name<Value<U>> for Value<T>
@@ -244,4 +347,26 @@ mod tests {
         let value: Value<&f64> = Value::Na;
         assert_eq!(serde_json::to_string(&value).unwrap(), "null");
     }
+
+    #[test]
+    fn combinators() {
+        let two = Value::Exists(2u64);
+        let na: Value<u64> = Value::Na;
+
+        assert_eq!(two.and_then(|v| Value::Exists(v * 3)), Value::Exists(6));
+        assert_eq!(two.and_then(|_| Value::Na::<u64>), Value::Na);
+        assert_eq!(na.and_then(|v| Value::Exists(v * 3)), Value::Na);
+        assert_eq!(na.or(two), two);
+        assert_eq!(two.or_else(|| Value::Exists(5)), two);
+        assert_eq!(two.filter(|&v| v > 2), Value::Na);
+        assert_eq!(na.unwrap_or_default(), 0);
+
+        assert_eq!(two.zip(Value::Exists("a")), Value::Exists((2, "a")));
+        assert_eq!(two.zip(Value::Na::<&str>), Value::Na);
+
+        assert_eq!(two.into_option(), Some(2));
+        assert_eq!(Value::from(None::<u64>), na);
+        assert_eq!(two.ok_or("missing"), Ok(2));
+        assert_eq!(na.ok_or_else(|| "missing"), Err("missing"));
+    }
 }
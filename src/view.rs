@@ -13,34 +13,52 @@ object with all of the records of the two source `DataView`s.
 parameters.
 
 */
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 #[cfg(test)]
 use std::collections::VecDeque;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
+use std::ops::Add;
+use std::rc::Rc;
 
+use num_traits::AsPrimitive;
 use prettytable as pt;
+use rand::Rng;
 #[cfg(feature = "serialize")]
 use serde::ser::{Serialize, SerializeMap, Serializer};
+use typenum::{Add1, B1, U0};
 
 use access::*;
 use cons::*;
+use datetime::{DateTime, DateTimeIndex, Period, UtcOffset};
 use error;
-use field::FieldData;
+use field::{FieldData, FieldIdent};
 use fieldlist::FieldPayloadCons;
 #[cfg(test)]
 use frame::StoreRefCount;
-use frame::{Framed, IntoFrame, IntoMeltFrame, IntoStrFrame};
+use frame::{
+    CollectFramePermutations, FramePermutations, Framed, IntoFrame, IntoMeltFrame, IntoStrFrame,
+    LabelField, PermutationUpdates, ShareFramePermutations, SharePermutations,
+    UpdateFramePermutation, UpdateSharedPermutation,
+};
 use join::*;
 use label::*;
+use normalize::StrNormalization;
 use partial::{DeriveCapabilities, Func, FuncDefault, Implemented, IsImplemented, PartialMap};
 use permute::{
-    FilterPerm, SortOrder, SortOrderComparator, SortOrderUnstable, SortOrderUnstableComparator,
-    UpdatePermutation,
+    Collation, FilterPerm, PolicyOrd, SignedZero, SortOrder, SortOrderComparator, SortOrderRadix,
+    SortOrderUnstable, SortOrderUnstableComparator, SortPolicy, TotalF64, UpdatePermutation,
 };
+use provenance::{Operation, OperationLog, WithOperationLog};
+use query::{Comparison, Query, QueryFn};
+use rng::operation_rng;
 use select::{FieldSelect, SelectFieldByLabel};
-use store::{IntoStore, IntoView};
+use stats::{ArgExtrema, Quantile, WeightedQuantile};
+use store::{CacheHashes, DataStore, IntoStore, IntoView};
 use value::Value;
 
 /// Cons-list of `DataFrame`s held by a `DataView. `FrameIndex` is simply an index used by
@@ -66,6 +84,8 @@ pub(crate) type FrameLookupCons<Label, FrameIndex, FrameLabel, Tail> =
 pub struct DataView<Labels, Frames> {
     pub(crate) _labels: PhantomData<Labels>,
     pub(crate) frames: Frames,
+    pub(crate) log: OperationLog,
+    pub(crate) sort_cache: SortCache,
 }
 
 /// Marker struct with the details of where to find a field's data. The `FrameIndex` specifies
@@ -92,14 +112,163 @@ where
     type FrameLabel = FrameLabel;
 }
 
+// identifies a stored field of a `DataView` (see `FrameFieldId`)
+type FieldId = (TypeId, TypeId);
+
+// a cached sort of a `DataView` by one of its fields
+#[derive(Debug, Clone)]
+enum CachedSort {
+    // the view is already sorted by the field
+    Sorted,
+    // the order of the view's records which sorts it by the field
+    Order(Rc<Vec<usize>>),
+}
+
+/// Cache of the sorts of a `DataView` by its fields, computed for the view's current records (in
+/// their current order), keyed by field and by whether or not the sort is stable. Fields are
+/// identified by their frame and their label within that frame, so the cache is unaffected by
+/// relabeling. Clones of a `DataView` share its cache until either of them changes its records, so
+/// sorting several copies of a view by the same field only computes the sort order once.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SortCache(Rc<RefCell<HashMap<(FieldId, bool), CachedSort>>>);
+
+impl SortCache {
+    // creates the cache of a view which has just been sorted by `field`
+    fn sorted(field: FieldId, stable: bool) -> SortCache {
+        let cache = SortCache::default();
+        cache
+            .0
+            .borrow_mut()
+            .insert((field, stable), CachedSort::Sorted);
+        cache
+    }
+
+    // returns the cached sort by `field`, if any; a stable sort is also a valid unstable sort, but
+    // not vice versa
+    fn get(&self, field: FieldId, stable: bool) -> Option<CachedSort> {
+        let cache = self.0.borrow();
+        if stable {
+            cache.get(&(field, true)).cloned()
+        } else {
+            cache
+                .get(&(field, false))
+                .or_else(|| cache.get(&(field, true)))
+                .cloned()
+        }
+    }
+
+    // caches the order which sorts the view by `field`
+    fn insert(&self, field: FieldId, stable: bool, order: Rc<Vec<usize>>) {
+        self.0
+            .borrow_mut()
+            .insert((field, stable), CachedSort::Order(order));
+    }
+
+    // creates the cache of a view after removing some of its records (without reordering the
+    // remaining ones): the view is still sorted by the same fields, but the computed sort orders no
+    // longer apply
+    fn filtered(&self) -> SortCache {
+        self.retain(|_, sort| match *sort {
+            CachedSort::Sorted => true,
+            CachedSort::Order(_) => false,
+        })
+    }
+
+    // creates the cache of a view with the same records as this one, but only containing the
+    // fields `fields`
+    fn subset(&self, fields: &[FieldId]) -> SortCache {
+        self.retain(|field, _| fields.contains(&field))
+    }
+
+    // creates an unshared copy of this cache, for a view with the same records as this one and
+    // additional fields (whose sorts must not be cached for this view)
+    pub(crate) fn detached(&self) -> SortCache {
+        self.retain(|_, _| true)
+    }
+
+    // creates a new cache containing the cached sorts for which `keep` returns true
+    fn retain<F>(&self, mut keep: F) -> SortCache
+    where
+        F: FnMut(FieldId, &CachedSort) -> bool,
+    {
+        let cache = SortCache::default();
+        cache.0.borrow_mut().extend(
+            self.0
+                .borrow()
+                .iter()
+                .filter(|&(&(field, _), sort)| keep(field, sort))
+                .map(|(&key, sort)| (key, sort.clone())),
+        );
+        cache
+    }
+
+    // returns whether or not the view is known to be sorted by `field`
+    #[cfg(test)]
+    fn is_sorted(&self, field: FieldId, stable: bool) -> bool {
+        match self.get(field, stable) {
+            Some(CachedSort::Sorted) => true,
+            _ => false,
+        }
+    }
+
+    // returns whether or not the order which sorts the view by `field` has been cached
+    #[cfg(test)]
+    fn has_order(&self, field: FieldId, stable: bool) -> bool {
+        match self.get(field, stable) {
+            Some(CachedSort::Order(_)) => true,
+            _ => false,
+        }
+    }
+}
+
 impl<Labels, Frames> DataView<Labels, Frames> {
     /// Creates a new `DataView` with `frames`.
     pub fn new(frames: Frames) -> DataView<Labels, Frames> {
         DataView {
             _labels: PhantomData,
             frames,
+            log: OperationLog::default(),
+            sort_cache: SortCache::default(),
         }
     }
+
+    /// Consumes this `DataView` and returns a `DataView` which records the operations (filters,
+    /// sorts, relabelings, merges, and joins) subsequently performed on it, starting with a
+    /// [Load](../provenance/enum.Operation.html#variant.Load) operation from `source`. Any
+    /// previously-recorded operations are discarded.
+    pub fn record_operations<S: Into<String>>(mut self, source: S) -> Self {
+        self.log = OperationLog::new();
+        self.log.record(Operation::Load {
+            source: source.into(),
+        });
+        self
+    }
+
+    /// Consumes this `DataView` and returns it with a user-specified operation recorded in its
+    /// operation log, for documenting steps which are not automatically recorded. Does nothing if
+    /// operation logging has not been enabled with [record_operations](#method.record_operations).
+    pub fn log_operation<S: Into<String>>(mut self, description: S) -> Self {
+        self.log.record(Operation::Custom(description.into()));
+        self
+    }
+
+    /// Returns the sequence of operations which produced this `DataView`, or `None` if operation
+    /// logging has not been enabled with [record_operations](#method.record_operations).
+    pub fn operations(&self) -> Option<&[Operation]> {
+        self.log.operations()
+    }
+
+    /// Returns the [OperationLog](../provenance/struct.OperationLog.html) of this `DataView`.
+    pub fn operation_log(&self) -> &OperationLog {
+        &self.log
+    }
+}
+
+impl<Labels, Frames> WithOperationLog for DataView<Labels, Frames> {
+    fn with_operation_log(mut self, log: OperationLog) -> Self {
+        self.log = log;
+        self
+    }
 }
 
 impl<Labels, Frames> DataView<Labels, Frames> {
@@ -130,6 +299,44 @@ where
     type LabelList = LCons<FrameIndex, <Tail as FrameIndexList>::LabelList>;
 }
 
+/// A trait for computing the frame index following the greatest frame index in a `ViewFrameCons`
+/// cons-list, which is the index for the next frame added to a `DataView`. Frame indices are
+/// always increasing, but not necessarily contiguous (subviews drop frames which none of their
+/// fields reference).
+pub trait NextFrameIndex {
+    /// The next frame index.
+    type Output;
+}
+impl NextFrameIndex for Nil {
+    type Output = U0;
+}
+impl<FrameIndex, Frame, Tail> NextFrameIndex for ViewFrameCons<FrameIndex, Frame, Tail>
+where
+    Tail: NextFrameIndexAfter<FrameIndex>,
+{
+    type Output = <Tail as NextFrameIndexAfter<FrameIndex>>::Output;
+}
+
+/// Helper trait for [NextFrameIndex](trait.NextFrameIndex.html). Computes the next frame index of
+/// a cons-list which follows a frame with index `PrevFrameIndex`.
+pub trait NextFrameIndexAfter<PrevFrameIndex> {
+    /// The next frame index.
+    type Output;
+}
+impl<PrevFrameIndex> NextFrameIndexAfter<PrevFrameIndex> for Nil
+where
+    PrevFrameIndex: Add<B1>,
+{
+    type Output = Add1<PrevFrameIndex>;
+}
+impl<PrevFrameIndex, FrameIndex, Frame, Tail> NextFrameIndexAfter<PrevFrameIndex>
+    for ViewFrameCons<FrameIndex, Frame, Tail>
+where
+    Tail: NextFrameIndexAfter<FrameIndex>,
+{
+    type Output = <Tail as NextFrameIndexAfter<FrameIndex>>::Output;
+}
+
 impl<Labels, Frames> DataView<Labels, Frames>
 where
     Frames: Clone,
@@ -150,8 +357,38 @@ where
     {
         Subview::<LabelList>::subview(self)
     }
+    /// Generate a new subview of this `DataView` containing all of its fields except those with
+    /// labels in `LabelList` (a [LabelCons](../label/type.LabelCons.html) list of labels, which
+    /// can be generated using the [Labels](../macro.Labels.html) macro). Fields remain in their
+    /// original order.
+    pub fn v_except<LabelList>(&self) -> <Self as Subview<ExceptLabels<Labels, LabelList>>>::Output
+    where
+        Labels: AssocLabels,
+        <Labels as AssocLabels>::Labels: SetDiff<LabelList>,
+        Self: Subview<ExceptLabels<Labels, LabelList>>,
+    {
+        Subview::<ExceptLabels<Labels, LabelList>>::subview(self)
+    }
+    /// Generate a new subview of this `DataView` containing only the fields whose labels also
+    /// exist in `other`. Fields remain in their original order.
+    pub fn v_common<OtherLabels, OtherFrames>(
+        &self,
+        _other: &DataView<OtherLabels, OtherFrames>,
+    ) -> <Self as Subview<CommonLabels<Labels, OtherLabels>>>::Output
+    where
+        Labels: AssocLabels,
+        OtherLabels: AssocLabels,
+        <Labels as AssocLabels>::Labels: SetIntersect<<OtherLabels as AssocLabels>::Labels>,
+        Self: Subview<CommonLabels<Labels, OtherLabels>>,
+    {
+        Subview::<CommonLabels<Labels, OtherLabels>>::subview(self)
+    }
 }
 
+type ExceptLabels<Labels, LabelList> = LabelsDiff<<Labels as AssocLabels>::Labels, LabelList>;
+type CommonLabels<Labels, OtherLabels> =
+    LabelsIntersect<<Labels as AssocLabels>::Labels, <OtherLabels as AssocLabels>::Labels>;
+
 /// Trait for generating a subview of a [DataView](struct.DataView.html). `LabelList` is the fields
 /// to keep in the generated `DataView`.
 pub trait Subview<LabelList> {
@@ -159,25 +396,34 @@ pub trait Subview<LabelList> {
     type Output;
 
     /// Generate a new subview of this `DataView`, resulting in a newly created `DataView` object
-    /// only containing the fields matching the labels in `LabelList`.
+    /// only containing the fields matching the labels in `LabelList`. Frames which do not contain
+    /// any of these fields are not included in the subview.
     fn subview(&self) -> Self::Output;
 }
 
+type SubviewFrameIndices<Labels, LabelList> =
+    <<Labels as LabelSubset<LabelList>>::Output as FrameIndexList>::LabelList;
+
 impl<Labels, Frames, LabelList> Subview<LabelList> for DataView<Labels, Frames>
 where
-    Labels: FrameIndexList + HasLabels<LabelList> + LabelSubset<LabelList>,
-    <Labels as LabelSubset<LabelList>>::Output: Reorder<LabelList>,
-    Frames: Clone + SubsetClone<<Labels as FrameIndexList>::LabelList>,
+    Labels: HasLabels<LabelList> + LabelSubset<LabelList>,
+    <Labels as LabelSubset<LabelList>>::Output: Reorder<LabelList> + FrameIndexList + FrameFieldIds,
+    Frames: Clone + SubsetClone<SubviewFrameIndices<Labels, LabelList>>,
 {
     type Output = DataView<
         <<Labels as LabelSubset<LabelList>>::Output as Reorder<LabelList>>::Output,
-        <Frames as SubsetClone<<Labels as FrameIndexList>::LabelList>>::Output,
+        <Frames as SubsetClone<SubviewFrameIndices<Labels, LabelList>>>::Output,
     >;
 
     fn subview(&self) -> Self::Output {
         DataView {
             _labels: PhantomData,
             frames: self.frames.subset_clone(),
+            log: self.log.clone(),
+            // only keep the cached sorts of fields which remain in the subview
+            sort_cache: self.sort_cache.subset(
+                &<<Labels as LabelSubset<LabelList>>::Output as FrameFieldIds>::frame_field_ids(),
+            ),
         }
     }
 }
@@ -201,6 +447,23 @@ where
     }
 }
 
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Returns a copy of the value (possibly NA) of the field labeled `Label` in record `idx`.
+    /// Fails with an `IndexError` identifying the field if `idx` is out of bounds.
+    pub fn datum<Label>(&self, idx: usize) -> error::Result<Value<VFieldTypeOf<Self, Label>>>
+    where
+        Self: SelectFieldByLabel<Label>,
+        Label: LabelName,
+        VFieldTypeOf<Self, Label>: Clone,
+    {
+        let field = self.field::<Label>();
+        field
+            .get_datum(idx)
+            .map(|value| value.cloned())
+            .map_err(|e| e.in_field(Label::name()))
+    }
+}
+
 impl<Labels, Frames> DataView<Labels, Frames>
 where
     Labels: Len,
@@ -291,6 +554,54 @@ pub type FrameIndexOf<Labels, Label> =
 pub type FrameLabelOf<Labels, Label> =
     <<Labels as FindFrameDetails<Label>>::FrameDetails as FrameDetails>::FrameLabel;
 
+/// Trait for identifying the stored field associated with the label `Label` in the label lookup
+/// list `Self`, by the frame index of the frame containing the field and the label of the field
+/// within that frame. Unlike `Label`, this identifier is unaffected by relabeling.
+pub trait FrameFieldId<Label> {
+    /// Returns the type IDs of the frame index and frame label of the field associated with
+    /// `Label`.
+    fn frame_field_id() -> (TypeId, TypeId);
+}
+impl<Labels, Label> FrameFieldId<Label> for Labels
+where
+    Labels: FindFrameDetails<Label>,
+    FrameIndexOf<Labels, Label>: 'static,
+    FrameLabelOf<Labels, Label>: 'static,
+{
+    fn frame_field_id() -> (TypeId, TypeId) {
+        (
+            TypeId::of::<FrameIndexOf<Labels, Label>>(),
+            TypeId::of::<FrameLabelOf<Labels, Label>>(),
+        )
+    }
+}
+
+/// Trait for identifying the stored fields associated with all of the labels in a label lookup
+/// list (see [FrameFieldId](trait.FrameFieldId.html)).
+pub trait FrameFieldIds {
+    /// Returns the type IDs of the frame index and frame label of each field in this label lookup
+    /// list.
+    fn frame_field_ids() -> Vec<(TypeId, TypeId)>;
+}
+impl FrameFieldIds for Nil {
+    fn frame_field_ids() -> Vec<(TypeId, TypeId)> {
+        vec![]
+    }
+}
+impl<Label, FrameIndex, FrameLabel, Tail> FrameFieldIds
+    for FrameLookupCons<Label, FrameIndex, FrameLabel, Tail>
+where
+    FrameIndex: 'static,
+    FrameLabel: 'static,
+    Tail: FrameFieldIds,
+{
+    fn frame_field_ids() -> Vec<(TypeId, TypeId)> {
+        let mut ids = Tail::frame_field_ids();
+        ids.push((TypeId::of::<FrameIndex>(), TypeId::of::<FrameLabel>()));
+        ids
+    }
+}
+
 /// Marker trait for being able to find a frame of label `Label` within label lookup list `Labels`
 /// in this type
 pub trait FindFrame<Labels, Label>: LookupValuedElemByLabel<FrameIndexOf<Labels, Label>>
@@ -366,17 +677,20 @@ where
     Labels: FindFrameDetails<Label>,
     Frames: FindFrame<Labels, Label>,
     FrameOf<Frames, Labels, Label>: SelectFieldByLabel<FrameLabelOf<Labels, Label>>,
-    FieldOf<Frames, Labels, Label>: SelfValued + Clone,
+    FieldOf<Frames, Labels, Label>: SelfValued + Clone + LabelField,
     FieldTypeOf<Frames, Labels, Label>: fmt::Debug,
+    Label: LabelName,
 {
     type DType = FieldTypeOf<Frames, Labels, Label>;
     type Output = FieldOf<Frames, Labels, Label>;
 
     fn select_field(&self) -> Self::Output {
+        // label the field with its label in this view, so that access errors identify it
         SelectFieldByLabel::<FrameLabelOf<Labels, Label>>::select_field(
             LookupValuedElemByLabel::<FrameIndexOf<Labels, Label>>::elem(self).value_ref(),
         )
         .clone()
+        .with_label(Label::name())
     }
 }
 
@@ -534,7 +848,7 @@ macro_rules! impl_addcell_is_impl {
         }
     )*}
 }
-impl_addcell_is_impl![String &str f64 f32 u64 u32 i64 i32 bool];
+impl_addcell_is_impl![String &str f64 f32 u64 u32 i64 i32 bool TotalF64 DateTime];
 
 impl<Labels, Frames> DataView<Labels, Frames> {
     /// Construct a new `DataView` with the label `CurrLabel` relabeled with the label `NewLabel`.
@@ -543,10 +857,17 @@ impl<Labels, Frames> DataView<Labels, Frames> {
     ) -> DataView<<Labels as Relabel<CurrLabel, NewLabel>>::Output, Frames>
     where
         Labels: Relabel<CurrLabel, NewLabel>,
+        CurrLabel: LabelName,
+        NewLabel: LabelName,
     {
         DataView {
             _labels: PhantomData,
             frames: self.frames,
+            log: self.log.with(Operation::Relabel {
+                from: CurrLabel::name(),
+                to: NewLabel::name(),
+            }),
+            sort_cache: self.sort_cache,
         }
     }
 }
@@ -656,6 +977,10 @@ impl<Labels, Frames> DataView<Labels, Frames> {
     /// Combine two `DataView` objects using specified join, creating a new `DataStore` object with
     /// a subset of records from the two source `DataView`s according to the join parameters.
     ///
+    /// The records of the joined `DataView` are ordered by the record order of this `DataView`,
+    /// then by the record order of `right` (see
+    /// [join_with_order](struct.DataView.html#method.join_with_order)).
+    ///
     /// Note that since this is creating a new `DataStore` object, it will be allocated new data to
     /// store the contents of the joined `DataView`s.
     pub fn join<Join, RLabels, RFrames>(
@@ -676,18 +1001,238 @@ impl<Labels, Frames> DataView<Labels, Frames> {
         //     }
         // }
     }
+
+    /// Combine two `DataView` objects using specified join, as with
+    /// [join](struct.DataView.html#method.join), while checking that the matched records have the
+    /// relationship specified by `cardinality` (one-to-one, one-to-many, or many-to-one).
+    ///
+    /// Fails with a `CardinalityMismatch` error containing the number of offending keys on each
+    /// side if the relationship differs, which usually indicates an unexpectedly duplicated key
+    /// that would otherwise multiply the number of rows in the result.
+    pub fn join_with_cardinality<Join, RLabels, RFrames>(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        cardinality: Cardinality,
+    ) -> error::Result<<Self as SortMergeJoin<RLabels, RFrames, Join>>::Output>
+    where
+        Self: SortMergeJoin<RLabels, RFrames, Join>,
+    {
+        SortMergeJoin::join_with_cardinality(self, right, cardinality)
+    }
+
+    /// Combine two `DataView` objects using specified join, as with
+    /// [join](struct.DataView.html#method.join), with the records of the joined `DataView` ordered
+    /// as specified by `order`. `JoinOrder::Unspecified` skips ordering the records by the record
+    /// order of this `DataView` (then `right`), which is faster for large joins whose record order
+    /// is unimportant.
+    pub fn join_with_order<Join, RLabels, RFrames>(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        order: JoinOrder,
+    ) -> <Self as SortMergeJoin<RLabels, RFrames, Join>>::Output
+    where
+        Self: SortMergeJoin<RLabels, RFrames, Join>,
+    {
+        SortMergeJoin::join_with_order(self, right, order)
+    }
+
+    /// Combine two `DataView` objects using specified join, as with
+    /// [join](struct.DataView.html#method.join), additionally returning
+    /// [JoinStats](../join/struct.JoinStats.html) describing the matched records: the number of
+    /// records in the joined `DataView`, the number of records of either `DataView` which did not
+    /// match any records, and the key which matched the most right-hand records.
+    pub fn join_with_stats<Join, RLabels, RFrames>(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+    ) -> (
+        <Self as SortMergeJoin<RLabels, RFrames, Join>>::Output,
+        JoinStats<<Self as SortMergeJoin<RLabels, RFrames, Join>>::Key>,
+    )
+    where
+        Self: SortMergeJoin<RLabels, RFrames, Join>,
+        <Self as SortMergeJoin<RLabels, RFrames, Join>>::Key: Clone,
+    {
+        SortMergeJoin::join_with_stats(self, right)
+    }
+
+    /// Combine two `DataView` objects using specified join, as with
+    /// [join](struct.DataView.html#method.join), but comparing keys computed from the join fields
+    /// with the key-extraction functions `left_key` (applied to the left-hand join field) and
+    /// `right_key` (applied to the right-hand join field) instead of the field values themselves.
+    /// This allows joining on derived keys (e.g. the lowercase of a string field) without adding
+    /// temporary fields to either `DataView`.
+    ///
+    /// Missing values in either join field never match. The joined `DataView` contains the
+    /// original (not the derived) values of the join fields.
+    pub fn join_by<Join, RLabels, RFrames, LKeyFn, RKeyFn>(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        left_key: LKeyFn,
+        right_key: RKeyFn,
+    ) -> <Self as KeyedJoin<RLabels, RFrames, Join, LKeyFn, RKeyFn>>::Output
+    where
+        Self: KeyedJoin<RLabels, RFrames, Join, LKeyFn, RKeyFn>,
+    {
+        KeyedJoin::join_by(self, right, left_key, right_key)
+    }
+
+    /// Combine two `DataView` objects by approximately matching the string fields labeled `LLabel`
+    /// (in this `DataView`) and `RLabel` (in `right`), creating a new `DataStore` object with the
+    /// pairs of records whose values match as specified by `fuzzy` (for example, with a
+    /// Levenshtein distance of at most `2`). This is useful for record-linkage tasks, such as
+    /// matching country names which are spelled differently across data sets.
+    ///
+    /// Each record may match multiple records in the other `DataView`. Missing values never match.
+    /// The records of the joined `DataView` are ordered by the record order of this `DataView`,
+    /// then by the record order of `right`.
+    pub fn fuzzy_join<LLabel, RLabel, RLabels, RFrames>(
+        &self,
+        right: &DataView<RLabels, RFrames>,
+        fuzzy: &FuzzyMatch,
+    ) -> <Self as FuzzyJoin<RLabels, RFrames, LLabel, RLabel>>::Output
+    where
+        Self: FuzzyJoin<RLabels, RFrames, LLabel, RLabel>,
+    {
+        FuzzyJoin::<RLabels, RFrames, LLabel, RLabel>::fuzzy_join(self, right, fuzzy)
+    }
+
+    /// Adds the field labeled `LookupValue` from the `DataView` `lookup` to this `DataView`, by
+    /// looking up the value of the field labeled `KeyLabel` of each record of this `DataView` in
+    /// the field labeled `LookupKey` of `lookup`. Returns a new `DataView` with the looked-up
+    /// values in an additional field labeled `LookupValue`.
+    ///
+    /// This is a lighter-weight alternative to a [join](struct.DataView.html#method.join) for
+    /// enriching a `DataView` with values from a small lookup table: the lookup is performed with
+    /// a hash map, and the rows (and row order) of this `DataView` are preserved. Records with a
+    /// missing key, or a key not found in `lookup`, have a missing value. If a key appears more
+    /// than once in `lookup`, its first occurrence is used.
+    pub fn map_from<KeyLabel, LookupKey, LookupValue, LLabels, LFrames>(
+        &self,
+        lookup: &DataView<LLabels, LFrames>,
+    ) -> <Self as AddFrame<
+        AddedFieldFrame<LookupValue, VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>>,
+    >>::Output
+    where
+        Self: SelectFieldByLabel<KeyLabel>
+            + AddFrame<
+                AddedFieldFrame<
+                    LookupValue,
+                    VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>,
+                >,
+            >,
+        DataView<LLabels, LFrames>:
+            SelectFieldByLabel<LookupKey, DType = VFieldTypeOf<Self, KeyLabel>>
+                + SelectFieldByLabel<LookupValue>,
+        VFieldTypeOf<Self, KeyLabel>: Hash + Eq,
+        VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>>: IntoStore<LookupValue>,
+        <FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>> as IntoStore<
+            LookupValue,
+        >>::Output: IntoFrame,
+    {
+        let lookup_keys = lookup.field::<LookupKey>();
+        let lookup_values = lookup.field::<LookupValue>();
+        let mut map = HashMap::new();
+        for (key, value) in lookup_keys.iter().zip(lookup_values.iter()) {
+            if let Value::Exists(key) = key {
+                map.entry(key).or_insert(value);
+            }
+        }
+        let mapped: FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>> = self
+            .field::<KeyLabel>()
+            .iter()
+            .map(|key| {
+                key.and_then(|key| map.get(key).map_or(Value::Na, |value| value.cloned()))
+            })
+            .collect();
+        self.add_frame(IntoStore::<LookupValue>::into_store(mapped).into_frame())
+    }
+
+    /// Adds the field labeled `LookupValue` from the `DataView` `lookup` to this `DataView`, by
+    /// matching the numeric field labeled `KeyLabel` of each record of this `DataView` with the
+    /// nearest key (in the direction specified by `direction`) in the numeric field labeled
+    /// `LookupKey` of `lookup`. This is an as-of lookup, useful for aligning records with the most
+    /// recent (or next) observation in another data set, such as matching trades with quotes by
+    /// timestamp.
+    ///
+    /// If `tolerance` is specified, only matches whose key differs from the search key by at most
+    /// `tolerance` are accepted. Records with a missing key, or without an acceptable match, have a
+    /// missing value. Returns a new `DataView` with the looked-up values in an additional field
+    /// labeled `LookupValue`; the rows (and row order) of this `DataView` are preserved.
+    pub fn map_from_nearest<KeyLabel, LookupKey, LookupValue, LLabels, LFrames>(
+        &self,
+        lookup: &DataView<LLabels, LFrames>,
+        direction: AsOf,
+        tolerance: Option<f64>,
+    ) -> <Self as AddFrame<
+        AddedFieldFrame<LookupValue, VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>>,
+    >>::Output
+    where
+        Self: SelectFieldByLabel<KeyLabel>
+            + AddFrame<
+                AddedFieldFrame<
+                    LookupValue,
+                    VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>,
+                >,
+            >,
+        DataView<LLabels, LFrames>:
+            SelectFieldByLabel<LookupKey> + SelectFieldByLabel<LookupValue>,
+        VFieldTypeOf<Self, KeyLabel>: AsPrimitive<f64>,
+        VFieldTypeOf<DataView<LLabels, LFrames>, LookupKey>: AsPrimitive<f64>,
+        VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>>: IntoStore<LookupValue>,
+        <FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>> as IntoStore<
+            LookupValue,
+        >>::Output: IntoFrame,
+    {
+        let lookup_values = lookup.field::<LookupValue>();
+        let mapped: FieldData<VFieldTypeOf<DataView<LLabels, LFrames>, LookupValue>> =
+            asof_indices(
+                &self.field::<KeyLabel>(),
+                &lookup.field::<LookupKey>(),
+                direction,
+                tolerance,
+            )
+            .into_iter()
+            .map(|idx| {
+                idx.map_or(Value::Na, |idx| {
+                    lookup_values.get_datum(idx).unwrap().cloned()
+                })
+            })
+            .collect();
+        self.add_frame(IntoStore::<LookupValue>::into_store(mapped).into_frame())
+    }
 }
 
 impl<FrameIndex, Frame, Tail> UpdatePermutation for ViewFrameCons<FrameIndex, Frame, Tail>
 where
-    Frame: Valued<Value = Frame>,
-    ValueOf<Frame>: UpdatePermutation,
-    Tail: UpdatePermutation,
+    Self: UpdateSharedPermutation,
 {
-    fn update_permutation(mut self, order: &[usize]) -> Self {
-        self.head = Labeled::from(self.head.value().update_permutation(order));
-        self.tail = self.tail.update_permutation(order);
-        self
+    fn update_permutation(self, order: &[usize]) -> Self {
+        // frames which share a permutation only need it updated once
+        self.update_shared_permutation(order, &mut PermutationUpdates::default())
+    }
+}
+
+impl<FrameIndex, Frame, Tail> UpdateSharedPermutation for ViewFrameCons<FrameIndex, Frame, Tail>
+where
+    for<'a> Self: Map<UpdateFramePermutation<'a>, Output = Self>,
+{
+    fn update_shared_permutation(self, order: &[usize], updates: &mut PermutationUpdates) -> Self {
+        self.map_list(&mut UpdateFramePermutation { order, updates })
+    }
+}
+
+impl<FrameIndex, Frame, Tail> SharePermutations for ViewFrameCons<FrameIndex, Frame, Tail>
+where
+    for<'a, 'b> &'a Self: Fold<CollectFramePermutations, &'b mut FramePermutations>,
+    for<'a> Self: Map<ShareFramePermutations<'a>, Output = Self>,
+{
+    fn collect_permutations(&self, frames: &mut FramePermutations) {
+        Fold::fold_list(self, frames, &mut CollectFramePermutations);
+    }
+    fn share_permutations(self, frames: &FramePermutations) -> Self {
+        self.map_list(&mut ShareFramePermutations { frames })
     }
 }
 
@@ -695,21 +1240,58 @@ impl<Labels, Frames> DataView<Labels, Frames>
 where
     Frames: UpdatePermutation,
 {
+    /// Consumes this `DataView` and returns a `DataView` containing the records at indices
+    /// `order` (in that order), applying the mapping to all of its frames in a single pass. Frames
+    /// which share a permutation are only updated once, and permutations which are not shared
+    /// with any other `DataView` are updated in place where possible.
+    ///
+    /// # Panics
+    /// Panics if any index in `order` is out of bounds.
+    pub fn update_permutations(mut self, order: &[usize]) -> Self
+    where
+        Self: NRows,
+    {
+        let nrows = self.nrows();
+        assert!(
+            order.iter().all(|&idx| idx < nrows),
+            "record index out of bounds (number of records: {})",
+            nrows
+        );
+        self.frames = self.frames.update_permutation(order);
+        self.sort_cache = SortCache::default();
+        self
+    }
+
     /// Sorts this `DataView` by the provided label. This sort is stable -- it preserves the
     /// original order of equal elements. Consumes the `DataView` and returns a `DataView`
     /// sorted by values from field identified by `Label` in ascending order, with missing (NA)
     /// values at the beginning of the order (considered to be of 'lesser' value than existing
-    /// values).
-    pub fn sort_by_label<Label>(mut self) -> Self
+    /// values). Fields of integer values are sorted using a radix sort.
+    pub fn sort_by_label<Label>(self) -> Self
     where
         Self: SelectFieldByLabel<Label>,
         <Self as SelectFieldByLabel<Label>>::Output: SortOrder,
+        Labels: FrameFieldId<Label>,
+        Label: LabelName,
     {
-        // find sort order for this field
-        let sorted = self.field::<Label>().sort_order();
-        // apply sort order to each frame
-        self.frames = self.frames.update_permutation(&sorted);
-        self
+        self.sort_cached::<Label, _>(true, |field| field.sort_order())
+    }
+
+    /// Sorts this `DataView` by the provided integer-valued label using a radix sort, which is
+    /// typically faster than a comparison sort for large fields. This sort is stable, and produces
+    /// the same order as [sort_by_label](#method.sort_by_label) (which already uses a radix sort
+    /// for fields of the built-in integer types; this method also accepts fields of any type
+    /// implementing [RadixKey](../permute/trait.RadixKey.html)). Consumes the `DataView` and
+    /// returns a `DataView` sorted by values from field identified by `Label` in ascending order,
+    /// with missing (NA) values at the beginning of the order.
+    pub fn sort_by_label_radix<Label>(self) -> Self
+    where
+        Self: SelectFieldByLabel<Label>,
+        <Self as SelectFieldByLabel<Label>>::Output: SortOrderRadix,
+        Labels: FrameFieldId<Label>,
+        Label: LabelName,
+    {
+        self.sort_cached::<Label, _>(true, |field| field.sort_order_radix())
     }
 
     /// Sorts this `DataView` by the provided label. This sort is unstable -- it does not
@@ -717,15 +1299,44 @@ where
     /// `DataView` and returns a `DataView sorted by values from field identifier by `Label` in
     /// ascending order, with missing (NA) values at the beginning of the order (considered to be of
     /// 'lesser' value than existing values).
-    pub fn sort_unstable_by_label<Label>(mut self) -> Self
+    pub fn sort_unstable_by_label<Label>(self) -> Self
     where
         Self: SelectFieldByLabel<Label>,
         <Self as SelectFieldByLabel<Label>>::Output: SortOrderUnstable,
+        Labels: FrameFieldId<Label>,
+        Label: LabelName,
     {
-        // find sort order for this field
-        let sorted = self.field::<Label>().sort_order_unstable();
-        // apply sort order to each frame
-        self.frames = self.frames.update_permutation(&sorted);
+        self.sort_cached::<Label, _>(false, |field| field.sort_order_unstable())
+    }
+
+    // sorts this `DataView` by the field labeled `Label` using the (stable, if `stable`) sort order
+    // computed by `sort_order`, reusing the cached sort by that field if there is one
+    fn sort_cached<Label, F>(mut self, stable: bool, sort_order: F) -> Self
+    where
+        Self: SelectFieldByLabel<Label>,
+        Labels: FrameFieldId<Label>,
+        Label: LabelName,
+        F: FnOnce(&<Self as SelectFieldByLabel<Label>>::Output) -> Vec<usize>,
+    {
+        let field = <Labels as FrameFieldId<Label>>::frame_field_id();
+        let order = match self.sort_cache.get(field, stable) {
+            // re-sorting by a field this view is already sorted by leaves the order unchanged
+            Some(CachedSort::Sorted) => None,
+            Some(CachedSort::Order(order)) => Some(order),
+            None => {
+                let order = Rc::new(sort_order(&self.field::<Label>()));
+                // cache the order for any other views sharing this view's records
+                self.sort_cache.insert(field, stable, order.clone());
+                Some(order)
+            }
+        };
+        if let Some(order) = order {
+            self.frames = self.frames.update_permutation(&order);
+            self.sort_cache = SortCache::sorted(field, stable);
+        }
+        self.log.record(Operation::Sort {
+            label: Label::name(),
+        });
         self
     }
 
@@ -738,11 +1349,65 @@ where
     where
         Self: SelectFieldByLabel<Label>,
         <Self as SelectFieldByLabel<Label>>::Output: SortOrderComparator<F>,
+        Label: LabelName,
     {
         // find sort order for this field
         let sorted = self.field::<Label>().sort_order_by(compare);
         // apply sort order to each frame
         self.frames = self.frames.update_permutation(&sorted);
+        self.sort_cache = SortCache::default();
+        self.log.record(Operation::Sort {
+            label: Label::name(),
+        });
+        self
+    }
+
+    /// Sorts this `DataView` by the provided label, placing missing (NA) values and
+    /// floating-point NaN values as specified by `policy` (for example, missing values last). This
+    /// also allows sorting by floating-point fields. This sort is stable -- it preserves the
+    /// original order of equal elements. Consumes the `DataView` and returns a `DataView` sorted
+    /// by values from field identified by `Label` in ascending order.
+    pub fn sort_by_label_with_policy<Label>(mut self, policy: SortPolicy) -> Self
+    where
+        Self: SelectFieldByLabel<Label>,
+        VFieldTypeOf<Self, Label>: PolicyOrd,
+        Label: LabelName,
+    {
+        let sorted = self.field::<Label>().sort_order_by(
+            |left: Value<&VFieldTypeOf<Self, Label>>, right: Value<&VFieldTypeOf<Self, Label>>| {
+                policy.compare_values(left, right)
+            },
+        );
+        self.frames = self.frames.update_permutation(&sorted);
+        self.sort_cache = SortCache::default();
+        self.log.record(Operation::Sort {
+            label: Label::name(),
+        });
+        self
+    }
+
+    /// Sorts this `DataView` by the provided string-valued label using the string ordering
+    /// specified by `collation` (for example, case-insensitive or natural numeric ordering). This
+    /// sort is stable -- it preserves the original order of equal elements (including strings
+    /// which are equal under `collation`). Consumes the `DataView` and returns a `DataView` sorted
+    /// by values from field identified by `Label` in ascending order, with missing (NA) values at
+    /// the beginning of the order.
+    pub fn sort_by_label_collated<Label>(mut self, collation: Collation) -> Self
+    where
+        Self: SelectFieldByLabel<Label>,
+        VFieldTypeOf<Self, Label>: AsRef<str>,
+        Label: LabelName,
+    {
+        let sorted = self.field::<Label>().sort_order_by(
+            |left: Value<&VFieldTypeOf<Self, Label>>, right: Value<&VFieldTypeOf<Self, Label>>| {
+                collation.compare_values(left, right)
+            },
+        );
+        self.frames = self.frames.update_permutation(&sorted);
+        self.sort_cache = SortCache::default();
+        self.log.record(Operation::Sort {
+            label: Label::name(),
+        });
         self
     }
 
@@ -755,11 +1420,16 @@ where
     where
         Self: SelectFieldByLabel<Label>,
         <Self as SelectFieldByLabel<Label>>::Output: SortOrderUnstableComparator<F>,
+        Label: LabelName,
     {
         // find sort order for this field
         let sorted = self.field::<Label>().sort_order_unstable_by(compare);
         // apply sort order to each frame
         self.frames = self.frames.update_permutation(&sorted);
+        self.sort_cache = SortCache::default();
+        self.log.record(Operation::Sort {
+            label: Label::name(),
+        });
         self
     }
 
@@ -771,48 +1441,323 @@ where
     where
         Self: SelectFieldByLabel<Label>,
         <Self as SelectFieldByLabel<Label>>::Output: FilterPerm<P>,
+        Label: LabelName,
     {
         let perm = self.field::<Label>().filter_perm(predicate);
         self.frames = self.frames.update_permutation(&perm);
+        self.sort_cache = self.sort_cache.filtered();
+        self.log.record(Operation::Filter {
+            label: Label::name(),
+        });
         self
     }
-}
-
-/// Trait for finding a cons-list of fields (implementing
-/// [DataIndex](../access/trait.DataIndex.html)) from frames list `Frames` using the `LabelList`
-/// list of labels. `LabelList` should consist of labels that exist within `Self` (this trait is
-/// implemented by label lookup lists).
-pub trait FieldList<LabelList, Frames> {
-    /// Resultant cons-list of fields.
-    type Output;
-
-    /// Returns the cons-list of fields from the frames list `frames`.
-    fn field_list(frames: &Frames) -> Self::Output;
-}
-
-impl<LabelList, Frames> FieldList<LabelList, Frames> for Nil {
-    type Output = Nil;
 
-    fn field_list(_frames: &Frames) -> Nil {
-        Nil
+    /// Filters this `DataView` using a [query](../query/index.html) expression (e.g.
+    /// `"DeptId == 1 && VacationHrs >= 0"`) which refers to fields by their label names. Consumes
+    /// this `DataView` and returns a new `DataView` such that only those rows which satisfy the
+    /// expression remain. This is useful when filter predicates are only known at runtime (from
+    /// command-line arguments or configuration files, for example).
+    ///
+    /// Fails if the expression is malformed, refers to a field that does not exist in this
+    /// `DataView`, or compares a field with a literal of an incompatible type.
+    pub fn query(mut self, expression: &str) -> error::Result<Self>
+    where
+        Self: FieldMap<QueryFn> + NRows,
+        Labels: StrLabels,
+    {
+        let query = Query::parse(expression)?;
+        let labels = Labels::labels_vec();
+        let mask = query.eval(self.nrows(), &mut |comparison: &Comparison| {
+            let target = labels
+                .iter()
+                .position(|&label| label == comparison.field)
+                .ok_or_else(|| {
+                    error::AgnesError::FieldNotFound(FieldIdent::from(comparison.field.as_str()))
+                })?;
+            let mut func = QueryFn::new(comparison.clone(), target);
+            self.field_map(&mut func);
+            func.into_result()
+        })?;
+        let perm = mask
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &keep)| if keep { Some(idx) } else { None })
+            .collect::<Vec<_>>();
+        self.frames = self.frames.update_permutation(&perm);
+        self.sort_cache = self.sort_cache.filtered();
+        self.log.record(Operation::Query {
+            expression: expression.to_string(),
+        });
+        Ok(self)
     }
-}
 
-impl<LabelList, Frames, Label, FrameIndex, FrameLabel, Tail> FieldList<LabelList, Frames>
-    for FrameLookupCons<Label, FrameIndex, FrameLabel, Tail>
-where
-    LabelList: Member<Label>,
-    Self: FieldListPred<LabelList, Frames, <LabelList as Member<Label>>::IsMember>,
-{
-    type Output =
-        <Self as FieldListPred<LabelList, Frames, <LabelList as Member<Label>>::IsMember>>::Output;
+    // keeps the records whose key (in the field labeled `LLabel`) matches (or, if `anti`, does
+    // not match) a key in the field labeled `RLabel` of `right`
+    fn semi_join_records<LLabel, RLabel, RLabels, RFrames>(
+        mut self,
+        right: &DataView<RLabels, RFrames>,
+        method: SemiJoinMethod,
+        anti: bool,
+    ) -> Self
+    where
+        Self: SelectFieldByLabel<LLabel>,
+        VFieldTypeOf<Self, LLabel>: Hash + Eq,
+        DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel, DType = VFieldTypeOf<Self, LLabel>>,
+        LLabel: LabelName,
+        RLabel: LabelName,
+    {
+        let matches = semi_join_matches(&self.field::<LLabel>(), &right.field::<RLabel>(), method);
+        let perm = matches
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &matched)| if matched != anti { Some(idx) } else { None })
+            .collect::<Vec<_>>();
+        self.frames = self.frames.update_permutation(&perm);
+        self.sort_cache = self.sort_cache.filtered();
+        self.log.record(Operation::SemiJoin {
+            left_label: LLabel::name(),
+            right_label: RLabel::name(),
+            anti,
+            right: right.log.to_vec(),
+        });
+        self
+    }
 
-    fn field_list(frames: &Frames) -> Self::Output {
-        Self::field_list_pred(frames)
+    /// Filters this `DataView` by a semi-join with `right`: consumes this `DataView` and returns a
+    /// new `DataView` such that only those records whose value in the field labeled `LLabel`
+    /// equals a value in the field labeled `RLabel` of `right` remain. Unlike a
+    /// [join](struct.DataView.html#method.join), no fields are added, and each record is kept at
+    /// most once (in its original order) regardless of how many records of `right` it matches.
+    /// Missing values never match.
+    ///
+    /// `method` specifies how keys are matched; `SemiJoinMethod::BloomFilter` first discards the
+    /// records whose keys are definitely not in `right` using a compact
+    /// [BloomFilter](../join/struct.BloomFilter.html), which is useful when `right` is large.
+    pub fn semi_join<LLabel, RLabel, RLabels, RFrames>(
+        self,
+        right: &DataView<RLabels, RFrames>,
+        method: SemiJoinMethod,
+    ) -> Self
+    where
+        Self: SelectFieldByLabel<LLabel>,
+        VFieldTypeOf<Self, LLabel>: Hash + Eq,
+        DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel, DType = VFieldTypeOf<Self, LLabel>>,
+        LLabel: LabelName,
+        RLabel: LabelName,
+    {
+        self.semi_join_records::<LLabel, RLabel, _, _>(right, method, false)
     }
-}
 
-/// Helper trait for ([FieldList](trait.FieldList.html)). `IsMember` is whether or not the head of
+    /// Filters this `DataView` by an anti-join with `right`: consumes this `DataView` and returns
+    /// a new `DataView` such that only those records whose value in the field labeled `LLabel`
+    /// does not equal any value in the field labeled `RLabel` of `right` remain (including records
+    /// with a missing value). See [semi_join](struct.DataView.html#method.semi_join).
+    pub fn anti_join<LLabel, RLabel, RLabels, RFrames>(
+        self,
+        right: &DataView<RLabels, RFrames>,
+        method: SemiJoinMethod,
+    ) -> Self
+    where
+        Self: SelectFieldByLabel<LLabel>,
+        VFieldTypeOf<Self, LLabel>: Hash + Eq,
+        DataView<RLabels, RFrames>: SelectFieldByLabel<RLabel, DType = VFieldTypeOf<Self, LLabel>>,
+        LLabel: LabelName,
+        RLabel: LabelName,
+    {
+        self.semi_join_records::<LLabel, RLabel, _, _>(right, method, true)
+    }
+
+    /// Returns a single-row `DataView` containing the full record in which the field labeled
+    /// `Label` has its minimum value. Missing values are ignored, and ties resolve to the earliest
+    /// record. Returns `None` if no values exist in the field.
+    pub fn min_row_by<Label>(&self) -> Option<Self>
+    where
+        Self: SelectFieldByLabel<Label>,
+        VFieldTypeOf<Self, Label>: PartialOrd,
+        Frames: Clone,
+    {
+        self.field::<Label>().idxmin().map(|idx| self.row(idx))
+    }
+
+    /// Returns a single-row `DataView` containing the full record in which the field labeled
+    /// `Label` has its maximum value. Missing values are ignored, and ties resolve to the earliest
+    /// record. Returns `None` if no values exist in the field.
+    pub fn max_row_by<Label>(&self) -> Option<Self>
+    where
+        Self: SelectFieldByLabel<Label>,
+        VFieldTypeOf<Self, Label>: PartialOrd,
+        Frames: Clone,
+    {
+        self.field::<Label>().idxmax().map(|idx| self.row(idx))
+    }
+
+    // creates a single-row view of the record at index `idx`
+    fn row(&self, idx: usize) -> Self
+    where
+        Frames: Clone,
+    {
+        self.rows(&[idx])
+    }
+
+    // creates a view of the records at indices `indices` (in that order)
+    fn rows(&self, indices: &[usize]) -> Self
+    where
+        Frames: Clone,
+    {
+        DataView {
+            _labels: PhantomData,
+            frames: self.frames.clone().update_permutation(indices),
+            log: self.log.clone(),
+            sort_cache: SortCache::default(),
+        }
+    }
+}
+
+/// A saved record filtering and ordering state of a [DataView](struct.DataView.html), created
+/// with [snapshot](struct.DataView.html#method.snapshot). A snapshot only holds references to the
+/// underlying data stores along with the current permutations, so it is cheap to create and hold.
+#[derive(Debug, Clone)]
+pub struct ViewSnapshot<Frames> {
+    frames: Frames,
+    log: OperationLog,
+    sort_cache: SortCache,
+}
+
+impl<Labels, Frames> DataView<Labels, Frames>
+where
+    Frames: Clone,
+{
+    /// Saves the current filtering and sorting state of this `DataView`. The `DataView` can later
+    /// be reverted to this state with [restore](#method.restore), undoing any filters or sorts
+    /// applied since the snapshot was taken.
+    pub fn snapshot(&self) -> ViewSnapshot<Frames> {
+        ViewSnapshot {
+            frames: self.frames.clone(),
+            log: self.log.clone(),
+            sort_cache: self.sort_cache.clone(),
+        }
+    }
+
+    /// Consumes this `DataView` and returns a `DataView` with the filtering and sorting state
+    /// saved in `snapshot`. The snapshot is not consumed, so it can be restored multiple times.
+    pub fn restore(mut self, snapshot: &ViewSnapshot<Frames>) -> Self {
+        self.frames = snapshot.frames.clone();
+        self.log = snapshot.log.clone();
+        self.sort_cache = snapshot.sort_cache.clone();
+        self
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames>
+where
+    Self: NRows,
+    Frames: JoinIntoStore<Labels, DataStore<Nil>>,
+{
+    /// Returns an iterator over consecutive batches of (at most) `batch_size` records of this
+    /// `DataView`, each materialized into a new [DataStore](../store/struct.DataStore.html). Only
+    /// one batch is materialized at a time, so this can be used to feed the data to consumers of
+    /// record batches without copying the whole `DataView` at once. The final batch contains the
+    /// remaining records, and may be smaller than `batch_size`.
+    ///
+    /// # Panics
+    /// Panics if `batch_size` is `0`.
+    pub fn iter_batches(&self, batch_size: usize) -> Batches<Labels, Frames> {
+        assert!(batch_size > 0, "batch size must be positive");
+        Batches {
+            view: self,
+            batch_size,
+            start: 0,
+        }
+    }
+
+    /// Consumes this `DataView` and copies its visible records into a new
+    /// [DataStore](../store/struct.DataStore.html). The resulting store contains the fields of
+    /// this `DataView` in the same order and under the same (possibly relabeled) labels, with
+    /// records in the order they appear in this `DataView` (after any filtering and sorting).
+    ///
+    /// Note that this allocates new data for every field; the original data is not affected.
+    pub fn into_store(self) -> <Frames as JoinIntoStore<Labels, DataStore<Nil>>>::Output {
+        let indices = (0..self.nrows()).collect::<Vec<_>>();
+        self.frames
+            .join_into_store(DataStore::<Nil>::empty(), &indices)
+            .expect("record indices are within bounds")
+    }
+}
+
+/// Iterator over fixed-size batches of records of a [DataView](struct.DataView.html), created
+/// with [iter_batches](struct.DataView.html#method.iter_batches).
+#[derive(Debug)]
+pub struct Batches<'a, Labels: 'a, Frames: 'a> {
+    view: &'a DataView<Labels, Frames>,
+    batch_size: usize,
+    start: usize,
+}
+
+impl<'a, Labels, Frames> Iterator for Batches<'a, Labels, Frames>
+where
+    DataView<Labels, Frames>: NRows,
+    Frames: JoinIntoStore<Labels, DataStore<Nil>>,
+{
+    type Item = <Frames as JoinIntoStore<Labels, DataStore<Nil>>>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nrows = self.view.nrows();
+        if self.start >= nrows {
+            return None;
+        }
+        let end = (self.start + self.batch_size).min(nrows);
+        let indices = (self.start..end).collect::<Vec<_>>();
+        self.start = end;
+        Some(
+            self.view
+                .frames
+                .join_into_store(DataStore::<Nil>::empty(), &indices)
+                .expect("batch indices are within bounds"),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.view.nrows().saturating_sub(self.start);
+        let nbatches = (remaining + self.batch_size - 1) / self.batch_size;
+        (nbatches, Some(nbatches))
+    }
+}
+
+/// Trait for finding a cons-list of fields (implementing
+/// [DataIndex](../access/trait.DataIndex.html)) from frames list `Frames` using the `LabelList`
+/// list of labels. `LabelList` should consist of labels that exist within `Self` (this trait is
+/// implemented by label lookup lists).
+pub trait FieldList<LabelList, Frames> {
+    /// Resultant cons-list of fields.
+    type Output;
+
+    /// Returns the cons-list of fields from the frames list `frames`.
+    fn field_list(frames: &Frames) -> Self::Output;
+}
+
+impl<LabelList, Frames> FieldList<LabelList, Frames> for Nil {
+    type Output = Nil;
+
+    fn field_list(_frames: &Frames) -> Nil {
+        Nil
+    }
+}
+
+impl<LabelList, Frames, Label, FrameIndex, FrameLabel, Tail> FieldList<LabelList, Frames>
+    for FrameLookupCons<Label, FrameIndex, FrameLabel, Tail>
+where
+    LabelList: Member<Label>,
+    Self: FieldListPred<LabelList, Frames, <LabelList as Member<Label>>::IsMember>,
+{
+    type Output =
+        <Self as FieldListPred<LabelList, Frames, <LabelList as Member<Label>>::IsMember>>::Output;
+
+    fn field_list(frames: &Frames) -> Self::Output {
+        Self::field_list_pred(frames)
+    }
+}
+
+/// Helper trait for ([FieldList](trait.FieldList.html)). `IsMember` is whether or not the head of
 /// `Self` is a member of the list `LabelList`.
 pub trait FieldListPred<LabelList, Frames, IsMember> {
     /// The output field list.
@@ -865,7 +1810,7 @@ pub struct Record<'a, Fields> {
 }
 
 impl<'a, Fields> Record<'a, Fields> {
-    fn new(field_list: &'a Fields, idx: usize) -> Record<'a, Fields> {
+    pub(crate) fn new(field_list: &'a Fields, idx: usize) -> Record<'a, Fields> {
         Record {
             fields: field_list,
             idx,
@@ -891,7 +1836,15 @@ where
     where
         H: Hasher,
     {
-        self.get_datum(idx).unwrap().hash(state);
+        // use the cached hash of the value if available, to avoid rehashing it; uncached values
+        // are hashed the same way the cache is computed, so that records hash identically whether
+        // or not their fields have cached hashes
+        let hash = self.cached_hash(idx).unwrap_or_else(|| {
+            let mut hasher = DefaultHasher::new();
+            self.get_datum(idx).unwrap().hash(&mut hasher);
+            hasher.finish()
+        });
+        state.write_u64(hash);
     }
 }
 
@@ -929,10 +1882,11 @@ where
     }
 }
 
-/// Trait for computing equality of a single index (record) within a list of data fields.
+/// Trait for computing equality of single indices (records) within lists of data fields.
 pub trait PartialEqIndex {
-    /// Returns equality of the values within this list of data fields with the index `idx`.
-    fn eq_index(&self, other: &Self, idx: usize) -> bool;
+    /// Returns equality of the values within this list of data fields with the index `idx` and the
+    /// values within the list of data fields `other` with the index `other_idx`.
+    fn eq_index(&self, idx: usize, other: &Self, other_idx: usize) -> bool;
 }
 
 impl<T, DI> PartialEqIndex for Framed<T, DI>
@@ -940,15 +1894,15 @@ where
     for<'a> Value<&'a T>: PartialEq,
     Self: DataIndex<DType = T>,
 {
-    fn eq_index(&self, other: &Self, idx: usize) -> bool {
+    fn eq_index(&self, idx: usize, other: &Self, other_idx: usize) -> bool {
         self.get_datum(idx)
             .unwrap()
-            .eq(&other.get_datum(idx).unwrap())
+            .eq(&other.get_datum(other_idx).unwrap())
     }
 }
 
 impl PartialEqIndex for Nil {
-    fn eq_index(&self, _other: &Nil, _idx: usize) -> bool {
+    fn eq_index(&self, _idx: usize, _other: &Nil, _other_idx: usize) -> bool {
         true
     }
 }
@@ -958,8 +1912,9 @@ where
     Head: PartialEqIndex,
     Tail: PartialEqIndex,
 {
-    fn eq_index(&self, other: &Self, idx: usize) -> bool {
-        self.head.eq_index(&other.head, idx) && self.tail.eq_index(&other.tail, idx)
+    fn eq_index(&self, idx: usize, other: &Self, other_idx: usize) -> bool {
+        self.head.eq_index(idx, &other.head, other_idx)
+            && self.tail.eq_index(idx, &other.tail, other_idx)
     }
 }
 
@@ -968,7 +1923,7 @@ where
     Fields: PartialEqIndex,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.fields.eq_index(other.fields, self.idx)
+        self.fields.eq_index(self.idx, other.fields, other.idx)
     }
 }
 
@@ -1086,7 +2041,251 @@ where
         DataView {
             _labels: PhantomData,
             frames: new_frames,
+            log: self.log.clone(),
+            sort_cache: SortCache::default(),
+        }
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames>
+where
+    Self: NRows,
+    Labels: FieldList<Labels, Frames>,
+    <Labels as FieldList<Labels, Frames>>::Output: HashIndex + PartialEqIndex,
+{
+    // computes the indices of the first appearance of each distinct record in this `DataView`,
+    // keeping only those for which `keep` (called with whether or not the record also appears in
+    // `other`) returns true
+    fn distinct_row_indices<F>(&self, other: &Self, mut keep: F) -> Vec<usize>
+    where
+        F: FnMut(bool) -> bool,
+    {
+        let fl = self.field_list::<Labels>();
+        let other_fl = other.field_list::<Labels>();
+        let other_records = (0..other.nrows())
+            .map(|i| Record::new(&other_fl, i))
+            .collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        let mut indices = vec![];
+        for i in 0..self.nrows() {
+            let record = Record::new(&fl, i);
+            if !seen.contains(&record) {
+                if keep(other_records.contains(&record)) {
+                    indices.push(i);
+                }
+                seen.insert(record);
+            }
+        }
+        indices
+    }
+
+    /// Consumes this `DataView` and returns a new `DataView` containing the distinct records which
+    /// also appear in `other` (a `DataView` with the same fields), in their original order. Records
+    /// are compared across all fields, with missing (NA) values considered equal to each other.
+    ///
+    /// All fields must implement `Hash`.
+    pub fn intersect_rows(mut self, other: &Self) -> Self
+    where
+        Frames: UpdatePermutation,
+    {
+        let indices = self.distinct_row_indices(other, |in_other| in_other);
+        self.frames = self.frames.update_permutation(&indices);
+        self.sort_cache = self.sort_cache.filtered();
+        self.log.record(Operation::SetOperation {
+            operation: "intersect",
+            right: other.log.to_vec(),
+        });
+        self
+    }
+
+    /// Consumes this `DataView` and returns a new `DataView` containing the distinct records which
+    /// do not appear in `other` (a `DataView` with the same fields), in their original order.
+    /// Records are compared across all fields, with missing (NA) values considered equal to each
+    /// other.
+    ///
+    /// All fields must implement `Hash`.
+    pub fn difference_rows(mut self, other: &Self) -> Self
+    where
+        Frames: UpdatePermutation,
+    {
+        let indices = self.distinct_row_indices(other, |in_other| !in_other);
+        self.frames = self.frames.update_permutation(&indices);
+        self.sort_cache = self.sort_cache.filtered();
+        self.log.record(Operation::SetOperation {
+            operation: "difference",
+            right: other.log.to_vec(),
+        });
+        self
+    }
+
+    /// Returns a new `DataView` containing the distinct records which appear in either this
+    /// `DataView` or `other` (a `DataView` with the same fields): the distinct records of this
+    /// `DataView` in their original order, followed by the distinct records of `other` which do not
+    /// appear in this `DataView`. Records are compared across all fields, with missing (NA) values
+    /// considered equal to each other.
+    ///
+    /// All fields must implement `Hash`.
+    pub fn union_rows(
+        &self,
+        other: &Self,
+    ) -> <<Frames as ConcatIntoStore<Labels, DataStore<Nil>>>::Output as IntoView>::Output
+    where
+        Frames: ConcatIntoStore<Labels, DataStore<Nil>>,
+        <Frames as ConcatIntoStore<Labels, DataStore<Nil>>>::Output: IntoView,
+        <<Frames as ConcatIntoStore<Labels, DataStore<Nil>>>::Output as IntoView>::Output:
+            WithOperationLog,
+    {
+        let indices = self.distinct_row_indices(other, |_| true);
+        let other_indices = other.distinct_row_indices(self, |in_self| !in_self);
+        self.frames
+            .concat_into_store(
+                &other.frames,
+                DataStore::<Nil>::empty(),
+                &indices,
+                &other_indices,
+            )
+            .into_view()
+            .with_operation_log(self.log.with(Operation::SetOperation {
+                operation: "union",
+                right: other.log.to_vec(),
+            }))
+    }
+
+    /// Compares this `DataView` with `old`, an older snapshot of the same data (a `DataView` with
+    /// the same fields), matching records between the two using the field labeled `Key`. Returns
+    /// a [RowChanges](struct.RowChanges.html) containing the records of this `DataView` whose key
+    /// does not appear in `old` (added), the records of `old` whose key does not appear in this
+    /// `DataView` (removed), and the records of this `DataView` whose key appears in `old` but
+    /// which do not match any record in `old` with that key across all fields (modified). Missing
+    /// (NA) values are considered equal to each other, both in keys and in other fields.
+    ///
+    /// All fields must implement `Hash`.
+    pub fn changes_since<Key>(&self, old: &Self) -> RowChanges<Labels, Frames>
+    where
+        Self: SelectFieldByLabel<Key>,
+        VFieldTypeOf<Self, Key>: Hash + Eq,
+        Frames: Clone + UpdatePermutation,
+    {
+        let fl = self.field_list::<Labels>();
+        let old_fl = old.field_list::<Labels>();
+        let keys = self.field::<Key>();
+        let old_keys = old.field::<Key>();
+
+        let mut old_records = HashMap::new();
+        for i in 0..old.nrows() {
+            old_records
+                .entry(old_keys.get_datum(i))
+                .or_insert_with(Vec::new)
+                .push(Record::new(&old_fl, i));
+        }
+
+        let mut added = vec![];
+        let mut modified = vec![];
+        let mut new_keys = HashSet::new();
+        for i in 0..self.nrows() {
+            let key = keys.get_datum(i);
+            match old_records.get(&key) {
+                Some(records) => {
+                    if !records.contains(&Record::new(&fl, i)) {
+                        modified.push(i);
+                    }
+                }
+                None => added.push(i),
+            }
+            new_keys.insert(key);
         }
+        let removed = (0..old.nrows())
+            .filter(|&i| !new_keys.contains(&old_keys.get_datum(i)))
+            .collect::<Vec<_>>();
+
+        RowChanges {
+            added: self.rows(&added),
+            removed: old.rows(&removed),
+            modified: self.rows(&modified),
+        }
+    }
+}
+
+/// Records which changed between two snapshots of the same data, as computed by
+/// [changes_since](struct.DataView.html#method.changes_since).
+#[derive(Debug, Clone)]
+pub struct RowChanges<Labels, Frames> {
+    /// Records of the newer `DataView` whose key does not appear in the older `DataView`.
+    pub added: DataView<Labels, Frames>,
+    /// Records of the older `DataView` whose key does not appear in the newer `DataView`.
+    pub removed: DataView<Labels, Frames>,
+    /// Records of the newer `DataView` whose key appears in the older `DataView`, but with
+    /// different values.
+    pub modified: DataView<Labels, Frames>,
+}
+
+/// A field of a [DataView](struct.DataView.html) with its label and data type erased, as returned
+/// by [erased_fields](struct.DataView.html#method.erased_fields).
+#[derive(Debug)]
+pub struct ErasedField<'a> {
+    /// Name of the field's label.
+    pub name: &'static str,
+    /// Name of the field's data type.
+    pub ty: &'static str,
+    /// Type-erased access to the field's data.
+    pub data: Box<dyn ErasedDataIndex + 'a>,
+}
+
+/// Trait for collecting the fields of a view as [ErasedField](struct.ErasedField.html)s. Used by
+/// [erased_fields](struct.DataView.html#method.erased_fields).
+pub trait ErasedFields<'a, Frames> {
+    /// Pushes each field with a label in this label list (using data from `frames`) onto `out`.
+    fn erased_fields(frames: &'a Frames, out: &mut Vec<ErasedField<'a>>);
+}
+
+impl<'a, Frames> ErasedFields<'a, Frames> for Nil {
+    fn erased_fields(_frames: &'a Frames, _out: &mut Vec<ErasedField<'a>>) {}
+}
+
+impl<'a, Frames, Label, FrameIndex, FrameLabel, Tail> ErasedFields<'a, Frames>
+    for FrameLookupCons<Label, FrameIndex, FrameLabel, Tail>
+where
+    Frames: SelectFieldFromLabels<Self, Label>,
+    <Frames as SelectFieldFromLabels<Self, Label>>::Output: 'a,
+    <Frames as SelectFieldFromLabels<Self, Label>>::DType: Display,
+    Label: LabelName,
+    Tail: ErasedFields<'a, Frames>,
+{
+    fn erased_fields(frames: &'a Frames, out: &mut Vec<ErasedField<'a>>) {
+        out.push(ErasedField {
+            name: Label::name(),
+            ty: Label::str_type(),
+            data: Box::new(Erased::new(
+                SelectFieldFromLabels::<Self, Label>::select_field(frames),
+            )),
+        });
+        Tail::erased_fields(frames, out)
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Returns the fields of this `DataView` (in order) with their labels and data types erased,
+    /// allowing them to be iterated over at runtime as trait objects. This is useful for generic
+    /// tooling (such as serializers or display code) which does not need to know the data types
+    /// of the fields at compile time.
+    pub fn erased_fields<'a>(&'a self) -> Vec<ErasedField<'a>>
+    where
+        Labels: ErasedFields<'a, Frames>,
+    {
+        let mut fields = vec![];
+        Labels::erased_fields(&self.frames, &mut fields);
+        fields
+    }
+
+    /// Returns the field of this `DataView` with the label named `name` (with its label and data
+    /// type erased), or `None` if this `DataView` has no such field.
+    pub fn erased_field<'a>(&'a self, name: &str) -> Option<ErasedField<'a>>
+    where
+        Labels: ErasedFields<'a, Frames>,
+    {
+        self.erased_fields()
+            .into_iter()
+            .find(|field| field.name == name)
     }
 }
 
@@ -1580,493 +2779,2488 @@ where
         DataView {
             _labels: PhantomData,
             frames: record_frames,
+            log: self.log.clone(),
+            sort_cache: SortCache::default(),
         }
         .add_frame(agg_frame)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fmt::Debug;
-    use std::path::Path;
-
-    use csv_sniffer::metadata::Metadata;
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Groups the records of this `DataView` by the unique composite values of the fields labeled
+    /// by `KeyLabels`, and computes a single value per group using `f`, which is called with the
+    /// row indices (in this `DataView`) of the records in each group.
+    ///
+    /// Returns a new `DataView` containing the fields in `KeyLabels` (one row per group, in order
+    /// of first appearance) along with the computed values in a new field labeled `OutLabel`.
+    ///
+    /// This is a more general version of [aggregate](struct.DataView.html#method.aggregate), useful
+    /// for computations which require access to all of a group's records at once.
+    pub fn group_apply<KeyLabels, OutLabel, T, F>(
+        &self,
+        f: F,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, T>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, T>,
+        F: FnMut(&[usize]) -> Value<T>,
+    {
+        GroupApply::<KeyLabels, OutLabel, T>::group_apply(self, f)
+    }
 
-    use super::*;
-    use source::csv::{CsvReader, CsvSource, IntoCsvSrcSchema};
+    /// Computes and caches the hashes of the values of the fields labeled by `KeyLabels`, so that
+    /// subsequent operations which group records by these fields (such as
+    /// [aggregate](struct.DataView.html#method.aggregate) and
+    /// [group_apply](struct.DataView.html#method.group_apply)) do not need to rehash their values.
+    /// This is useful when repeatedly grouping by the same fields, especially string fields.
+    ///
+    /// The hashes are cached alongside the stored field data (see
+    /// [CacheHashes](../store/trait.CacheHashes.html)), so they are also used by any other views of
+    /// the same data, including filtered and sorted views. Joins compare key values directly
+    /// (sorting and merging them) rather than hashing them, so they do not use cached hashes.
+    pub fn cache_key_hashes<KeyLabels>(&self)
+    where
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: CacheHashes,
+    {
+        self.field_list::<KeyLabels>().cache_hashes();
+    }
 
-    #[cfg(feature = "test-utils")]
-    use test_utils::*;
+    /// Computes the `q`th quantile (`0.0 <= q <= 1.0`) of the numeric field labeled `ValueLabel`
+    /// for each group of records sharing the same values in the `KeyLabels` fields. Quantiles are
+    /// computed as with [Quantile](../stats/trait.Quantile.html).
+    ///
+    /// Returns a new `DataView` containing the fields in `KeyLabels` along with the quantiles in a
+    /// new field labeled `OutLabel`. Groups without any existing values have a missing quantile.
+    pub fn group_quantile<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+        q: f64,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, f64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: AsPrimitive<f64> + Debug,
+    {
+        self.group_apply::<KeyLabels, OutLabel, f64, _>(|rows| {
+            self.field::<ValueLabel>().permute(rows).quantile(q).into()
+        })
+    }
 
-    use access::DataIndex;
-    use error::*;
+    /// Computes the `q`th quantile (`0.0 <= q <= 1.0`) of the numeric field labeled `ValueLabel`,
+    /// using the frequency weights in the field labeled `WeightLabel`, for each group of records
+    /// sharing the same values in the `KeyLabels` fields. Quantiles are computed as with
+    /// [WeightedQuantile](../stats/trait.WeightedQuantile.html).
+    ///
+    /// Returns a new `DataView` containing the fields in `KeyLabels` along with the quantiles in a
+    /// new field labeled `OutLabel`. Groups without any existing values have a missing quantile.
+    pub fn group_weighted_quantile<KeyLabels, ValueLabel, WeightLabel, OutLabel>(
+        &self,
+        q: f64,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, f64>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, f64>
+            + SelectFieldByLabel<ValueLabel>
+            + SelectFieldByLabel<WeightLabel>,
+        VFieldTypeOf<Self, ValueLabel>: AsPrimitive<f64> + Debug,
+        VFieldTypeOf<Self, WeightLabel>: AsPrimitive<f64> + Debug,
+    {
+        self.group_apply::<KeyLabels, OutLabel, f64, _>(|rows| {
+            self.field::<ValueLabel>()
+                .permute(rows)
+                .weighted_quantile(&self.field::<WeightLabel>().permute(rows), q)
+                .into()
+        })
+    }
+}
 
-    fn load_csv_file<Schema>(
-        filename: &str,
-        schema: Schema,
-    ) -> (CsvReader<Schema::CsvSrcSchema>, Metadata)
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Returns the first existing value of the field labeled `ValueLabel` within each group of
+    /// records sharing the same values in the `KeyLabels` fields, in a new field labeled
+    /// `OutLabel`. Groups without any existing values have a missing value.
+    pub fn group_first<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>>::Output
     where
-        Schema: IntoCsvSrcSchema,
-        <Schema as IntoCsvSrcSchema>::CsvSrcSchema: Debug,
+        Self: GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>
+            + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: Clone,
     {
-        let data_filepath = Path::new(file!()) // start as this file
-            .parent()
-            .unwrap() // navigate up to src directory
-            .parent()
-            .unwrap() // navigate up to root directory
-            .join("tests") // navigate into integration tests directory
-            .join("data") // navigate into data directory
-            .join(filename); // navigate to target file
+        let values = self.field::<ValueLabel>();
+        self.group_apply::<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>, _>(|rows| {
+            rows.iter()
+                .map(|&row| values.get_datum(row).unwrap())
+                .find(|value| value.exists())
+                .map_or(Value::Na, |value| value.cloned())
+        })
+    }
 
-        let source = CsvSource::new(data_filepath).unwrap();
-        (
-            CsvReader::new(&source, schema).unwrap(),
-            source.metadata().clone(),
-        )
+    /// Returns the last existing value of the field labeled `ValueLabel` within each group of
+    /// records sharing the same values in the `KeyLabels` fields, in a new field labeled
+    /// `OutLabel`. Groups without any existing values have a missing value.
+    pub fn group_last<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>
+            + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: Clone,
+    {
+        let values = self.field::<ValueLabel>();
+        self.group_apply::<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>, _>(|rows| {
+            rows.iter()
+                .rev()
+                .map(|&row| values.get_datum(row).unwrap())
+                .find(|value| value.exists())
+                .map_or(Value::Na, |value| value.cloned())
+        })
     }
 
-    tablespace![
-        pub table gdp {
-            CountryName: String,
-            CountryCode: String,
-            Year1983: f64,
-        }
-    ];
+    /// Returns the value (possibly missing) of the field labeled `ValueLabel` in the `n`th record
+    /// (starting at `0`) of each group of records sharing the same values in the `KeyLabels`
+    /// fields, in a new field labeled `OutLabel`. Groups with `n` or fewer records have a missing
+    /// value.
+    pub fn group_nth<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+        n: usize,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>>
+            + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: Clone,
+    {
+        let values = self.field::<ValueLabel>();
+        self.group_apply::<KeyLabels, OutLabel, VFieldTypeOf<Self, ValueLabel>, _>(|rows| {
+            rows.get(n)
+                .map_or(Value::Na, |&row| values.get_datum(row).unwrap().cloned())
+        })
+    }
 
-    #[test]
-    fn lookup_field() {
-        let gdp_schema = schema![
-            fieldname gdp::CountryName = "Country Name";
-            fieldname gdp::CountryCode = "Country Code";
-            fieldname gdp::Year1983 = "1983";
-        ];
+    /// Returns the row index (within this `DataView`) of the minimum existing value of the field
+    /// labeled `ValueLabel` within each group of records sharing the same values in the
+    /// `KeyLabels` fields, in a new field labeled `OutLabel`. Ties resolve to the earliest row.
+    /// Groups without any existing values have a missing index.
+    pub fn group_idxmin<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, u64>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, u64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: PartialOrd + Debug,
+    {
+        self.group_apply::<KeyLabels, OutLabel, u64, _>(|rows| {
+            self.field::<ValueLabel>()
+                .permute(rows)
+                .idxmin()
+                .map_or(Value::Na, |idx| Value::Exists(rows[idx] as u64))
+        })
+    }
+
+    /// Returns the row index (within this `DataView`) of the maximum existing value of the field
+    /// labeled `ValueLabel` within each group of records sharing the same values in the
+    /// `KeyLabels` fields, in a new field labeled `OutLabel`. Ties resolve to the earliest row.
+    /// Groups without any existing values have a missing index.
+    pub fn group_idxmax<KeyLabels, ValueLabel, OutLabel>(
+        &self,
+    ) -> <Self as GroupApply<KeyLabels, OutLabel, u64>>::Output
+    where
+        Self: GroupApply<KeyLabels, OutLabel, u64> + SelectFieldByLabel<ValueLabel>,
+        VFieldTypeOf<Self, ValueLabel>: PartialOrd + Debug,
+    {
+        self.group_apply::<KeyLabels, OutLabel, u64, _>(|rows| {
+            self.field::<ValueLabel>()
+                .permute(rows)
+                .idxmax()
+                .map_or(Value::Na, |idx| Value::Exists(rows[idx] as u64))
+        })
+    }
+}
+
+/// Trait providing the `group_apply` method for computing a value for each group of records. See
+/// the intrinsic method [group_apply](struct.DataView.html#method.group_apply) for more details.
+pub trait GroupApply<KeyLabels, OutLabel, T> {
+    /// Type produced by this group_apply method.
+    type Output;
+
+    /// Perform the 'group_apply' operation. See the intrinsic method
+    /// [group_apply](struct.DataView.html#method.group_apply) for more details.
+    fn group_apply<F>(&self, f: F) -> Self::Output
+    where
+        F: FnMut(&[usize]) -> Value<T>;
+
+    /// Perform the 'group_apply' operation over previously-computed `groups` of row indices
+    /// (such as those of a [GroupBy](../groupby/struct.GroupBy.html)) instead of grouping the
+    /// records by the `KeyLabels` fields. Each group must be non-empty, and the records of each
+    /// group must share the same values in the `KeyLabels` fields.
+    fn group_apply_to<F>(&self, groups: &[Vec<usize>], f: F) -> Self::Output
+    where
+        F: FnMut(&[usize]) -> Value<T>;
+}
+
+impl<Labels, Frames, KeyLabels, OutLabel, T> GroupApply<KeyLabels, OutLabel, T>
+    for DataView<Labels, Frames>
+where
+    Self: NRows,
+    Labels: FieldList<KeyLabels, Frames> + LabelSubset<KeyLabels> + FrameIndexList,
+    <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+    <Labels as LabelSubset<KeyLabels>>::Output: Reorder<KeyLabels>,
+    T: Debug + Default,
+    FieldData<T>: IntoStore<OutLabel>,
+    <FieldData<T> as IntoStore<OutLabel>>::Output: IntoFrame,
+    Frames: NRows + SubsetClone<<Labels as FrameIndexList>::LabelList>,
+    <Frames as SubsetClone<<Labels as FrameIndexList>::LabelList>>::Output: UpdatePermutation,
+    DataView<
+        <<Labels as LabelSubset<KeyLabels>>::Output as Reorder<KeyLabels>>::Output,
+        <Frames as SubsetClone<<Labels as FrameIndexList>::LabelList>>::Output,
+    >: AddFrame<AddedFieldFrame<OutLabel, T>>,
+{
+    // output is KeyLabels, then single OutLabel column
+    type Output = <DataView<
+        <<Labels as LabelSubset<KeyLabels>>::Output as Reorder<KeyLabels>>::Output,
+        <Frames as SubsetClone<<Labels as FrameIndexList>::LabelList>>::Output,
+    > as AddFrame<AddedFieldFrame<OutLabel, T>>>::Output;
+
+    fn group_apply<F>(&self, f: F) -> Self::Output
+    where
+        F: FnMut(&[usize]) -> Value<T>,
+    {
+        let groups = self.group_rows::<KeyLabels>();
+        GroupApply::<KeyLabels, OutLabel, T>::group_apply_to(self, &groups, f)
+    }
+
+    fn group_apply_to<F>(&self, groups: &[Vec<usize>], mut f: F) -> Self::Output
+    where
+        F: FnMut(&[usize]) -> Value<T>,
+    {
+        // each group is represented by its first record
+        let indices = groups.iter().map(|rows| rows[0]).collect::<Vec<_>>();
+        let group_data: FieldData<T> = groups.iter().map(|rows| f(&rows[..])).collect();
+        let group_frame = IntoStore::<OutLabel>::into_store(group_data).into_frame();
+
+        let record_frames = self.frames.subset_clone().update_permutation(&indices);
+
+        DataView {
+            _labels: PhantomData,
+            frames: record_frames,
+            log: self.log.clone(),
+            sort_cache: SortCache::default(),
+        }
+        .add_frame(group_frame)
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Adds the field `data` to this `DataView` with label `NewLabel`, returning a new `DataView`
+    /// with the additional field. The values of `data` are aligned with the current (possibly
+    /// sorted or filtered) rows of this `DataView`.
+    ///
+    /// Fails if the length of `data` does not match the number of rows in this `DataView`.
+    pub fn add_field<NewLabel, T>(
+        &self,
+        data: FieldData<T>,
+    ) -> error::Result<<Self as AddField<NewLabel, T>>::Output>
+    where
+        Self: AddField<NewLabel, T>,
+    {
+        AddField::<NewLabel, T>::add_field(self, data)
+    }
+}
+
+/// Trait for adding a field of data with label `NewLabel` to a [DataView](struct.DataView.html).
+/// See the intrinsic method [add_field](struct.DataView.html#method.add_field) for more details.
+pub trait AddField<NewLabel, T> {
+    /// Type produced by adding the field.
+    type Output;
+
+    /// Add the field `data` with label `NewLabel`. See the intrinsic method
+    /// [add_field](struct.DataView.html#method.add_field) for more details.
+    fn add_field(&self, data: FieldData<T>) -> error::Result<Self::Output>;
+}
+
+/// Type of the frame created when adding a field of type `T` with label `Label` to a `DataView`.
+pub type AddedFieldFrame<Label, T> =
+    <<FieldData<T> as IntoStore<Label>>::Output as IntoFrame>::Output;
+
+impl<Labels, Frames, NewLabel, T> AddField<NewLabel, T> for DataView<Labels, Frames>
+where
+    Self: NRows + AddFrame<AddedFieldFrame<NewLabel, T>>,
+    FieldData<T>: IntoStore<NewLabel>,
+    <FieldData<T> as IntoStore<NewLabel>>::Output: IntoFrame,
+{
+    type Output = <Self as AddFrame<AddedFieldFrame<NewLabel, T>>>::Output;
+
+    fn add_field(&self, data: FieldData<T>) -> error::Result<Self::Output> {
+        if data.len() != self.nrows() {
+            return Err(error::AgnesError::LengthMismatch {
+                expected: self.nrows(),
+                actual: data.len(),
+            });
+        }
+        Ok(self.add_frame(IntoStore::<NewLabel>::into_store(data).into_frame()))
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Assigns a group identifier to each run of consecutive equal values in the field labeled
+    /// `Label` (in the current row order of this `DataView`), returning a new `DataView` with an
+    /// additional `u64` field labeled `GroupLabel` containing these identifiers. Identifiers start
+    /// at `0` and increase by one at the start of each new run. Consecutive missing values are
+    /// considered a single run.
+    ///
+    /// The group identifier field can be used as a grouping key, for example with
+    /// [aggregate](struct.DataView.html#method.aggregate), to compute run-length summaries.
+    pub fn group_consecutive<Label, GroupLabel>(
+        &self,
+    ) -> <Self as AddFrame<AddedFieldFrame<GroupLabel, u64>>>::Output
+    where
+        Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<GroupLabel, u64>>,
+        VFieldTypeOf<Self, Label>: PartialEq,
+        FieldData<u64>: IntoStore<GroupLabel>,
+        <FieldData<u64> as IntoStore<GroupLabel>>::Output: IntoFrame,
+    {
+        let run_ids = run_ids(&self.field::<Label>(), |prev, curr| prev == curr);
+        self.add_frame(IntoStore::<GroupLabel>::into_store(run_ids).into_frame())
+    }
+
+    /// Assigns a group identifier to each run of consecutive values in the numeric field labeled
+    /// `Label` (in the current row order of this `DataView`) where each value differs from the
+    /// previous value by at most `threshold`. This is useful for session analysis, where `Label`
+    /// is an ordered timestamp-like field and a new session starts after a gap of more than
+    /// `threshold`.
+    ///
+    /// Returns a new `DataView` with an additional `u64` field labeled `GroupLabel` containing
+    /// the identifiers, as with [group_consecutive](struct.DataView.html#method.group_consecutive).
+    /// Missing values always start a new run.
+    pub fn group_consecutive_within<Label, GroupLabel>(
+        &self,
+        threshold: f64,
+    ) -> <Self as AddFrame<AddedFieldFrame<GroupLabel, u64>>>::Output
+    where
+        Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<GroupLabel, u64>>,
+        VFieldTypeOf<Self, Label>: AsPrimitive<f64>,
+        FieldData<u64>: IntoStore<GroupLabel>,
+        <FieldData<u64> as IntoStore<GroupLabel>>::Output: IntoFrame,
+    {
+        let run_ids = run_ids(&self.field::<Label>(), |prev, curr| match (prev, curr) {
+            (Value::Exists(&prev), Value::Exists(&curr)) => {
+                let (prev, curr): (f64, f64) = (prev.as_(), curr.as_());
+                (curr - prev).abs() <= threshold
+            }
+            _ => false,
+        });
+        self.add_frame(IntoStore::<GroupLabel>::into_store(run_ids).into_frame())
+    }
+
+    /// Detects gaps in the ordered numeric (or [DateTime](../datetime/struct.DateTime.html)) field
+    /// labeled `Label`: pairs of consecutive existing values (in the current row order of this
+    /// `DataView`) which differ by more than the expected step `step`. `DateTime` values are
+    /// compared in seconds. Missing values are skipped.
+    ///
+    /// Returns a new `DataView` with one record per gap, containing the last value before the gap
+    /// in a field labeled `StartLabel` and the first value after the gap in a field labeled
+    /// `EndLabel`. A complete series results in an empty `DataView`.
+    pub fn gaps<Label, StartLabel, EndLabel>(
+        &self,
+        step: f64,
+    ) -> <Self as Gaps<Label, StartLabel, EndLabel>>::Output
+    where
+        Self: Gaps<Label, StartLabel, EndLabel>,
+    {
+        Gaps::<Label, StartLabel, EndLabel>::gaps(self, step)
+    }
+}
+
+/// Trait providing the `gaps` method for detecting gaps in an ordered field. See the intrinsic
+/// method [gaps](struct.DataView.html#method.gaps) for more details.
+pub trait Gaps<Label, StartLabel, EndLabel> {
+    /// Type produced by this gaps method.
+    type Output;
+
+    /// Perform the 'gaps' operation. See the intrinsic method
+    /// [gaps](struct.DataView.html#method.gaps) for more details.
+    fn gaps(&self, step: f64) -> Self::Output;
+}
+
+// `DataView` containing a single field labeled `Label` with data type `T`
+pub(crate) type SingleFieldView<Label, T> =
+    <<FieldData<T> as IntoStore<Label>>::Output as IntoView>::Output;
+
+impl<Labels, Frames, Label, StartLabel, EndLabel> Gaps<Label, StartLabel, EndLabel>
+    for DataView<Labels, Frames>
+where
+    Self: SelectFieldByLabel<Label>,
+    VFieldTypeOf<Self, Label>: AsPrimitive<f64>,
+    FieldData<VFieldTypeOf<Self, Label>>: IntoStore<StartLabel> + IntoStore<EndLabel>,
+    <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<StartLabel>>::Output: IntoView,
+    <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<EndLabel>>::Output: IntoFrame,
+    SingleFieldView<StartLabel, VFieldTypeOf<Self, Label>>:
+        AddFrame<AddedFieldFrame<EndLabel, VFieldTypeOf<Self, Label>>>,
+{
+    type Output = <SingleFieldView<StartLabel, VFieldTypeOf<Self, Label>> as AddFrame<
+        AddedFieldFrame<EndLabel, VFieldTypeOf<Self, Label>>,
+    >>::Output;
+
+    fn gaps(&self, step: f64) -> Self::Output {
+        let mut starts = vec![];
+        let mut ends = vec![];
+        let mut prev: Option<VFieldTypeOf<Self, Label>> = None;
+        for value in self.field::<Label>().iter() {
+            if let Value::Exists(&curr) = value {
+                if let Some(prev) = prev {
+                    let (prev_pos, curr_pos): (f64, f64) = (prev.as_(), curr.as_());
+                    if curr_pos - prev_pos > step {
+                        starts.push(prev);
+                        ends.push(curr);
+                    }
+                }
+                prev = Some(curr);
+            }
+        }
+        let starts: FieldData<_> = starts.into();
+        let ends: FieldData<_> = ends.into();
+        IntoStore::<StartLabel>::into_store(starts)
+            .into_view()
+            .add_frame(IntoStore::<EndLabel>::into_store(ends).into_frame())
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Limits the values of the field labeled `Label` to the range `[min, max]`, returning a new
+    /// `DataView` with an additional field labeled `OutLabel` containing the clipped values. Values
+    /// less than `min` are replaced with `min`, and values greater than `max` are replaced with
+    /// `max`. Missing values remain missing.
+    pub fn clip<Label, OutLabel>(
+        &self,
+        min: VFieldTypeOf<Self, Label>,
+        max: VFieldTypeOf<Self, Label>,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        VFieldTypeOf<Self, Label>: PartialOrd + Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let clipped: FieldData<VFieldTypeOf<Self, Label>> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| {
+                value.map(|value| {
+                    if *value < min {
+                        min.clone()
+                    } else if *value > max {
+                        max.clone()
+                    } else {
+                        value.clone()
+                    }
+                })
+            })
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(clipped).into_frame())
+    }
+
+    /// Winsorizes the values of the numeric field labeled `Label`, returning a new `DataView`
+    /// with an additional `f64` field labeled `OutLabel` containing the winsorized values. The
+    /// lowest `lower_pct` fraction of values are replaced with the `lower_pct` quantile, and the
+    /// highest `upper_pct` fraction of values are replaced with the `1 - upper_pct` quantile
+    /// (quantiles computed as with [Quantile](../stats/trait.Quantile.html)). Missing values
+    /// remain missing.
+    ///
+    /// # Panics
+    /// Panics if `lower_pct` or `upper_pct` are not within `[0, 1]`, or if
+    /// `lower_pct + upper_pct > 1`.
+    pub fn winsorize<Label, OutLabel>(
+        &self,
+        lower_pct: f64,
+        upper_pct: f64,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, f64>>>::Output
+    where
+        Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<OutLabel, f64>>,
+        VFieldTypeOf<Self, Label>: AsPrimitive<f64>,
+        FieldData<f64>: IntoStore<OutLabel>,
+        <FieldData<f64> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        assert!(
+            lower_pct >= 0.0 && upper_pct >= 0.0 && lower_pct + upper_pct <= 1.0,
+            "invalid winsorizing limits: {}, {}",
+            lower_pct,
+            upper_pct
+        );
+        let field = self.field::<Label>();
+        let bounds = (field.quantile(lower_pct), field.quantile(1.0 - upper_pct));
+        let winsorized: FieldData<f64> = field
+            .iter()
+            .map(|value| {
+                value.map(|&value| {
+                    let value: f64 = value.as_();
+                    match bounds {
+                        (Some(lower), _) if value < lower => lower,
+                        (_, Some(upper)) if value > upper => upper,
+                        _ => value,
+                    }
+                })
+            })
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(winsorized).into_frame())
+    }
+
+    /// Replaces the values of the field labeled `Label` which satisfy `predicate` with missing
+    /// values, returning a new `DataView` with an additional field labeled `OutLabel` containing
+    /// the resulting values. This is useful for data sources which use sentinel values (e.g. `0`
+    /// or `-999`) to denote missing data. Values which are already missing remain missing.
+    pub fn na_if<Label, OutLabel, P>(
+        &self,
+        mut predicate: P,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        VFieldTypeOf<Self, Label>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+        P: FnMut(&VFieldTypeOf<Self, Label>) -> bool,
+    {
+        let nullified: FieldData<VFieldTypeOf<Self, Label>> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| value.filter(|value| !predicate(value)).cloned())
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(nullified).into_frame())
+    }
+
+    /// Normalizes the values of the string field labeled `Label` as specified by `normalization`,
+    /// returning a new `DataView` with an additional `String` field labeled `OutLabel` containing
+    /// the normalized values. Missing values remain missing.
+    ///
+    /// The normalized field can be used as a grouping key (for example, with
+    /// [aggregate](struct.DataView.html#method.aggregate)) so that values which only differ in case
+    /// or whitespace are grouped together.
+    pub fn normalize_str<Label, OutLabel>(
+        &self,
+        normalization: StrNormalization,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, String>>>::Output
+    where
+        Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<OutLabel, String>>,
+        VFieldTypeOf<Self, Label>: AsRef<str>,
+        FieldData<String>: IntoStore<OutLabel>,
+        <FieldData<String> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let normalized: FieldData<String> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| value.map(|value| normalization.normalize(value.as_ref())))
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(normalized).into_frame())
+    }
+
+    /// Converts the values of the numeric field labeled `Label` into totally-ordered, hashable
+    /// [TotalF64](../permute/struct.TotalF64.html) values (treating signed zeros as specified by
+    /// `signed_zero`), returning a new `DataView` with an additional field labeled `OutLabel`
+    /// containing the converted values. Missing values remain missing.
+    ///
+    /// The converted field can be used as a join or grouping key (for example, with
+    /// [aggregate](struct.DataView.html#method.aggregate)), which is not possible with `f64`
+    /// fields directly.
+    pub fn float_key<Label, OutLabel>(
+        &self,
+        signed_zero: SignedZero,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, TotalF64>>>::Output
+    where
+        Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<OutLabel, TotalF64>>,
+        VFieldTypeOf<Self, Label>: AsPrimitive<f64>,
+        FieldData<TotalF64>: IntoStore<OutLabel>,
+        <FieldData<TotalF64> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let keys: FieldData<TotalF64> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| value.map(|&value| TotalF64::new(value.as_(), signed_zero)))
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(keys).into_frame())
+    }
+
+    /// Converts the [DateTime](../datetime/struct.DateTime.html) values of the field labeled
+    /// `Label` to the timezone with offset `offset`, returning a new `DataView` with an additional
+    /// field labeled `OutLabel` containing the converted values. Missing values remain missing.
+    ///
+    /// Converted values represent the same instants in time (and so compare, join, and sort
+    /// identically to the originals), but are displayed and broken into calendar components (with
+    /// [dt](../datetime/trait.DateTimeIndex.html#method.dt)) in the new timezone.
+    pub fn to_timezone<Label, OutLabel>(
+        &self,
+        offset: UtcOffset,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, DateTime>>>::Output
+    where
+        Self: SelectFieldByLabel<Label, DType = DateTime>
+            + AddFrame<AddedFieldFrame<OutLabel, DateTime>>,
+        FieldData<DateTime>: IntoStore<OutLabel>,
+        <FieldData<DateTime> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let converted: FieldData<DateTime> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| value.map(|value| value.to_timezone(offset)))
+            .collect();
+        self.add_frame(IntoStore::<OutLabel>::into_store(converted).into_frame())
+    }
+
+    /// Maps the [DateTime](../datetime/struct.DateTime.html) values of the field labeled `Label`
+    /// to the start of their calendar period `period` (e.g. the first day of their month or
+    /// quarter, or the Monday of their ISO week), returning a new `DataView` with an additional
+    /// field labeled `OutLabel` containing the period starts. Missing values remain missing.
+    ///
+    /// The added field can be used as a grouping key for calendar reporting (for example, with
+    /// [aggregate](struct.DataView.html#method.aggregate)).
+    pub fn to_period<Label, OutLabel>(
+        &self,
+        period: Period,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, DateTime>>>::Output
+    where
+        Self: SelectFieldByLabel<Label, DType = DateTime>
+            + AddFrame<AddedFieldFrame<OutLabel, DateTime>>,
+        FieldData<DateTime>: IntoStore<OutLabel>,
+        <FieldData<DateTime> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let periods = self.field::<Label>().dt().period_start(period);
+        self.add_frame(IntoStore::<OutLabel>::into_store(periods).into_frame())
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Fills missing values of the field labeled `Label` with the most recent preceding existing
+    /// value (in the current row order of this `DataView`), returning a new `DataView` with an
+    /// additional field labeled `OutLabel` containing the filled values. Missing values before the
+    /// first existing value remain missing.
+    pub fn fill_forward<Label, OutLabel>(
+        &self,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: NRows
+            + SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        VFieldTypeOf<Self, Label>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let filled = fill_missing(&self.field::<Label>(), &vec![0; self.nrows()], false);
+        self.add_frame(IntoStore::<OutLabel>::into_store(filled).into_frame())
+    }
+
+    /// Fills missing values of the field labeled `Label` with the nearest following existing
+    /// value (in the current row order of this `DataView`), returning a new `DataView` with an
+    /// additional field labeled `OutLabel` containing the filled values. Missing values after the
+    /// last existing value remain missing.
+    pub fn fill_backward<Label, OutLabel>(
+        &self,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: NRows
+            + SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        VFieldTypeOf<Self, Label>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let filled = fill_missing(&self.field::<Label>(), &vec![0; self.nrows()], true);
+        self.add_frame(IntoStore::<OutLabel>::into_store(filled).into_frame())
+    }
+
+    /// Fills missing values of the field labeled `Label` with the most recent preceding existing
+    /// value among records sharing the same values in the `KeyLabels` fields, returning a new
+    /// `DataView` with an additional field labeled `OutLabel` containing the filled values.
+    ///
+    /// Records are processed in the current row order of this `DataView`, so panel or
+    /// longitudinal data should be sorted (e.g. by time) before filling. Values are never carried
+    /// across groups: missing values before the first existing value of a group remain missing.
+    pub fn fill_forward_within<KeyLabels, Label, OutLabel>(
+        &self,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: NRows
+            + SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+        VFieldTypeOf<Self, Label>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let group_ids = self.group_ids::<KeyLabels>();
+        let filled = fill_missing(&self.field::<Label>(), &group_ids, false);
+        self.add_frame(IntoStore::<OutLabel>::into_store(filled).into_frame())
+    }
+
+    /// Fills missing values of the field labeled `Label` with the nearest following existing
+    /// value among records sharing the same values in the `KeyLabels` fields, returning a new
+    /// `DataView` with an additional field labeled `OutLabel` containing the filled values.
+    ///
+    /// As with [fill_forward_within](struct.DataView.html#method.fill_forward_within), records
+    /// are processed in the current row order of this `DataView`, and values are never carried
+    /// across groups.
+    pub fn fill_backward_within<KeyLabels, Label, OutLabel>(
+        &self,
+    ) -> <Self as AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>>::Output
+    where
+        Self: NRows
+            + SelectFieldByLabel<Label>
+            + AddFrame<AddedFieldFrame<OutLabel, VFieldTypeOf<Self, Label>>>,
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+        VFieldTypeOf<Self, Label>: Clone + Debug + Default,
+        FieldData<VFieldTypeOf<Self, Label>>: IntoStore<OutLabel>,
+        <FieldData<VFieldTypeOf<Self, Label>> as IntoStore<OutLabel>>::Output: IntoFrame,
+    {
+        let group_ids = self.group_ids::<KeyLabels>();
+        let filled = fill_missing(&self.field::<Label>(), &group_ids, true);
+        self.add_frame(IntoStore::<OutLabel>::into_store(filled).into_frame())
+    }
+
+    // assigns each record an identifier for the group of records sharing the same values in the
+    // `KeyLabels` fields, numbered in order of first appearance
+    fn group_ids<KeyLabels>(&self) -> Vec<usize>
+    where
+        Self: NRows,
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+    {
+        let fl = self.field_list::<KeyLabels>();
+        let mut map = HashMap::new();
+        (0..self.nrows())
+            .map(|i| {
+                let next_id = map.len();
+                *map.entry(Record::new(&fl, i)).or_insert(next_id)
+            })
+            .collect()
+    }
+
+    // groups the row indices of this view by the values in the `KeyLabels` fields, with groups in
+    // order of first appearance
+    pub(crate) fn group_rows<KeyLabels>(&self) -> Vec<Vec<usize>>
+    where
+        Self: NRows,
+        Labels: FieldList<KeyLabels, Frames>,
+        <Labels as FieldList<KeyLabels, Frames>>::Output: HashIndex + PartialEqIndex,
+    {
+        let fl = self.field_list::<KeyLabels>();
+        let mut map = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for i in 0..self.nrows() {
+            let group_idx = *map.entry(Record::new(&fl, i)).or_insert_with(|| {
+                groups.push(vec![]);
+                groups.len() - 1
+            });
+            groups[group_idx].push(i);
+        }
+        groups
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames>
+where
+    Self: NRows,
+{
+    /// Randomly assigns each record of this `DataView` to one of `n` buckets, returning a new
+    /// `DataView` with an additional `u64` field labeled `BucketLabel` containing the bucket
+    /// identifiers (from `0` to `n - 1`). The assignment is reproducible: the same `seed` always
+    /// produces the same assignment for a `DataView` with the same number of rows. This is useful
+    /// for A/B sampling or assigning cross-validation folds. A `None` seed uses the crate-level
+    /// default seed (see the [rng](../rng/index.html) module).
+    ///
+    /// # Panics
+    /// Panics if `n` is `0`.
+    pub fn assign_buckets<BucketLabel>(
+        &self,
+        n: u64,
+        seed: Option<u64>,
+    ) -> <Self as AddField<BucketLabel, u64>>::Output
+    where
+        Self: AddField<BucketLabel, u64>,
+    {
+        assert!(n > 0, "number of buckets must be positive");
+        let mut rng = operation_rng(seed);
+        let buckets: FieldData<u64> = (0..self.nrows()).map(|_| rng.gen_range(0, n)).collect();
+        AddField::<BucketLabel, u64>::add_field(self, buckets)
+            .expect("bucket field length matches number of rows")
+    }
+}
+
+// computes run identifiers for the values in `field`, where `same_run` determines whether two
+// consecutive values are part of the same run
+fn run_ids<DI, F>(field: &DI, same_run: F) -> FieldData<u64>
+where
+    DI: DataIndex,
+    F: Fn(Value<&DI::DType>, Value<&DI::DType>) -> bool,
+{
+    let mut run_id = 0u64;
+    let mut prev = None;
+    field
+        .iter()
+        .map(|curr| {
+            if let Some(prev) = prev {
+                if !same_run(prev, curr) {
+                    run_id += 1;
+                }
+            }
+            prev = Some(curr);
+            run_id
+        })
+        .collect()
+}
+
+// fills missing values of `field` with the nearest existing value among preceding records (or
+// following records, if `backward` is true) with the same group identifier in `group_ids`
+fn fill_missing<DI>(field: &DI, group_ids: &[usize], backward: bool) -> FieldData<DI::DType>
+where
+    DI: DataIndex,
+    DI::DType: Clone + Debug + Default,
+{
+    let num_groups = group_ids.iter().max().map_or(0, |&max_id| max_id + 1);
+    let mut last_values: Vec<Option<&DI::DType>> = vec![None; num_groups];
+    let mut fill = |idx: usize| {
+        let last_value = &mut last_values[group_ids[idx]];
+        if let Value::Exists(value) = field.get_datum(idx).unwrap() {
+            *last_value = Some(value);
+        }
+        Value::from(last_value.cloned())
+    };
+    if backward {
+        let mut filled = (0..field.len()).rev().map(&mut fill).collect::<Vec<_>>();
+        filled.reverse();
+        FieldData::from_field_vec(filled)
+    } else {
+        (0..field.len()).map(&mut fill).collect()
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames> {
+    /// Bins the values of the numeric field labeled `Label` into the intervals defined by the
+    /// ascending bin boundaries `edges`, returning a new `DataView` with an additional `String`
+    /// field labeled `BinLabel` which contains the interval each value falls into.
+    ///
+    /// Intervals are closed on the left and open on the right (e.g. `[0, 10)`), except for the
+    /// last interval, which is closed on both ends. Values outside of the range of `edges` as well
+    /// as missing values are assigned a missing bin.
+    ///
+    /// The resulting bin field can be used directly as a grouping key, for example with
+    /// [aggregate](struct.DataView.html#method.aggregate), to aggregate a numeric field over
+    /// ranges of values.
+    pub fn cut<Label, BinLabel>(&self, edges: &[f64]) -> <Self as Cut<Label, BinLabel>>::Output
+    where
+        Self: Cut<Label, BinLabel>,
+    {
+        Cut::<Label, BinLabel>::cut(self, edges)
+    }
+}
+
+/// Trait for binning a numeric field into intervals. See the intrinsic method
+/// [cut](struct.DataView.html#method.cut) for more details.
+pub trait Cut<Label, BinLabel> {
+    /// Type produced by this cut method.
+    type Output;
+
+    /// Bin the values of the field labeled `Label` into intervals defined by `edges`. See the
+    /// intrinsic method [cut](struct.DataView.html#method.cut) for more details.
+    fn cut(&self, edges: &[f64]) -> Self::Output;
+}
+
+impl<Labels, Frames, Label, BinLabel> Cut<Label, BinLabel> for DataView<Labels, Frames>
+where
+    Self: SelectFieldByLabel<Label> + AddFrame<AddedFieldFrame<BinLabel, String>>,
+    VFieldTypeOf<Self, Label>: AsPrimitive<f64>,
+    FieldData<String>: IntoStore<BinLabel>,
+    <FieldData<String> as IntoStore<BinLabel>>::Output: IntoFrame,
+{
+    type Output = <Self as AddFrame<AddedFieldFrame<BinLabel, String>>>::Output;
+
+    fn cut(&self, edges: &[f64]) -> Self::Output {
+        let nbins = edges.len().saturating_sub(1);
+        let bin_labels = (0..nbins)
+            .map(|i| {
+                if i + 1 == nbins {
+                    format!("[{}, {}]", edges[i], edges[i + 1])
+                } else {
+                    format!("[{}, {})", edges[i], edges[i + 1])
+                }
+            })
+            .collect::<Vec<_>>();
+        let find_bin = |value: f64| {
+            (0..nbins).find(|&i| {
+                // last bin is closed on the right
+                value >= edges[i]
+                    && (value < edges[i + 1] || (i + 1 == nbins && value == edges[i + 1]))
+            })
+        };
+
+        let bins: FieldData<String> = self
+            .field::<Label>()
+            .iter()
+            .map(|value| {
+                value.and_then(|&value| {
+                    Value::from(find_bin(value.as_())).map(|bin| bin_labels[bin].clone())
+                })
+            })
+            .collect();
+        self.add_frame(IntoStore::<BinLabel>::into_store(bins).into_frame())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::path::Path;
+
+    use csv_sniffer::metadata::Metadata;
+
+    use super::*;
+    use source::csv::{CsvReader, CsvSource, IntoCsvSrcSchema};
+
+    #[cfg(feature = "test-utils")]
+    use test_utils::*;
+
+    use access::DataIndex;
+    use error::*;
+
+    fn load_csv_file<Schema>(
+        filename: &str,
+        schema: Schema,
+    ) -> (CsvReader<Schema::CsvSrcSchema>, Metadata)
+    where
+        Schema: IntoCsvSrcSchema,
+        <Schema as IntoCsvSrcSchema>::CsvSrcSchema: Debug,
+    {
+        let data_filepath = Path::new(file!()) // start as this file
+            .parent()
+            .unwrap() // navigate up to src directory
+            .parent()
+            .unwrap() // navigate up to root directory
+            .join("tests") // navigate into integration tests directory
+            .join("data") // navigate into data directory
+            .join(filename); // navigate to target file
+
+        let source = CsvSource::new(data_filepath).unwrap();
+        (
+            CsvReader::new(&source, schema).unwrap(),
+            source.metadata().clone(),
+        )
+    }
+
+    tablespace![
+        pub table gdp {
+            CountryName: String,
+            CountryCode: String,
+            Year1983: f64,
+        }
+    ];
+
+    #[test]
+    fn lookup_field() {
+        let gdp_schema = schema![
+            fieldname gdp::CountryName = "Country Name";
+            fieldname gdp::CountryCode = "Country Code";
+            fieldname gdp::Year1983 = "1983";
+        ];
+
+        let (mut csv_rdr, _metadata) = load_csv_file("gdp.csv", gdp_schema.clone());
+        let ds = csv_rdr.read().unwrap();
+        let view = ds.into_view();
+
+        let country_name = view.field::<gdp::CountryName>();
+        println!("{:?}", country_name);
+    }
+
+    #[test]
+    fn generate_dataindex_cons() {
+        let gdp_schema = schema![
+            fieldname gdp::CountryName = "Country Name";
+            fieldname gdp::CountryCode = "Country Code";
+            fieldname gdp::Year1983 = "1983";
+        ];
 
         let (mut csv_rdr, _metadata) = load_csv_file("gdp.csv", gdp_schema.clone());
         let ds = csv_rdr.read().unwrap();
         let view = ds.into_view();
 
-        let country_name = view.field::<gdp::CountryName>();
-        println!("{:?}", country_name);
+        println!("{}", view);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn merge() {
+        let dv1 = sample_emp_table().into_view();
+        let dv2 = sample_emp_table_extra().into_view();
+
+        println!("{}", dv1);
+        println!("{}", dv2);
+
+        let merged_dv = dv1.merge(&dv2).unwrap();
+        println!("{}", merged_dv);
+        assert_eq!(merged_dv.nrows(), 7);
+        assert_eq!(merged_dv.nfields(), 6);
+        assert_eq!(
+            merged_dv.fieldnames(),
+            vec![
+                "EmpId",
+                "DeptId",
+                "EmpName",
+                "SalaryOffset",
+                "DidTraining",
+                "VacationHrs"
+            ]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn merge_dimension_mismatch() {
+        let dv1 = sample_emp_table().into_view();
+        let dv2 = sample_dept_table().into_view();
+
+        println!("{}", dv1);
+        println!("{}", dv2);
+
+        let merge_result = dv1.merge(&dv2);
+        match merge_result {
+            Ok(_) => {
+                panic!("Merge was expected to fail (dimension mismatch), but succeeded");
+            }
+            Err(AgnesError::DimensionMismatch(_)) => { /* expected */ }
+            Err(e) => {
+                panic!("Incorrect error: {:?}", e);
+            }
+        };
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn map_from_nearest() {
+        let dv = sample_emp_table().into_view();
+        let lookup = dept_table(vec![1u64, 4, 7], vec!["a", "b", "c"]).into_view();
+        // EmpId: [0, 2, 5, 6, 8, 9, 10]
+        let names = |direction: AsOf, tolerance: Option<f64>| {
+            dv.map_from_nearest::<emp_table::EmpId, dept_table::DeptId, dept_table::DeptName, _, _>(
+                &lookup, direction, tolerance,
+            )
+            .field::<dept_table::DeptName>()
+            .to_value_vec()
+            .into_iter()
+            .map(|name| {
+                name.map(|name| name.to_string())
+                    .unwrap_or_else(|| "NA".to_string())
+            })
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(AsOf::Backward, None),
+            vec!["NA", "a", "b", "b", "c", "c", "c"]
+        );
+        assert_eq!(
+            names(AsOf::Backward, Some(1.0)),
+            vec!["NA", "a", "b", "NA", "c", "NA", "NA"]
+        );
+        assert_eq!(
+            names(AsOf::Forward, None),
+            vec!["a", "b", "c", "c", "NA", "NA", "NA"]
+        );
+        assert_eq!(
+            names(AsOf::Nearest, None),
+            vec!["a", "a", "b", "c", "c", "c", "c"]
+        );
+        assert_eq!(
+            names(AsOf::Nearest, Some(2.0)),
+            vec!["a", "a", "b", "c", "c", "c", "NA"]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn map_from() {
+        let dv = sample_emp_table().into_view();
+        // department 4 is missing, department 1 appears twice
+        let lookup = dept_table(
+            vec![1u64, 2, 3, 1],
+            vec!["Marketing", "Sales", "Manufacturing", "Other"],
+        )
+        .into_view();
+
+        let mapped = dv
+            .map_from::<emp_table::DeptId, dept_table::DeptId, dept_table::DeptName, _, _>(
+                &lookup,
+            );
+        assert_eq!(mapped.nrows(), 7);
+        assert_eq!(mapped.nfields(), 4);
+        assert_eq!(
+            mapped.field::<emp_table::EmpId>().to_vec(),
+            vec![0u64, 2, 5, 6, 8, 9, 10]
+        );
+        assert_eq!(
+            mapped.field::<dept_table::DeptName>().to_value_vec(),
+            vec![
+                Value::Exists("Marketing".to_string()),
+                Value::Exists("Sales".to_string()),
+                Value::Exists("Marketing".to_string()),
+                Value::Exists("Marketing".to_string()),
+                Value::Exists("Manufacturing".to_string()),
+                Value::Na,
+                Value::Na
+            ]
+        );
+    }
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::test_utils::emp_table::Table>)
+
+        pub table emp_table2 {
+            EmpId: u64,
+            DeptId: u64,
+            EmpName: String,
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn merge_different_stores() {
+        let dv1 = sample_emp_table().into_view();
+
+        // would NOT COMPILE due to field name collision (see compile-fail/merge_errors test)
+        // let merge_result = dv1.merge(&sample_emp_table().into_view());
+
+        // if we use a sample employee table generated in another tablespace, however:
+        let ds2: emp_table2::Store = sample_emp_table![];
+        let dv2 = ds2.into_view();
+
+        println!("{}", dv1);
+        println!("{}", dv2);
+
+        let merged_dv = dv1.merge(&dv2).unwrap();
+
+        println!("{}", merged_dv);
+        assert_eq!(merged_dv.nrows(), 7);
+        assert_eq!(merged_dv.nfields(), 6);
+        assert_eq!(
+            merged_dv.fieldnames(),
+            vec!["EmpId", "DeptId", "EmpName", "EmpId", "DeptId", "EmpName"]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn normalized_keys() {
+        let dv1 = sample_emp_table().into_view();
+        let ds2: emp_table2::Store = emp_table![
+            vec![0u64, 2, 5],
+            vec![1u64, 2, 1],
+            vec![" SALLY", "jamie ", "B o b"]
+        ];
+        let dv2 = ds2.into_view();
+
+        let normalization = StrNormalization::new().case_insensitive().trimmed();
+        let joined_dv = dv1
+            .join_by::<Join<emp_table::EmpName, emp_table2::EmpName, Equal>, _, _, _, _>(
+                &dv2,
+                normalization.key_fn(),
+                normalization.key_fn(),
+            );
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpId>().to_vec(),
+            vec![0u64, 2]
+        );
+        assert_eq!(
+            joined_dv.field::<emp_table2::EmpName>().to_vec(),
+            vec![" SALLY", "jamie "]
+        );
+
+        let normalized =
+            dv2.normalize_str::<emp_table2::EmpName, emp_table3::EmployeeName>(normalization);
+        assert_eq!(
+            normalized.field::<emp_table3::EmployeeName>().to_vec(),
+            vec!["sally", "jamie", "b o b"]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn fuzzy_join() {
+        use fuzzy::{Blocking, FuzzyMatch, Similarity};
+
+        let dv1 = sample_emp_table().into_view();
+        let ds2: emp_table2::Store = emp_table![
+            vec![0u64, 2, 5, 6],
+            vec![1u64, 2, 1, 1],
+            vec!["sallie", "Jaime", "Rob", "Kara"]
+        ];
+        let dv2 = ds2.into_view();
+
+        let fuzzy = FuzzyMatch::new(Similarity::Levenshtein(2))
+            .normalization(StrNormalization::new().case_insensitive());
+        let joined_dv =
+            dv1.fuzzy_join::<emp_table::EmpName, emp_table2::EmpName, _, _>(&dv2, &fuzzy);
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpName>().to_vec(),
+            vec!["Sally", "Jamie", "Bob", "Cara"]
+        );
+        assert_eq!(
+            joined_dv.field::<emp_table2::EmpName>().to_vec(),
+            vec!["sallie", "Jaime", "Rob", "Kara"]
+        );
+
+        // blocking on first letter excludes matches with differing first letters
+        let fuzzy = fuzzy.blocking(Blocking::Prefix(1));
+        let joined_dv =
+            dv1.fuzzy_join::<emp_table::EmpName, emp_table2::EmpName, _, _>(&dv2, &fuzzy);
+        assert_eq!(
+            joined_dv.field::<emp_table::EmpName>().to_vec(),
+            vec!["Sally", "Jamie"]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::view::tests::emp_table2::Table>)
+
+        pub table emp_table3 {
+            EmployeeId: u64,
+            DepartmentId: u64,
+            EmployeeName: String,
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn relabel() {
+        let dv1 = sample_emp_table().into_view();
+        let dv2 = sample_emp_table().into_view();
+
+        // much like merge_different_stores, this won't compile
+        // let merged_dv = dv1.merge(&dv2).unwrap();
+        // if we relabel all the fields in one of the two tables, however, we can go ahead and merge
+        let dv1 = dv1.relabel::<emp_table::EmpId, emp_table3::EmployeeId>();
+        let dv1 = dv1.relabel::<emp_table::DeptId, emp_table3::DepartmentId>();
+        let dv1 = dv1.relabel::<emp_table::EmpName, emp_table3::EmployeeName>();
+
+        let merged_dv = dv1.merge(&dv2).unwrap();
+        println!("{}", merged_dv);
+        assert_eq!(merged_dv.nrows(), 7);
+        assert_eq!(merged_dv.nfields(), 6);
+        assert_eq!(
+            merged_dv.fieldnames(),
+            vec![
+                "EmployeeId",
+                "DepartmentId",
+                "EmployeeName",
+                "EmpId",
+                "DeptId",
+                "EmpName"
+            ]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::view::tests::emp_table3::Table>)
+
+        pub table emp_table4 {
+            EmplId: u64 = {"Employee Id"},
+            DeptId: u64 = {"Department Id"},
+            EmpName: String = {"Employee Name"},
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::view::tests::emp_table4::Table>)
+
+        pub table emp_bins {
+            VacationBin: String,
+            NumEmployees: u64,
+            RunId: u64,
+            Bucket: u64,
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn cut_aggregate() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        use view::tests::emp_bins::*;
+
+        let dv = sample_merged_emp_table();
+        let binned = dv.cut::<VacationHrs, VacationBin>(&[0.0, 25.0, 50.0, 100.0]);
+        assert_eq!(binned.nfields(), 7);
+        assert_eq!(
+            binned.field::<VacationBin>().to_value_vec(),
+            vec![
+                Value::Exists("[25, 50)".to_string()),
+                Value::Exists("[50, 100]".to_string()),
+                Value::Exists("[50, 100]".to_string()),
+                Value::Exists("[0, 25)".to_string()),
+                Value::Na,
+                Value::Exists("[0, 25)".to_string()),
+                Value::Exists("[0, 25)".to_string()),
+            ]
+        );
+
+        let counts = binned.aggregate::<Labels![VacationBin], EmpId, NumEmployees, _, _, _>(
+            0u64,
+            |count, _| *count += 1,
+        );
+        println!("{}", counts);
+        assert_eq!(counts.nrows(), 4);
+        assert_eq!(counts.field::<NumEmployees>().to_vec(), vec![1u64, 2, 3, 1]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::view::tests::emp_bins::Table>)
+
+        pub table survey {
+            Region: String,
+            Income: f64,
+            Freq: u64,
+            MedianIncome: f64,
+            GroupIncome: f64,
+            GroupRow: u64,
+            LimitedIncome: f64,
+            IncomeKey: TotalF64,
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn group_quantile() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "east"];
+            Income = [10.0, 20.0, 30.0, 50.0, 20.0, 15.0];
+            Freq = [1u64, 3, 1, 1, 2, 0];
+        ];
+
+        let medians = dv.group_quantile::<Labels![Region], Income, MedianIncome>(0.5);
+        println!("{}", medians);
+        assert_eq!(medians.nrows(), 3);
+        assert_eq!(
+            medians.field::<Region>().to_vec(),
+            vec!["north", "south", "east"]
+        );
+        assert_eq!(
+            medians.field::<MedianIncome>().to_vec(),
+            vec![20.0, 35.0, 15.0]
+        );
+
+        let medians =
+            dv.group_weighted_quantile::<Labels![Region], Income, Freq, MedianIncome>(0.5);
+        println!("{}", medians);
+        // 'north' expands to [10, 20, 20, 30], 'south' to [20, 20, 20, 50], and 'east' has no
+        // weighted values
+        assert_eq!(
+            medians.field::<MedianIncome>().to_value_vec(),
+            vec![Value::Exists(20.0), Value::Exists(20.0), Value::Na]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn group_consecutive() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        use view::tests::emp_bins::*;
+
+        let dv = sample_merged_emp_table();
+        let runs = dv.group_consecutive::<DeptId, RunId>();
+        assert_eq!(
+            runs.field::<RunId>().to_vec(),
+            vec![0u64, 1, 2, 2, 3, 4, 4]
+        );
+
+        let runs = dv.group_consecutive_within::<VacationHrs, RunId>(10.0);
+        assert_eq!(
+            runs.field::<RunId>().to_vec(),
+            vec![0u64, 0, 1, 2, 3, 3, 4]
+        );
+
+        // run-length summary
+        let run_lengths = runs.aggregate::<Labels![RunId], EmpId, NumEmployees, _, _, _>(
+            0u64,
+            |count, _| *count += 1,
+        );
+        assert_eq!(
+            run_lengths.field::<NumEmployees>().to_vec(),
+            vec![2u64, 1, 1, 2, 1]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn group_first_last_nth() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "east"];
+            Income = [10.0, 20.0, 30.0, 50.0, 20.0, 15.0];
+        ];
+
+        let first = dv.group_first::<Labels![Region], Income, GroupIncome>();
+        assert_eq!(first.field::<GroupIncome>().to_vec(), vec![10.0, 20.0, 15.0]);
+        let last = dv.group_last::<Labels![Region], Income, GroupIncome>();
+        assert_eq!(last.field::<GroupIncome>().to_vec(), vec![20.0, 50.0, 15.0]);
+        let nth = dv.group_nth::<Labels![Region], Income, GroupIncome>(1);
+        assert_eq!(
+            nth.field::<GroupIncome>().to_value_vec(),
+            vec![Value::Exists(30.0), Value::Exists(50.0), Value::Na]
+        );
+
+        let idxmin = dv.group_idxmin::<Labels![Region], Income, GroupRow>();
+        assert_eq!(idxmin.field::<GroupRow>().to_vec(), vec![0u64, 1, 5]);
+        let idxmax = dv.group_idxmax::<Labels![Region], Income, GroupRow>();
+        assert_eq!(idxmax.field::<GroupRow>().to_vec(), vec![2u64, 3, 5]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn dataview_macro() {
+        use view::tests::survey::*;
+
+        let dv = dataview![
+            Region,  Income, Freq;
+            "north", 10.0,   1u64;
+            "south", 20.0,   3;
+            "east",  15.0,   0;
+        ];
+        println!("{}", dv);
+        assert_eq!((dv.nrows(), dv.nfields()), (3, 3));
+        assert_eq!(
+            dv.field::<Region>().to_vec(),
+            vec!["north", "south", "east"]
+        );
+        assert_eq!(dv.field::<Income>().to_vec(), vec![10.0, 20.0, 15.0]);
+        assert_eq!(dv.field::<Freq>().to_vec(), vec![1, 3, 0]);
+
+        let single = dataview![
+            Income;
+            -5.0 * 2.0;
+        ];
+        assert_eq!(single.field::<Income>().to_vec(), vec![-10.0]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn cache_key_hashes() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "east"];
+            Income = [10.0, 20.0, 30.0, 50.0, 20.0, 15.0];
+        ];
+        let uncached = dv.group_first::<Labels![Region], Income, GroupIncome>();
+        assert_eq!(dv.field::<Region>().cached_hash(0), None);
+
+        dv.cache_key_hashes::<Labels![Region]>();
+        let region = dv.field::<Region>();
+        assert!(region.cached_hash(0).is_some());
+        assert_eq!(region.cached_hash(0), region.cached_hash(2));
+        assert_ne!(region.cached_hash(0), region.cached_hash(1));
+        let cached = dv.group_first::<Labels![Region], Income, GroupIncome>();
+        assert_eq!(
+            cached.field::<Region>().to_vec(),
+            uncached.field::<Region>().to_vec()
+        );
+        assert_eq!(
+            cached.field::<GroupIncome>().to_vec(),
+            vec![10.0, 20.0, 15.0]
+        );
+
+        // cached hashes are shared with sorted views of the same data
+        let sorted = dv.clone().sort_by_label::<Income>();
+        assert_eq!(
+            sorted.field::<Region>().cached_hash(0),
+            region.cached_hash(0)
+        );
+        let sorted_first = sorted.group_first::<Labels![Region], Income, GroupIncome>();
+        assert_eq!(
+            sorted_first.field::<Region>().to_vec(),
+            vec!["north", "east", "south"]
+        );
+        assert_eq!(
+            sorted_first.field::<GroupIncome>().to_vec(),
+            vec![10.0, 15.0, 20.0]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn clip_winsorize() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "east"];
+            Income = [10.0, 20.0, 30.0, 50.0, 20.0, 15.0];
+        ];
+
+        let clipped = dv.clip::<Income, LimitedIncome>(12.0, 40.0);
+        assert_eq!(
+            clipped.field::<LimitedIncome>().to_vec(),
+            vec![12.0, 20.0, 30.0, 40.0, 20.0, 15.0]
+        );
+
+        // sorted values are [10, 15, 20, 20, 30, 50], with 20% and 80% quantiles of 15 and 30
+        let winsorized = dv.winsorize::<Income, LimitedIncome>(0.2, 0.2);
+        assert_eq!(
+            winsorized.field::<LimitedIncome>().to_vec(),
+            vec![15.0, 20.0, 30.0, 30.0, 20.0, 15.0]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn na_if() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "east"];
+            Income = [10.0, 0.0, 30.0, 0.0, -999.0, 15.0];
+        ];
+
+        let nullified = dv.na_if::<Income, LimitedIncome, _>(|&income| income <= 0.0);
+        assert_eq!(
+            nullified.field::<LimitedIncome>().to_value_vec(),
+            vec![
+                Value::Exists(10.0),
+                Value::Na,
+                Value::Exists(30.0),
+                Value::Na,
+                Value::Na,
+                Value::Exists(15.0)
+            ]
+        );
+        // original field is unchanged
+        assert_eq!(nullified.field::<Income>().to_vec()[1], 0.0);
+
+        // values which are already missing remain missing
+        let renullified = nullified
+            .v::<Labels![Region, LimitedIncome]>()
+            .na_if::<LimitedIncome, Income, _>(|&income| income > 20.0);
+        assert_eq!(
+            renullified.field::<Income>().to_value_vec(),
+            vec![
+                Value::Exists(10.0),
+                Value::Na,
+                Value::Na,
+                Value::Na,
+                Value::Na,
+                Value::Exists(15.0)
+            ]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn fill() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "north", "south", "north", "south"];
+            Income = [0.0, 20.0, 10.0, 0.0, 0.0, 0.0];
+        ];
+        // missing values: [NA, 20, 10, NA, NA, NA]
+        let dv = dv.na_if::<Income, LimitedIncome, _>(|&income| income == 0.0);
+
+        assert_eq!(
+            dv.fill_forward::<LimitedIncome, GroupIncome>()
+                .field::<GroupIncome>()
+                .to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists(20.0),
+                Value::Exists(10.0),
+                Value::Exists(10.0),
+                Value::Exists(10.0),
+                Value::Exists(10.0)
+            ]
+        );
+        assert_eq!(
+            dv.fill_backward::<LimitedIncome, GroupIncome>()
+                .field::<GroupIncome>()
+                .to_value_vec(),
+            vec![
+                Value::Exists(20.0),
+                Value::Exists(20.0),
+                Value::Exists(10.0),
+                Value::Na,
+                Value::Na,
+                Value::Na
+            ]
+        );
+        assert_eq!(
+            dv.fill_forward_within::<Labels![Region], LimitedIncome, GroupIncome>()
+                .field::<GroupIncome>()
+                .to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists(20.0),
+                Value::Exists(10.0),
+                Value::Exists(20.0),
+                Value::Exists(10.0),
+                Value::Exists(20.0)
+            ]
+        );
+        assert_eq!(
+            dv.fill_backward_within::<Labels![Region], LimitedIncome, GroupIncome>()
+                .field::<GroupIncome>()
+                .to_value_vec(),
+            vec![
+                Value::Exists(10.0),
+                Value::Exists(20.0),
+                Value::Exists(10.0),
+                Value::Na,
+                Value::Na,
+                Value::Na
+            ]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn assign_buckets() {
+        use view::tests::emp_bins::*;
+
+        let dv = sample_merged_emp_table();
+        let bucketed = dv.assign_buckets::<Bucket>(3, Some(42));
+        assert_eq!(bucketed.nrows(), 7);
+        assert_eq!(bucketed.nfields(), 7);
+        let buckets = bucketed.field::<Bucket>().to_vec();
+        assert!(buckets.iter().all(|&bucket| bucket < 3));
+
+        // same seed results in same assignment
+        assert_eq!(
+            dv.assign_buckets::<Bucket>(3, Some(42))
+                .field::<Bucket>()
+                .to_vec(),
+            buckets
+        );
+
+        // single bucket
+        assert_eq!(
+            dv.assign_buckets::<Bucket>(1, Some(7))
+                .field::<Bucket>()
+                .to_vec(),
+            vec![0u64; 7]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn add_field() {
+        use view::tests::emp_bins::*;
+
+        let dv = sample_emp_table().into_view();
+        let dv = dv
+            .add_field::<NumEmployees, u64>(FieldData::from_vec(vec![1u64, 1, 1, 1, 1, 1, 1]))
+            .unwrap();
+        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName", "NumEmployees"]);
+
+        match dv.add_field::<VacationBin, String>(FieldData::from_vec(vec!["a".to_string()])) {
+            Err(AgnesError::LengthMismatch { expected, actual }) => {
+                assert_eq!((expected, actual), (7, 1));
+            }
+            _ => panic!("expected length mismatch"),
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn name_change() {
+        let ds: emp_table4::Store = sample_emp_table![];
+        let dv = ds.into_view();
+
+        println!("{}", dv);
+        assert_eq!(dv.nrows(), 7);
+        assert_eq!(dv.nfields(), 3);
+        assert_eq!(
+            dv.fieldnames(),
+            vec!["Employee Id", "Department Id", "Employee Name"]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn fieldnames() {
+        let ds = sample_emp_table();
+        let dv = ds.into_view();
+        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
     }
 
+    #[cfg(feature = "test-utils")]
     #[test]
-    fn generate_dataindex_cons() {
-        let gdp_schema = schema![
-            fieldname gdp::CountryName = "Country Name";
-            fieldname gdp::CountryCode = "Country Code";
-            fieldname gdp::Year1983 = "1983";
-        ];
+    fn erased_fields() {
+        let dv = sample_merged_emp_table();
+        let fields = dv.erased_fields();
+        assert_eq!(
+            fields.iter().map(|field| field.name).collect::<Vec<_>>(),
+            dv.fieldnames()
+        );
+        assert_eq!(
+            fields.iter().map(|field| field.ty).collect::<Vec<_>>(),
+            vec!["u64", "u64", "String", "i64", "bool", "f32"]
+        );
+        assert!(fields.iter().all(|field| field.data.len() == 7));
+        assert_eq!(
+            fields
+                .iter()
+                .map(|field| field.data.get_string(2).unwrap().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["5", "1", "Bob", "12", "true", "98.3"]
+        );
 
-        let (mut csv_rdr, _metadata) = load_csv_file("gdp.csv", gdp_schema.clone());
-        let ds = csv_rdr.read().unwrap();
-        let view = ds.into_view();
+        let name = dv.erased_field("EmpName").unwrap();
+        assert_eq!(
+            name.data.get_string(6).unwrap(),
+            Value::Exists("Ann".to_string())
+        );
+        assert_eq!(name.data.count_na(), 0);
+        assert!(dv.erased_field("Salary").is_none());
 
-        println!("{}", view);
+        let sorted = dv.sort_by_label::<emp_table::EmpName>();
+        let name = sorted.erased_field("EmpName").unwrap();
+        assert_eq!(
+            name.data.get_string(0).unwrap(),
+            Value::Exists("Ann".to_string())
+        );
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn merge() {
-        let dv1 = sample_emp_table().into_view();
-        let dv2 = sample_emp_table_extra().into_view();
+    fn subview() {
+        use test_utils::emp_table::*;
+        let ds = sample_emp_table();
+        let dv = ds.into_view();
+        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+        assert_eq!(dv.store_ref_counts(), vec![1]);
+        assert_eq!(dv.nrows(), 7);
+        assert_eq!(dv.nfields(), 3);
 
-        println!("{}", dv1);
-        println!("{}", dv2);
+        let subdv1 = dv.v::<Labels![EmpId]>();
+        assert_eq!(subdv1.fieldnames(), vec!["EmpId"]);
+        assert_eq!(dv.store_ref_counts(), vec![2]);
+        assert_eq!(subdv1.nrows(), 7);
+        assert_eq!(subdv1.nfields(), 1);
 
-        let merged_dv = dv1.merge(&dv2).unwrap();
-        println!("{}", merged_dv);
-        assert_eq!(merged_dv.nrows(), 7);
-        assert_eq!(merged_dv.nfields(), 6);
+        let subdv1 = dv.v::<Labels![EmpId]>();
+        assert_eq!(subdv1.fieldnames(), vec!["EmpId"]);
+        assert_eq!(dv.store_ref_counts(), vec![3]);
+        assert_eq!(subdv1.nrows(), 7);
+        assert_eq!(subdv1.nfields(), 1);
+
+        let subdv2 = dv.v::<Labels![EmpId, DeptId]>();
+        assert_eq!(subdv2.fieldnames(), vec!["EmpId", "DeptId"]);
+        assert_eq!(dv.store_ref_counts(), vec![4]);
+        assert_eq!(subdv2.nrows(), 7);
+        assert_eq!(subdv2.nfields(), 2);
+
+        let subdv2 = dv.v::<Labels![EmpId, DeptId]>();
+        assert_eq!(subdv2.fieldnames(), vec!["EmpId", "DeptId"]);
+        assert_eq!(dv.store_ref_counts(), vec![5]);
+        assert_eq!(subdv2.nrows(), 7);
+        assert_eq!(subdv2.nfields(), 2);
+
+        let subdv3 = dv.v::<Labels![EmpId, DeptId, EmpName]>();
+        assert_eq!(subdv3.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+        assert_eq!(dv.store_ref_counts(), vec![6]);
+        assert_eq!(subdv3.nrows(), 7);
+        assert_eq!(subdv3.nfields(), 3);
+
+        let subdv3 = dv.v::<Labels![EmpId, DeptId, EmpName]>();
+        assert_eq!(subdv3.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+        assert_eq!(dv.store_ref_counts(), vec![7]);
+        assert_eq!(subdv3.nrows(), 7);
+        assert_eq!(subdv3.nfields(), 3);
+
+        // Subview of a subview
+        let subdv4 = subdv2.v::<Labels![DeptId]>();
+        assert_eq!(subdv4.fieldnames(), vec!["DeptId"]);
+        assert_eq!(dv.store_ref_counts(), vec![8]);
+        assert_eq!(subdv4.nrows(), 7);
+        assert_eq!(subdv4.nfields(), 1);
+
+        let subdv4 = subdv2.v::<Labels![EmpId]>();
+        assert_eq!(subdv4.fieldnames(), vec!["EmpId"]);
+        assert_eq!(dv.store_ref_counts(), vec![9]);
+        assert_eq!(subdv4.nrows(), 7);
+        assert_eq!(subdv4.nfields(), 1);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn subview_merged() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+
+        let dv = sample_merged_emp_table();
+        println!("{:?}", dv.store_ref_counts());
+
+        let subdv = dv.v::<Labels![DeptId, DidTraining]>();
+        println!("{}", subdv);
+        assert_eq!(subdv.fieldnames(), vec!["DeptId", "DidTraining"]);
+        assert_eq!(dv.store_ref_counts(), vec![2, 2]);
+        assert_eq!(subdv.nrows(), 7);
+        assert_eq!(subdv.nfields(), 2);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn subview_label_sets() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+
+        let dv = sample_merged_emp_table();
+        let subdv = dv.v_except::<Labels![EmpName, SalaryOffset]>();
         assert_eq!(
-            merged_dv.fieldnames(),
-            vec![
-                "EmpId",
-                "DeptId",
-                "EmpName",
-                "SalaryOffset",
-                "DidTraining",
-                "VacationHrs"
-            ]
+            subdv.fieldnames(),
+            vec!["EmpId", "DeptId", "DidTraining", "VacationHrs"]
         );
+        assert_eq!(subdv.nrows(), 7);
+
+        let other = sample_emp_table()
+            .into_view()
+            .v::<Labels![EmpName, EmpId]>();
+        let subdv = dv.v_common(&other);
+        assert_eq!(subdv.fieldnames(), vec!["EmpId", "EmpName"]);
+        assert_eq!(subdv.nframes(), 1);
+
+        // union of label lists
+        let subdv = dv.v::<LabelsUnion<Labels![DidTraining], Labels![EmpId, DidTraining]>>();
+        assert_eq!(subdv.fieldnames(), vec!["DidTraining", "EmpId"]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn merge_dimension_mismatch() {
-        let dv1 = sample_emp_table().into_view();
-        let dv2 = sample_dept_table().into_view();
+    fn subview_prune_frames() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+
+        let dv = sample_merged_emp_table();
+        assert_eq!(dv.nframes(), 2);
+
+        // frames which contain none of the selected fields are dropped
+        let subdv = dv.v::<Labels![DidTraining, VacationHrs]>();
+        assert_eq!(subdv.nframes(), 1);
+        assert_eq!(dv.store_ref_counts(), vec![1, 2]);
+
+        // frames merged into a pruned view don't collide with its remaining frames
+        let merged = subdv.merge(&sample_emp_table().into_view()).unwrap();
+        assert_eq!(merged.nframes(), 2);
+        assert_eq!(
+            merged.fieldnames(),
+            vec!["DidTraining", "VacationHrs", "EmpId", "DeptId", "EmpName"]
+        );
+        assert_eq!(
+            merged.field::<DidTraining>().to_vec(),
+            vec![false, false, true, true, true, false, true]
+        );
+        assert_eq!(
+            merged.field::<EmpId>().to_vec(),
+            vec![0u64, 2, 5, 6, 8, 9, 10]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn merge_shared_frames() {
+        use test_utils::emp_table::*;
+
+        let dv = sample_emp_table().into_view();
+        let not_dept2 = |val: Value<&u64>| val != valref![2];
+        let ids = dv.clone().filter::<DeptId, _>(not_dept2).v::<Labels![EmpId]>();
+        let names = dv.filter::<DeptId, _>(not_dept2).v::<Labels![EmpName]>();
+        let merged = ids.merge(&names).unwrap();
+
+        // identically-filtered frames of the same store share a permutation
+        let mut permutations = FramePermutations::default();
+        merged.frames.collect_permutations(&mut permutations);
+        assert_eq!(permutations.num_distinct(), 1);
+
+        // and continue to share it after updates
+        let sorted = merged.sort_by_label::<EmpName>();
+        let mut permutations = FramePermutations::default();
+        sorted.frames.collect_permutations(&mut permutations);
+        assert_eq!(permutations.num_distinct(), 1);
+        assert_eq!(
+            sorted.field::<EmpId>().to_vec(),
+            vec![10u64, 5, 6, 8, 9, 0]
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn subview_order() {
+        use test_utils::emp_table::*;
+        let dv = sample_emp_table().into_view();
+        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+
+        let subdv = dv.v::<Labels![DeptId, EmpId]>();
+        assert_eq!(subdv.fieldnames(), vec!["DeptId", "EmpId"]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn subview_no_shared_frame() {
+        // test to make sure frames aren't shared between view and subview
+        use test_utils::emp_table::*;
+        let dv = sample_emp_table().into_view();
+        assert_eq!(dv.nrows(), 7);
+
+        let subdv = dv.v::<Labels![DeptId, EmpId]>();
+        assert_eq!(subdv.nrows(), 7);
+
+        let newdv = dv.filter::<DeptId, _>(|val: Value<&_>| val.map_or(false, |&v| v == 1));
+        assert_eq!(newdv.nrows(), 3);
+        assert_eq!(subdv.nrows(), 7);
+    }
+
+    //TODO: multi-frame subview tests (which filter out no-longer-needed frames)
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn sort() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let orig_dv = sample_merged_emp_table();
+        assert_eq!(orig_dv.nrows(), 7);
+
+        // sort by name
+        let dv1 = orig_dv.clone();
+        let dv1 = dv1.sort_by_label::<EmpName>();
+        assert_eq!(
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louis", "Louise", "Sally"]
+        );
+        assert_eq!(dv1.field::<EmpId>().to_vec(), vec![10u64, 5, 6, 2, 8, 9, 0]);
+
+        // re-sort by empid
+        let dv2 = dv1.clone();
+        let dv2 = dv2.sort_by_label::<EmpId>();
+        assert_eq!(
+            dv2.field::<EmpName>().to_vec(),
+            vec!["Sally", "Jamie", "Bob", "Cara", "Louis", "Louise", "Ann"]
+        );
+        assert_eq!(dv2.field::<EmpId>().to_vec(), vec![0u64, 2, 5, 6, 8, 9, 10]);
+
+        // make sure dv1 is still sorted by EmpName
+        assert_eq!(
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louis", "Louise", "Sally"]
+        );
+        assert_eq!(dv1.field::<EmpId>().to_vec(), vec![10u64, 5, 6, 2, 8, 9, 0]);
+
+        // starting with sorted by name, sort by vacation hours
+        let dv3 = dv1.clone();
+        let dv3 = dv3.sort_by_label_comparator::<VacationHrs, _>(
+            |left: Value<&f32>, right: Value<&f32>| left.partial_cmp(&right).unwrap(),
+        );
+        assert_eq!(
+            dv3.field::<EmpName>().to_vec(),
+            vec!["Louis", "Louise", "Cara", "Ann", "Sally", "Jamie", "Bob"]
+        );
+        assert_eq!(dv3.field::<EmpId>().to_vec(), vec![8u64, 9, 6, 10, 0, 2, 5]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn filter() {
+        use test_utils::emp_table::*;
+        let orig_dv = sample_emp_table().into_view();
+        assert_eq!(orig_dv.nrows(), 7);
+
+        // set filtering by department ID
+        let dv1 = orig_dv.clone();
+        let dv1 = dv1.filter::<DeptId, _>(|val: Value<&u64>| val == valref![1]);
+        println!("{}", dv1);
+        assert_eq!(dv1.nrows(), 3);
+        assert_eq!(
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Sally", "Bob", "Cara"]
+        );
 
-        println!("{}", dv1);
-        println!("{}", dv2);
+        // filter a second time
+        let dv1 = dv1.filter::<EmpId, _>(|val: Value<&u64>| val >= valref![6]);
+        assert_eq!(dv1.nrows(), 1);
+        assert_eq!(dv1.field::<EmpName>().to_vec(), vec!["Cara"]);
 
-        let merge_result = dv1.merge(&dv2);
-        match merge_result {
-            Ok(_) => {
-                panic!("Merge was expected to fail (dimension mismatch), but succeeded");
-            }
-            Err(AgnesError::DimensionMismatch(_)) => { /* expected */ }
-            Err(e) => {
-                panic!("Incorrect error: {:?}", e);
-            }
-        };
-    }
-    #[cfg(feature = "test-utils")]
-    tablespace![
-        @continue(typenum::Add1<::test_utils::emp_table::Table>)
+        // that same filter on the original DV has different results
+        let dv2 = orig_dv.clone();
+        let dv2 = dv2.filter::<EmpId, _>(|val: Value<&u64>| val >= valref![6]);
+        assert_eq!(dv2.nrows(), 4);
+        assert_eq!(
+            dv2.field::<EmpName>().to_vec(),
+            vec!["Cara", "Louis", "Louise", "Ann"]
+        );
 
-        pub table emp_table2 {
-            EmpId: u64,
-            DeptId: u64,
-            EmpName: String,
-        }
-    ];
+        // let's try filtering by a different department on dv2
+        let dv2 = dv2.filter::<DeptId, _>(|val: Value<&u64>| val == valref![4]);
+        assert_eq!(dv2.nrows(), 2);
+        assert_eq!(dv2.field::<EmpName>().to_vec(), vec!["Louise", "Ann"]);
+    }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn merge_different_stores() {
-        let dv1 = sample_emp_table().into_view();
-
-        // would NOT COMPILE due to field name collision (see compile-fail/merge_errors test)
-        // let merge_result = dv1.merge(&sample_emp_table().into_view());
+    fn filter_sort() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let orig_dv = sample_merged_emp_table();
+        assert_eq!(orig_dv.nrows(), 7);
 
-        // if we use a sample employee table generated in another tablespace, however:
-        let ds2: emp_table2::Store = sample_emp_table![];
-        let dv2 = ds2.into_view();
+        // start by filtering for employees with remaining vacation hours
+        let dv1 = orig_dv.clone();
+        let dv1 = dv1.filter::<VacationHrs, _>(|val: Value<&f32>| val >= 0.0);
+        assert_eq!(dv1.nrows(), 6);
+        // only Louis has negative hours, so rest of employees still remain
+        assert_eq!(
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Sally", "Jamie", "Bob", "Cara", "Louise", "Ann"]
+        );
 
-        println!("{}", dv1);
-        println!("{}", dv2);
+        // next, sort by employee name
+        let dv2 = dv1.clone();
+        let dv2 = dv2.sort_by_label::<EmpName>();
+        assert_eq!(
+            dv2.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
+        );
 
-        let merged_dv = dv1.merge(&dv2).unwrap();
+        // filter by people in department 1
+        let dv3 = dv2.clone();
+        let dv3 = dv3.filter::<DeptId, _>(|val: Value<&u64>| val == 1);
+        assert_eq!(dv3.nrows(), 3);
+        // should just be the people in department 1, in employee name order
+        assert_eq!(
+            dv3.field::<EmpName>().to_vec(),
+            vec!["Bob", "Cara", "Sally"]
+        );
 
-        println!("{}", merged_dv);
-        assert_eq!(merged_dv.nrows(), 7);
-        assert_eq!(merged_dv.nfields(), 6);
+        // check that dv1 still has the original ordering
         assert_eq!(
-            merged_dv.fieldnames(),
-            vec!["EmpId", "DeptId", "EmpName", "EmpId", "DeptId", "EmpName"]
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Sally", "Jamie", "Bob", "Cara", "Louise", "Ann"]
         );
-    }
 
-    #[cfg(feature = "test-utils")]
-    tablespace![
-        @continue(typenum::Add1<::view::tests::emp_table2::Table>)
+        // ok, now filter dv1 by department 1
+        let dv1 = dv1.filter::<DeptId, _>(|val: Value<&u64>| val == 1);
+        assert_eq!(dv1.nrows(), 3);
+        // should be the people in department 1, but in original name order
+        assert_eq!(
+            dv1.field::<EmpName>().to_vec(),
+            vec!["Sally", "Bob", "Cara"]
+        );
 
-        pub table emp_table3 {
-            EmployeeId: u64,
-            DepartmentId: u64,
-            EmployeeName: String,
-        }
-    ];
+        // make sure dv2 hasn't been affected by any of the other changes
+        assert_eq!(
+            dv2.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
+        );
+    }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn relabel() {
-        let dv1 = sample_emp_table().into_view();
-        let dv2 = sample_emp_table().into_view();
+    fn snapshot_restore() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let dv = sample_merged_emp_table();
+        let orig = dv.snapshot();
 
-        // much like merge_different_stores, this won't compile
-        // let merged_dv = dv1.merge(&dv2).unwrap();
-        // if we relabel all the fields in one of the two tables, however, we can go ahead and merge
-        let dv1 = dv1.relabel::<emp_table::EmpId, emp_table3::EmployeeId>();
-        let dv1 = dv1.relabel::<emp_table::DeptId, emp_table3::DepartmentId>();
-        let dv1 = dv1.relabel::<emp_table::EmpName, emp_table3::EmployeeName>();
+        let dv = dv
+            .filter::<VacationHrs, _>(|val: Value<&f32>| val >= 0.0)
+            .sort_by_label::<EmpName>();
+        let sorted = dv.snapshot();
+        let dv = dv.filter::<DeptId, _>(|val: Value<&u64>| val == 1);
+        assert_eq!(
+            dv.field::<EmpName>().to_vec(),
+            vec!["Bob", "Cara", "Sally"]
+        );
 
-        let merged_dv = dv1.merge(&dv2).unwrap();
-        println!("{}", merged_dv);
-        assert_eq!(merged_dv.nrows(), 7);
-        assert_eq!(merged_dv.nfields(), 6);
+        // revert the department filter
+        let dv = dv.restore(&sorted);
         assert_eq!(
-            merged_dv.fieldnames(),
-            vec![
-                "EmployeeId",
-                "DepartmentId",
-                "EmployeeName",
-                "EmpId",
-                "DeptId",
-                "EmpName"
-            ]
+            dv.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
         );
-    }
 
-    #[cfg(feature = "test-utils")]
-    tablespace![
-        @continue(typenum::Add1<::view::tests::emp_table3::Table>)
+        // revert to the original state, and then apply a different filter
+        let dv = dv.restore(&orig);
+        assert_eq!(dv.nrows(), 7);
+        assert_eq!(
+            dv.field::<EmpName>().to_vec(),
+            vec!["Sally", "Jamie", "Bob", "Cara", "Louis", "Louise", "Ann"]
+        );
+        let dv = dv.filter::<DeptId, _>(|val: Value<&u64>| val == 4);
+        assert_eq!(dv.field::<EmpName>().to_vec(), vec!["Louise", "Ann"]);
 
-        pub table emp_table4 {
-            EmplId: u64 = {"Employee Id"},
-            DeptId: u64 = {"Department Id"},
-            EmpName: String = {"Employee Name"},
-        }
-    ];
+        // snapshots can be restored more than once
+        let dv = dv.restore(&sorted);
+        assert_eq!(dv.nrows(), 6);
+    }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn name_change() {
-        let ds: emp_table4::Store = sample_emp_table![];
-        let dv = ds.into_view();
+    fn update_permutations() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
 
-        println!("{}", dv);
-        assert_eq!(dv.nrows(), 7);
-        assert_eq!(dv.nfields(), 3);
+        let dv = sample_merged_emp_table().update_permutations(&[6, 0, 2]);
+        assert_eq!(dv.nrows(), 3);
+        assert_eq!(dv.field::<EmpId>().to_vec(), vec![10u64, 0, 5]);
         assert_eq!(
-            dv.fieldnames(),
-            vec!["Employee Id", "Department Id", "Employee Name"]
+            dv.field::<DidTraining>().to_vec(),
+            vec![true, false, true]
         );
+
+        let dv = dv.update_permutations(&[1, 2]);
+        assert_eq!(dv.field::<EmpName>().to_vec(), vec!["Sally", "Bob"]);
+        assert_eq!(dv.field::<DidTraining>().to_vec(), vec![false, true]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn fieldnames() {
-        let ds = sample_emp_table();
-        let dv = ds.into_view();
-        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+    #[should_panic]
+    fn update_permutations_out_of_bounds() {
+        sample_emp_table().into_view().update_permutations(&[0, 7]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn subview() {
+    fn datum() {
         use test_utils::emp_table::*;
-        let ds = sample_emp_table();
-        let dv = ds.into_view();
-        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
-        assert_eq!(dv.store_ref_counts(), vec![1]);
-        assert_eq!(dv.nrows(), 7);
-        assert_eq!(dv.nfields(), 3);
-
-        let subdv1 = dv.v::<Labels![EmpId]>();
-        assert_eq!(subdv1.fieldnames(), vec!["EmpId"]);
-        assert_eq!(dv.store_ref_counts(), vec![2]);
-        assert_eq!(subdv1.nrows(), 7);
-        assert_eq!(subdv1.nfields(), 1);
-
-        let subdv1 = dv.v::<Labels![EmpId]>();
-        assert_eq!(subdv1.fieldnames(), vec!["EmpId"]);
-        assert_eq!(dv.store_ref_counts(), vec![3]);
-        assert_eq!(subdv1.nrows(), 7);
-        assert_eq!(subdv1.nfields(), 1);
-
-        let subdv2 = dv.v::<Labels![EmpId, DeptId]>();
-        assert_eq!(subdv2.fieldnames(), vec!["EmpId", "DeptId"]);
-        assert_eq!(dv.store_ref_counts(), vec![4]);
-        assert_eq!(subdv2.nrows(), 7);
-        assert_eq!(subdv2.nfields(), 2);
-
-        let subdv2 = dv.v::<Labels![EmpId, DeptId]>();
-        assert_eq!(subdv2.fieldnames(), vec!["EmpId", "DeptId"]);
-        assert_eq!(dv.store_ref_counts(), vec![5]);
-        assert_eq!(subdv2.nrows(), 7);
-        assert_eq!(subdv2.nfields(), 2);
-
-        let subdv3 = dv.v::<Labels![EmpId, DeptId, EmpName]>();
-        assert_eq!(subdv3.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
-        assert_eq!(dv.store_ref_counts(), vec![6]);
-        assert_eq!(subdv3.nrows(), 7);
-        assert_eq!(subdv3.nfields(), 3);
-
-        let subdv3 = dv.v::<Labels![EmpId, DeptId, EmpName]>();
-        assert_eq!(subdv3.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
-        assert_eq!(dv.store_ref_counts(), vec![7]);
-        assert_eq!(subdv3.nrows(), 7);
-        assert_eq!(subdv3.nfields(), 3);
-
-        // Subview of a subview
-        let subdv4 = subdv2.v::<Labels![DeptId]>();
-        assert_eq!(subdv4.fieldnames(), vec!["DeptId"]);
-        assert_eq!(dv.store_ref_counts(), vec![8]);
-        assert_eq!(subdv4.nrows(), 7);
-        assert_eq!(subdv4.nfields(), 1);
+        let dv = sample_emp_table()
+            .into_view()
+            .filter::<DeptId, _>(|val: Value<&_>| val.map_or(false, |&v| v == 1));
+        assert_eq!(
+            dv.datum::<EmpName>(2).unwrap(),
+            Value::Exists("Cara".to_string())
+        );
 
-        let subdv4 = subdv2.v::<Labels![EmpId]>();
-        assert_eq!(subdv4.fieldnames(), vec!["EmpId"]);
-        assert_eq!(dv.store_ref_counts(), vec![9]);
-        assert_eq!(subdv4.nrows(), 7);
-        assert_eq!(subdv4.nfields(), 1);
+        // out-of-bounds access is reported relative to the filtered frame
+        let err = dv.datum::<EmpName>(3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Index error: index 3 exceeds data length 3 of field EmpName"
+        );
+        // fields accessed through a view identify themselves (by their label in the view)
+        let err = dv.field::<EmpName>().get_datum(7).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Index error: index 7 exceeds data length 3 of field EmpName"
+        );
+        let err = dv
+            .relabel::<DeptId, emp_table3::DepartmentId>()
+            .field::<emp_table3::DepartmentId>()
+            .get_datum(3)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Index error: index 3 exceeds data length 3 of field DepartmentId"
+        );
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn subview_merged() {
-        use test_utils::emp_table::*;
-        use test_utils::extra_emp::*;
+    fn float_key() {
+        use std::f64::NAN;
+        use view::tests::survey::*;
 
-        let dv = sample_merged_emp_table();
-        println!("{:?}", dv.store_ref_counts());
+        let dv = table![
+            Region = ["north", "south", "east", "west", "north"];
+            Income = [1.0, NAN, -0.0, 0.0, NAN];
+        ];
 
-        let subdv = dv.v::<Labels![DeptId, DidTraining]>();
-        println!("{}", subdv);
-        assert_eq!(subdv.fieldnames(), vec!["DeptId", "DidTraining"]);
-        assert_eq!(dv.store_ref_counts(), vec![2, 2]);
-        assert_eq!(subdv.nrows(), 7);
-        assert_eq!(subdv.nfields(), 2);
+        let keyed = dv.float_key::<Income, IncomeKey>(SignedZero::Equal);
+        let counts = keyed
+            .aggregate::<Labels![IncomeKey], Region, Freq, _, _, _>(0u64, |count, _| *count += 1);
+        assert_eq!(
+            counts.field::<IncomeKey>().to_vec(),
+            vec![
+                TotalF64::from(1.0),
+                TotalF64::from(NAN),
+                TotalF64::from(0.0)
+            ]
+        );
+        assert_eq!(counts.field::<Freq>().to_vec(), vec![1u64, 2, 2]);
+
+        let keyed = dv.float_key::<Income, IncomeKey>(SignedZero::Distinct);
+        assert_eq!(keyed.unique_indices::<Labels![IncomeKey]>(), vec![0, 1, 2, 3]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn subview_order() {
-        use test_utils::emp_table::*;
-        let dv = sample_emp_table().into_view();
-        assert_eq!(dv.fieldnames(), vec!["EmpId", "DeptId", "EmpName"]);
+    fn sort_with_policy() {
+        use view::tests::survey::*;
+
+        let dv = table![
+            Region = ["north", "south", "east", "west"];
+            Income = [20.0, 0.0, ::std::f64::NAN, 10.0];
+        ];
+        // missing values: [20, NA, NaN, 10]
+        let dv = dv.na_if::<Income, LimitedIncome, _>(|&income| income == 0.0);
 
-        let subdv = dv.v::<Labels![DeptId, EmpId]>();
-        assert_eq!(subdv.fieldnames(), vec!["DeptId", "EmpId"]);
+        let sorted = dv
+            .clone()
+            .sort_by_label_with_policy::<LimitedIncome>(SortPolicy::new());
+        assert_eq!(
+            sorted.field::<Region>().to_vec(),
+            vec!["south", "east", "west", "north"]
+        );
+
+        let sorted = dv
+            .sort_by_label_with_policy::<LimitedIncome>(SortPolicy::new().na_last().nan_last());
+        assert_eq!(
+            sorted.field::<Region>().to_vec(),
+            vec!["west", "north", "east", "south"]
+        );
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn subview_no_shared_frame() {
-        // test to make sure frames aren't shared between view and subview
+    fn sort_collated() {
         use test_utils::emp_table::*;
-        let dv = sample_emp_table().into_view();
-        assert_eq!(dv.nrows(), 7);
+        let ds: emp_table::Store = emp_table![
+            vec![0u64, 1, 2, 3],
+            vec![1u64, 1, 2, 2],
+            vec!["unit10", "Unit9", "unit1", "apartment"]
+        ];
+        let dv = ds.into_view();
 
-        let subdv = dv.v::<Labels![DeptId, EmpId]>();
-        assert_eq!(subdv.nrows(), 7);
+        let sorted = dv.clone().sort_by_label::<EmpName>();
+        assert_eq!(sorted.field::<EmpId>().to_vec(), vec![1u64, 3, 2, 0]);
 
-        let newdv = dv.filter::<DeptId, _>(|val: Value<&_>| val.map_or(false, |&v| v == 1));
-        assert_eq!(newdv.nrows(), 3);
-        assert_eq!(subdv.nrows(), 7);
-    }
+        let sorted = dv
+            .clone()
+            .sort_by_label_collated::<EmpName>(Collation::new().case_insensitive());
+        assert_eq!(sorted.field::<EmpId>().to_vec(), vec![3u64, 2, 0, 1]);
 
-    //TODO: multi-frame subview tests (which filter out no-longer-needed frames)
+        let sorted =
+            dv.sort_by_label_collated::<EmpName>(Collation::new().case_insensitive().numeric());
+        assert_eq!(
+            sorted.field::<EmpName>().to_vec(),
+            vec!["apartment", "unit1", "Unit9", "unit10"]
+        );
+    }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn sort() {
+    fn sort_cache() {
+        use std::any::TypeId;
         use test_utils::emp_table::*;
         use test_utils::extra_emp::*;
-        let orig_dv = sample_merged_emp_table();
-        assert_eq!(orig_dv.nrows(), 7);
 
-        // sort by name
-        let dv1 = orig_dv.clone();
-        let dv1 = dv1.sort_by_label::<EmpName>();
+        fn field_id<Label, Labels, Frames>(_: &DataView<Labels, Frames>) -> (TypeId, TypeId)
+        where
+            Labels: FrameFieldId<Label>,
+        {
+            Labels::frame_field_id()
+        }
+
+        let base = sample_merged_emp_table();
+        let name = field_id::<EmpName, _, _>(&base);
+        let id = field_id::<EmpId, _, _>(&base);
+        let dv = base.clone().sort_by_label::<EmpName>();
+        assert!(dv.sort_cache.is_sorted(name, true));
+        assert!(dv.sort_cache.is_sorted(name, false));
+        // the computed order is shared with clones of the unsorted view
+        assert!(base.sort_cache.has_order(name, true));
+        let resorted = base.clone().sort_by_label::<EmpName>();
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Ann", "Bob", "Cara", "Jamie", "Louis", "Louise", "Sally"]
+            resorted.field::<EmpId>().to_vec(),
+            dv.field::<EmpId>().to_vec()
         );
-        assert_eq!(dv1.field::<EmpId>().to_vec(), vec![10u64, 5, 6, 2, 8, 9, 0]);
 
-        // re-sort by empid
-        let dv2 = dv1.clone();
-        let dv2 = dv2.sort_by_label::<EmpId>();
+        // filtering preserves the sort order
+        let dv = dv.filter::<VacationHrs, _>(|val: Value<&f32>| val >= 0.0);
+        assert!(dv.sort_cache.is_sorted(name, true));
+        let resorted = dv.clone().sort_by_label::<EmpName>();
         assert_eq!(
-            dv2.field::<EmpName>().to_vec(),
-            vec!["Sally", "Jamie", "Bob", "Cara", "Louis", "Louise", "Ann"]
+            resorted.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
         );
-        assert_eq!(dv2.field::<EmpId>().to_vec(), vec![0u64, 2, 5, 6, 8, 9, 10]);
 
-        // make sure dv1 is still sorted by EmpName
+        // an unstable sort doesn't satisfy a later stable sort
+        let dv = dv.sort_unstable_by_label::<EmpId>();
+        assert!(dv.sort_cache.is_sorted(id, false));
+        assert!(!dv.sort_cache.is_sorted(id, true));
+        assert!(!dv.sort_cache.is_sorted(name, false));
+        let dv = dv.sort_by_label::<EmpId>();
+        assert!(dv.sort_cache.is_sorted(id, true));
+
+        // relabeling keeps the cached sorts, subviews only keep those of their own fields
+        let dv = dv.relabel::<EmpId, emp_table3::EmployeeId>();
+        assert!(dv.sort_cache.is_sorted(id, true));
+        let names = dv.v::<Labels![EmpName]>();
+        assert!(!names.sort_cache.is_sorted(id, true));
+        let ids = dv.v::<Labels![emp_table3::EmployeeId]>();
+        assert!(ids.sort_cache.is_sorted(id, true));
+
+        // sorting by another field invalidates the cached order
+        let dv = dv.sort_by_label::<EmpName>();
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Ann", "Bob", "Cara", "Jamie", "Louis", "Louise", "Sally"]
+            dv.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
         );
-        assert_eq!(dv1.field::<EmpId>().to_vec(), vec![10u64, 5, 6, 2, 8, 9, 0]);
-
-        // starting with sorted by name, sort by vacation hours
-        let dv3 = dv1.clone();
-        let dv3 = dv3.sort_by_label_comparator::<VacationHrs, _>(
+        let dv = dv.sort_by_label_comparator::<VacationHrs, _>(
             |left: Value<&f32>, right: Value<&f32>| left.partial_cmp(&right).unwrap(),
         );
+        assert!(!dv.sort_cache.is_sorted(name, false));
+        let dv = dv.sort_by_label::<EmpName>();
         assert_eq!(
-            dv3.field::<EmpName>().to_vec(),
-            vec!["Louis", "Louise", "Cara", "Ann", "Sally", "Jamie", "Bob"]
+            dv.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
         );
-        assert_eq!(dv3.field::<EmpId>().to_vec(), vec![8u64, 9, 6, 10, 0, 2, 5]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn filter() {
+    fn sort_radix() {
         use test_utils::emp_table::*;
-        let orig_dv = sample_emp_table().into_view();
-        assert_eq!(orig_dv.nrows(), 7);
-
-        // set filtering by department ID
-        let dv1 = orig_dv.clone();
-        let dv1 = dv1.filter::<DeptId, _>(|val: Value<&u64>| val == valref![1]);
-        println!("{}", dv1);
-        assert_eq!(dv1.nrows(), 3);
+        use test_utils::extra_emp::*;
+        let dv = sample_merged_emp_table();
+        let radix_sorted = dv.clone().sort_by_label_radix::<SalaryOffset>();
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Sally", "Bob", "Cara"]
+            radix_sorted.field::<SalaryOffset>().to_vec(),
+            vec![-33i64, -5, -1, 0, 4, 10, 12]
         );
-
-        // filter a second time
-        let dv1 = dv1.filter::<EmpId, _>(|val: Value<&u64>| val >= valref![6]);
-        assert_eq!(dv1.nrows(), 1);
-        assert_eq!(dv1.field::<EmpName>().to_vec(), vec!["Cara"]);
-
-        // that same filter on the original DV has different results
-        let dv2 = orig_dv.clone();
-        let dv2 = dv2.filter::<EmpId, _>(|val: Value<&u64>| val >= valref![6]);
-        assert_eq!(dv2.nrows(), 4);
         assert_eq!(
-            dv2.field::<EmpName>().to_vec(),
-            vec!["Cara", "Louis", "Louise", "Ann"]
+            radix_sorted.field::<EmpName>().to_vec(),
+            dv.sort_by_label::<SalaryOffset>()
+                .field::<EmpName>()
+                .to_vec()
         );
-
-        // let's try filtering by a different department on dv2
-        let dv2 = dv2.filter::<DeptId, _>(|val: Value<&u64>| val == valref![4]);
-        assert_eq!(dv2.nrows(), 2);
-        assert_eq!(dv2.field::<EmpName>().to_vec(), vec!["Louise", "Ann"]);
     }
 
     #[cfg(feature = "test-utils")]
     #[test]
-    fn filter_sort() {
+    fn iter_batches() {
         use test_utils::emp_table::*;
         use test_utils::extra_emp::*;
-        let orig_dv = sample_merged_emp_table();
-        assert_eq!(orig_dv.nrows(), 7);
+        let dv = sample_merged_emp_table().sort_by_label::<EmpName>();
 
-        // start by filtering for employees with remaining vacation hours
-        let dv1 = orig_dv.clone();
-        let dv1 = dv1.filter::<VacationHrs, _>(|val: Value<&f32>| val >= 0.0);
-        assert_eq!(dv1.nrows(), 6);
-        // only Louis has negative hours, so rest of employees still remain
+        let batches = dv.iter_batches(3).collect::<Vec<_>>();
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Sally", "Jamie", "Bob", "Cara", "Louise", "Ann"]
+            batches.iter().map(|batch| batch.nrows()).collect::<Vec<_>>(),
+            vec![3, 3, 1]
         );
-
-        // next, sort by employee name
-        let dv2 = dv1.clone();
-        let dv2 = dv2.sort_by_label::<EmpName>();
         assert_eq!(
-            dv2.field::<EmpName>().to_vec(),
-            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
+            batches[1].field::<EmpName>().to_vec(),
+            vec!["Jamie", "Louis", "Louise"]
         );
-
-        // filter by people in department 1
-        let dv3 = dv2.clone();
-        let dv3 = dv3.filter::<DeptId, _>(|val: Value<&u64>| val == 1);
-        assert_eq!(dv3.nrows(), 3);
-        // should just be the people in department 1, in employee name order
+        let names = batches
+            .iter()
+            .flat_map(|batch| batch.field::<EmpName>().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(names, dv.field::<EmpName>().to_vec());
+        let hours = batches
+            .iter()
+            .flat_map(|batch| batch.field::<VacationHrs>().to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(hours, dv.field::<VacationHrs>().to_vec());
+
+        assert_eq!(dv.iter_batches(7).count(), 1);
+        assert_eq!(dv.iter_batches(100).size_hint(), (1, Some(1)));
         assert_eq!(
-            dv3.field::<EmpName>().to_vec(),
-            vec!["Bob", "Cara", "Sally"]
+            dv.filter::<DeptId, _>(|val: Value<&u64>| val == 10)
+                .iter_batches(2)
+                .count(),
+            0
         );
+    }
 
-        // check that dv1 still has the original ordering
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn into_store() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let dv = sample_merged_emp_table()
+            .filter::<DeptId, _>(|val: Value<&u64>| val != 2)
+            .sort_by_label::<EmpName>()
+            .v::<Labels![VacationHrs, EmpName, EmpId]>()
+            .relabel::<EmpId, emp_table3::EmployeeId>();
+
+        let ds = dv.clone().into_store();
+        assert_eq!(ds.nrows(), 6);
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Sally", "Jamie", "Bob", "Cara", "Louise", "Ann"]
+            ds.field::<EmpName>().to_vec(),
+            vec!["Ann", "Bob", "Cara", "Louis", "Louise", "Sally"]
+        );
+        assert_eq!(
+            ds.field::<emp_table3::EmployeeId>().to_vec(),
+            vec![10u64, 5, 6, 8, 9, 0]
+        );
+        let materialized = ds.into_view();
+        assert_eq!(
+            materialized.fieldnames(),
+            vec!["VacationHrs", "EmpName", "EmployeeId"]
+        );
+        assert_eq!(
+            materialized.field::<VacationHrs>().to_vec(),
+            dv.field::<VacationHrs>().to_vec()
         );
+    }
 
-        // ok, now filter dv1 by department 1
-        let dv1 = dv1.filter::<DeptId, _>(|val: Value<&u64>| val == 1);
-        assert_eq!(dv1.nrows(), 3);
-        // should be the people in department 1, but in original name order
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn query() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let dv = sample_merged_emp_table();
+
+        let queried = dv
+            .clone()
+            .query("DeptId == 1 && VacationHrs >= 20")
+            .unwrap();
+        assert_eq!(queried.field::<EmpName>().to_vec(), vec!["Sally", "Bob"]);
+
+        let queried = dv
+            .clone()
+            .query("!(DeptId == 1 || DeptId == 4) || EmpName < 'B'")
+            .unwrap();
         assert_eq!(
-            dv1.field::<EmpName>().to_vec(),
-            vec!["Sally", "Bob", "Cara"]
+            queried.field::<EmpName>().to_vec(),
+            vec!["Jamie", "Louis", "Ann"]
         );
 
-        // make sure dv2 hasn't been affected by any of the other changes
+        let queried = dv.clone().query("DidTraining == true").unwrap();
         assert_eq!(
-            dv2.field::<EmpName>().to_vec(),
-            vec!["Ann", "Bob", "Cara", "Jamie", "Louise", "Sally"]
+            queried.field::<EmpName>().to_vec(),
+            vec!["Bob", "Cara", "Louis", "Ann"]
         );
+
+        // missing field
+        match dv.clone().query("Salary > 10") {
+            Err(AgnesError::FieldNotFound(_)) => {}
+            _ => panic!("expected missing field error"),
+        }
+        // type mismatch
+        match dv.clone().query("EmpName > 10") {
+            Err(AgnesError::Query(_)) => {}
+            _ => panic!("expected query error"),
+        }
+        // malformed expression
+        assert!(dv.clone().query("DeptId >").is_err());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn min_max_row_by() {
+        use test_utils::emp_table::*;
+        use test_utils::extra_emp::*;
+        let dv = sample_merged_emp_table();
+
+        let min_row = dv.min_row_by::<VacationHrs>().unwrap();
+        assert_eq!(min_row.nrows(), 1);
+        assert_eq!(min_row.nfields(), 6);
+        assert_eq!(min_row.field::<EmpName>().to_vec(), vec!["Louis"]);
+        assert_eq!(min_row.field::<DeptId>().to_vec(), vec![3u64]);
+
+        let max_row = dv.max_row_by::<SalaryOffset>().unwrap();
+        assert_eq!(max_row.field::<EmpName>().to_vec(), vec!["Bob"]);
+        assert_eq!(max_row.field::<SalaryOffset>().to_vec(), vec![12i64]);
+
+        // still works on a filtered view
+        let dv = dv.filter::<DeptId, _>(|val: Value<&u64>| val == 4);
+        let max_row = dv.max_row_by::<VacationHrs>().unwrap();
+        assert_eq!(max_row.field::<EmpName>().to_vec(), vec!["Ann"]);
     }
 
     #[cfg(feature = "test-utils")]
@@ -2123,4 +5317,333 @@ mod tests {
         println!("{}", uniq_vals);
         assert_eq!(uniq_vals.fieldnames(), vec!["DidTraining", "DeptId",]);
     }
+
+    #[cfg(feature = "test-utils")]
+    tablespace![
+        @continue(typenum::Add1<::view::tests::survey::Table>)
+
+        pub table events {
+            Timestamp: DateTime,
+            Amount: f64,
+            Year: i32,
+            Weekday: u32,
+            Total: f64,
+            LocalTimestamp: DateTime,
+            Month: DateTime,
+            GapStart: DateTime,
+            GapEnd: DateTime,
+            Seq: u64,
+            SeqGapStart: u64,
+            SeqGapEnd: u64,
+        }
+    ];
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn dt_components() {
+        use view::tests::events::*;
+
+        let dt = |s: &str| s.parse::<DateTime>().unwrap();
+        let dv = table![
+            Timestamp = [dt("2018-12-31 23:00"), dt("2019-01-05"), dt("2019-03-01T08:15:00Z")];
+            Amount = [10.0, 20.0, 0.0];
+        ];
+        println!("{}", dv);
+
+        let years = dv.field::<Timestamp>().dt().year();
+        assert_eq!(years.to_vec(), vec![2018, 2019, 2019]);
+        let dv = dv
+            .add_field::<Year, _>(years)
+            .unwrap()
+            .add_field::<Weekday, _>(dv.field::<Timestamp>().dt().weekday())
+            .unwrap();
+        assert_eq!(dv.field::<Weekday>().to_vec(), vec![1u32, 6, 5]);
+        let totals = dv.aggregate::<Labels![Year], Amount, Total, _, _, _>(0.0, |total, amount| {
+            *total += amount.unwrap_or(&0.0)
+        });
+        assert_eq!(totals.field::<Year>().to_vec(), vec![2018, 2019]);
+        assert_eq!(totals.field::<Total>().to_vec(), vec![10.0, 20.0]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn to_timezone() {
+        use view::tests::events::*;
+
+        // logs recorded in different timezones, normalized to UTC when parsed
+        let dt = |s: &str| s.parse::<DateTime>().unwrap();
+        let dv = table![
+            Timestamp = [
+                dt("2018-07-04 09:00-04:00"),
+                dt("2018-07-04 12:00Z"),
+                dt("2018-07-04 14:30+02:00")
+            ];
+            Amount = [1.0, 2.0, 3.0];
+        ];
+        let sorted = dv.clone().sort_by_label::<Timestamp>();
+        assert_eq!(sorted.field::<Amount>().to_vec(), vec![2.0, 3.0, 1.0]);
+
+        let local = dv.to_timezone::<Timestamp, LocalTimestamp>(UtcOffset::west(4 * 3600).unwrap());
+        println!("{}", local);
+        assert_eq!(
+            local.field::<LocalTimestamp>().dt().hour().to_vec(),
+            vec![9u32, 8, 8]
+        );
+        assert_eq!(
+            local.field::<Timestamp>().dt().hour().to_vec(),
+            vec![13u32, 12, 12]
+        );
+        assert_eq!(
+            local.field::<LocalTimestamp>().to_vec(),
+            local.field::<Timestamp>().to_vec()
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn to_period() {
+        use view::tests::events::*;
+
+        let dt = |s: &str| s.parse::<DateTime>().unwrap();
+        let dv = table![
+            Timestamp = [
+                dt("2018-01-31 23:30"),
+                dt("2018-02-01 00:30"),
+                dt("2018-01-15 12:00"),
+                dt("2018-02-28 08:00")
+            ];
+            Amount = [1.0, 2.0, 3.0, 4.0];
+        ];
+
+        let monthly = dv.to_period::<Timestamp, Month>(Period::Month);
+        println!("{}", monthly);
+        let totals = monthly
+            .aggregate::<Labels![Month], Amount, Total, _, _, _>(0.0, |total, amount| {
+                *total += amount.unwrap_or(&0.0)
+            });
+        assert_eq!(
+            totals.field::<Month>().to_vec(),
+            vec![dt("2018-01-01"), dt("2018-02-01")]
+        );
+        assert_eq!(totals.field::<Total>().to_vec(), vec![4.0, 6.0]);
+
+        // periods are determined in the timezone in which the values are presented
+        let local = dv
+            .to_timezone::<Timestamp, LocalTimestamp>(UtcOffset::east(3600).unwrap())
+            .to_period::<LocalTimestamp, Month>(Period::Month);
+        assert_eq!(
+            local.field::<Month>().to_vec()[0],
+            dt("2018-02-01T00:00+01:00")
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn gaps() {
+        use view::tests::events::*;
+
+        let dt = |s: &str| s.parse::<DateTime>().unwrap();
+        let dv = table![
+            Timestamp = [
+                dt("2018-07-04 00:00"),
+                dt("2018-07-04 01:00"),
+                dt("2018-07-04 04:00"),
+                dt("2018-07-04 05:00"),
+                dt("2018-07-04 07:30")
+            ];
+            Seq = [1u64, 2, 3, 5, 6];
+        ];
+
+        let gaps = dv.gaps::<Timestamp, GapStart, GapEnd>(3600.0);
+        println!("{}", gaps);
+        assert_eq!(
+            gaps.field::<GapStart>().to_vec(),
+            vec![dt("2018-07-04 01:00"), dt("2018-07-04 05:00")]
+        );
+        assert_eq!(
+            gaps.field::<GapEnd>().to_vec(),
+            vec![dt("2018-07-04 04:00"), dt("2018-07-04 07:30")]
+        );
+
+        let seq_gaps = dv.gaps::<Seq, SeqGapStart, SeqGapEnd>(1.0);
+        assert_eq!(seq_gaps.field::<SeqGapStart>().to_vec(), vec![3]);
+        assert_eq!(seq_gaps.field::<SeqGapEnd>().to_vec(), vec![5]);
+
+        // missing values are skipped
+        let dv = dv.na_if::<Seq, SeqGapStart, _>(|&seq| seq == 5);
+        let seq_gaps = dv.gaps::<SeqGapStart, SeqGapEnd, Seq>(1.0);
+        assert_eq!(seq_gaps.field::<SeqGapEnd>().to_vec(), vec![3]);
+        assert_eq!(seq_gaps.field::<Seq>().to_vec(), vec![6]);
+
+        assert_eq!(dv.gaps::<Seq, SeqGapStart, SeqGapEnd>(2.0).nrows(), 0);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn set_operations() {
+        let names = |names: Vec<Option<&str>>| {
+            FieldData::from_field_vec(
+                names
+                    .into_iter()
+                    .map(|name| name.map(|name| name.to_string()).into())
+                    .collect(),
+            )
+        };
+        let old = dept_table_from_field(
+            vec![1u64, 2, 2, 3, 4].into(),
+            names(vec![
+                Some("Marketing"),
+                Some("Sales"),
+                Some("Sales"),
+                None,
+                Some("R&D"),
+            ]),
+        )
+        .into_view()
+        .record_operations("old");
+        let new = dept_table_from_field(
+            vec![1u64, 3, 4, 5, 5].into(),
+            names(vec![
+                Some("Marketing"),
+                None,
+                Some("Research"),
+                Some("Ops"),
+                Some("Ops"),
+            ]),
+        )
+        .into_view()
+        .record_operations("new");
+
+        let kept = old.clone().intersect_rows(&new);
+        println!("{}", kept);
+        assert_eq!(kept.field::<dept_table::DeptId>().to_vec(), vec![1, 3]);
+        assert_eq!(
+            kept.field::<dept_table::DeptName>().to_value_vec(),
+            vec![Value::Exists("Marketing".to_string()), Value::Na]
+        );
+        assert_eq!(
+            kept.operations().unwrap()[1],
+            Operation::SetOperation {
+                operation: "intersect",
+                right: vec![Operation::Load {
+                    source: "new".into()
+                }],
+            }
+        );
+
+        let removed = old.clone().difference_rows(&new);
+        assert_eq!(removed.field::<dept_table::DeptId>().to_vec(), vec![2, 4]);
+        assert_eq!(
+            removed.field::<dept_table::DeptName>().to_vec(),
+            vec!["Sales", "R&D"]
+        );
+
+        let all = old.union_rows(&new);
+        println!("{}", all);
+        assert_eq!(
+            all.field::<dept_table::DeptId>().to_vec(),
+            vec![1, 2, 3, 4, 4, 5]
+        );
+        assert_eq!(
+            all.field::<dept_table::DeptName>().to_value_vec(),
+            vec![
+                Value::Exists("Marketing".to_string()),
+                Value::Exists("Sales".to_string()),
+                Value::Na,
+                Value::Exists("R&D".to_string()),
+                Value::Exists("Research".to_string()),
+                Value::Exists("Ops".to_string()),
+            ]
+        );
+        assert_eq!(
+            format!("{}", all.operations().unwrap()[1]),
+            "union rows with [load new]"
+        );
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn set_operations_cached_hashes() {
+        let names = |names: Vec<&str>| {
+            FieldData::from_vec(names.into_iter().map(|name| name.to_string()).collect())
+        };
+        let old = dept_table_from_field(
+            vec![1u64, 2, 3].into(),
+            names(vec!["Marketing", "Sales", "R&D"]),
+        )
+        .into_view();
+        let new = dept_table_from_field(
+            vec![3u64, 1, 4].into(),
+            names(vec!["R&D", "Marketing", "Ops"]),
+        )
+        .into_view();
+
+        // only one side of each set operation has cached hashes
+        old.cache_key_hashes::<Labels![dept_table::DeptId, dept_table::DeptName]>();
+        assert!(old.field::<dept_table::DeptName>().cached_hash(0).is_some());
+        assert_eq!(new.field::<dept_table::DeptName>().cached_hash(0), None);
+
+        let kept = old.clone().intersect_rows(&new);
+        assert_eq!(kept.field::<dept_table::DeptId>().to_vec(), vec![1, 3]);
+        let kept = new.clone().intersect_rows(&old);
+        assert_eq!(kept.field::<dept_table::DeptId>().to_vec(), vec![3, 1]);
+        let removed = old.clone().difference_rows(&new);
+        assert_eq!(removed.field::<dept_table::DeptId>().to_vec(), vec![2]);
+        let all = old.union_rows(&new);
+        assert_eq!(all.field::<dept_table::DeptId>().to_vec(), vec![1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn changes_since() {
+        let names = |names: Vec<Option<&str>>| {
+            FieldData::from_field_vec(
+                names
+                    .into_iter()
+                    .map(|name| name.map(|name| name.to_string()).into())
+                    .collect(),
+            )
+        };
+        let old = dept_table_from_field(
+            vec![1u64, 2, 3, 4].into(),
+            names(vec![Some("Marketing"), Some("Sales"), None, Some("R&D")]),
+        )
+        .into_view();
+        let new = dept_table_from_field(
+            vec![5u64, 1, 3, 4].into(),
+            names(vec![Some("Ops"), Some("Marketing"), None, Some("Research")]),
+        )
+        .into_view();
+
+        let changes = new.changes_since::<dept_table::DeptId>(&old);
+        println!(
+            "{}\n{}\n{}",
+            changes.added, changes.removed, changes.modified
+        );
+        assert_eq!(
+            changes.added.field::<dept_table::DeptId>().to_vec(),
+            vec![5]
+        );
+        assert_eq!(
+            changes.added.field::<dept_table::DeptName>().to_vec(),
+            vec!["Ops"]
+        );
+        assert_eq!(
+            changes.removed.field::<dept_table::DeptId>().to_vec(),
+            vec![2]
+        );
+        assert_eq!(
+            changes.modified.field::<dept_table::DeptId>().to_vec(),
+            vec![4]
+        );
+        assert_eq!(
+            changes.modified.field::<dept_table::DeptName>().to_vec(),
+            vec!["Research"]
+        );
+
+        let changes = new.changes_since::<dept_table::DeptId>(&new);
+        assert_eq!(changes.added.nrows(), 0);
+        assert_eq!(changes.removed.nrows(), 0);
+        assert_eq!(changes.modified.nrows(), 0);
+    }
 }
@@ -1,17 +1,23 @@
 /*!
-Functions for displaying statistics about a `DataView`.
+Functions for displaying statistics about a `DataView`, including summaries of the missing data
+within its fields and profiles of the values of its fields.
 */
 
-use std::fmt;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
 
+use num_traits::AsPrimitive;
 use prettytable as pt;
 
 use access::{DataIndex, NRows};
 use cons::Len;
+use datetime::DateTime;
 use label::{StrLabels, StrTypes};
 use partial::*;
+use permute::TotalF64;
 use stats::*;
-use view::{AssocDataIndexCons, AssocDataIndexConsOf, DataView};
+use value::Value;
+use view::{AssocDataIndexCons, AssocDataIndexConsOf, DataView, FieldMap};
 
 /// Structure containing general statistics of a `DataView`.
 #[derive(Debug, Clone)]
@@ -128,7 +134,12 @@ macro_rules! impl_stats_fns {
             where
                 DI: DataIndex<DType=$dtype>
             {
-                self.values.push(data.min().map_or(String::new(), ToString::to_string));
+                // use the cached extrema if available
+                let min = match data.cached_summary() {
+                    Some(summary) => summary.min,
+                    None => data.min().cloned(),
+                };
+                self.values.push(min.map_or(String::new(), |min| min.to_string()));
             }
         }
         impl IsImplemented<MinFn> for $dtype {
@@ -141,7 +152,11 @@ macro_rules! impl_stats_fns {
             where
                 DI: DataIndex<DType=$dtype>
             {
-                self.values.push(data.max().map_or(String::new(), ToString::to_string));
+                let max = match data.cached_summary() {
+                    Some(summary) => summary.max,
+                    None => data.max().cloned(),
+                };
+                self.values.push(max.map_or(String::new(), |max| max.to_string()));
             }
         }
         impl IsImplemented<MaxFn> for $dtype {
@@ -214,7 +229,7 @@ macro_rules! impl_stats_fns_nonimpl {
     )*}
 }
 
-impl_stats_fns_nonimpl![bool String];
+impl_stats_fns_nonimpl![bool String TotalF64 DateTime];
 
 impl<Labels, Frames> DataView<Labels, Frames>
 where
@@ -261,6 +276,441 @@ where
     }
 }
 
+/// Function (implementing [Func](../partial/trait.Func.html)) for counting the missing values in
+/// each field. Implemented for all data types.
+#[derive(Debug, Default)]
+pub struct CountNaFn {
+    counts: Vec<usize>,
+}
+impl<DType> Func<DType> for CountNaFn {
+    type Output = ();
+    fn call<DI>(&mut self, data: &DI) -> Self::Output
+    where
+        DI: DataIndex<DType = DType>,
+    {
+        self.counts.push(data.count_na());
+    }
+}
+impl<T> IsImplemented<CountNaFn> for T {
+    type IsImpl = Implemented;
+}
+
+/// Function (implementing [Func](../partial/trait.Func.html)) for recording which values in each
+/// field are missing. Implemented for all data types.
+#[derive(Debug, Default)]
+pub struct NaMaskFn {
+    masks: Vec<Vec<bool>>,
+}
+impl<DType> Func<DType> for NaMaskFn {
+    type Output = ();
+    fn call<DI>(&mut self, data: &DI) -> Self::Output
+    where
+        DI: DataIndex<DType = DType>,
+    {
+        self.masks
+            .push(data.iter().map(|value| value.is_na()).collect());
+    }
+}
+impl<T> IsImplemented<NaMaskFn> for T {
+    type IsImpl = Implemented;
+}
+
+/// Partially-implemented function (implementing [Func](../partial/trait.Func.html) and
+/// [FuncDefault](../partial/trait.FuncDefault.html)) for determining whether a field is constant
+/// (has fewer than two distinct existing values).
+#[derive(Debug, Default)]
+pub struct IsConstantFn {
+    constant: Vec<bool>,
+}
+impl<DType> Func<DType> for IsConstantFn
+where
+    DType: PartialEq,
+{
+    type Output = ();
+    fn call<DI>(&mut self, data: &DI) -> Self::Output
+    where
+        DI: DataIndex<DType = DType>,
+    {
+        let mut existing = data.iter().filter_map(|value| value.into_option());
+        let constant = match existing.next() {
+            Some(first) => existing.all(|value| value == first),
+            None => true,
+        };
+        self.constant.push(constant);
+    }
+}
+impl FuncDefault for IsConstantFn {
+    type Output = ();
+    fn call(&mut self) -> Self::Output {
+        self.constant.push(false);
+    }
+}
+macro_rules! impl_is_constant_is_impl {
+    ($($dtype:ty)*) => {$(
+        impl IsImplemented<IsConstantFn> for $dtype {
+            type IsImpl = Implemented;
+        }
+    )*}
+}
+impl_is_constant_is_impl![f64 f32 u64 u32 usize i64 i32 isize bool String TotalF64 DateTime];
+
+// number of most common values reported in a field profile
+const PROFILE_TOP_VALUES: usize = 5;
+// number of example values reported in a field profile
+const PROFILE_EXAMPLES: usize = 3;
+
+/// Profile of the values of a single field, as computed by
+/// [profile](../view/struct.DataView.html#method.profile).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldProfile {
+    /// Name of the field.
+    pub name: String,
+    /// Data type of the field.
+    pub ty: String,
+    /// The number of existing (non-missing) values.
+    pub num_exists: usize,
+    /// The number of NA (missing) values.
+    pub num_na: usize,
+    /// The percentage of values which are missing.
+    pub percent_na: f64,
+    /// The number of distinct existing values.
+    pub cardinality: usize,
+    /// The most common existing values (up to five) along with their number of occurrences,
+    /// ordered from most to least common, with ties ordered by first appearance.
+    pub top_values: Vec<(String, usize)>,
+    /// The minimum existing value, or `None` if no values exist.
+    pub min: Option<String>,
+    /// The maximum existing value, or `None` if no values exist.
+    pub max: Option<String>,
+    /// The arithmetic mean of existing values, or `None` for non-numeric fields or if no values
+    /// exist.
+    pub mean: Option<f64>,
+    /// The first (up to three) distinct existing values.
+    pub examples: Vec<String>,
+}
+
+/// Number and percentage of missing values in a single field, as computed by
+/// [na_summary](../view/struct.DataView.html#method.na_summary).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldNaSummary {
+    /// Name of the field.
+    pub name: String,
+    /// Number of missing values.
+    pub num_na: usize,
+    /// Percentage of the values which are missing.
+    pub percent_na: f64,
+}
+
+/// Summary of the missing values in each field of a `DataView`, as computed by
+/// [na_summary](../view/struct.DataView.html#method.na_summary). Displaying an `NaSummary`
+/// renders the summary as a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaSummary {
+    /// The number of records in the summarized `DataView`.
+    pub nrows: usize,
+    /// Summaries of each field, in the order of the `DataView`'s fields.
+    pub fields: Vec<FieldNaSummary>,
+}
+
+/// A combination of fields which are jointly missing in some records of a `DataView`, as
+/// computed by [na_patterns](../view/struct.DataView.html#method.na_patterns).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaPattern {
+    /// Names of the fields missing a value (empty for complete records).
+    pub missing_fields: Vec<String>,
+    /// Number of records exhibiting this pattern.
+    pub num_records: usize,
+    /// Percentage of records exhibiting this pattern.
+    pub percent_records: f64,
+}
+
+/// The missingness patterns of the records of a `DataView`, as computed by
+/// [na_patterns](../view/struct.DataView.html#method.na_patterns). Displaying an `NaPatterns`
+/// renders the patterns as a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NaPatterns {
+    /// The number of records in the summarized `DataView`.
+    pub nrows: usize,
+    /// Missingness patterns, ordered from most to least common (with ties ordered by first
+    /// appearance).
+    pub patterns: Vec<NaPattern>,
+}
+
+/// Profiles of the values of each field of a `DataView`, as computed by
+/// [profile](../view/struct.DataView.html#method.profile). Displaying a `ViewProfile` renders
+/// the profiles as a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewProfile {
+    /// The number of records in the profiled `DataView`.
+    pub nrows: usize,
+    /// Profiles of each field, in the order of the `DataView`'s fields.
+    pub fields: Vec<FieldProfile>,
+}
+
+/// Function (implementing [Func](../partial/trait.Func.html)) for computing the
+/// [FieldProfile](struct.FieldProfile.html) of each field in a single pass over its values.
+#[derive(Debug, Default)]
+pub struct ProfileFn {
+    profiles: Vec<FieldProfile>,
+}
+
+// computes the profile of the values in `data` in a single pass, using `as_f64` (if provided) to
+// compute the mean of numeric values; the field name and type are left empty
+fn profile_field<DI>(data: &DI, as_f64: Option<fn(&DI::DType) -> f64>) -> FieldProfile
+where
+    DI: DataIndex,
+    DI::DType: PartialOrd + Display,
+{
+    let mut num_na = 0;
+    let mut min: Option<&DI::DType> = None;
+    let mut max: Option<&DI::DType> = None;
+    let mut sum = 0.0;
+    // (value, count) of distinct values in order of first appearance
+    let mut counts: Vec<(String, usize)> = vec![];
+    let mut count_indices = HashMap::new();
+    for value in data.iter() {
+        let value = match value {
+            Value::Exists(value) => value,
+            Value::Na => {
+                num_na += 1;
+                continue;
+            }
+        };
+        if min.map_or(true, |min| value < min) {
+            min = Some(value);
+        }
+        if max.map_or(true, |max| value > max) {
+            max = Some(value);
+        }
+        if let Some(as_f64) = as_f64 {
+            sum += as_f64(value);
+        }
+        let value = value.to_string();
+        let idx = *count_indices.entry(value.clone()).or_insert_with(|| {
+            counts.push((value, 0));
+            counts.len() - 1
+        });
+        counts[idx].1 += 1;
+    }
+
+    let num_exists = data.len() - num_na;
+    let examples = counts
+        .iter()
+        .take(PROFILE_EXAMPLES)
+        .map(|&(ref value, _)| value.clone())
+        .collect();
+    let cardinality = counts.len();
+    // stable sort preserves the order of first appearance among ties
+    counts.sort_by(|left, right| right.1.cmp(&left.1));
+    counts.truncate(PROFILE_TOP_VALUES);
+    FieldProfile {
+        name: String::new(),
+        ty: String::new(),
+        num_exists,
+        num_na,
+        percent_na: percent(num_na, data.len()),
+        cardinality,
+        top_values: counts,
+        min: min.map(|min| min.to_string()),
+        max: max.map(|max| max.to_string()),
+        mean: match (as_f64, num_exists) {
+            (Some(_), n) if n > 0 => Some(sum / n as f64),
+            _ => None,
+        },
+        examples,
+    }
+}
+
+macro_rules! impl_profile_fn_numeric {
+    ($($dtype:ty)*) => {$(
+        impl Func<$dtype> for ProfileFn {
+            type Output = ();
+            fn call<DI>(&mut self, data: &DI) -> Self::Output
+            where
+                DI: DataIndex<DType = $dtype>,
+            {
+                self.profiles.push(profile_field(
+                    data,
+                    Some(|value: &$dtype| AsPrimitive::<f64>::as_(*value)),
+                ));
+            }
+        }
+        impl IsImplemented<ProfileFn> for $dtype {
+            type IsImpl = Implemented;
+        }
+    )*}
+}
+impl_profile_fn_numeric![f64 f32 u64 u32 usize i64 i32 isize];
+
+macro_rules! impl_profile_fn_nonnumeric {
+    ($($dtype:ty)*) => {$(
+        impl Func<$dtype> for ProfileFn {
+            type Output = ();
+            fn call<DI>(&mut self, data: &DI) -> Self::Output
+            where
+                DI: DataIndex<DType = $dtype>,
+            {
+                self.profiles.push(profile_field(data, None));
+            }
+        }
+        impl IsImplemented<ProfileFn> for $dtype {
+            type IsImpl = Implemented;
+        }
+    )*}
+}
+impl_profile_fn_nonnumeric![bool String DateTime];
+
+// percentage of `total` represented by `count`, or 0 if `total` is 0
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
+
+impl<Labels, Frames> DataView<Labels, Frames>
+where
+    Frames: NRows,
+    Labels: StrLabels,
+{
+    /// Summarizes the missing values in each field of this `DataView`: the number of missing
+    /// values in each field, and the percentage of its values which are missing. The returned
+    /// [NaSummary](struct.NaSummary.html) can be inspected directly, or displayed as a table.
+    pub fn na_summary(&self) -> NaSummary
+    where
+        Self: FieldMap<CountNaFn>,
+    {
+        let nrows = self.nrows();
+        let mut count_fn = CountNaFn::default();
+        self.field_map(&mut count_fn);
+
+        NaSummary {
+            nrows,
+            fields: Labels::labels()
+                .into_iter()
+                .zip(count_fn.counts)
+                .map(|(name, num_na)| FieldNaSummary {
+                    name: name.to_string(),
+                    num_na,
+                    percent_na: percent(num_na, nrows),
+                })
+                .collect(),
+        }
+    }
+
+    /// Lists the combinations of fields which are jointly missing in the records of this
+    /// `DataView`. Each [NaPattern](struct.NaPattern.html) describes one missingness pattern: the
+    /// names of the fields missing a value (empty for complete records), the number of records
+    /// exhibiting that pattern, and the percentage of records exhibiting that pattern. Patterns
+    /// are ordered from most to least common, with ties ordered by first appearance.
+    pub fn na_patterns(&self) -> NaPatterns
+    where
+        Self: FieldMap<NaMaskFn>,
+    {
+        let nrows = self.nrows();
+        let mut mask_fn = NaMaskFn::default();
+        self.field_map(&mut mask_fn);
+        let masks = mask_fn.masks;
+
+        // (pattern, number of records) in order of first appearance
+        let mut patterns: Vec<(Vec<bool>, usize)> = vec![];
+        let mut pattern_indices = HashMap::new();
+        for row in 0..nrows {
+            let pattern = masks.iter().map(|mask| mask[row]).collect::<Vec<_>>();
+            let idx = *pattern_indices.entry(pattern.clone()).or_insert_with(|| {
+                patterns.push((pattern, 0));
+                patterns.len() - 1
+            });
+            patterns[idx].1 += 1;
+        }
+        // stable sort preserves the order of first appearance among ties
+        patterns.sort_by(|left, right| right.1.cmp(&left.1));
+
+        let names = Labels::labels();
+        NaPatterns {
+            nrows,
+            patterns: patterns
+                .into_iter()
+                .map(|(pattern, num_records)| NaPattern {
+                    missing_fields: names
+                        .iter()
+                        .zip(pattern)
+                        .filter(|&(_, is_na)| is_na)
+                        .map(|(&name, _)| name.to_string())
+                        .collect(),
+                    num_records,
+                    percent_records: percent(num_records, nrows),
+                })
+                .collect(),
+        }
+    }
+
+    /// Computes a profile of the values of each field in this `DataView`: its number of missing
+    /// values, its number of distinct values (cardinality), its most common values, its extrema,
+    /// its mean (for numeric fields), and some example values. Each field's profile is computed in
+    /// a single pass over its values. The returned [ViewProfile](struct.ViewProfile.html) can be
+    /// inspected directly, or displayed as a table.
+    pub fn profile(&self) -> ViewProfile
+    where
+        Self: FieldMap<ProfileFn>,
+        Labels: StrTypes,
+    {
+        let mut profile_fn = ProfileFn::default();
+        self.field_map(&mut profile_fn);
+        let mut fields = profile_fn.profiles;
+        for ((field, name), ty) in fields
+            .iter_mut()
+            .zip(Labels::labels())
+            .zip(Labels::str_types())
+        {
+            field.name = name.to_string();
+            field.ty = ty.to_string();
+        }
+        ViewProfile {
+            nrows: self.nrows(),
+            fields,
+        }
+    }
+
+    /// Returns the names of the fields in this `DataView` which do not contain any existing
+    /// values. Since the fields of a `DataView` are determined at compile time, these fields can
+    /// be pruned by taking a [subview](../view/struct.DataView.html#method.subview) of the
+    /// remaining fields.
+    pub fn empty_fields(&self) -> Vec<&'static str>
+    where
+        Self: FieldMap<CountNaFn>,
+    {
+        let nrows = self.nrows();
+        let mut count_fn = CountNaFn::default();
+        self.field_map(&mut count_fn);
+        Labels::labels()
+            .into_iter()
+            .zip(count_fn.counts)
+            .filter(|&(_, count)| count == nrows)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Returns the names of the fields in this `DataView` which are constant: fields with at most
+    /// one distinct existing value (including fields without any existing values). As with
+    /// [empty_fields](#method.empty_fields), these fields can be pruned by taking a
+    /// [subview](../view/struct.DataView.html#method.subview) of the remaining fields.
+    pub fn constant_fields(&self) -> Vec<&'static str>
+    where
+        Self: FieldMap<IsConstantFn>,
+    {
+        let mut constant_fn = IsConstantFn::default();
+        self.field_map(&mut constant_fn);
+        Labels::labels()
+            .into_iter()
+            .zip(constant_fn.constant)
+            .filter(|&(_, constant)| constant)
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
 impl fmt::Display for ViewStats {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(
@@ -302,8 +752,101 @@ impl fmt::Display for ViewStats {
     }
 }
 
+impl Display for ViewProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "DataView with {} rows", self.nrows)?;
+
+        let mut table = pt::Table::new();
+        table.set_titles(
+            [
+                "Field",
+                "Type",
+                "NA %",
+                "Distinct",
+                "Min",
+                "Max",
+                "Mean",
+                "Top Values",
+                "Examples",
+            ]
+            .iter()
+            .into(),
+        );
+        for field in &self.fields {
+            let top_values = field
+                .top_values
+                .iter()
+                .map(|&(ref value, count)| format!("{} ({})", value, count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            table.add_row(pt::row::Row::new(vec![
+                cell![field.name],
+                cell![field.ty],
+                cell![format!("{:.1}", field.percent_na)],
+                cell![field.cardinality],
+                cell![field.min.as_ref().map_or("", |min| min.as_str())],
+                cell![field.max.as_ref().map_or("", |max| max.as_str())],
+                cell![field.mean.map_or(String::new(), |mean| mean.to_string())],
+                cell![top_values],
+                cell![field.examples.join(", ")],
+            ]));
+        }
+
+        table.set_format(*pt::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.fmt(f)?;
+
+        Ok(())
+    }
+}
+
+impl Display for NaSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "DataView with {} rows", self.nrows)?;
+
+        let mut table = pt::Table::new();
+        table.set_titles(["Field", "NA", "NA %"].iter().into());
+        for field in &self.fields {
+            table.add_row(pt::row::Row::new(vec![
+                cell![field.name],
+                cell![field.num_na],
+                cell![format!("{:.1}", field.percent_na)],
+            ]));
+        }
+
+        table.set_format(*pt::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.fmt(f)?;
+
+        Ok(())
+    }
+}
+
+impl Display for NaPatterns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "DataView with {} rows", self.nrows)?;
+
+        let mut table = pt::Table::new();
+        table.set_titles(["Missing Fields", "Records", "Records %"].iter().into());
+        for pattern in &self.patterns {
+            table.add_row(pt::row::Row::new(vec![
+                cell![pattern.missing_fields.join(", ")],
+                cell![pattern.num_records],
+                cell![format!("{:.1}", pattern.percent_records)],
+            ]));
+        }
+
+        table.set_format(*pt::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.fmt(f)?;
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    use cons::Nil;
+    use store::{DataStore, IntoView};
     use test_utils::*;
 
     macro_rules! assert_float_eq {
@@ -366,4 +909,159 @@ mod tests {
         assert_float_eq!(vs2.means[2], 34.0857143); // VacationHrs mean
         assert_float_eq!(vs2.stdevs[2], 35.070948); // VacationHrs stdev
     }
+
+    tablespace![
+        table survey {
+            Age: u64,
+            Income: f64,
+            Name: String,
+        }
+        table events {
+            Occurred: DateTime,
+            Score: TotalF64,
+        }
+    ];
+
+    #[test]
+    fn na_summary() {
+        let ds: survey::Store = DataStore::<Nil>::empty()
+            .push_back_from_value_iter(vec![Some(30u64), None, Some(40), None, Some(22)])
+            .push_back_from_value_iter(vec![Some(1.5), None, None, None, Some(3.0)])
+            .push_back_from_value_iter(vec![
+                Some("Sally".to_string()),
+                Some("Jamie".to_string()),
+                Some("Bob".to_string()),
+                None,
+                Some("Cara".to_string()),
+            ]);
+        let dv = ds.into_view();
+
+        let summary = dv.na_summary();
+        println!("{}", summary);
+        assert_eq!(summary.nrows, 5);
+        assert_eq!(
+            summary
+                .fields
+                .iter()
+                .map(|field| (field.name.as_str(), field.num_na, field.percent_na))
+                .collect::<Vec<_>>(),
+            vec![("Age", 2, 40.0), ("Income", 3, 60.0), ("Name", 1, 20.0)]
+        );
+
+        let patterns = dv.na_patterns();
+        println!("{}", patterns);
+        assert_eq!(
+            patterns
+                .patterns
+                .iter()
+                .map(|pattern| pattern.missing_fields.join(", "))
+                .collect::<Vec<_>>(),
+            vec!["", "Age, Income", "Income", "Age, Income, Name"]
+        );
+        assert_eq!(
+            patterns
+                .patterns
+                .iter()
+                .map(|pattern| (pattern.num_records, pattern.percent_records))
+                .collect::<Vec<_>>(),
+            vec![(2, 40.0), (1, 20.0), (1, 20.0), (1, 20.0)]
+        );
+
+        // complete data has a single pattern with no missing fields
+        let patterns = sample_emp_table().into_view().na_patterns();
+        assert_eq!(patterns.patterns.len(), 1);
+        assert!(patterns.patterns[0].missing_fields.is_empty());
+        assert_eq!(patterns.patterns[0].num_records, 7);
+    }
+
+    #[test]
+    fn empty_constant_fields() {
+        let ds: survey::Store = DataStore::<Nil>::empty()
+            .push_back_from_value_iter(vec![Some(30u64), None, Some(30)])
+            .push_back_from_value_iter(vec![None::<f64>, None, None])
+            .push_back_from_value_iter(vec![
+                Some("Sally".to_string()),
+                None,
+                Some("Bob".to_string()),
+            ]);
+        let dv = ds.into_view();
+        assert_eq!(dv.empty_fields(), vec!["Income"]);
+        assert_eq!(dv.constant_fields(), vec!["Age", "Income"]);
+
+        let dv = sample_emp_table().into_view();
+        assert!(dv.empty_fields().is_empty());
+        assert!(dv.constant_fields().is_empty());
+    }
+
+    #[test]
+    fn datetime_fields() {
+        let occurred = "2018-07-04 12:30:00".parse::<DateTime>().unwrap();
+        let ds: events::Store = DataStore::<Nil>::empty()
+            .push_back_from_value_iter(vec![Some(occurred), None, Some(occurred)])
+            .push_back_from_value_iter(vec![Some(TotalF64::from(1.5)), Some(1.5.into()), None]);
+        let dv = ds.into_view();
+
+        // numeric statistics are not computed for datetime or total-ordered float fields
+        let vs = dv.view_stats();
+        assert_eq!(vs.nrows, 3);
+        assert_eq!(vs.mins, vec!["".to_string(), "".to_string()]);
+        assert_eq!(vs.sums, vec!["".to_string(), "".to_string()]);
+
+        assert_eq!(dv.constant_fields(), vec!["Occurred", "Score"]);
+
+        let dv = dv
+            .query("Occurred >= '2018-07-04' && Score == 1.5")
+            .unwrap();
+        assert_eq!(dv.nrows(), 1);
+        assert!(dv.clone().query("Occurred == 'not a datetime'").is_err());
+    }
+
+    #[test]
+    fn profile() {
+        let ds: survey::Store = DataStore::<Nil>::empty()
+            .push_back_from_value_iter(vec![Some(30u64), None, Some(40), Some(30), Some(22)])
+            .push_back_from_value_iter(vec![None::<f64>, None, None, None, None])
+            .push_back_from_value_iter(vec![
+                Some("Sally".to_string()),
+                Some("Jamie".to_string()),
+                Some("Bob".to_string()),
+                None,
+                Some("Bob".to_string()),
+            ]);
+        let profile = ds.into_view().profile();
+        println!("{}", profile);
+        assert_eq!(profile.nrows, 5);
+        assert_eq!(profile.fields.len(), 3);
+
+        let age = &profile.fields[0];
+        assert_eq!((age.name.as_str(), age.ty.as_str()), ("Age", "u64"));
+        assert_eq!((age.num_exists, age.num_na, age.cardinality), (4, 1, 3));
+        assert_eq!(age.percent_na, 20.0);
+        assert_eq!(
+            age.top_values,
+            vec![
+                ("30".to_string(), 2),
+                ("40".to_string(), 1),
+                ("22".to_string(), 1)
+            ]
+        );
+        assert_eq!(age.min, Some("22".to_string()));
+        assert_eq!(age.max, Some("40".to_string()));
+        assert_eq!(age.mean, Some(30.5));
+        assert_eq!(age.examples, vec!["30", "40", "22"]);
+
+        let income = &profile.fields[1];
+        assert_eq!((income.num_exists, income.cardinality), (0, 0));
+        assert_eq!(income.percent_na, 100.0);
+        assert!(income.top_values.is_empty());
+        assert_eq!((income.min.clone(), income.mean), (None, None));
+
+        let name = &profile.fields[2];
+        assert_eq!(name.ty, "String");
+        assert_eq!(name.top_values[0], ("Bob".to_string(), 2));
+        assert_eq!(name.min, Some("Bob".to_string()));
+        assert_eq!(name.max, Some("Sally".to_string()));
+        assert_eq!(name.mean, None);
+        assert_eq!(name.examples, vec!["Sally", "Jamie", "Bob"]);
+    }
 }
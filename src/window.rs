@@ -0,0 +1,303 @@
+/*!
+Rolling-window and exponentially weighted statistics over fields with numeric data.
+
+Each computation produces a new [FieldData](../field/struct.FieldData.html) with the same
+number of rows as its input, where the value at each index is computed over the window of values
+ending at (and including) that index. For rolling-window statistics, indices for which the window
+has not yet been filled are missing (NA). The resulting field can be added to a `DataView` using
+[add_field](../view/struct.DataView.html#method.add_field).
+*/
+use num_traits::AsPrimitive;
+
+use access::DataIndex;
+use error::*;
+use field::FieldData;
+use value::Value;
+
+/// A trait for computing rolling-window pairwise statistics between two fields.
+pub trait RollingCorr<Other> {
+    /// Computes the sample covariance between this field and `other` over a rolling window of
+    /// `window` records. Windows which contain a missing value in either field result in a
+    /// missing value.
+    ///
+    /// Fails if this field and `other` do not have the same length.
+    fn rolling_cov(&self, other: &Other, window: usize) -> Result<FieldData<f64>>;
+
+    /// Computes the Pearson correlation coefficient between this field and `other` over a rolling
+    /// window of `window` records. Windows which contain a missing value in either field, or in
+    /// which either field has no variance, result in a missing value.
+    ///
+    /// Fails if this field and `other` do not have the same length.
+    fn rolling_corr(&self, other: &Other, window: usize) -> Result<FieldData<f64>>;
+}
+
+impl<DI, Other> RollingCorr<Other> for DI
+where
+    DI: DataIndex,
+    DI::DType: AsPrimitive<f64>,
+    Other: DataIndex,
+    Other::DType: AsPrimitive<f64>,
+{
+    fn rolling_cov(&self, other: &Other, window: usize) -> Result<FieldData<f64>> {
+        rolling_pairwise(self, other, window, |cov, _, _| Value::Exists(cov))
+    }
+
+    fn rolling_corr(&self, other: &Other, window: usize) -> Result<FieldData<f64>> {
+        rolling_pairwise(self, other, window, |cov, var_left, var_right| {
+            if var_left == 0.0 || var_right == 0.0 {
+                Value::Na
+            } else {
+                Value::Exists(cov / (var_left * var_right).sqrt())
+            }
+        })
+    }
+}
+
+// computes the sample covariance and sample variances for each window of `window` value pairs,
+// and applies `f` to produce the output value
+fn rolling_pairwise<Left, Right, F>(
+    left: &Left,
+    right: &Right,
+    window: usize,
+    f: F,
+) -> Result<FieldData<f64>>
+where
+    Left: DataIndex,
+    Left::DType: AsPrimitive<f64>,
+    Right: DataIndex,
+    Right::DType: AsPrimitive<f64>,
+    F: Fn(f64, f64, f64) -> Value<f64>,
+{
+    if left.len() != right.len() {
+        return Err(AgnesError::LengthMismatch {
+            expected: left.len(),
+            actual: right.len(),
+        });
+    }
+    let pairs = left
+        .iter()
+        .zip(right.iter())
+        .map(|(l, r)| l.zip(r).map(|(&l, &r)| (l.as_(), r.as_())).into_option())
+        .collect::<Vec<_>>();
+
+    Ok((0..pairs.len())
+        .map(|end| {
+            // sample statistics require at least two values
+            if window < 2 || end + 1 < window {
+                return Value::Na;
+            }
+            let mut values = Vec::with_capacity(window);
+            for pair in &pairs[end + 1 - window..=end] {
+                match *pair {
+                    Some(pair) => values.push(pair),
+                    None => return Value::Na,
+                }
+            }
+            let n = window as f64;
+            let mean_left = values.iter().map(|&(l, _)| l).sum::<f64>() / n;
+            let mean_right = values.iter().map(|&(_, r)| r).sum::<f64>() / n;
+            let (mut cov, mut var_left, mut var_right) = (0.0, 0.0, 0.0);
+            for &(l, r) in &values {
+                cov += (l - mean_left) * (r - mean_right);
+                var_left += (l - mean_left) * (l - mean_left);
+                var_right += (r - mean_right) * (r - mean_right);
+            }
+            f(cov / (n - 1.0), var_left / (n - 1.0), var_right / (n - 1.0))
+        })
+        .collect())
+}
+
+/// Smoothing parameter for exponentially weighted statistics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    /// Smoothing factor `alpha` (`0 < alpha <= 1`) applied directly. Larger values discount older
+    /// observations faster.
+    Alpha(f64),
+    /// Decay specified in terms of span (`span >= 1`), equivalent to `alpha = 2 / (span + 1)`.
+    Span(f64),
+}
+
+impl Smoothing {
+    /// Returns the smoothing factor `alpha` for this parameter.
+    ///
+    /// # Panics
+    /// Panics if the resulting `alpha` is not within `(0, 1]`.
+    pub fn alpha(&self) -> f64 {
+        let alpha = match *self {
+            Smoothing::Alpha(alpha) => alpha,
+            Smoothing::Span(span) => 2.0 / (span + 1.0),
+        };
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "invalid smoothing factor: {:?}",
+            self
+        );
+        alpha
+    }
+}
+
+/// A trait for computing exponentially weighted statistics of a field.
+pub trait ExpWeighted {
+    /// Computes the exponentially weighted moving average of this field, where the average at
+    /// each index is `alpha * value + (1 - alpha) * previous_average`. Missing values produce a
+    /// missing average and are skipped when updating the average; indices before the first
+    /// existing value are also missing.
+    ///
+    /// # Panics
+    /// Panics if `smoothing` does not specify a valid smoothing factor.
+    fn ewma(&self, smoothing: Smoothing) -> FieldData<f64>;
+
+    /// Computes the exponentially weighted moving variance of this field, using the same
+    /// weighting and missing value handling as [ewma](#tymethod.ewma).
+    ///
+    /// # Panics
+    /// Panics if `smoothing` does not specify a valid smoothing factor.
+    fn ewvar(&self, smoothing: Smoothing) -> FieldData<f64>;
+}
+
+impl<DI> ExpWeighted for DI
+where
+    DI: DataIndex,
+    DI::DType: AsPrimitive<f64>,
+{
+    fn ewma(&self, smoothing: Smoothing) -> FieldData<f64> {
+        exp_weighted(self, smoothing.alpha(), |mean, _| mean)
+    }
+
+    fn ewvar(&self, smoothing: Smoothing) -> FieldData<f64> {
+        exp_weighted(self, smoothing.alpha(), |_, var| var)
+    }
+}
+
+// runs the exponentially weighted mean / variance recursion over the values of `field`, producing
+// an output value with `f` from the current mean and variance
+fn exp_weighted<DI, F>(field: &DI, alpha: f64, f: F) -> FieldData<f64>
+where
+    DI: DataIndex,
+    DI::DType: AsPrimitive<f64>,
+    F: Fn(f64, f64) -> f64,
+{
+    let mut state: Option<(f64, f64)> = None;
+    field
+        .iter()
+        .map(|value| match value {
+            Value::Exists(&value) => {
+                let value: f64 = value.as_();
+                let (mean, var) = match state {
+                    Some((mean, var)) => {
+                        let delta = value - mean;
+                        (
+                            mean + alpha * delta,
+                            (1.0 - alpha) * (var + alpha * delta * delta),
+                        )
+                    }
+                    None => (value, 0.0),
+                };
+                state = Some((mean, var));
+                Value::Exists(f(mean, var))
+            }
+            Value::Na => Value::Na,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_cov_corr() {
+        let x: FieldData<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0].into();
+        let y: FieldData<f64> = vec![2.0, 4.0, 6.0, 8.0, 10.0].into();
+        let z: FieldData<f64> = vec![5.0, 4.0, 3.0, 2.0, 1.0].into();
+
+        assert_eq!(
+            x.rolling_cov(&y, 3).unwrap().to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Na,
+                Value::Exists(2.0),
+                Value::Exists(2.0),
+                Value::Exists(2.0)
+            ]
+        );
+        assert_eq!(
+            x.rolling_corr(&y, 3).unwrap().to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Na,
+                Value::Exists(1.0),
+                Value::Exists(1.0),
+                Value::Exists(1.0)
+            ]
+        );
+        assert_eq!(
+            x.rolling_corr(&z, 5).unwrap().to_value_vec(),
+            vec![Value::Na, Value::Na, Value::Na, Value::Na, Value::Exists(-1.0)]
+        );
+    }
+
+    #[test]
+    fn rolling_na() {
+        let x = FieldData::from_field_vec(vec![
+            Value::Exists(1.0),
+            Value::Exists(2.0),
+            Value::Na,
+            Value::Exists(4.0),
+            Value::Exists(5.0),
+        ]);
+        let y: FieldData<u64> = vec![1u64, 2, 3, 4, 5].into();
+        assert_eq!(
+            x.rolling_cov(&y, 2).unwrap().to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists(0.5),
+                Value::Na,
+                Value::Na,
+                Value::Exists(0.5)
+            ]
+        );
+
+        let short: FieldData<u64> = vec![1u64, 2].into();
+        assert!(x.rolling_corr(&short, 2).is_err());
+    }
+
+    #[test]
+    fn exp_weighted() {
+        let x = FieldData::from_field_vec(vec![
+            Value::Na,
+            Value::Exists(1.0),
+            Value::Na,
+            Value::Exists(3.0),
+            Value::Exists(5.0),
+        ]);
+        assert_eq!(
+            x.ewma(Smoothing::Alpha(0.5)).to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists(1.0),
+                Value::Na,
+                Value::Exists(2.0),
+                Value::Exists(3.5)
+            ]
+        );
+        // span of 3 is equivalent to alpha of 0.5
+        assert_eq!(
+            x.ewvar(Smoothing::Span(3.0)).to_value_vec(),
+            vec![
+                Value::Na,
+                Value::Exists(0.0),
+                Value::Na,
+                Value::Exists(1.0),
+                Value::Exists(2.75)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn exp_weighted_invalid() {
+        let x: FieldData<f64> = vec![1.0, 2.0].into();
+        x.ewma(Smoothing::Alpha(1.5));
+    }
+}
@@ -1,10 +1,20 @@
 use std::fmt::Debug;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use csv_sniffer::metadata::Metadata;
 
 use agnes::source::csv::{CsvReader, CsvSource, IntoCsvSrcSchema};
 
+pub fn data_path(filename: &str) -> PathBuf {
+    Path::new(file!()) // start as this file
+        .parent()
+        .unwrap() // navigate up to common directory
+        .parent()
+        .unwrap() // navigate up to tests directory
+        .join("data") // navigate into data directory
+        .join(filename) // navigate to target file
+}
+
 pub fn load_csv_file<Schema>(
     filename: &str,
     schema: Schema,
@@ -13,15 +23,7 @@ where
     Schema: IntoCsvSrcSchema,
     <Schema as IntoCsvSrcSchema>::CsvSrcSchema: Debug,
 {
-    let data_filepath = Path::new(file!()) // start as this file
-        .parent()
-        .unwrap() // navigate up to common directory
-        .parent()
-        .unwrap() // navigate up to tests directory
-        .join("data") // navigate into data directory
-        .join(filename); // navigate to target file
-
-    let source = CsvSource::new(data_filepath).unwrap();
+    let source = CsvSource::new(data_path(filename)).unwrap();
     (
         CsvReader::new(&source, schema).unwrap(),
         source.metadata().clone(),
@@ -16,4 +16,7 @@ fn main()
 
     let merged = dv1.merge(&dv2).unwrap();
     //~^ ERROR E0271
+
+    let merged_stores = sample_emp_table().merge_stores(sample_emp_table()).unwrap();
+    //~^ ERROR E0271
 }
@@ -5,11 +5,33 @@ extern crate typenum;
 
 mod common;
 
+use agnes::datetime::DateTime;
+
 tablespace![
     pub table gdp {
         CountryName: String,
         CountryCode: String,
         Year1983: f64,
+        CountryLabel: String,
+        Year1983Or1984: f64,
+        Year: u32,
+        Gdp: f64,
+    }
+    pub table events {
+        EventId: u64,
+        Timestamp: DateTime,
+        TimestampMs: DateTime,
+        LocalTime: DateTime,
+    }
+    pub table sample {
+        State: String,
+        Val1: u64,
+    }
+    pub table indicators {
+        Country: String,
+        Gdp2015: f64,
+        Year: String,
+        LifeExpectancy: f64,
     }
 ];
 
@@ -59,3 +81,410 @@ fn csv_load_test_skip() {
     assert_eq!(subdv.nfields(), 2);
     println!("{}", subdv);
 }
+
+#[test]
+fn csv_load_predicate() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use gdp::*;
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldname gdp::CountryCode = "Country Code";
+        fieldname gdp::Year1983 = "1983";
+    ];
+
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema.clone());
+    let mut csv_rdr = csv_rdr
+        .with_predicate("`1983` > 1e12 && `Country Code` != 'WLD'")
+        .unwrap();
+    let dv = csv_rdr.read().unwrap().into_view();
+    assert!(dv.nrows() > 0);
+    assert!(dv.nrows() < 264);
+    for value in dv.field::<Year1983>().iter() {
+        assert!(value.exists() && *value.unwrap() > 1e12);
+    }
+    assert!(dv
+        .field::<CountryCode>()
+        .iter()
+        .all(|code| code.unwrap() != "WLD"));
+
+    // predicates can refer to columns outside of the schema
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema.clone());
+    let mut csv_rdr = csv_rdr
+        .with_predicate("`Country Code` == 'ABW' || `Indicator Code` == 'none'")
+        .unwrap();
+    let dv = csv_rdr.read().unwrap().into_view();
+    assert_eq!(dv.nrows(), 1);
+
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema);
+    assert!(csv_rdr.with_predicate("Missing == 1").is_err());
+}
+
+#[test]
+fn csv_load_sample() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use gdp::*;
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldname gdp::CountryCode = "Country Code";
+        fieldname gdp::Year1983 = "1983";
+    ];
+
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema.clone());
+    let dv = csv_rdr.with_sample(10, Some(7)).read().unwrap().into_view();
+    assert_eq!(dv.nrows(), 10);
+
+    // sampling is reproducible for the same seed
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema.clone());
+    let dv2 = csv_rdr.with_sample(10, Some(7)).read().unwrap().into_view();
+    assert_eq!(
+        dv.field::<CountryCode>().to_vec(),
+        dv2.field::<CountryCode>().to_vec()
+    );
+
+    // samples are chosen from records which satisfy the predicate
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema.clone());
+    let dv = csv_rdr
+        .with_predicate("`Country Code` == 'ABW' || `Country Code` == 'WLD'")
+        .unwrap()
+        .with_sample(1, Some(7))
+        .read()
+        .unwrap()
+        .into_view();
+    assert_eq!(dv.nrows(), 1);
+    let code = dv.field::<CountryCode>().to_vec()[0].clone();
+    assert!(code == "ABW" || code == "WLD");
+
+    // all records are loaded if the sample is larger than the file
+    let (csv_rdr, _) = common::load_csv_file("gdp.csv", gdp_schema);
+    let dv = csv_rdr
+        .with_sample(1000, Some(7))
+        .read()
+        .unwrap()
+        .into_view();
+    assert_eq!(dv.nrows(), 264);
+}
+
+#[test]
+fn csv_schema_drift() {
+    use agnes::source::csv::{CsvColumn, CsvSource};
+    use csv_sniffer::Type;
+
+    let source = CsvSource::new(common::data_path("gdp.csv")).unwrap();
+    let columns = source.columns().unwrap();
+    assert_eq!(columns.len(), 63);
+    assert_eq!(columns[0], CsvColumn::new("Country Name", Type::Text));
+
+    // no drift from the file's own columns
+    let drift = source.schema_drift(&columns).unwrap();
+    assert!(drift.is_empty());
+    assert_eq!(drift.to_string(), "no schema changes");
+
+    let mut expected = columns.clone();
+    // 'Country Name' column is new
+    expected.remove(0);
+    // 'Population' column is missing
+    expected.push(CsvColumn::new("Population", Type::Unsigned));
+    // '1983' column changed type
+    let idx = expected.iter().position(|col| col.name == "1983").unwrap();
+    expected[idx].ty = Type::Text;
+    let drift = source.schema_drift(&expected).unwrap();
+    println!("{}", drift);
+    assert_eq!(drift.added, vec!["Country Name"]);
+    assert_eq!(drift.removed, vec!["Population"]);
+    assert_eq!(drift.type_changes.len(), 1);
+    assert_eq!(drift.type_changes[0].0, "1983");
+    assert_eq!(drift.type_changes[0].1, Type::Text);
+    assert!(!drift.reordered);
+
+    // columns which exist in both files, but in a different order
+    let mut expected = columns;
+    expected.swap(1, 2);
+    let drift = source.schema_drift(&expected).unwrap();
+    assert!(drift.added.is_empty() && drift.removed.is_empty());
+    assert!(drift.reordered);
+    assert!(!drift.is_empty());
+}
+
+#[test]
+fn csv_load_rules() {
+    use agnes::access::DataIndex;
+    use agnes::error::AgnesError;
+    use agnes::select::FieldSelect;
+    use gdp::*;
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldindex gdp::CountryCode = 1usize, required;
+        fieldname gdp::Year1983 = "1983", default -1.0, required;
+    ];
+    let (mut csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    let dv = csv_rdr.read().unwrap().into_view();
+    assert_eq!(dv.nrows(), 264);
+    let year1983 = dv.field::<Year1983>();
+    assert!(year1983.iter().all(|value| value.exists()));
+    assert_eq!(year1983.iter().filter(|value| *value == -1.0).count(), 77);
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryName = "Country Name";
+        fieldname gdp::Year1983 = "1983", required;
+    ];
+    let (mut csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    match csv_rdr.read() {
+        Err(AgnesError::MissingRequired { field, count }) => {
+            assert_eq!(field.to_string(), "Year1983");
+            assert_eq!(count, 77);
+        }
+        Err(e) => panic!("expected missing required values error, found: {}", e),
+        Ok(_) => panic!("expected missing required values error"),
+    }
+}
+
+#[test]
+fn csv_load_combined() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use agnes::value::Value;
+    use gdp::*;
+
+    let gdp_schema = schema![
+        fieldnames gdp::CountryLabel = ["Country Code", "Country Name"], concat " - ";
+        fieldindices gdp::Year1983Or1984 = [27usize, 28usize], coalesce;
+    ];
+    let (mut csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    let dv = csv_rdr.read().unwrap().into_view();
+    println!("{}", dv);
+    assert_eq!(dv.nrows(), 264);
+
+    let labels = dv.field::<CountryLabel>();
+    assert_eq!(
+        labels.get_datum(0).unwrap(),
+        Value::Exists(&"ABW - Aruba".to_string())
+    );
+    assert_eq!(
+        labels.get_datum(3).unwrap(),
+        Value::Exists(&"ALB - Albania".to_string())
+    );
+
+    // Albania has no value for 1983, so its value for 1984 is used
+    let years = dv.field::<Year1983Or1984>();
+    assert_eq!(
+        years.get_datum(3).unwrap(),
+        Value::Exists(&1_924_242_453.007_93)
+    );
+    assert_eq!(years.iter().filter(|value| value.is_na()).count(), 75);
+
+    // combined fields can be required
+    let gdp_schema = schema![
+        fieldnames gdp::Year1983Or1984 = ["1983", "1984"], coalesce, required;
+    ];
+    let (mut csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    assert!(csv_rdr.read().is_err());
+}
+
+#[test]
+fn csv_load_stacked() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use agnes::value::Value;
+    use gdp::*;
+
+    let gdp_schema = schema![
+        fieldname gdp::CountryCode = "Country Code";
+        fieldnames gdp::Gdp = ["1983", "1984"], stack gdp::Year;
+    ];
+    let (mut csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    let dv = csv_rdr.read().unwrap().into_view();
+    println!("{}", dv);
+    // one record for each stacked column of each source record
+    assert_eq!(dv.nrows(), 528);
+    assert_eq!(dv.fieldnames(), vec!["CountryCode", "Year", "Gdp"]);
+
+    let codes = dv.field::<CountryCode>();
+    assert_eq!(
+        codes.get_datum(6).unwrap(),
+        Value::Exists(&"ALB".to_string())
+    );
+    assert_eq!(
+        codes.get_datum(7).unwrap(),
+        Value::Exists(&"ALB".to_string())
+    );
+    let years = dv.field::<Year>();
+    assert_eq!(years.get_datum(6).unwrap(), Value::Exists(&1983));
+    assert_eq!(years.get_datum(7).unwrap(), Value::Exists(&1984));
+    // Albania has no value for 1983
+    let gdp = dv.field::<Gdp>();
+    assert!(gdp.get_datum(6).unwrap().is_na());
+    assert_eq!(
+        gdp.get_datum(7).unwrap(),
+        Value::Exists(&1_924_242_453.007_93)
+    );
+
+    // stacked columns can be designated by index, and cleaning rules apply to the stacked values
+    let gdp_schema = schema![
+        fieldindices gdp::Gdp = [28usize, 27usize], stack gdp::Year, default 0.0;
+    ];
+    let (csv_rdr, _) = common::load_csv_file("gdp.nopreamble.csv", gdp_schema);
+    let dv = csv_rdr
+        .with_predicate("`Country Code` == 'ALB'")
+        .unwrap()
+        .read()
+        .unwrap()
+        .into_view();
+    assert_eq!(dv.field::<Year>().to_vec(), vec![1984, 1983]);
+    assert_eq!(dv.field::<Gdp>().to_vec(), vec![1_924_242_453.007_93, 0.0]);
+}
+
+#[test]
+fn csv_load_reader() {
+    use std::fs::File;
+    use std::io::Read;
+
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use agnes::source::csv::load_csv_from_reader;
+    use sample::*;
+
+    let sample_schema = schema![
+        fieldname sample::State = "State";
+        fieldname sample::Val1 = "val1";
+    ];
+
+    let file = File::open(common::data_path("sample1.csv")).unwrap();
+    let dv = load_csv_from_reader(file, sample_schema.clone()).unwrap();
+    assert_eq!(dv.nrows(), 8);
+    assert_eq!(
+        dv.field::<State>().to_vec(),
+        vec!["OH", "PA", "NH", "NC", "CA", "NY", "VA", "SC"]
+    );
+
+    // compressed contents are decompressed in memory
+    #[cfg(feature = "gzip")]
+    {
+        let file = File::open(common::data_path("sample1.csv.gz")).unwrap();
+        let dv_gz = load_csv_from_reader(file, sample_schema.clone()).unwrap();
+        assert_eq!(dv_gz.field::<Val1>().to_vec(), dv.field::<Val1>().to_vec());
+    }
+
+    // in-memory buffers
+    let mut contents = vec![];
+    File::open(common::data_path("sample1.csv"))
+        .unwrap()
+        .read_to_end(&mut contents)
+        .unwrap();
+    let dv_mem = load_csv_from_reader(&contents[..], sample_schema).unwrap();
+    assert_eq!(dv_mem.field::<Val1>().to_vec(), dv.field::<Val1>().to_vec());
+}
+
+#[test]
+fn csv_load_multiheader() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use agnes::source::csv::{CsvReader, CsvSource};
+    use indicators::*;
+
+    let source = CsvSource::new(common::data_path("multiheader.csv"))
+        .unwrap()
+        .with_header_rows(2);
+    let columns = source.columns().unwrap();
+    assert_eq!(
+        columns
+            .iter()
+            .map(|col| col.name.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Country",
+            "GDP.2014",
+            "GDP.2015",
+            "Life Expectancy.2014",
+            "Life Expectancy.2015"
+        ]
+    );
+    // category headers are filled in from the previous column
+    assert_eq!(columns[2].levels, vec!["GDP", "2015"]);
+    assert_eq!(columns[4].levels, vec!["Life Expectancy", "2015"]);
+
+    let schema = schema![
+        fieldname indicators::Country = "Country";
+        fieldname indicators::Gdp2015 = "GDP.2015";
+    ];
+    let dv = CsvReader::new(&source, schema)
+        .unwrap()
+        .read()
+        .unwrap()
+        .into_view();
+    assert_eq!(dv.nrows(), 4);
+    assert_eq!(
+        dv.field::<Country>().to_vec(),
+        vec!["Aruba", "Albania", "Algeria", "Angola"]
+    );
+    assert_eq!(
+        dv.field::<Gdp2015>().to_vec(),
+        vec![2691.6, 11335.3, 165979.3, 116193.6]
+    );
+
+    // the columns of a category can be stacked
+    let schema = schema![
+        fieldname indicators::Country = "Country";
+        fieldnames indicators::LifeExpectancy = ["Life Expectancy.2014", "Life Expectancy.2015"],
+            stack indicators::Year;
+    ];
+    let dv = CsvReader::new(&source, schema)
+        .unwrap()
+        .with_predicate("Country == 'Angola'")
+        .unwrap()
+        .read()
+        .unwrap()
+        .into_view();
+    assert_eq!(
+        dv.field::<Year>().to_vec(),
+        vec!["Life Expectancy.2014", "Life Expectancy.2015"]
+    );
+    assert_eq!(dv.field::<LifeExpectancy>().to_vec(), vec![59.4, 60.0]);
+}
+
+#[test]
+fn csv_load_timestamps() {
+    use agnes::access::DataIndex;
+    use agnes::select::FieldSelect;
+    use agnes::value::Value;
+    use events::*;
+
+    let events_schema = schema![
+        fieldname events::EventId = "id";
+        fieldname events::Timestamp as unix_seconds = "timestamp";
+        fieldindex events::TimestampMs as unix_millis = 2usize;
+        fieldname events::LocalTime = "local_time";
+    ];
+
+    let (mut csv_rdr, _) = common::load_csv_file("events.csv", events_schema);
+    let dv = csv_rdr.read().unwrap().into_view();
+    println!("{}", dv);
+    assert_eq!(dv.nrows(), 4);
+
+    let expected = ["2018-07-04", "2018-07-05", "2018-07-06", "2018-07-07"]
+        .iter()
+        .map(|date| format!("{} 12:30:00", date).parse::<DateTime>().unwrap())
+        .collect::<Vec<_>>();
+    // local times in various timezones are normalized to UTC
+    assert_eq!(dv.field::<LocalTime>().to_vec(), expected);
+    assert_eq!(
+        dv.field::<Timestamp>().to_value_vec(),
+        vec![
+            Value::Exists(expected[0]),
+            Value::Exists(expected[1]),
+            Value::Na,
+            Value::Exists(expected[3]),
+        ]
+    );
+    let millis = dv.field::<TimestampMs>();
+    assert_eq!(
+        millis.get_datum(0).unwrap().unwrap().timestamp_millis(),
+        1_530_707_400_250
+    );
+    assert!(millis.get_datum(1).unwrap().is_na());
+    assert_eq!(millis.get_datum(2).unwrap(), Value::Exists(&expected[2]));
+}
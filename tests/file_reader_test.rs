@@ -8,7 +8,7 @@ extern crate tokio_io;
 use std::io::Read;
 use std::path::Path;
 
-use agnes::source::file::{FileLocator, FileReader, Uri};
+use agnes::source::file::{FileLocator, FileReader, LocalFileReader, Uri};
 
 #[test]
 fn load_test_sync() {
@@ -43,3 +43,38 @@ fn load_test_sync() {
     // 103 tests/data/sample1.csv
     assert_eq!(file1_contents.len(), 103);
 }
+
+#[cfg(all(feature = "gzip", feature = "zstandard"))]
+#[test]
+fn load_compressed() {
+    use agnes::source::file::FileCompression;
+
+    let read_file = |filename: &str| {
+        let data_filepath = Path::new(file!())
+            .parent()
+            .unwrap()
+            .join("data")
+            .join(filename);
+        let mut reader = LocalFileReader::new(&FileLocator::File(data_filepath)).unwrap();
+
+        let mut buf = String::new();
+        reader.read_to_string(&mut buf).unwrap();
+        buf
+    };
+
+    let uncompressed = read_file("sample1.csv");
+    assert_eq!(uncompressed.len(), 103);
+    assert_eq!(read_file("sample1.csv.gz"), uncompressed);
+    assert_eq!(read_file("sample1.csv.zst"), uncompressed);
+
+    assert_eq!(
+        FileCompression::detect(b"\x1f\x8b\x08"),
+        Some(FileCompression::Gzip)
+    );
+    assert_eq!(
+        FileCompression::detect(b"\x28\xb5\x2f\xfd"),
+        Some(FileCompression::Zstd)
+    );
+    assert_eq!(FileCompression::detect(b"a,b,c"), None);
+    assert_eq!(FileCompression::detect(b""), None);
+}